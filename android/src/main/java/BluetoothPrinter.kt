@@ -28,7 +28,35 @@ class BluetoothPrinter(private val context: Context) {
         manager?.adapter
     }
 
-    fun printRawData(macAddress: String, data: ByteArray) {
+    fun printRawData(macAddress: String, data: ByteArray, pauses: List<PrintPause> = emptyList()) {
+        Log.d(TAG, "Connecting to $macAddress (${data.size} bytes)")
+        val socket = openSocket(macAddress)
+        try {
+            Log.d(TAG, "Connected, sending ${data.size} bytes")
+            writePaced(data, pauses) { offset, length ->
+                socket.outputStream.write(data, offset, length)
+                socket.outputStream.flush()
+            }
+            Log.d(TAG, "Print complete")
+        } finally {
+            try { socket.close() } catch (_: Exception) {}
+        }
+    }
+
+    /** Sends [data] and returns the printer's answer (see [readResponses]). */
+    fun queryRawData(macAddress: String, data: ByteArray, responses: Int): ByteArray {
+        Log.d(TAG, "Querying $macAddress (${data.size} bytes)")
+        val socket = openSocket(macAddress)
+        try {
+            socket.outputStream.write(data)
+            socket.outputStream.flush()
+            return readResponses(socket.inputStream, responses)
+        } finally {
+            try { socket.close() } catch (_: Exception) {}
+        }
+    }
+
+    private fun openSocket(macAddress: String): BluetoothSocket {
         val adapter = bluetoothAdapter
             ?: throw IllegalStateException("Bluetooth adapter not available")
 
@@ -46,20 +74,11 @@ class BluetoothPrinter(private val context: Context) {
             }
         }
 
-        Log.d(TAG, "Connecting to $macAddress (${data.size} bytes)")
         val device = adapter.getRemoteDevice(macAddress)
         // Discovery slows down / breaks an active RFCOMM connection.
         adapter.cancelDiscovery()
 
-        val socket = connectSocket(device)
-        try {
-            Log.d(TAG, "Connected, sending ${data.size} bytes")
-            socket.outputStream.write(data)
-            socket.outputStream.flush()
-            Log.d(TAG, "Print complete")
-        } finally {
-            try { socket.close() } catch (_: Exception) {}
-        }
+        return connectSocket(device)
     }
 
     /**
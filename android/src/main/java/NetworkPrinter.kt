@@ -13,19 +13,38 @@ class NetworkPrinter {
 
     private val TAG = "NetworkPrinter"
 
-    fun printRawData(identifier: String, data: ByteArray) {
+    fun printRawData(identifier: String, data: ByteArray, pauses: List<PrintPause> = emptyList()) {
         val host = identifier.substringBeforeLast(':')
         val port = identifier.substringAfterLast(':').toIntOrNull()
             ?: throw IllegalArgumentException("Invalid network identifier: $identifier")
 
         Log.d(TAG, "Connecting to $host:$port (${data.size} bytes)")
+        Socket().use { socket ->
+            socket.connect(InetSocketAddress(host, port), CONNECT_TIMEOUT_MS)
+            socket.getOutputStream().apply {
+                writePaced(data, pauses) { offset, length ->
+                    write(data, offset, length)
+                    flush()
+                }
+            }
+            Log.d(TAG, "Network print complete")
+        }
+    }
+
+    /** Sends [data] and returns the printer's answer (see [readResponses]). */
+    fun queryRawData(identifier: String, data: ByteArray, responses: Int): ByteArray {
+        val host = identifier.substringBeforeLast(':')
+        val port = identifier.substringAfterLast(':').toIntOrNull()
+            ?: throw IllegalArgumentException("Invalid network identifier: $identifier")
+
+        Log.d(TAG, "Querying $host:$port (${data.size} bytes)")
         Socket().use { socket ->
             socket.connect(InetSocketAddress(host, port), CONNECT_TIMEOUT_MS)
             socket.getOutputStream().apply {
                 write(data)
                 flush()
             }
-            Log.d(TAG, "Network print complete")
+            return readResponses(socket.getInputStream(), responses)
         }
     }
 
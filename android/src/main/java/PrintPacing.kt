@@ -0,0 +1,38 @@
+package com.luis3132.thermal_printer
+
+import app.tauri.plugin.JSObject
+
+/**
+ * Pause requested by the Rust side (`PrintPause`): after the first [offset] bytes
+ * the transport waits [delayMs] before writing the rest. Used to pace tall images
+ * on slow links.
+ */
+data class PrintPause(val offset: Int, val delayMs: Long)
+
+/** Reads the optional `pauses` array sent with `print_raw_data`. */
+fun parsePauses(args: JSObject): List<PrintPause> {
+    val array = args.optJSONArray("pauses") ?: return emptyList()
+    return (0 until array.length()).map { i ->
+        val pause = array.getJSONObject(i)
+        PrintPause(pause.getInt("offset"), pause.getLong("delay_ms"))
+    }
+}
+
+/**
+ * Calls [write] with consecutive `(offset, length)` slices of [data], sleeping at
+ * each pause in between. Without pauses it is a single call for the whole payload.
+ */
+inline fun writePaced(
+    data: ByteArray,
+    pauses: List<PrintPause>,
+    write: (offset: Int, length: Int) -> Unit
+) {
+    var start = 0
+    for (pause in pauses) {
+        val end = pause.offset.coerceIn(start, data.size)
+        if (end > start) write(start, end - start)
+        start = end
+        if (pause.delayMs > 0) Thread.sleep(pause.delayMs)
+    }
+    if (start < data.size) write(start, data.size - start)
+}
@@ -0,0 +1,35 @@
+package com.luis3132.thermal_printer
+
+import java.io.ByteArrayOutputStream
+import java.io.InputStream
+import java.net.SocketTimeoutException
+
+private const val QUERY_TIMEOUT_MS = 5000L
+private const val POLL_INTERVAL_MS = 20L
+
+/**
+ * Reads from [input] until [responses] NUL-terminated answers have arrived (see
+ * `query_raw_data`). Polls `available()` so it also works on Bluetooth sockets,
+ * which have no read timeout.
+ */
+fun readResponses(input: InputStream, responses: Int): ByteArray {
+    val output = ByteArrayOutputStream()
+    val buffer = ByteArray(256)
+    val deadline = System.currentTimeMillis() + QUERY_TIMEOUT_MS
+    var received = 0
+    while (received < responses) {
+        if (System.currentTimeMillis() > deadline) {
+            throw SocketTimeoutException("Printer did not answer in time")
+        }
+        val available = input.available()
+        if (available == 0) {
+            Thread.sleep(POLL_INTERVAL_MS)
+            continue
+        }
+        val read = input.read(buffer, 0, minOf(available, buffer.size))
+        if (read < 0) break
+        output.write(buffer, 0, read)
+        received += (0 until read).count { buffer[it] == 0.toByte() }
+    }
+    return output.toByteArray()
+}
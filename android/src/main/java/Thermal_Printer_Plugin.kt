@@ -14,6 +14,7 @@ import app.tauri.plugin.JSObject
 import app.tauri.plugin.Plugin
 import android.Manifest
 import android.os.Build
+import java.net.SocketTimeoutException
 
 @TauriPlugin(
     permissions = [
@@ -155,30 +156,122 @@ class Thermal_Printer_Plugin(private val activity: Activity) : Plugin(activity)
                     return@Thread invoke.reject("Invalid print data encoding")
                 }
 
+                val pauses = parsePauses(args)
+
                 Log.d(TAG, "Printing to $identifier (${bytes.size} bytes)")
 
                 val context = activity.applicationContext
                 when {
                     identifier.startsWith("VID:") -> {
-                        UsbPrinter(context).printRawData(identifier, bytes)
+                        UsbPrinter(context).printRawData(identifier, bytes, pauses)
                     }
                     networkRegex.matches(identifier) -> {
-                        NetworkPrinter().printRawData(identifier, bytes)
+                        NetworkPrinter().printRawData(identifier, bytes, pauses)
                     }
                     macRegex.matches(identifier) -> {
-                        BluetoothPrinter(context).printRawData(identifier, bytes)
+                        BluetoothPrinter(context).printRawData(identifier, bytes, pauses)
                     }
                     else -> {
-                        return@Thread invoke.reject("Unrecognized printer identifier: $identifier")
+                        return@Thread invoke.reject(
+                            "Unrecognized printer identifier: $identifier",
+                            "PRINTER_NOT_FOUND"
+                        )
                     }
                 }
 
                 invoke.resolve()
 
+            } catch (e: SocketTimeoutException) {
+                // El código coincide con `Error::code()` en Rust para que el frontend
+                // reciba el mismo error en todas las plataformas.
+                Log.e(TAG, "Print timeout: ${e.message}", e)
+                invoke.reject("Print timeout: ${e.message}", "TRANSPORT_TIMEOUT")
             } catch (e: Exception) {
                 Log.e(TAG, "Print error: ${e.message}", e)
                 invoke.reject("Print error: ${e.message}")
             }
         }.start()
     }
+
+    // ─────────────────────────────────────────────────────────────
+    // query_raw_data — envía una petición y devuelve la respuesta
+    // ─────────────────────────────────────────────────────────────
+
+    @Command
+    fun query_raw_data(invoke: Invoke) {
+        val identifier = try {
+            invoke.getArgs().getString("identifier", null)
+        } catch (e: Exception) {
+            null
+        }
+
+        if (identifier != null && isBluetoothIdentifier(identifier) &&
+            needsBluetoothRuntimePermission()
+        ) {
+            requestPermissionForAlias("bluetooth", invoke, "bluetoothQueryPermissionCallback")
+            return
+        }
+        doQueryRawData(invoke)
+    }
+
+    @PermissionCallback
+    fun bluetoothQueryPermissionCallback(invoke: Invoke) {
+        if (getPermissionState("bluetooth") != PermissionState.GRANTED) {
+            Log.w(TAG, "Bluetooth permission denied for query")
+            invoke.reject("Bluetooth permission denied")
+            return
+        }
+        doQueryRawData(invoke)
+    }
+
+    private fun doQueryRawData(invoke: Invoke) {
+        Thread {
+            try {
+                val args = invoke.getArgs()
+
+                val identifier = args.getString("identifier", null)
+                    ?: return@Thread invoke.reject("Missing printer identifier")
+
+                val bytes = try {
+                    Base64.decode(args.getString("data", ""), Base64.DEFAULT)
+                } catch (e: IllegalArgumentException) {
+                    return@Thread invoke.reject("Invalid query data encoding")
+                }
+                val responses = args.getInteger("responses", 1) ?: 1
+
+                val context = activity.applicationContext
+                val answer = when {
+                    networkRegex.matches(identifier) -> {
+                        NetworkPrinter().queryRawData(identifier, bytes, responses)
+                    }
+                    macRegex.matches(identifier) -> {
+                        BluetoothPrinter(context).queryRawData(identifier, bytes, responses)
+                    }
+                    identifier.startsWith("VID:") -> {
+                        return@Thread invoke.reject(
+                            "Reading from USB printers is not supported",
+                            "UNSUPPORTED_PLATFORM"
+                        )
+                    }
+                    else -> {
+                        return@Thread invoke.reject(
+                            "Unrecognized printer identifier: $identifier",
+                            "PRINTER_NOT_FOUND"
+                        )
+                    }
+                }
+
+                val result = JSObject()
+                result.put("data", Base64.encodeToString(answer, Base64.NO_WRAP))
+                invoke.resolve(result)
+
+            } catch (e: SocketTimeoutException) {
+                Log.e(TAG, "Query timeout: ${e.message}", e)
+                invoke.reject("Query timeout: ${e.message}", "TRANSPORT_TIMEOUT")
+            } catch (e: Exception) {
+                Log.e(TAG, "Query error: ${e.message}", e)
+                invoke.reject("Query error: ${e.message}")
+            }
+        }.start()
+    }
 }
@@ -30,7 +30,7 @@ class UsbPrinter(private val context: Context) {
     private val usbManager: UsbManager
         get() = context.getSystemService(Context.USB_SERVICE) as UsbManager
 
-    fun printRawData(identifier: String, data: ByteArray) {
+    fun printRawData(identifier: String, data: ByteArray, pauses: List<PrintPause> = emptyList()) {
         val (vendorId, productId) = parseIdentifier(identifier)
 
         val device = usbManager.deviceList.values.firstOrNull {
@@ -57,16 +57,18 @@ class UsbPrinter(private val context: Context) {
             }
 
             Log.d(TAG, "Sending ${data.size} bytes over USB to $identifier")
-            var offset = 0
             val chunkSize = 16384
-            while (offset < data.size) {
-                val len = minOf(chunkSize, data.size - offset)
-                val chunk = data.copyOfRange(offset, offset + len)
-                val sent = connection.bulkTransfer(endpoint, chunk, len, TRANSFER_TIMEOUT_MS)
-                if (sent < 0) {
-                    throw IllegalStateException("USB bulkTransfer failed at offset $offset")
+            writePaced(data, pauses) { start, length ->
+                var offset = start
+                while (offset < start + length) {
+                    val len = minOf(chunkSize, start + length - offset)
+                    val chunk = data.copyOfRange(offset, offset + len)
+                    val sent = connection.bulkTransfer(endpoint, chunk, len, TRANSFER_TIMEOUT_MS)
+                    if (sent < 0) {
+                        throw IllegalStateException("USB bulkTransfer failed at offset $offset")
+                    }
+                    offset += sent
                 }
-                offset += sent
             }
             Log.d(TAG, "USB print complete")
         } finally {
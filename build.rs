@@ -2,6 +2,14 @@ const COMMANDS: &[&str] = &[
     "print_thermal_printer",
     "list_thermal_printers",
     "test_thermal_printer",
+    "validate_print_job",
+    "store_nv_graphic",
+    "print_nv_graphic",
+    "delete_nv_graphic",
+    "list_nv_graphics",
+    "nv_graphics_capacity",
+    "register_font",
+    "register_image_asset",
 ];
 
 fn main() {
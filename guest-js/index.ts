@@ -51,11 +51,14 @@ export function getPaperSizePixelsWidth(paperSize: PaperSize): number {
  *   `ENCODE.ACCENT_REMOVER`.
  * - `use_gbk` controls whether unmapped non-ASCII characters may fall back
  *   to GBK. It defaults to `false`.
+ * - `strict` makes the job fail with `ENCODING_FAILED` when a character cannot
+ *   be encoded, instead of sending it as raw UTF-8. It defaults to `false`.
  */
 export interface CodePage {
   code_page: number
   encode?: Encode
   use_gbk?: boolean
+  strict?: boolean
 }
 
 // ─── Text style constants ─────────────────────────────────────────────────────
@@ -69,6 +72,12 @@ export type TextSize = 'normal' | 'height' | 'width' | 'double'
 /** Font options */
 export type TextFont = 'A' | 'B' | 'C'
 
+/** Print color on two-color printers */
+export type TextColor = 'black' | 'red'
+
+/** How a code is printed: firmware command, software raster, or chosen from the printer profile */
+export type RenderMode = 'native' | 'raster' | 'auto'
+
 /** Barcode text position options */
 export type BarcodeTextPosition = 'none' | 'above' | 'below' | 'both'
 
@@ -135,7 +144,11 @@ export const numericBarcodeData = (data: string): NumericBarcodeData => data as
 export const code39BarcodeData = (data: string): Code39BarcodeData => data as Code39BarcodeData
 /** Brand a string as CODABAR data. Pure compile-time cast. */
 export const codabarBarcodeData = (data: string): CodabarBarcodeData => data as CodabarBarcodeData
-/** Brand a string as GS1 (AI format) data. Pure compile-time cast. */
+/**
+ * Brand a string as GS1 (AI format) data, e.g. `(01)09501101530003(10)ABC`.
+ * Pure compile-time cast — the backend validates every AI (length, charset,
+ * check digit, dates) before printing.
+ */
 export const gs1BarcodeData = (data: string): Gs1BarcodeData => data as Gs1BarcodeData
 
 /** QR error correction level */
@@ -171,6 +184,11 @@ export const TEXT_FONT = {
   C: 'C' as TextFont,
 } as const
 
+export const TEXT_COLOR = {
+  BLACK: 'black' as TextColor,
+  RED: 'red' as TextColor,
+} as const
+
 export const BARCODE_TYPE = {
   UPC_A: 'UPC-A' as BarcodeType,
   UPC_E: 'UPC-E' as BarcodeType,
@@ -239,6 +257,8 @@ export interface GlobalStyles {
   size?: TextSize
   /** Double-strike / double-print (`ESC G`). Reinforces bold on generic printers. */
   double_strike?: boolean
+  /** Print color (`ESC r`) on two-color printers. Single-color printers ignore it. */
+  color?: TextColor
   /**
    * When `true`, resets the printer (`ESC @`) and re-applies the code page.
    * Takes priority: all other style fields in the same section are ignored.
@@ -251,11 +271,38 @@ export interface GlobalStyles {
 export interface Title {
   text: string
   styles?: GlobalStyles
+  /** Render with a registered font as a bitmap instead of the printer font. */
+  font?: RegisteredFont
 }
 
 export interface Subtitle {
   text: string
   styles?: GlobalStyles
+  /** Render with a registered font as a bitmap instead of the printer font. */
+  font?: RegisteredFont
+}
+
+/**
+ * Font for a Title/Subtitle rendered as a bitmap. The text follows `styles.align`
+ * (Title defaults to 'center'); the other styles don't apply. Ignored inside
+ * PageMode and Columns. FontText also accepts it in place of base64 data.
+ */
+export interface RegisteredFont {
+  /** Name given to {@link register_font} */
+  name: string
+  /**
+   * Size in points (1/72 inch). Defaults to 18 for Title, 14 for Subtitle and
+   * 24 dots for FontText.
+   */
+  size?: number
+  /**
+   * 100–900. Variable fonts use their `wght` axis; other fonts are emboldened above
+   * 400. Title and Subtitle default to 700 unless their `styles.bold` is `false`
+   * (then 400); FontText defaults to 400.
+   */
+  weight?: number
+  /** Extra space between characters, in dots (negative tightens). */
+  letter_spacing?: number
 }
 
 export interface Text {
@@ -306,6 +353,91 @@ export interface Qr {
   error_correction: QrErrorCorrection
   model: 1 | 2
   align?: TextAlign
+  /**
+   * `native` (default) uses the firmware QR command (`GS ( k`); `raster` encodes
+   * the QR in Rust and prints it as an image (always Model 2); `auto` picks
+   * `raster` when the job's `profile.native_qr` is `false`.
+   */
+  render_mode?: RenderMode
+  /**
+   * Target symbol width in dots. Overrides `size`: the backend computes the QR
+   * version from the data and error correction level and uses the largest module
+   * size that fits (never wider than the paper).
+   */
+  target_width?: number
+  /** Same as `target_width` in millimetres (8 dots/mm). */
+  target_width_mm?: number
+}
+
+/** Options shared by the payment QR sections (same meaning as in {@link Qr}). */
+export interface PaymentQrOptions {
+  /** Module size 1–16 (default 6) */
+  size?: number
+  /** Default `M` */
+  error_correction?: QrErrorCorrection
+  align?: TextAlign
+  render_mode?: RenderMode
+  target_width?: number
+  target_width_mm?: number
+}
+
+/** EMVCo data object: two-digit ID and its value. */
+export interface EmvField {
+  id: number
+  value: string
+}
+
+/** EMVCo template: either a primitive `value` or nested `fields`. */
+export interface EmvTemplate {
+  id: number
+  value?: string
+  fields?: EmvField[]
+}
+
+/**
+ * EMVCo merchant-presented payment QR (PIX, UPI, ...). The backend builds the
+ * TLV payload, appends the CRC16 and prints it as a {@link Qr}.
+ */
+export interface EmvQr extends PaymentQrOptions {
+  /** Merchant account information (IDs 02–51), e.g. PIX = ID 26 */
+  merchant_accounts: EmvTemplate[]
+  /** ISO 18245 merchant category code (default `0000`) */
+  merchant_category_code?: string
+  /** ISO 4217 numeric currency code, e.g. `986` */
+  currency: string
+  /** e.g. `10.50`; omit to let the payer enter it */
+  amount?: string
+  /** ISO 3166-1 alpha-2, e.g. `BR` */
+  country_code: string
+  /** Max 25 characters */
+  merchant_name: string
+  /** Max 15 characters */
+  merchant_city: string
+  postal_code?: string
+  /** Additional data template (ID 62), e.g. `{ id: 5, value: 'ORDER42' }` */
+  additional_data?: EmvField[]
+  /** `true` = dynamic (`12`), `false` = static (`11`); omitted when unset */
+  dynamic?: boolean
+}
+
+/** EPC069-12 SEPA credit transfer QR ("GiroCode"), printed as a {@link Qr}. */
+export interface SepaQr extends PaymentQrOptions {
+  /** Beneficiary name, max 70 characters */
+  name: string
+  /** Spaces allowed; check digits are verified */
+  iban: string
+  /** 8 or 11 characters, optional inside the EEA */
+  bic?: string
+  /** Euros, e.g. `12.50` */
+  amount?: string
+  /** ISO 20022 purpose code (4 characters) */
+  purpose?: string
+  /** Structured creditor reference (max 35). Exclusive with `text`. */
+  reference?: string
+  /** Unstructured remittance text (max 140). Exclusive with `reference`. */
+  text?: string
+  /** Beneficiary-to-originator information (max 70) */
+  information?: string
 }
 
 export interface Barcode {
@@ -317,14 +449,33 @@ export interface Barcode {
   height: number
   text_position: BarcodeTextPosition
   align?: TextAlign
+  /**
+   * `native` (default) uses the firmware barcode command (`GS k`); `raster`
+   * encodes the barcode in Rust and prints it as an image with quiet zones and a
+   * bitmap HRI font, identical on every printer; `auto` picks `raster` when the
+   * job's profile says the firmware lacks the symbology.
+   */
+  render_mode?: RenderMode
+  /**
+   * EAN13, EAN8, UPC-A, UPC-E: append the computed check digit when the data
+   * omits it. A check digit that is present is always verified.
+   */
+  auto_check_digit?: boolean
 }
 
 export interface DataMatrixModel {
   data: string
   /** Module size 1–16 */
   size: number
+  /**
+   * `native` (default) sends `GS ( k`; `raster` encodes an ECC 200 symbol in Rust
+   * (smallest square size) and prints it as an image; `auto` picks `raster` when
+   * the job's `profile.native_data_matrix` is `false`.
+   */
+  render_mode?: RenderMode
 }
 
+/** PDF417 (stacked 2D). */
 export interface Pdf417 {
   data: string
   /** Columns 0 (auto) or 1–30 */
@@ -333,10 +484,16 @@ export interface Pdf417 {
   rows: number
   /** Module width 2–8 */
   width: number
-  /** Row height 2–8 */
+  /** Row height 2–8, as a multiple of the module width */
   height: number
   /** Error correction level 0–8 */
   error_correction: number
+  /**
+   * `native` (default) sends `GS ( k`; `raster` encodes the symbol in Rust with
+   * the same columns, rows and error correction and prints it as an image; `auto`
+   * picks `raster` when the job's `profile.native_pdf417` is `false`.
+   */
+  render_mode?: RenderMode
 }
 
 /**
@@ -353,6 +510,12 @@ export interface Aztec {
   /** Error correction as a percentage of capacity (5–95) */
   error_correction: number
   align?: TextAlign
+  /**
+   * `native` (default) sends `GS ( k`; `raster` encodes the symbol in Rust with
+   * the same mode, layers and error correction and prints it as an image; `auto`
+   * picks `raster` when the job's `profile.native_aztec` is `false`.
+   */
+  render_mode?: RenderMode
 }
 
 /** GS1 DataBar 2D subtype. */
@@ -372,13 +535,23 @@ export interface Gs1Databar2d {
 
 /**
  * MaxiCode (2D). Fixed physical size. Requires printer firmware support
- * (advanced Epson models).
+ * (advanced Epson models) unless rendered as raster.
  */
 export interface MaxiCode {
+  /**
+   * In modes 2 and 3, starts with `postcode<GS>country<GS>class<GS>`, optionally
+   * preceded by the `[)>` `<RS>01<GS>vv` header.
+   */
   data: string
   /** Mode 2–6 (4 is the general-purpose default) */
   mode: number
   align?: TextAlign
+  /**
+   * `native` (default) sends `GS ( k`; `raster` encodes the symbol in Rust with
+   * the same mode and prints the hexagonal grid as an image; `auto` picks
+   * `raster` when the job's `profile.native_maxicode` is `false`.
+   */
+  render_mode?: RenderMode
 }
 
 /**
@@ -397,18 +570,136 @@ export interface Composite {
   align?: TextAlign
 }
 
-export interface Image {
-  /** Base64 encoded image (with or without data URI prefix) */
+/** Binarization algorithm used when `dithering` is true */
+export type DitheringAlgorithm =
+  | 'threshold'
+  | 'floyd_steinberg'
+  | 'atkinson'
+  | 'jarvis_judice_ninke'
+  | 'stucki'
+  | 'sierra'
+  | 'bayer4'
+  | 'bayer8'
+
+/** Tone adjustments applied before binarization. Output is deterministic. */
+export interface ImageTuning {
+  /** Defaults to 'floyd_steinberg'. Ignored when `dithering` is false. */
+  dithering_algorithm?: DitheringAlgorithm
+  /** Black/white cut-off 0–255. Defaults to 127. */
+  threshold?: number
+  /** -100 to 100. Defaults to 0. */
+  brightness?: number
+  /** -100 to 100. Defaults to 0. */
+  contrast?: number
+  /** 0.1 to 10; values above 1 lighten midtones. Defaults to 1. */
+  gamma?: number
+  /** Unsharp mask strength 0 to 10. Defaults to 0. */
+  sharpen?: number
+  /** Swap black and white. Defaults to false. */
+  invert?: boolean
+  /** Keep only this rectangle of the source image, in source pixels. Applied first. */
+  crop?: ImageCrop
+  /** Clockwise rotation after `crop`. Use 90/270 to print wide images down the roll. */
+  rotate?: 0 | 90 | 180 | 270
+  /** Remove white or transparent margins around the content, after `rotate`. */
+  trim?: boolean
+  /** White space around the printed image, in dots. Counts toward `max_width`. */
+  padding?: ImagePadding
+}
+
+/** Rectangle of the source image, in pixels. */
+export interface ImageCrop {
+  x: number
+  y: number
+  width: number
+  height: number
+}
+
+/** White space around an image, in dots. Missing sides are 0. */
+export interface ImagePadding {
+  top?: number
+  right?: number
+  bottom?: number
+  left?: number
+}
+
+/**
+ * Command used to send an image: `raster` (`GS v 0`, default), `bit_image_8` /
+ * `bit_image_24` (`ESC *` columns, for printers that ignore `GS v 0`),
+ * `graphics` (`GS ( L`), `multi_tone` (4-tone `GS ( L`, see `PrinterProfile.multi_tone`) or
+ * `two_color` (black and red `GS ( L` planes, see `PrinterProfile.two_color`).
+ */
+export type ImageCommandMode =
+  | 'raster'
+  | 'bit_image_8'
+  | 'bit_image_24'
+  | 'graphics'
+  | 'multi_tone'
+  | 'two_color'
+
+/** Logo storage/print commands: `FS q`/`FS p` (default) or `GS ( L` NV graphics */
+export type LogoCommandMode = 'nv_bit_image' | 'graphics'
+
+export interface Image extends ImageTuning {
+  /**
+   * Base64 encoded image (with or without data URI prefix), `file://<absolute path>`
+   * (only once the app enables it from Rust with `allow_file_images`),
+   * `resource://<path in the app resources>`, `asset:<name>` for an image
+   * registered with {@link register_image_asset}, or inline SVG markup. SVG
+   * images are drawn at exactly `max_width` (or the paper width).
+   */
   data: string
   /** Max width in pixels. 0 = use full paper width. */
   max_width: number
   align: TextAlign
   dithering: boolean
   size: ImageMode
+  /** Defaults to 'raster'. Ignored inside `set_logo`. */
+  command_mode?: ImageCommandMode
+  /**
+   * Send the image in bands of this many rows, one command each, for printers that
+   * cap the height per command. Rounded down to a multiple of 24 in the `bit_image_*`
+   * modes. The printed result is identical.
+   */
+  band_height?: number
+  /** Pause in milliseconds after each band, for slow transports. Requires `band_height`. */
+  band_delay_ms?: number
+}
+
+/** Base direction of a paragraph: from its first strong character, or forced */
+export type TextDirection = 'auto' | 'ltr' | 'rtl'
+
+/**
+ * Text shaped with a TrueType/OpenType font and printed as a raster image, for
+ * scripts the printer's code pages cannot print (Arabic, Hebrew, Thai,
+ * Devanagari, ...). Lines wrap at word boundaries to the paper width.
+ */
+export interface FontText {
+  text: string
+  /**
+   * Base64 encoded TTF/OTF font file (with or without data URI prefix), or a font
+   * registered with `register_font`.
+   */
+  font: string | RegisteredFont
+  /**
+   * Font size in points (1/72 inch), like the registered font's `size`, which it
+   * overrides. Defaults to 24 dots (about 8.5 pt, the height of native font A).
+   */
+  size?: number
+  /** Defaults to 'right' for right-to-left paragraphs, 'left' otherwise. */
+  align?: TextAlign
+  /** Defaults to 'auto'. */
+  direction?: TextDirection
+  /** Extra space between lines, in dots. Defaults to 0. */
+  line_spacing?: number
 }
 
 export interface Logo {
-  /** NV memory key code (1–255). Ignored when `set_logo` is set. Defaults to 1. */
+  /**
+   * NV memory key code (1–255, or 1–99 in `graphics` mode). Ignored when `set_logo`
+   * is set, except in `graphics` mode where it is the key the image is stored
+   * under. Defaults to 1.
+   */
   key_code?: number
   /** Print mode. Ignored when `set_logo` is set. Defaults to 'normal'. */
   mode?: ImageMode
@@ -419,6 +710,13 @@ export interface Logo {
    * later be printed with a `logo()` section (without `set_logo`).
    */
   set_logo?: Image
+  /** Defaults to 'nv_bit_image'. */
+  command_mode?: LogoCommandMode
+  /**
+   * Two-character NV graphics key (e.g. `'LG'`) used instead of `key_code` in
+   * `graphics` mode. Same keys as {@link store_nv_graphic}.
+   */
+  key?: string
 }
 
 export interface Line {
@@ -456,6 +754,57 @@ export interface PrintAreaWidth {
   value: number
 }
 
+/** Print direction inside a page mode area (`ESC T`). */
+export type PageDirection = 'left_to_right' | 'bottom_to_top' | 'right_to_left' | 'top_to_bottom'
+
+export interface PageElement {
+  /** Left edge, in dots from the page area origin. */
+  x: number
+  /** Top edge, in dots from the page area origin. */
+  y: number
+  /**
+   * Width of the element's box in dots: text wraps to it and codes/images are
+   * limited to it. Defaults to the rest of the page area after `x`.
+   */
+  width?: number
+  /** `Title`, `Subtitle`, `Text`, `Qr`, `Barcode` or `Image`. */
+  content: PrintSections
+}
+
+export interface PageMode {
+  /** Page area origin in dots. Defaults to 0. */
+  x?: number
+  y?: number
+  /** Page area width in dots. Defaults to the paper width. */
+  width?: number
+  /**
+   * Page area height in dots. Defaults to the bottom edge of the lowest element;
+   * required for `bottom_to_top` and `top_to_bottom`.
+   */
+  height?: number
+  /** Defaults to `left_to_right`. Element coordinates follow the rotated page. */
+  direction?: PageDirection
+  elements: PageElement[]
+}
+
+export interface Column {
+  /** Width in dots. Columns without a width share the remaining paper width. */
+  width?: number
+  /** Vertical alignment inside the band. Defaults to `top`. */
+  valign?: 'top' | 'middle' | 'bottom'
+  /**
+   * `Title`, `Subtitle`, `Text`, `Qr`, `Barcode` or `Image`, stacked top to bottom.
+   * Text is wrapped to the column width and images are scaled down to it.
+   */
+  content: PrintSections[]
+}
+
+export interface Columns {
+  columns: Column[]
+  /** Space between columns in dots. Defaults to 16. */
+  gap?: number
+}
+
 // ─── Union type ───────────────────────────────────────────────────────────────
 
 export type PrintSections =
@@ -469,6 +818,8 @@ export type PrintSections =
   | { Drawer: Drawer }
   | { GlobalStyles: GlobalStyles }
   | { Qr: Qr }
+  | { EmvQr: EmvQr }
+  | { SepaQr: SepaQr }
   | { Barcode: Barcode }
   | { Table: Table }
   | { DataMatrix: DataMatrixModel }
@@ -478,6 +829,7 @@ export type PrintSections =
   | { MaxiCode: MaxiCode }
   | { Composite: Composite }
   | { Image: Image }
+  | { FontText: FontText }
   | { Logo: Logo }
   | { Line: Line }
   | { LineSpacing: LineSpacing }
@@ -486,14 +838,123 @@ export type PrintSections =
   | { TabStops: TabStops }
   | { LeftMargin: LeftMargin }
   | { PrintAreaWidth: PrintAreaWidth }
+  | { PageMode: PageMode }
+  | { Columns: Columns }
 
 // ─── Request interfaces ───────────────────────────────────────────────────────
 
+/**
+ * Firmware capabilities of the target printer. Sections with `render_mode: 'auto'`
+ * use it to choose between the native command and software rendering.
+ */
+export interface PrinterProfile {
+  /** The firmware implements QR codes (`GS ( k` cn=49). Defaults to `true`. */
+  native_qr?: boolean
+  /** The firmware implements the classic 1D barcodes (`GS k` 65–73). Defaults to `true`. */
+  native_barcodes?: boolean
+  /** The firmware implements GS1-128 and GS1 DataBar (`GS k` 74–78). Defaults to `true`. */
+  native_gs1_barcodes?: boolean
+  /** The firmware implements DataMatrix (`GS ( k` cn=54). Defaults to `true`. */
+  native_data_matrix?: boolean
+  /** The firmware implements Aztec Code (`GS ( k` cn=53). Defaults to `true`. */
+  native_aztec?: boolean
+  /** The firmware implements PDF417 (`GS ( k` cn=48). Defaults to `true`. */
+  native_pdf417?: boolean
+  /** The firmware implements MaxiCode (`GS ( k` cn=50). Defaults to `true`. */
+  native_maxicode?: boolean
+  /**
+   * The firmware implements download graphics (`GS ( L` fn 83/85): identical `Image`
+   * sections repeated in a job are sent once and printed by key. Defaults to `false`.
+   */
+  download_graphics?: boolean
+  /**
+   * The firmware implements multiple-tone graphics (`GS ( L` fn 112, `a = 52`).
+   * Without it, `multi_tone` images are dithered to black and white. Defaults to `false`.
+   */
+  multi_tone?: boolean
+  /**
+   * The printer has a second (red) color for graphics (`GS ( L` fn 112, `c = 50`).
+   * Without it, `two_color` images are dithered to black and white. Defaults to `false`.
+   */
+  two_color?: boolean
+}
+
 export interface PrintJobRequest {
   printer: string
   sections: PrintSections[]
   options: CodePage
   paper_size: PaperSize
+  /** Defaults to a printer that supports every native command. */
+  profile?: PrinterProfile
+}
+
+/** Request for {@link register_font}. Set either `path` or `data`. */
+export interface RegisterFontRequest {
+  /** Name used by `RegisteredFont.name`. Registering the same name again replaces the font. */
+  name: string
+  /** TTF/OTF file relative to the app's resource directory. Not available on Android. */
+  path?: string
+  /** Base64 encoded TTF/OTF file (with or without data URI prefix) */
+  data?: string
+}
+
+/** Request for {@link register_image_asset}. */
+export interface RegisterImageAssetRequest {
+  /** Name used as `Image.data = 'asset:<name>'`. Registering the same name again replaces the image. */
+  name: string
+  /** The image in any form `Image.data` accepts: base64, `file://` or `resource://`. */
+  data: string
+}
+
+/** Request for the NV graphics commands ({@link store_nv_graphic} and friends). */
+export interface NvGraphicsRequest {
+  printer: string
+  /**
+   * Two printable ASCII characters, e.g. `'LG'`. Required to store and print;
+   * {@link delete_nv_graphic} without a key deletes every stored graphic.
+   */
+  key?: string
+  /** Image to store ({@link store_nv_graphic} only), processed like an `Image` section. */
+  image?: Image
+  /** Print mode for {@link print_nv_graphic}. Defaults to 'normal'. */
+  mode?: ImageMode
+  /** Paper used to size the stored image. Defaults to 'Mm80'. */
+  paper_size?: PaperSize
+}
+
+export interface NvGraphicsKeyList {
+  keys: string[]
+  /**
+   * `false` when the printer still reported more keys after the follow-up
+   * requests; `keys` holds the ones read so far.
+   */
+  complete: boolean
+}
+
+/** NV graphics memory size in bytes. */
+export interface NvGraphicsCapacity {
+  total: number
+  remaining: number
+}
+
+/** A single problem found by {@link validate_print_job}. */
+export interface ValidationIssue {
+  /** Same codes as {@link PrinterErrorCode} for errors; `UNENCODABLE_CHAR`, `IMAGE_TOO_WIDE`, `COLUMN_WIDTHS_IGNORED` for warnings. */
+  code: string
+  /** Zero-based section index, `null` for job-level problems (`printer`, `sections`). */
+  index: number | null
+  /** Section type, e.g. `"Table"`. */
+  kind: string | null
+  /** Location of the offending value, e.g. `sections[2].body[1][0].text`. */
+  path: string
+  message: string
+}
+
+export interface ValidationReport {
+  /** `true` when there are no errors. Warnings don't make a job invalid. */
+  valid: boolean
+  errors: ValidationIssue[]
+  warnings: ValidationIssue[]
 }
 
 export interface PrinterInfo {
@@ -542,13 +1003,13 @@ export interface TestPrintRequest {
 // ─── Helper builders ──────────────────────────────────────────────────────────
 
 /** Creates a Title section */
-export function title(text: string, styles?: GlobalStyles): PrintSections {
-  return { Title: { text, styles } }
+export function title(text: string, styles?: GlobalStyles, font?: RegisteredFont): PrintSections {
+  return { Title: { text, styles, font } }
 }
 
 /** Creates a Subtitle section */
-export function subtitle(text: string, styles?: GlobalStyles): PrintSections {
-  return { Subtitle: { text, styles } }
+export function subtitle(text: string, styles?: GlobalStyles, font?: RegisteredFont): PrintSections {
+  return { Subtitle: { text, styles, font } }
 }
 
 /** Creates a Text section */
@@ -660,6 +1121,9 @@ export function qr(
     error_correction?: QrErrorCorrection
     model?: 1 | 2
     align?: TextAlign
+    render_mode?: RenderMode
+    target_width?: number
+    target_width_mm?: number
   },
 ): PrintSections {
   return {
@@ -669,16 +1133,34 @@ export function qr(
       error_correction: options?.error_correction ?? 'M',
       model: options?.model ?? 2,
       align: options?.align,
+      render_mode: options?.render_mode,
+      target_width: options?.target_width,
+      target_width_mm: options?.target_width_mm,
     },
   }
 }
 
+/**
+ * Creates an EMVCo merchant-presented payment QR (PIX, UPI, ...). The payload
+ * and its CRC16 are built by the backend.
+ */
+export function emvQr(payment: EmvQr): PrintSections {
+  return { EmvQr: payment }
+}
+
+/** Creates an EPC069-12 SEPA credit transfer QR. */
+export function sepaQr(payment: SepaQr): PrintSections {
+  return { SepaQr: payment }
+}
+
 /** Options shared by every barcode factory. */
 export interface BarcodeOptions {
   width?: number
   height?: number
   text_position?: BarcodeTextPosition
   align?: TextAlign
+  render_mode?: RenderMode
+  auto_check_digit?: boolean
 }
 
 function makeBarcodeSection(
@@ -694,6 +1176,8 @@ function makeBarcodeSection(
       height: options?.height ?? 80,
       text_position: options?.text_position ?? 'below',
       align: options?.align,
+      render_mode: options?.render_mode,
+      auto_check_digit: options?.auto_check_digit,
     },
   }
 }
@@ -767,11 +1251,16 @@ export function gs1Barcode(
 }
 
 /** Creates a DataMatrix section */
-export function dataMatrix(data: string, size: number = 6): PrintSections {
+export function dataMatrix(
+  data: string,
+  size: number = 6,
+  options?: { render_mode?: RenderMode },
+): PrintSections {
   return {
     DataMatrix: {
       data,
       size,
+      render_mode: options?.render_mode,
     },
   }
 }
@@ -785,6 +1274,7 @@ export function pdf417(
     width?: number
     height?: number
     error_correction?: number
+    render_mode?: RenderMode
   },
 ): PrintSections {
   return {
@@ -795,6 +1285,7 @@ export function pdf417(
       width: options?.width ?? 2,
       height: options?.height ?? 3,
       error_correction: options?.error_correction ?? 2,
+      render_mode: options?.render_mode,
     },
   }
 }
@@ -811,6 +1302,7 @@ export function aztec(
     size?: number
     error_correction?: number
     align?: TextAlign
+    render_mode?: RenderMode
   },
 ): PrintSections {
   return {
@@ -821,6 +1313,7 @@ export function aztec(
       size: options?.size ?? 3,
       error_correction: options?.error_correction ?? 23,
       align: options?.align,
+      render_mode: options?.render_mode,
     },
   }
 }
@@ -856,6 +1349,7 @@ export function maxicode(
   options?: {
     mode?: number
     align?: TextAlign
+    render_mode?: RenderMode
   },
 ): PrintSections {
   return {
@@ -863,6 +1357,7 @@ export function maxicode(
       data,
       mode: options?.mode ?? 4,
       align: options?.align,
+      render_mode: options?.render_mode,
     },
   }
 }
@@ -893,15 +1388,19 @@ export function composite(
 /** Creates an Image section */
 export function image(
   data: string,
-  options?: {
+  options?: ImageTuning & {
     max_width?: number
     align?: TextAlign
     dithering?: boolean
     size?: ImageMode
+    command_mode?: ImageCommandMode
+    band_height?: number
+    band_delay_ms?: number
   },
 ): PrintSections {
   return {
     Image: {
+      ...options,
       data,
       max_width: options?.max_width ?? 0,
       align: options?.align ?? 'center',
@@ -911,12 +1410,29 @@ export function image(
   }
 }
 
+/**
+ * Creates a FontText section: `text` is rendered with `font` (base64 TTF/OTF or a
+ * registered font) and printed as an image.
+ */
+export function fontText(
+  text: string,
+  font: string | RegisteredFont,
+  options?: Omit<FontText, 'text' | 'font'>,
+): PrintSections {
+  return { FontText: { ...options, text, font } }
+}
+
 /** Creates a Logo section that prints a logo already stored in NV memory (`FS p`). */
-export function logo(key_code: number = 1, mode: ImageMode = 'normal'): PrintSections {
+export function logo(
+  key_code: number = 1,
+  mode: ImageMode = 'normal',
+  command_mode?: LogoCommandMode,
+): PrintSections {
   return {
     Logo: {
       key_code,
       mode,
+      command_mode,
     },
   }
 }
@@ -928,14 +1444,24 @@ export function logo(key_code: number = 1, mode: ImageMode = 'normal'): PrintSec
  */
 export function setLogo(
   data: string,
-  options?: {
+  options?: ImageTuning & {
     max_width?: number
     dithering?: boolean
+    /** Key to store under in 'graphics' mode (1–99). Defaults to 1. */
+    key_code?: number
+    /** Two-character key for 'graphics' mode; overrides `key_code`. */
+    key?: string
+    command_mode?: LogoCommandMode
   },
 ): PrintSections {
+  const { key_code, key, command_mode, ...tuning } = options ?? {}
   return {
     Logo: {
+      key_code,
+      key,
+      command_mode,
       set_logo: {
+        ...tuning,
         data,
         max_width: options?.max_width ?? 0,
         align: 'left',
@@ -946,11 +1472,100 @@ export function setLogo(
   }
 }
 
+/**
+ * Places `content` at `x`/`y` dots inside a `pageMode()` section, optionally in a
+ * box `width` dots wide
+ */
+export function at(x: number, y: number, content: PrintSections, width?: number): PageElement {
+  return { x, y, width, content }
+}
+
+/**
+ * Creates a PageMode section (`ESC L` … `ESC FF`): elements are printed at
+ * absolute dot positions, optionally rotated with `direction`.
+ */
+export function pageMode(
+  elements: PageElement[],
+  options?: {
+    x?: number
+    y?: number
+    width?: number
+    height?: number
+    direction?: PageDirection
+  },
+): PrintSections {
+  return {
+    PageMode: {
+      x: options?.x,
+      y: options?.y,
+      width: options?.width,
+      height: options?.height,
+      direction: options?.direction,
+      elements,
+    },
+  }
+}
+
+/** Creates a column for `columns()` */
+export function column(
+  content: PrintSections[],
+  options?: { width?: number; valign?: 'top' | 'middle' | 'bottom' },
+): Column {
+  return { width: options?.width, valign: options?.valign, content }
+}
+
+/**
+ * Creates a Columns section: side-by-side columns printed in one band (page mode),
+ * e.g. a QR code next to the order details. The band height is computed automatically.
+ */
+export function columns(cols: Column[], gap?: number): PrintSections {
+  return { Columns: { columns: cols, gap } }
+}
+
+// ─── Errors ───────────────────────────────────────────────────────────────────
+
+/** Stable error codes. New codes may be added; existing ones never change. */
+export type PrinterErrorCode =
+  | 'IO'
+  | 'UNSUPPORTED_PLATFORM'
+  | 'PLUGIN_INVOKE'
+  | 'INVALID_JOB'
+  | 'INVALID_SECTION'
+  | 'ENCODING_FAILED'
+  | 'IMAGE_DECODE'
+  | 'PRINTER_NOT_FOUND'
+  | 'TRANSPORT_TIMEOUT'
+  | 'PAPER_OUT'
+
+/**
+ * Error rejected by every command. Key localized messages on `code`;
+ * `retryable` tells whether re-sending the same job may succeed.
+ */
+export interface PrinterError {
+  code: PrinterErrorCode
+  message: string
+  retryable: boolean
+  /** Index of the failing section (`INVALID_SECTION`, `ENCODING_FAILED`, `IMAGE_DECODE`). */
+  index?: number
+  /** Section type, e.g. `"Barcode"` (`INVALID_SECTION`). */
+  kind?: string
+  reason?: string
+  /** Character that could not be encoded (`ENCODING_FAILED`). */
+  char?: string
+  /** Printer name or identifier (`PRINTER_NOT_FOUND`). */
+  printer?: string
+}
+
+/** Narrows an unknown rejection value to a {@link PrinterError}. */
+export function isPrinterError(error: unknown): error is PrinterError {
+  return typeof error === 'object' && error !== null && 'code' in error && 'message' in error
+}
+
 // ─── Commands ─────────────────────────────────────────────────────────────────
 
 /**
  * Sends a print job to the specified thermal printer.
- * @throws {string} Error message from the printer or document generation if the job fails.
+ * @throws {PrinterError} If document generation or printing fails.
  */
 export async function print_thermal_printer(printJobRequest: PrintJobRequest): Promise<void> {
   await invoke('plugin:thermal-printer|print_thermal_printer', {
@@ -960,7 +1575,7 @@ export async function print_thermal_printer(printJobRequest: PrintJobRequest): P
 
 /**
  * Returns the list of available thermal printers on the current system.
- * @throws {string} Error message if printer enumeration fails.
+ * @throws {PrinterError} If printer enumeration fails.
  */
 export async function list_thermal_printers(): Promise<PrinterInfo[]> {
   return await invoke<PrinterInfo[]>('plugin:thermal-printer|list_thermal_printers')
@@ -968,10 +1583,100 @@ export async function list_thermal_printers(): Promise<PrinterInfo[]> {
 
 /**
  * Sends a test print job to verify the printer is working correctly.
- * @throws {string} Error message from the printer or document generation if the job fails.
+ * @throws {PrinterError} If document generation or printing fails.
  */
 export async function test_thermal_printer(testPrintRequest: TestPrintRequest): Promise<void> {
   await invoke('plugin:thermal-printer|test_thermal_printer', {
     printTestRequest: testPrintRequest,
   })
 }
+
+/**
+ * Checks a print job without printing it and reports every error and warning,
+ * each with its section index and path.
+ * @throws {PrinterError} Only if the command itself cannot run.
+ */
+export async function validate_print_job(printJobRequest: PrintJobRequest): Promise<ValidationReport> {
+  return await invoke<ValidationReport>('plugin:thermal-printer|validate_print_job', {
+    printJobRequest,
+  })
+}
+
+/**
+ * Stores an image in the printer's NV graphics memory (`GS ( L` fn 67) under a
+ * two-character key. It survives power-off and can be printed with {@link print_nv_graphic}
+ * or a `Logo` section in `graphics` mode.
+ * @throws {PrinterError} If the key or image is invalid or printing fails.
+ */
+export async function store_nv_graphic(nvGraphicsRequest: NvGraphicsRequest): Promise<void> {
+  await invoke('plugin:thermal-printer|store_nv_graphic', {
+    nvGraphicsRequest,
+  })
+}
+
+/**
+ * Prints a stored NV graphic by key (`GS ( L` fn 69).
+ * @throws {PrinterError} If the key is invalid or printing fails.
+ */
+export async function print_nv_graphic(nvGraphicsRequest: NvGraphicsRequest): Promise<void> {
+  await invoke('plugin:thermal-printer|print_nv_graphic', {
+    nvGraphicsRequest,
+  })
+}
+
+/**
+ * Deletes the NV graphic `key` (`GS ( L` fn 66), or every NV graphic when `key`
+ * is omitted (fn 65).
+ * @throws {PrinterError} If the key is invalid or printing fails.
+ */
+export async function delete_nv_graphic(nvGraphicsRequest: NvGraphicsRequest): Promise<void> {
+  await invoke('plugin:thermal-printer|delete_nv_graphic', {
+    nvGraphicsRequest,
+  })
+}
+
+/**
+ * Reads the keys defined in NV graphics memory. Needs a printer that can answer:
+ * CUPS `socket://` queues on Linux/macOS, network and Bluetooth printers on Android,
+ * network printers on iOS. Keys that don't fit in one answer are requested again
+ * until the printer sends the last block.
+ * @throws {PrinterError} `UNSUPPORTED_PLATFORM` on other connections, `TRANSPORT_TIMEOUT` if the printer does not answer.
+ */
+export async function list_nv_graphics(nvGraphicsRequest: NvGraphicsRequest): Promise<NvGraphicsKeyList> {
+  return await invoke<NvGraphicsKeyList>('plugin:thermal-printer|list_nv_graphics', {
+    nvGraphicsRequest,
+  })
+}
+
+/**
+ * Reads the total and remaining NV graphics memory in bytes. Same connection
+ * requirements as {@link list_nv_graphics}.
+ * @throws {PrinterError} `UNSUPPORTED_PLATFORM` on other connections, `TRANSPORT_TIMEOUT` if the printer does not answer.
+ */
+export async function nv_graphics_capacity(nvGraphicsRequest: NvGraphicsRequest): Promise<NvGraphicsCapacity> {
+  return await invoke<NvGraphicsCapacity>('plugin:thermal-printer|nv_graphics_capacity', {
+    nvGraphicsRequest,
+  })
+}
+
+/**
+ * Loads a font for Title/Subtitle sections with `font`. Fonts stay registered
+ * until the app exits.
+ * @throws {PrinterError} `INVALID_JOB` if the file is not a TTF/OTF font, `IO` if the path can't be read, `UNSUPPORTED_PLATFORM` for `path` on Android.
+ */
+export async function register_font(registerFontRequest: RegisterFontRequest): Promise<void> {
+  await invoke('plugin:thermal-printer|register_font', {
+    registerFontRequest,
+  })
+}
+
+/**
+ * Loads an image once so print jobs can refer to it as `asset:<name>` in
+ * `Image.data`. Assets stay registered until the app exits.
+ * @throws {PrinterError} `INVALID_JOB` if the image can't be read or decoded.
+ */
+export async function register_image_asset(registerImageAssetRequest: RegisterImageAssetRequest): Promise<void> {
+  await invoke('plugin:thermal-printer|register_image_asset', {
+    registerImageAssetRequest,
+  })
+}
@@ -18,11 +18,18 @@ final class BLEPrinter: NSObject, CBCentralManagerDelegate, CBPeripheralDelegate
   private var writeType: CBCharacteristicWriteType = .withoutResponse
 
   private var payload = Data()
-  private var chunks: [Data] = []
+  private var pauses: [PrintPause] = []
+  private var chunks: [(data: Data, delay: TimeInterval)] = []
+  private var pendingDelay: TimeInterval = 0
   private var completion: ((Error?) -> Void)?
   private var finished = false
 
-  func printRawData(identifier: String, data: Data, completion: @escaping (Error?) -> Void) {
+  func printRawData(
+    identifier: String,
+    data: Data,
+    pauses: [PrintPause] = [],
+    completion: @escaping (Error?) -> Void
+  ) {
     let uuidString = String(identifier.dropFirst("BLE:".count))
     guard let uuid = UUID(uuidString: uuidString) else {
       completion(Self.error("Invalid BLE identifier: \(identifier)"))
@@ -32,10 +39,12 @@ final class BLEPrinter: NSObject, CBCentralManagerDelegate, CBPeripheralDelegate
     queue.async {
       self.targetUUID = uuid
       self.payload = data
+      self.pauses = pauses
       self.completion = completion
       self.central = CBCentralManager(delegate: self, queue: self.queue)
 
-      self.queue.asyncAfter(deadline: .now() + Self.timeoutSeconds) { [weak self] in
+      let timeout = Self.timeoutSeconds + PrintPause.totalDelay(pauses)
+      self.queue.asyncAfter(deadline: .now() + timeout) { [weak self] in
         self?.finish(Self.error("BLE operation timeout"))
       }
     }
@@ -140,7 +149,9 @@ final class BLEPrinter: NSObject, CBCentralManagerDelegate, CBPeripheralDelegate
     error: Error?
   ) {
     if let error = error { finish(error); return }
-    writeNext(peripheral, characteristic)
+    queue.asyncAfter(deadline: .now() + pendingDelay) { [weak self] in
+      self?.writeNext(peripheral, characteristic)
+    }
   }
 
   // MARK: - Writing
@@ -149,12 +160,16 @@ final class BLEPrinter: NSObject, CBCentralManagerDelegate, CBPeripheralDelegate
     let mtu = peripheral.maximumWriteValueLength(for: writeType)
     let chunkSize = max(20, min(mtu, 180))
 
+    // Each paced segment is split into MTU-sized chunks; its last chunk carries the pause.
     chunks = []
-    var offset = 0
-    while offset < payload.count {
-      let end = min(offset + chunkSize, payload.count)
-      chunks.append(payload.subdata(in: offset..<end))
-      offset = end
+    for segment in PrintPause.segments(payload, pauses) {
+      var offset = segment.data.startIndex
+      while offset < segment.data.endIndex {
+        let end = min(offset + chunkSize, segment.data.endIndex)
+        let delay = end == segment.data.endIndex ? segment.delay : 0
+        chunks.append((segment.data.subdata(in: offset..<end), delay))
+        offset = end
+      }
     }
 
     writeNext(peripheral, characteristic)
@@ -168,11 +183,12 @@ final class BLEPrinter: NSObject, CBCentralManagerDelegate, CBPeripheralDelegate
     }
 
     let chunk = chunks.removeFirst()
-    peripheral.writeValue(chunk, for: characteristic, type: writeType)
+    pendingDelay = chunk.delay
+    peripheral.writeValue(chunk.data, for: characteristic, type: writeType)
 
     // For writeWithoutResponse there is no ACK callback — pace the stream slightly.
     if writeType == .withoutResponse {
-      queue.asyncAfter(deadline: .now() + 0.02) { [weak self] in
+      queue.asyncAfter(deadline: .now() + 0.02 + chunk.delay) { [weak self] in
         self?.writeNext(peripheral, characteristic)
       }
     }
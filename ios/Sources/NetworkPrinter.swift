@@ -10,7 +10,12 @@ final class NetworkPrinter {
   private var finished = false
   private let lock = NSLock()
 
-  func printRawData(identifier: String, data: Data, completion: @escaping (Error?) -> Void) {
+  func printRawData(
+    identifier: String,
+    data: Data,
+    pauses: [PrintPause] = [],
+    completion: @escaping (Error?) -> Void
+  ) {
     guard let sep = identifier.lastIndex(of: ":") else {
       completion(Self.error("Invalid network identifier: \(identifier)"))
       return
@@ -39,9 +44,7 @@ final class NetworkPrinter {
     connection.stateUpdateHandler = { state in
       switch state {
       case .ready:
-        connection.send(content: data, completion: .contentProcessed { sendError in
-          finish(sendError)
-        })
+        Self.send(PrintPause.segments(data, pauses)[...], over: connection, finish: finish)
       case .failed(let error):
         finish(error)
       case .cancelled:
@@ -52,13 +55,110 @@ final class NetworkPrinter {
     }
 
     // Guard against a connection that never becomes ready.
-    DispatchQueue.global().asyncAfter(deadline: .now() + Self.timeoutSeconds) {
+    let timeout = Self.timeoutSeconds + PrintPause.totalDelay(pauses)
+    DispatchQueue.global().asyncAfter(deadline: .now() + timeout) {
       finish(Self.error("Network connection timeout"))
     }
 
     connection.start(queue: .global())
   }
 
+  /// Sends `data` and reads until `responses` NUL-terminated answers have arrived
+  /// (see `query_raw_data`).
+  func queryRawData(
+    identifier: String,
+    data: Data,
+    responses: Int,
+    completion: @escaping (Data?, Error?) -> Void
+  ) {
+    guard let sep = identifier.lastIndex(of: ":"),
+      let portNumber = UInt16(identifier[identifier.index(after: sep)...]),
+      let port = NWEndpoint.Port(rawValue: portNumber)
+    else {
+      completion(nil, Self.error("Invalid network identifier: \(identifier)"))
+      return
+    }
+    let host = NWEndpoint.Host(String(identifier[identifier.startIndex..<sep]))
+
+    let connection = NWConnection(host: host, port: port, using: .tcp)
+    self.connection = connection
+
+    let finish: (Data?, Error?) -> Void = { [weak self] answer, error in
+      guard let self = self else { return }
+      self.lock.lock()
+      if self.finished { self.lock.unlock(); return }
+      self.finished = true
+      self.lock.unlock()
+      connection.cancel()
+      completion(answer, error)
+    }
+
+    var answer = Data()
+    func receive() {
+      connection.receive(minimumIncompleteLength: 1, maximumLength: 256) { chunk, _, isComplete, error in
+        if let error = error {
+          finish(nil, error)
+          return
+        }
+        if let chunk = chunk { answer.append(chunk) }
+        if answer.filter({ $0 == 0 }).count >= responses || isComplete {
+          finish(answer, nil)
+        } else {
+          receive()
+        }
+      }
+    }
+
+    connection.stateUpdateHandler = { state in
+      switch state {
+      case .ready:
+        connection.send(content: data, completion: .contentProcessed { sendError in
+          if let sendError = sendError {
+            finish(nil, sendError)
+          } else {
+            receive()
+          }
+        })
+      case .failed(let error):
+        finish(nil, error)
+      default:
+        break
+      }
+    }
+
+    DispatchQueue.global().asyncAfter(deadline: .now() + Self.timeoutSeconds) {
+      finish(nil, Self.error("Network query timeout"))
+    }
+
+    connection.start(queue: .global())
+  }
+
+  /// Sends the segments in order, waiting each segment's delay before the next one.
+  private static func send(
+    _ segments: ArraySlice<(data: Data, delay: TimeInterval)>,
+    over connection: NWConnection,
+    finish: @escaping (Error?) -> Void
+  ) {
+    guard let segment = segments.first else {
+      finish(nil)
+      return
+    }
+    connection.send(content: segment.data, completion: .contentProcessed { sendError in
+      if let sendError = sendError {
+        finish(sendError)
+        return
+      }
+      let rest = segments.dropFirst()
+      if rest.isEmpty {
+        finish(nil)
+      } else {
+        DispatchQueue.global().asyncAfter(deadline: .now() + segment.delay) {
+          Self.send(rest, over: connection, finish: finish)
+        }
+      }
+    })
+  }
+
   private static let timeoutSeconds: TimeInterval = 10.0
 
   private static func error(_ message: String) -> NSError {
@@ -7,6 +7,46 @@ class PrintRawArgs: Decodable {
   let identifier: String
   /// Final ESC/POS payload, Base64-encoded by the Rust side (see `src/mobile.rs`).
   let data: String
+  /// Byte offsets where the transport waits before writing the rest.
+  let pauses: [PrintPause]?
+}
+
+class QueryRawArgs: Decodable {
+  let identifier: String
+  /// Request bytes, Base64-encoded like `PrintRawArgs.data`.
+  let data: String
+  /// Number of NUL-terminated answers to read.
+  let responses: Int
+}
+
+/// Pause requested by the Rust side (`PrintPause`): after the first `offset` bytes
+/// the transport waits `delayMs` before writing the rest. Used to pace tall images.
+struct PrintPause: Decodable {
+  let offset: Int
+  let delayMs: Int
+
+  enum CodingKeys: String, CodingKey {
+    case offset
+    case delayMs = "delay_ms"
+  }
+
+  /// Splits `data` at `pauses`; each segment carries the delay (seconds) to wait after it.
+  static func segments(_ data: Data, _ pauses: [PrintPause]) -> [(data: Data, delay: TimeInterval)] {
+    var segments: [(data: Data, delay: TimeInterval)] = []
+    var start = 0
+    for pause in pauses {
+      let end = min(max(pause.offset, start), data.count)
+      segments.append((data.subdata(in: start..<end), TimeInterval(pause.delayMs) / 1000))
+      start = end
+    }
+    segments.append((data.subdata(in: start..<data.count), 0))
+    return segments
+  }
+
+  /// Total time spent waiting, added to the transport timeouts.
+  static func totalDelay(_ pauses: [PrintPause]) -> TimeInterval {
+    TimeInterval(pauses.reduce(0) { $0 + $1.delayMs }) / 1000
+  }
 }
 
 /// iOS transport layer for the thermal-printer plugin.
@@ -64,11 +104,12 @@ class ThermalPrinterPlugin: Plugin {
     }
 
     let identifier = args.identifier
+    let pauses = args.pauses ?? []
 
     if identifier.hasPrefix("BLE:") {
       let printer = BLEPrinter()
       self.blePrinter = printer
-      printer.printRawData(identifier: identifier, data: data) { error in
+      printer.printRawData(identifier: identifier, data: data, pauses: pauses) { error in
         if let error = error {
           invoke.reject("Print error: \(error.localizedDescription)")
         } else {
@@ -78,7 +119,7 @@ class ThermalPrinterPlugin: Plugin {
     } else if identifier.contains(":") {
       let printer = NetworkPrinter()
       self.networkPrinter = printer
-      printer.printRawData(identifier: identifier, data: data) { error in
+      printer.printRawData(identifier: identifier, data: data, pauses: pauses) { error in
         if let error = error {
           invoke.reject("Print error: \(error.localizedDescription)")
         } else {
@@ -86,7 +127,37 @@ class ThermalPrinterPlugin: Plugin {
         }
       }
     } else {
-      invoke.reject("Unrecognized printer identifier: \(identifier)")
+      invoke.reject("Unrecognized printer identifier: \(identifier)", code: "PRINTER_NOT_FOUND")
+    }
+  }
+
+  // ─────────────────────────────────────────────────────────────
+  // query_raw_data — sends a request and returns the printer's answer
+  // ─────────────────────────────────────────────────────────────
+
+  @objc public func query_raw_data(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(QueryRawArgs.self)
+
+    guard let data = Data(base64Encoded: args.data) else {
+      invoke.reject("Invalid query data encoding")
+      return
+    }
+
+    let identifier = args.identifier
+    if identifier.hasPrefix("BLE:") {
+      invoke.reject("Reading from BLE printers is not supported", code: "UNSUPPORTED_PLATFORM")
+    } else if identifier.contains(":") {
+      let printer = NetworkPrinter()
+      self.networkPrinter = printer
+      printer.queryRawData(identifier: identifier, data: data, responses: args.responses) { answer, error in
+        if let answer = answer {
+          invoke.resolve(["data": answer.base64EncodedString()])
+        } else {
+          invoke.reject("Query error: \(error?.localizedDescription ?? "no answer")")
+        }
+      }
+    } else {
+      invoke.reject("Unrecognized printer identifier: \(identifier)", code: "PRINTER_NOT_FOUND")
     }
   }
 }
@@ -26,3 +26,71 @@ pub async fn test_thermal_printer<R: Runtime>(
     app.thermal_printer()
         .test_thermal_printer(print_test_request)
 }
+
+#[command]
+pub async fn validate_print_job<R: Runtime>(
+    app: AppHandle<R>,
+    print_job_request: PrintJobRequest,
+) -> Result<ValidationReport> {
+    app.thermal_printer()
+        .validate_print_job(print_job_request)
+}
+
+#[command]
+pub async fn store_nv_graphic<R: Runtime>(
+    app: AppHandle<R>,
+    nv_graphics_request: NvGraphicsRequest,
+) -> Result<()> {
+    app.thermal_printer().store_nv_graphic(nv_graphics_request)
+}
+
+#[command]
+pub async fn print_nv_graphic<R: Runtime>(
+    app: AppHandle<R>,
+    nv_graphics_request: NvGraphicsRequest,
+) -> Result<()> {
+    app.thermal_printer().print_nv_graphic(nv_graphics_request)
+}
+
+#[command]
+pub async fn delete_nv_graphic<R: Runtime>(
+    app: AppHandle<R>,
+    nv_graphics_request: NvGraphicsRequest,
+) -> Result<()> {
+    app.thermal_printer().delete_nv_graphic(nv_graphics_request)
+}
+
+#[command]
+pub async fn list_nv_graphics<R: Runtime>(
+    app: AppHandle<R>,
+    nv_graphics_request: NvGraphicsRequest,
+) -> Result<NvGraphicsKeyList> {
+    app.thermal_printer().list_nv_graphics(nv_graphics_request)
+}
+
+#[command]
+pub async fn nv_graphics_capacity<R: Runtime>(
+    app: AppHandle<R>,
+    nv_graphics_request: NvGraphicsRequest,
+) -> Result<NvGraphicsCapacity> {
+    app.thermal_printer()
+        .nv_graphics_capacity(nv_graphics_request)
+}
+
+#[command]
+pub async fn register_font<R: Runtime>(
+    app: AppHandle<R>,
+    register_font_request: RegisterFontRequest,
+) -> Result<()> {
+    app.thermal_printer()
+        .register_font(register_font_request)
+}
+
+#[command]
+pub async fn register_image_asset<R: Runtime>(
+    app: AppHandle<R>,
+    register_image_asset_request: RegisterImageAssetRequest,
+) -> Result<()> {
+    app.thermal_printer()
+        .register_image_asset(register_image_asset_request)
+}
@@ -1,5 +1,9 @@
+use super::aztec_encoder::{self, AztecOptions};
+use crate::commands_esc_pos::codes::render_mode::RenderMode;
 use crate::commands_esc_pos::text::text_type::get_styles_diff;
+use crate::error::SectionError;
 use crate::models::print_sections::{Aztec as AztecSection, GlobalStyles};
+use crate::models::printer_profile::PrinterProfile;
 
 /// Constructor de comandos para códigos Aztec
 ///
@@ -61,6 +65,20 @@ impl Aztec {
         self
     }
 
+    /// Genera el Aztec como imagen raster (`GS v 0`) codificado en Rust, con el
+    /// mismo modo, capas y corrección que el comando nativo y `size` puntos por módulo.
+    pub fn get_raster_command(&self) -> Result<Vec<u8>, SectionError> {
+        let symbol = aztec_encoder::encode(
+            self.data.as_bytes(),
+            AztecOptions {
+                compact: self.mode == 1,
+                layers: self.layers as u32,
+                error_correction: self.error_correction as u32,
+            },
+        )?;
+        Ok(symbol.raster_command(self.size as u32))
+    }
+
     /// Genera el comando ESC/POS para Aztec
     ///
     /// NOTA: Aztec Code no es soportado por todas las impresoras térmicas.
@@ -139,28 +157,44 @@ impl Aztec {
     }
 }
 
-/// Procesa sección Aztec del modelo de impresión
-pub fn process_section(aztec: &AztecSection, current_styles: &GlobalStyles) -> Result<Vec<u8>, String> {
+/// Valida una sección Aztec y construye el comando equivalente
+pub fn from_section(aztec: &AztecSection) -> Result<Aztec, SectionError> {
     if aztec.data.is_empty() {
-        return Err("Aztec data cannot be empty".to_string());
+        return Err("Aztec data cannot be empty".into());
     }
 
-    let esc_pos_aztec = Aztec::new(aztec.data.clone())
+    Ok(Aztec::new(aztec.data.clone())
         .set_mode(aztec.mode)
         .set_layers(aztec.layers)
         .set_size(aztec.size)
-        .set_error_correction(aztec.error_correction);
+        .set_error_correction(aztec.error_correction))
+}
+
+/// Procesa sección Aztec del modelo de impresión (nativo o raster según
+/// `render_mode` y el perfil)
+pub fn process_section(
+    aztec: &AztecSection,
+    current_styles: &GlobalStyles,
+    profile: &PrinterProfile,
+) -> Result<Vec<u8>, SectionError> {
+    let esc_pos_aztec = from_section(aztec)?;
+    let aztec_command =
+        if RenderMode::from_name(aztec.render_mode.as_deref())?.is_raster(profile.native_aztec) {
+            esc_pos_aztec.get_raster_command()?
+        } else {
+            esc_pos_aztec.get_command()
+        };
 
     let mut data = Vec::new();
     if let Some(ref align) = aztec.align {
         let mut temp_styles = current_styles.clone();
         temp_styles.align = Some(align.clone());
         data.extend_from_slice(&get_styles_diff(current_styles, &temp_styles));
-        data.extend_from_slice(&esc_pos_aztec.get_command());
+        data.extend_from_slice(&aztec_command);
         data.extend_from_slice(b"\n");
         data.extend_from_slice(&get_styles_diff(&temp_styles, current_styles));
     } else {
-        data.extend_from_slice(&esc_pos_aztec.get_command());
+        data.extend_from_slice(&aztec_command);
         data.extend_from_slice(b"\n");
     }
 
@@ -0,0 +1,394 @@
+//! Codificador Aztec Code (ISO/IEC 24778) para el renderizado raster.
+//!
+//! Codificación de alto nivel simplificada: letras mayúsculas y espacios en modo
+//! Upper, series de dígitos en modo Digit y el resto de bytes con Binary Shift.
+//! El resultado es un símbolo válido aunque no siempre el más compacto posible.
+
+use crate::commands_esc_pos::codes::matrix_symbol::MatrixSymbol;
+use crate::commands_esc_pos::codes::reed_solomon::GaloisField;
+use crate::error::SectionError;
+
+/// Capas máximas en modo compacto y en modo completo
+const MAX_COMPACT_LAYERS: u32 = 4;
+const MAX_FULL_LAYERS: u32 = 32;
+
+// Palabras de control de la codificación de alto nivel
+const UPPER_DIGIT_LATCH: u32 = 30; // D/L desde Upper (5 bits)
+const UPPER_BINARY_SHIFT: u32 = 31; // B/S desde Upper (5 bits)
+const DIGIT_UPPER_LATCH: u32 = 14; // U/L desde Digit (4 bits)
+/// Longitud máxima de un Binary Shift (31 + 2^11 - 1)
+const MAX_BINARY_SHIFT: usize = 2078;
+
+/// Parámetros del símbolo (equivalentes a las funciones 65 y 69 de `GS ( k`)
+#[derive(Debug, Clone, Copy)]
+pub struct AztecOptions {
+    /// `true` = modo compacto (1-4 capas), `false` = modo completo (1-32 capas)
+    pub compact: bool,
+    /// Número de capas; 0 = el menor que admita los datos
+    pub layers: u32,
+    /// Porcentaje mínimo de la capacidad dedicado a corrección de errores
+    pub error_correction: u32,
+}
+
+/// Codifica `data` en un símbolo Aztec
+pub fn encode(data: &[u8], options: AztecOptions) -> Result<MatrixSymbol, SectionError> {
+    if data.is_empty() {
+        return Err("Aztec data cannot be empty".into());
+    }
+
+    let bits = high_level_encode(data);
+    let ecc_bits = bits.len() * options.error_correction as usize / 100 + 11;
+    let max_layers = if options.compact {
+        MAX_COMPACT_LAYERS
+    } else {
+        MAX_FULL_LAYERS
+    };
+
+    if options.layers > max_layers {
+        return Err(format!(
+            "Aztec {} symbols have at most {} layers, got {}",
+            mode_name(options.compact),
+            max_layers,
+            options.layers
+        )
+        .into());
+    }
+
+    let candidates = if options.layers == 0 {
+        1..=max_layers
+    } else {
+        options.layers..=options.layers
+    };
+
+    for layers in candidates {
+        let word_size = word_size(layers);
+        let total_bits = total_bits_in_layers(layers, options.compact);
+        let stuffed = stuff_bits(&bits, word_size);
+        let usable_bits = total_bits - total_bits % word_size;
+        let message_words = stuffed.len() / word_size;
+
+        // En modo compacto el mensaje de modo solo tiene 6 bits para el tamaño
+        if options.compact && message_words > 64 {
+            continue;
+        }
+        if stuffed.len() + ecc_bits > usable_bits {
+            continue;
+        }
+
+        let message = check_words(&stuffed, total_bits, word_size);
+        let mode_message = mode_message(options.compact, layers, message_words);
+        return Ok(build_matrix(
+            options.compact,
+            layers,
+            &message,
+            &mode_message,
+        ));
+    }
+
+    Err(if options.layers == 0 {
+        format!(
+            "Aztec data does not fit in any {} symbol with {}% error correction",
+            mode_name(options.compact),
+            options.error_correction
+        )
+    } else {
+        format!(
+            "Aztec data does not fit in a {} symbol with {} layers and {}% error correction",
+            mode_name(options.compact),
+            options.layers,
+            options.error_correction
+        )
+    }
+    .into())
+}
+
+fn mode_name(compact: bool) -> &'static str {
+    if compact {
+        "compact"
+    } else {
+        "full-range"
+    }
+}
+
+/// Bits de tamaño de palabra según el número de capas
+fn word_size(layers: u32) -> usize {
+    match layers {
+        0..=2 => 6,
+        3..=8 => 8,
+        9..=22 => 10,
+        _ => 12,
+    }
+}
+
+fn total_bits_in_layers(layers: u32, compact: bool) -> usize {
+    let base = if compact { 88 } else { 112 };
+    ((base + 16 * layers) * layers) as usize
+}
+
+fn field(word_size: usize) -> GaloisField {
+    match word_size {
+        4 => GaloisField::new(0x13, 16),
+        6 => GaloisField::new(0x43, 64),
+        8 => GaloisField::new(0x12D, 256),
+        10 => GaloisField::new(0x409, 1024),
+        _ => GaloisField::new(0x1069, 4096),
+    }
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: u32, count: usize) {
+    for i in (0..count).rev() {
+        bits.push(value >> i & 1 == 1);
+    }
+}
+
+fn upper_code(byte: u8) -> Option<u32> {
+    match byte {
+        b' ' => Some(1),
+        b'A'..=b'Z' => Some((byte - b'A') as u32 + 2),
+        _ => None,
+    }
+}
+
+/// Codificación de alto nivel; el decodificador empieza siempre en modo Upper
+fn high_level_encode(data: &[u8]) -> Vec<bool> {
+    let mut bits = Vec::new();
+    let mut i = 0;
+
+    while i < data.len() {
+        if let Some(code) = upper_code(data[i]) {
+            push_bits(&mut bits, code, 5);
+            i += 1;
+            continue;
+        }
+
+        let digits = data[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits > 0 {
+            push_bits(&mut bits, UPPER_DIGIT_LATCH, 5);
+            for &digit in &data[i..i + digits] {
+                push_bits(&mut bits, (digit - b'0') as u32 + 2, 4);
+            }
+            i += digits;
+            if i < data.len() {
+                push_bits(&mut bits, DIGIT_UPPER_LATCH, 4);
+            }
+            continue;
+        }
+
+        let run = data[i..]
+            .iter()
+            .take_while(|&&b| upper_code(b).is_none() && !b.is_ascii_digit())
+            .count()
+            .min(MAX_BINARY_SHIFT);
+        push_bits(&mut bits, UPPER_BINARY_SHIFT, 5);
+        if run <= 31 {
+            push_bits(&mut bits, run as u32, 5);
+        } else {
+            push_bits(&mut bits, 0, 5);
+            push_bits(&mut bits, (run - 31) as u32, 11);
+        }
+        for &byte in &data[i..i + run] {
+            push_bits(&mut bits, byte as u32, 8);
+        }
+        i += run;
+    }
+    bits
+}
+
+/// Relleno de bits: ninguna palabra puede ser todo ceros o todo unos. Si los
+/// primeros `word_size - 1` bits son iguales se añade el bit contrario y el bit
+/// pendiente pasa a la palabra siguiente. La última palabra se completa con unos.
+fn stuff_bits(bits: &[bool], word_size: usize) -> Vec<bool> {
+    let mut out = Vec::with_capacity(bits.len() + bits.len() / word_size + word_size);
+    let mask = (1u32 << word_size) - 2;
+    let mut i = 0;
+
+    while i < bits.len() {
+        let mut word = 0u32;
+        for j in 0..word_size {
+            if bits.get(i + j).copied().unwrap_or(true) {
+                word |= 1 << (word_size - 1 - j);
+            }
+        }
+
+        if word & mask == mask {
+            push_bits(&mut out, word & mask, word_size);
+            i += word_size - 1;
+        } else if word & mask == 0 {
+            push_bits(&mut out, word | 1, word_size);
+            i += word_size - 1;
+        } else {
+            push_bits(&mut out, word, word_size);
+            i += word_size;
+        }
+    }
+    out
+}
+
+/// Añade las palabras Reed-Solomon hasta ocupar `total_bits`; los bits que no
+/// completan una palabra van como ceros al principio
+fn check_words(bits: &[bool], total_bits: usize, word_size: usize) -> Vec<bool> {
+    let words: Vec<u32> = bits
+        .chunks(word_size)
+        .map(|chunk| chunk.iter().fold(0, |word, &bit| word << 1 | bit as u32))
+        .collect();
+    let total_words = total_bits / word_size;
+    let check = field(word_size).check_words(&words, total_words - words.len());
+
+    let mut out = vec![false; total_bits % word_size];
+    for word in words.iter().chain(&check) {
+        push_bits(&mut out, *word, word_size);
+    }
+    out
+}
+
+/// Mensaje de modo: capas y número de palabras de datos, protegido con RS en GF(16)
+fn mode_message(compact: bool, layers: u32, message_words: usize) -> Vec<bool> {
+    let mut bits = Vec::new();
+    if compact {
+        push_bits(&mut bits, layers - 1, 2);
+        push_bits(&mut bits, message_words as u32 - 1, 6);
+        check_words(&bits, 28, 4)
+    } else {
+        push_bits(&mut bits, layers - 1, 5);
+        push_bits(&mut bits, message_words as u32 - 1, 11);
+        check_words(&bits, 40, 4)
+    }
+}
+
+/// Dibuja las capas de datos en espiral, el mensaje de modo, la diana central y,
+/// en modo completo, la rejilla de referencia
+fn build_matrix(
+    compact: bool,
+    layers: u32,
+    message: &[bool],
+    mode_message: &[bool],
+) -> MatrixSymbol {
+    let base_size = (if compact { 11 } else { 14 }) + layers * 4;
+    let mut alignment: Vec<u32> = (0..base_size).collect();
+    let size = if compact {
+        base_size
+    } else {
+        // Cada 15 módulos desde el centro se inserta una línea de la rejilla
+        let size = base_size + 1 + 2 * ((base_size / 2 - 1) / 15);
+        let original_center = base_size / 2;
+        let center = size / 2;
+        for i in 0..original_center {
+            let offset = i + i / 15;
+            alignment[(original_center - i - 1) as usize] = center - offset - 1;
+            alignment[(original_center + i) as usize] = center + offset + 1;
+        }
+        size
+    };
+    let map = |i: u32| alignment[i as usize];
+    let bit = |i: usize| message.get(i).copied().unwrap_or(false);
+
+    let mut symbol = MatrixSymbol::new(size, size);
+    let mut row_offset = 0usize;
+    for i in 0..layers {
+        let row_size = (layers - i) * 4 + if compact { 9 } else { 12 };
+        let rs = row_size as usize;
+        for j in 0..row_size {
+            let column_offset = j as usize * 2;
+            for k in 0..2 {
+                let ku = k as usize;
+                if bit(row_offset + column_offset + ku) {
+                    symbol.set(map(i * 2 + k), map(i * 2 + j));
+                }
+                if bit(row_offset + rs * 2 + column_offset + ku) {
+                    symbol.set(map(i * 2 + j), map(base_size - 1 - i * 2 - k));
+                }
+                if bit(row_offset + rs * 4 + column_offset + ku) {
+                    symbol.set(
+                        map(base_size - 1 - i * 2 - k),
+                        map(base_size - 1 - i * 2 - j),
+                    );
+                }
+                if bit(row_offset + rs * 6 + column_offset + ku) {
+                    symbol.set(map(base_size - 1 - i * 2 - j), map(i * 2 + k));
+                }
+            }
+        }
+        row_offset += rs * 8;
+    }
+
+    draw_mode_message(&mut symbol, compact, mode_message);
+
+    let center = size / 2;
+    if compact {
+        draw_bullseye(&mut symbol, center, 5);
+    } else {
+        draw_bullseye(&mut symbol, center, 7);
+        let mut i = 0;
+        let mut j = 0;
+        while i < base_size / 2 - 1 {
+            let mut k = center & 1;
+            while k < size {
+                symbol.set(center - j, k);
+                symbol.set(center + j, k);
+                symbol.set(k, center - j);
+                symbol.set(k, center + j);
+                k += 2;
+            }
+            i += 15;
+            j += 16;
+        }
+    }
+    symbol
+}
+
+fn draw_mode_message(symbol: &mut MatrixSymbol, compact: bool, mode_message: &[bool]) {
+    let center = symbol.columns / 2;
+    if compact {
+        for i in 0..7 {
+            let offset = center - 3 + i;
+            let i = i as usize;
+            if mode_message[i] {
+                symbol.set(offset, center - 5);
+            }
+            if mode_message[i + 7] {
+                symbol.set(center + 5, offset);
+            }
+            if mode_message[20 - i] {
+                symbol.set(offset, center + 5);
+            }
+            if mode_message[27 - i] {
+                symbol.set(center - 5, offset);
+            }
+        }
+    } else {
+        for i in 0..10 {
+            let offset = center - 5 + i + i / 5;
+            let i = i as usize;
+            if mode_message[i] {
+                symbol.set(offset, center - 7);
+            }
+            if mode_message[i + 10] {
+                symbol.set(center + 7, offset);
+            }
+            if mode_message[29 - i] {
+                symbol.set(offset, center + 7);
+            }
+            if mode_message[39 - i] {
+                symbol.set(center - 7, offset);
+            }
+        }
+    }
+}
+
+/// Anillos concéntricos alternos y las marcas de orientación de las esquinas
+fn draw_bullseye(symbol: &mut MatrixSymbol, center: u32, size: u32) {
+    for i in (0..size).step_by(2) {
+        for j in center - i..=center + i {
+            symbol.set(j, center - i);
+            symbol.set(j, center + i);
+            symbol.set(center - i, j);
+            symbol.set(center + i, j);
+        }
+    }
+    symbol.set(center - size, center - size);
+    symbol.set(center - size + 1, center - size);
+    symbol.set(center - size, center - size + 1);
+    symbol.set(center + size, center - size);
+    symbol.set(center + size, center - size + 1);
+    symbol.set(center + size, center + size - 1);
+}
@@ -1,3 +1,4 @@
 pub mod aztec;
+pub mod aztec_encoder;
 
 pub use aztec::Aztec;
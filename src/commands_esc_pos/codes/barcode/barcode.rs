@@ -1,7 +1,14 @@
+use super::barcode_raster;
 use super::barcode_text_position::BarcodeTextPosition;
 use super::barcode_type::BarcodeType;
+use super::symbology;
+use crate::commands_esc_pos::codes::render_mode::RenderMode;
+use crate::commands_esc_pos::image_escpos::image_code::raster_command;
+use crate::commands_esc_pos::image_escpos::image_mode::ImageMode;
 use crate::commands_esc_pos::text::text_type::get_styles_diff;
+use crate::error::SectionError;
 use crate::models::print_sections::{Barcode as BarcodeSection, GlobalStyles};
+use crate::models::printer_profile::PrinterProfile;
 
 /// Constructor de comandos para códigos de barras
 #[derive(Debug, Clone)]
@@ -58,11 +65,34 @@ impl Barcode {
         self
     }
 
+    /// Codifica el código de barras en Rust y genera una imagen raster (`GS v 0`)
+    /// con el mismo ancho de módulo, alto y texto HRI que el comando nativo.
+    ///
+    /// # Arguments
+    /// * `max_width` - Ancho disponible en puntos; si no cabe se reduce el ancho de módulo
+    pub fn get_raster_command(&self, max_width: u32) -> Result<Vec<u8>, SectionError> {
+        let symbol = symbology::encode(self.barcode_type, &self.data)?;
+        let binary = barcode_raster::render(
+            &symbol,
+            self.width as u32,
+            self.height as u32,
+            self.text_position,
+            max_width,
+        )?;
+        Ok(raster_command(&binary, ImageMode::Normal))
+    }
+
     /// Genera el comando ESC/POS para imprimir el código de barras
     /// Usa el método 2 con longitud explícita (más moderno)
-    pub fn get_command(&self) -> Vec<u8> {
+    pub fn get_command(&self) -> Result<Vec<u8>, SectionError> {
         let mut output = Vec::new();
-        let data_bytes = self.data.as_bytes();
+        // CODE128 necesita los prefijos {A/{B/{C: el texto libre se convierte con
+        // la combinación de juegos más corta (los datos ya convertidos no cambian)
+        let data = match self.barcode_type {
+            BarcodeType::Code128 => symbology::code128::to_escpos(&self.data)?,
+            _ => self.data.clone(),
+        };
+        let data_bytes = data.as_bytes();
 
         // Establecer altura del código de barras: GS h n
         output.push(0x1D); // GS
@@ -87,45 +117,27 @@ impl Barcode {
         output.push(data_bytes.len() as u8); // n (longitud de datos)
         output.extend_from_slice(data_bytes); // d1...dn (datos)
 
-        output
+        Ok(output)
     }
 }
 
-/// Procesa sección Barcode del modelo de impresión
-pub fn process_section(
-    barcode: &BarcodeSection,
-    current_styles: &GlobalStyles,
-) -> Result<Vec<u8>, String> {
+/// Valida la sección y construye el código de barras
+pub fn from_section(barcode: &BarcodeSection) -> Result<Barcode, SectionError> {
     if barcode.data.is_empty() {
-        return Err("Barcode data cannot be empty".to_string());
+        return Err("Barcode data cannot be empty".into());
     }
     if barcode.height == 0 {
-        return Err("Barcode height must be greater than 0".to_string());
+        return Err("Barcode height must be greater than 0".into());
     }
 
-    let barcode_type = match barcode.barcode_type.as_str() {
-        "UPC-A" => BarcodeType::UpcA,
-        "UPC-E" => BarcodeType::UpcE,
-        "EAN13" => BarcodeType::Ean13,
-        "EAN8" => BarcodeType::Ean8,
-        "CODE39" => BarcodeType::Code39,
-        "ITF" => BarcodeType::Itf,
-        "CODABAR" => BarcodeType::Codabar,
-        "CODE93" => BarcodeType::Code93,
-        "CODE128" => BarcodeType::Code128,
-        "GS1-128" => BarcodeType::Gs1128,
-        "GS1-DATABAR-OMNI" => BarcodeType::Gs1DatabarOmni,
-        "GS1-DATABAR-TRUNCATED" => BarcodeType::Gs1DatabarTruncated,
-        "GS1-DATABAR-LIMITED" => BarcodeType::Gs1DatabarLimited,
-        "GS1-DATABAR-EXPANDED" => BarcodeType::Gs1DatabarExpanded,
-        _ => BarcodeType::Code128,
-    };
+    let barcode_type = BarcodeType::from_name(&barcode.barcode_type);
 
     if barcode_type.requires_numeric_data() && !barcode.data.chars().all(|c| c.is_ascii_digit()) {
         return Err(format!(
             "Barcode type '{}' only accepts numeric digits",
             barcode.barcode_type
-        ));
+        )
+        .into());
     }
 
     let text_position = match barcode.text_position.as_str() {
@@ -136,21 +148,67 @@ pub fn process_section(
         _ => BarcodeTextPosition::NotPrinted,
     };
 
-    let esc_pos_barcode = Barcode::new(barcode_type, barcode.data.clone())
+    let data = symbology::validate(
+        barcode_type,
+        &barcode.data,
+        barcode.auto_check_digit.unwrap_or(false),
+    )?;
+    if data.len() > u8::MAX as usize {
+        return Err(format!(
+            "Barcode data is too long ({} bytes, max {})",
+            data.len(),
+            u8::MAX
+        )
+        .into());
+    }
+
+    Ok(Barcode::new(barcode_type, data)
         .set_height(barcode.height)
         .set_width(barcode.width)
-        .set_text_position(text_position);
+        .set_text_position(text_position))
+}
+
+/// `true` si la sección se imprimirá como imagen según su `render_mode` y el perfil
+pub fn is_raster(barcode: &BarcodeSection, profile: &PrinterProfile) -> Result<bool, SectionError> {
+    let barcode_type = BarcodeType::from_name(&barcode.barcode_type);
+    let native_supported =
+        profile.native_barcodes && (!barcode_type.is_gs1() || profile.native_gs1_barcodes);
+    Ok(RenderMode::from_name(barcode.render_mode.as_deref())?.is_raster(native_supported))
+}
+
+/// Comando del código de barras (nativo o raster) sin alineación ni salto de línea
+pub fn command(
+    barcode: &BarcodeSection,
+    profile: &PrinterProfile,
+    max_width: u32,
+) -> Result<Vec<u8>, SectionError> {
+    let esc_pos_barcode = from_section(barcode)?;
+    if is_raster(barcode, profile)? {
+        esc_pos_barcode.get_raster_command(max_width)
+    } else {
+        esc_pos_barcode.get_command()
+    }
+}
+
+/// Procesa sección Barcode del modelo de impresión
+pub fn process_section(
+    barcode: &BarcodeSection,
+    current_styles: &GlobalStyles,
+    profile: &PrinterProfile,
+    paper_width_pixels: i32,
+) -> Result<Vec<u8>, SectionError> {
+    let barcode_command = command(barcode, profile, paper_width_pixels.max(0) as u32)?;
 
     let mut data = Vec::new();
     if let Some(ref align) = barcode.align {
         let mut temp_styles = current_styles.clone();
         temp_styles.align = Some(align.clone());
         data.extend_from_slice(&get_styles_diff(current_styles, &temp_styles));
-        data.extend_from_slice(&esc_pos_barcode.get_command());
+        data.extend_from_slice(&barcode_command);
         data.extend_from_slice(b"\n");
         data.extend_from_slice(&get_styles_diff(&temp_styles, current_styles));
     } else {
-        data.extend_from_slice(&esc_pos_barcode.get_command());
+        data.extend_from_slice(&barcode_command);
         data.extend_from_slice(b"\n");
     }
     Ok(data)
@@ -0,0 +1,94 @@
+//! Renderizado por software de códigos de barras 1D: módulos escalados, zonas de
+//! silencio y texto HRI con una fuente de mapa de bits.
+
+use super::barcode_text_position::BarcodeTextPosition;
+use super::hri_font::{self, CELL_WIDTH, GLYPH_HEIGHT};
+use super::symbology::Symbol;
+use crate::commands_esc_pos::image_escpos::image_processor::ImageProcessor;
+use crate::error::SectionError;
+use image::{ImageBuffer, Luma};
+
+/// Alto del bloque HRI en puntos: el mismo que ocupa la fuente A del firmware,
+/// para que el alto total coincida con el del comando nativo.
+pub const HRI_BLOCK_HEIGHT: u32 = 24;
+/// Escala de la fuente HRI (5×7 → 10×14 puntos)
+const HRI_SCALE: u32 = 2;
+
+/// Genera la imagen del código de barras. Si no cabe en `max_width` puntos con el
+/// ancho de módulo pedido, se reduce el ancho de módulo hasta que quepa.
+pub fn render(
+    symbol: &Symbol,
+    module_width: u32,
+    bar_height: u32,
+    text_position: BarcodeTextPosition,
+    max_width: u32,
+) -> Result<ImageBuffer<Luma<u8>, Vec<u8>>, SectionError> {
+    let total_modules = symbol.modules.len() as u32 + 2 * symbol.quiet_zone;
+    let module_width = (1..=module_width.max(1))
+        .rev()
+        .find(|width| total_modules * width <= max_width)
+        .ok_or_else(|| {
+            format!(
+                "Barcode needs at least {} dots but only {} are available",
+                total_modules, max_width
+            )
+        })?;
+
+    let width = total_modules * module_width;
+    let (above, below) = match text_position {
+        BarcodeTextPosition::NotPrinted => (false, false),
+        BarcodeTextPosition::Above => (true, false),
+        BarcodeTextPosition::Below => (false, true),
+        BarcodeTextPosition::Both => (true, true),
+    };
+    let text_rows = |shown: bool| if shown { HRI_BLOCK_HEIGHT } else { 0 };
+    let bars_top = text_rows(above);
+    let height = bars_top + bar_height + text_rows(below);
+
+    let mut pixels = vec![false; (width * height) as usize];
+    let offset = symbol.quiet_zone * module_width;
+    for (i, _) in symbol.modules.iter().enumerate().filter(|(_, dark)| **dark) {
+        let x0 = offset + i as u32 * module_width;
+        for y in bars_top..bars_top + bar_height {
+            let row = (y * width) as usize;
+            for x in x0..x0 + module_width {
+                pixels[row + x as usize] = true;
+            }
+        }
+    }
+
+    if above {
+        draw_hri(&mut pixels, width, 0, &symbol.hri);
+    }
+    if below {
+        draw_hri(&mut pixels, width, bars_top + bar_height, &symbol.hri);
+    }
+
+    Ok(ImageProcessor::modules_to_image(&pixels, width, 1, 1))
+}
+
+/// Dibuja el texto centrado en el bloque HRI que empieza en la fila `top`. Si no
+/// cabe a tamaño doble se dibuja a tamaño normal; lo que sobre se recorta.
+fn draw_hri(pixels: &mut [bool], width: u32, top: u32, text: &str) {
+    let chars: Vec<char> = text.chars().collect();
+    let text_width = |scale: u32| (chars.len() as u32 * CELL_WIDTH).saturating_sub(1) * scale;
+    let scale = if text_width(HRI_SCALE) <= width {
+        HRI_SCALE
+    } else {
+        1
+    };
+    let left = width.saturating_sub(text_width(scale)) / 2;
+    let top = top + (HRI_BLOCK_HEIGHT - GLYPH_HEIGHT * scale) / 2;
+
+    for (i, &ch) in chars.iter().enumerate() {
+        let char_left = left + i as u32 * CELL_WIDTH * scale;
+        for y in 0..GLYPH_HEIGHT * scale {
+            for x in 0..CELL_WIDTH * scale {
+                let px = char_left + x;
+                if px < width && hri_font::pixel(ch, x / scale, y / scale) {
+                    pixels[((top + y) * width + px) as usize] = true;
+                }
+            }
+        }
+    }
+}
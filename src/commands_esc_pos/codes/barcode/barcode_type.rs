@@ -25,6 +25,33 @@ impl BarcodeType {
         *self as u8
     }
 
+    /// Convierte el nombre usado en el modelo (`"EAN13"`, `"GS1-128"`, ...) al tipo.
+    /// Los nombres desconocidos se tratan como CODE128.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "UPC-A" => BarcodeType::UpcA,
+            "UPC-E" => BarcodeType::UpcE,
+            "EAN13" => BarcodeType::Ean13,
+            "EAN8" => BarcodeType::Ean8,
+            "CODE39" => BarcodeType::Code39,
+            "ITF" => BarcodeType::Itf,
+            "CODABAR" => BarcodeType::Codabar,
+            "CODE93" => BarcodeType::Code93,
+            "CODE128" => BarcodeType::Code128,
+            "GS1-128" => BarcodeType::Gs1128,
+            "GS1-DATABAR-OMNI" => BarcodeType::Gs1DatabarOmni,
+            "GS1-DATABAR-TRUNCATED" => BarcodeType::Gs1DatabarTruncated,
+            "GS1-DATABAR-LIMITED" => BarcodeType::Gs1DatabarLimited,
+            "GS1-DATABAR-EXPANDED" => BarcodeType::Gs1DatabarExpanded,
+            _ => BarcodeType::Code128,
+        }
+    }
+
+    /// Variantes GS1 (74-78), que no todo firmware implementa
+    pub fn is_gs1(&self) -> bool {
+        self.value() >= BarcodeType::Gs1128.value()
+    }
+
     /// Returns true if this barcode type only accepts numeric digits as data
     ///
     /// Nota: GS1-128 y GS1 DataBar Expanded admiten formato GS1 con AIs
@@ -0,0 +1,322 @@
+//! Cadenas de elementos GS1 escritas como `(AI)valor(AI)valor...`: análisis,
+//! validación de cada AI (longitud, juego de caracteres, dígito de control y
+//! fechas) y cadena de elementos con los separadores FNC1 de cada simbología.
+
+use crate::error::SectionError;
+
+/// Un identificador de aplicación (AI) con su valor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub ai: String,
+    pub value: String,
+}
+
+/// Elemento de datos codificable: un carácter o FNC1 (separador GS1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Item {
+    Char(u8),
+    Fnc1,
+}
+
+impl Item {
+    /// Valor 0-9 si el elemento es un dígito
+    pub fn digit(self) -> Option<u8> {
+        match self {
+            Item::Char(ch) if ch.is_ascii_digit() => Some(ch - b'0'),
+            _ => None,
+        }
+    }
+}
+
+/// Separa `(AI)valor` en elementos y valida cada uno según la tabla de AIs
+pub fn parse(data: &str) -> Result<Vec<Element>, SectionError> {
+    if !data.starts_with('(') {
+        return Err(format!(
+            "GS1 data must use the (AI)value format, e.g. (01)09501101530003, got '{}'",
+            data
+        )
+        .into());
+    }
+
+    let mut elements = Vec::new();
+    for part in data.split('(').skip(1) {
+        let (ai, value) = part
+            .split_once(')')
+            .ok_or_else(|| format!("Missing ')' after GS1 AI '({}'", part))?;
+        if !(2..=4).contains(&ai.len()) || !ai.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("Invalid GS1 AI '({})'", ai).into());
+        }
+        if value.is_empty() {
+            return Err(format!("GS1 AI ({}) has no value", ai).into());
+        }
+        let element = Element {
+            ai: ai.to_string(),
+            value: value.to_string(),
+        };
+        validate(&element)?;
+        elements.push(element);
+    }
+    Ok(elements)
+}
+
+/// Comprobación adicional sobre el valor de un AI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Check {
+    None,
+    /// Último dígito = dígito de control GS1 (módulo 10)
+    Mod10,
+    /// Fecha AAMMDD (DD = 00 indica "fin de mes")
+    Date,
+}
+
+/// Formato del valor de un AI
+#[derive(Debug, Clone, Copy)]
+struct AiSpec {
+    /// Longitud del AI en dígitos
+    ai_length: usize,
+    numeric: bool,
+    min: usize,
+    max: usize,
+    check: Check,
+    title: &'static str,
+}
+
+const fn fixed(ai_length: usize, length: usize, check: Check, title: &'static str) -> AiSpec {
+    AiSpec {
+        ai_length,
+        numeric: true,
+        min: length,
+        max: length,
+        check,
+        title,
+    }
+}
+
+const fn numeric(ai_length: usize, max: usize, title: &'static str) -> AiSpec {
+    AiSpec {
+        ai_length,
+        numeric: true,
+        min: 1,
+        max,
+        check: Check::None,
+        title,
+    }
+}
+
+const fn alphanumeric(ai_length: usize, max: usize, title: &'static str) -> AiSpec {
+    AiSpec {
+        ai_length,
+        numeric: false,
+        min: 1,
+        max,
+        check: Check::None,
+        title,
+    }
+}
+
+/// Tabla de AIs (GS1 General Specifications). Los AIs con indicador decimal
+/// (310n, 392n...) se buscan por sus tres primeros dígitos.
+#[rustfmt::skip]
+fn spec(ai: &str) -> Option<AiSpec> {
+    let spec = match ai {
+        "00" => fixed(2, 18, Check::Mod10, "SSCC"),
+        "01" => fixed(2, 14, Check::Mod10, "GTIN"),
+        "02" => fixed(2, 14, Check::Mod10, "CONTENT"),
+        "03" => fixed(2, 14, Check::Mod10, "MTO GTIN"),
+        "10" => alphanumeric(2, 20, "BATCH/LOT"),
+        "11" => fixed(2, 6, Check::Date, "PROD DATE"),
+        "12" => fixed(2, 6, Check::Date, "DUE DATE"),
+        "13" => fixed(2, 6, Check::Date, "PACK DATE"),
+        "15" => fixed(2, 6, Check::Date, "BEST BEFORE"),
+        "16" => fixed(2, 6, Check::Date, "SELL BY"),
+        "17" => fixed(2, 6, Check::Date, "USE BY"),
+        "20" => fixed(2, 2, Check::None, "VARIANT"),
+        "21" => alphanumeric(2, 20, "SERIAL"),
+        "22" => alphanumeric(2, 20, "CPV"),
+        "235" => alphanumeric(3, 28, "TPX"),
+        "240" => alphanumeric(3, 30, "ADDITIONAL ID"),
+        "241" => alphanumeric(3, 30, "CUST. PART No."),
+        "242" => numeric(3, 6, "MTO VARIANT"),
+        "243" => alphanumeric(3, 20, "PCN"),
+        "250" => alphanumeric(3, 30, "SECONDARY SERIAL"),
+        "251" => alphanumeric(3, 30, "REF. TO SOURCE"),
+        "254" => alphanumeric(3, 20, "GLN EXTENSION COMPONENT"),
+        "30" => numeric(2, 8, "VAR. COUNT"),
+        "37" => numeric(2, 8, "COUNT"),
+        "400" => alphanumeric(3, 30, "ORDER NUMBER"),
+        "401" => alphanumeric(3, 30, "GINC"),
+        "402" => fixed(3, 17, Check::Mod10, "GSIN"),
+        "403" => alphanumeric(3, 30, "ROUTE"),
+        "410" | "411" | "412" | "413" | "414" | "415" | "416" | "417" => {
+            fixed(3, 13, Check::Mod10, "GLN")
+        }
+        "420" => alphanumeric(3, 20, "SHIP TO POST"),
+        "421" => alphanumeric(3, 12, "SHIP TO POST"),
+        "422" | "424" | "426" => fixed(3, 3, Check::None, "ORIGIN"),
+        "423" | "425" => AiSpec { min: 3, ..numeric(3, 15, "COUNTRY") },
+        "7003" => fixed(4, 10, Check::None, "EXPIRY TIME"),
+        "7007" => AiSpec { min: 6, ..numeric(4, 12, "HARVEST DATE") },
+        "8005" => fixed(4, 6, Check::None, "PRICE PER UNIT"),
+        "8020" => alphanumeric(4, 25, "REF No."),
+        "90" => alphanumeric(2, 30, "INTERNAL"),
+        "91" | "92" | "93" | "94" | "95" | "96" | "97" | "98" | "99" => {
+            alphanumeric(2, 90, "INTERNAL")
+        }
+        _ => match ai.get(..3)? {
+            "310" | "311" | "312" | "313" | "314" | "315" | "316" | "320" | "321" | "322"
+            | "323" | "324" | "325" | "326" | "327" | "328" | "329" | "330" | "331" | "332"
+            | "333" | "334" | "335" | "336" | "337" | "340" | "341" | "342" | "343" | "344"
+            | "345" | "346" | "347" | "348" | "349" | "350" | "351" | "352" | "353" | "354"
+            | "355" | "356" | "357" | "360" | "361" | "362" | "363" | "364" | "365" | "366"
+            | "367" | "368" | "369" => fixed(4, 6, Check::None, "MEASURE"),
+            "390" | "392" => numeric(4, 15, "AMOUNT"),
+            "391" | "393" => AiSpec { min: 4, ..numeric(4, 18, "AMOUNT") },
+            _ => return None,
+        },
+    };
+    Some(spec)
+}
+
+/// Juego de caracteres GS1 (ISO 646, 82 caracteres) de los AIs alfanuméricos
+fn is_gs1_char(ch: u8) -> bool {
+    ch.is_ascii_alphanumeric() || b"!\"%&'()*+,-./:;<=>?_".contains(&ch)
+}
+
+/// Dígito de control GS1 (módulo 10, pesos 3/1 desde la derecha) de `digits`
+pub fn check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| (d - b'0') as u32 * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Valida el valor de un elemento según la tabla de AIs
+pub fn validate(element: &Element) -> Result<(), SectionError> {
+    let ai = element.ai.as_str();
+    let spec = spec(ai)
+        .filter(|spec| spec.ai_length == ai.len())
+        .ok_or_else(|| format!("Unknown GS1 AI ({})", ai))?;
+    let value = element.value.as_bytes();
+
+    if !(spec.min..=spec.max).contains(&value.len()) {
+        let expected = if spec.min == spec.max {
+            format!("{}", spec.max)
+        } else {
+            format!("{}-{}", spec.min, spec.max)
+        };
+        return Err(format!(
+            "GS1 AI ({}) {} must have {} characters, got {}",
+            ai,
+            spec.title,
+            expected,
+            value.len()
+        )
+        .into());
+    }
+    if spec.numeric {
+        if !value.iter().all(u8::is_ascii_digit) {
+            return Err(format!(
+                "GS1 AI ({}) {} only accepts digits, got '{}'",
+                ai, spec.title, element.value
+            )
+            .into());
+        }
+    } else if let Some(&ch) = value.iter().find(|&&ch| !is_gs1_char(ch)) {
+        return Err(format!(
+            "GS1 AI ({}) {} contains '{}', which is not in the GS1 character set",
+            ai, spec.title, ch as char
+        )
+        .into());
+    }
+
+    match spec.check {
+        Check::None => {}
+        Check::Mod10 => {
+            let (body, found) = value.split_at(value.len() - 1);
+            let expected = check_digit(body);
+            if found[0] - b'0' != expected {
+                return Err(format!(
+                    "GS1 AI ({}) {} check digit is {} but should be {}",
+                    ai, spec.title, found[0] as char, expected
+                )
+                .into());
+            }
+        }
+        Check::Date => {
+            let field = |i: usize| ((value[i] - b'0') * 10 + value[i + 1] - b'0') as u32;
+            let (year, month, day) = (field(0), field(2), field(4));
+            let days = match month {
+                2 if year % 4 == 0 => 29,
+                2 => 28,
+                4 | 6 | 9 | 11 => 30,
+                _ => 31,
+            };
+            if !(1..=12).contains(&month) || day > days {
+                return Err(format!(
+                    "GS1 AI ({}) {} is not a valid YYMMDD date: '{}'",
+                    ai, spec.title, element.value
+                )
+                .into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Longitud total (AI + valor) de los AIs de longitud predefinida, que no
+/// necesitan FNC1 como separador. Se decide por los dos primeros dígitos del AI.
+pub fn predefined_length(ai: &str) -> Option<usize> {
+    let length = match ai.get(..2)? {
+        "00" => 20,
+        "01" | "02" | "03" => 16,
+        "04" => 18,
+        "11" | "12" | "13" | "14" | "15" | "16" | "17" | "18" | "19" => 8,
+        "20" => 4,
+        "31" | "32" | "33" | "34" | "35" | "36" => 10,
+        "41" => 16,
+        _ => return None,
+    };
+    Some(length)
+}
+
+/// Concatena los elementos insertando FNC1 tras cada AI de longitud variable
+/// (salvo el último). No incluye el FNC1 inicial de la simbología.
+pub fn element_string(elements: &[Element]) -> Vec<Item> {
+    let mut items = Vec::new();
+    for (i, element) in elements.iter().enumerate() {
+        items.extend(element.ai.bytes().map(Item::Char));
+        items.extend(element.value.bytes().map(Item::Char));
+        if predefined_length(&element.ai).is_none() && i + 1 < elements.len() {
+            items.push(Item::Fnc1);
+        }
+    }
+    items
+}
+
+/// Datos para los símbolos `GS ( k` (DataBar 2D, Composite): cadena de elementos
+/// sin paréntesis con GS (0x1D) como FNC1 separador. Los datos que no usan la
+/// sintaxis `(AI)` se envían tal cual.
+pub fn escpos_2d_data(data: &str) -> Result<String, SectionError> {
+    if !data.starts_with('(') {
+        return Ok(data.to_string());
+    }
+    let elements = parse(data)?;
+    Ok(element_string(&elements)
+        .into_iter()
+        .map(|item| match item {
+            Item::Char(ch) => ch as char,
+            Item::Fnc1 => '\x1d',
+        })
+        .collect())
+}
+
+/// Texto legible: `(AI)valor` por cada elemento
+pub fn hri(elements: &[Element]) -> String {
+    elements
+        .iter()
+        .map(|element| format!("({}){}", element.ai, element.value))
+        .collect()
+}
@@ -1,6 +1,10 @@
 pub mod barcode;
+pub mod barcode_raster;
 pub mod barcode_text_position;
 pub mod barcode_type;
+pub mod gs1;
+pub mod hri_font;
+pub mod symbology;
 
 pub use barcode::Barcode;
 pub use barcode_text_position::BarcodeTextPosition;
@@ -0,0 +1,67 @@
+//! CODABAR (NW-7)
+
+use super::code39::pattern_widths;
+use super::{push_widths, Symbol};
+use crate::error::SectionError;
+
+const ALPHABET: &[u8] = b"0123456789-$:/.+ABCD";
+
+/// 7 elementos por carácter, bit a 1 = elemento ancho
+const PATTERNS: [u16; 20] = [
+    0x003, 0x006, 0x009, 0x060, 0x012, 0x042, 0x021, 0x024, 0x030, 0x048, 0x00C, 0x018, 0x045,
+    0x051, 0x054, 0x015, 0x01A, 0x029, 0x00B, 0x00E,
+];
+
+fn is_start_stop(ch: u8) -> bool {
+    matches!(ch.to_ascii_uppercase(), b'A'..=b'D')
+}
+
+/// Posición en el alfabeto del carácter `i`; A-D solo valen como inicio y fin
+fn index(bytes: &[u8], i: usize) -> Result<usize, SectionError> {
+    let ch = bytes[i].to_ascii_uppercase();
+    ALPHABET
+        .iter()
+        .position(|&c| c == ch)
+        .filter(|&index| index < 16 || i == 0 || i == bytes.len() - 1)
+        .ok_or_else(|| {
+            format!(
+                "CODABAR does not support the character '{}' here",
+                ch as char
+            )
+            .into()
+        })
+}
+
+/// Valida que los datos empiecen y terminen con A, B, C o D y que el resto sean
+/// caracteres `0-9 - $ : / . +`
+pub(crate) fn validate(data: &str) -> Result<(), SectionError> {
+    let bytes = data.as_bytes();
+    if bytes.len() < 2 || !is_start_stop(bytes[0]) || !is_start_stop(bytes[bytes.len() - 1]) {
+        return Err("CODABAR data must start and end with A, B, C or D".into());
+    }
+    (0..bytes.len()).try_for_each(|i| index(bytes, i).map(|_| ()))
+}
+
+/// Codifica los datos, que deben empezar y terminar con A, B, C o D
+pub fn encode(data: &str) -> Result<Symbol, SectionError> {
+    validate(data)?;
+    let bytes = data.as_bytes();
+
+    let mut modules = Vec::new();
+    for i in 0..bytes.len() {
+        if i > 0 {
+            modules.push(false); // Separación entre caracteres
+        }
+        push_widths(
+            &mut modules,
+            &pattern_widths(PATTERNS[index(bytes, i)?], 7),
+            true,
+        );
+    }
+
+    Ok(Symbol {
+        modules,
+        hri: data.to_string(),
+        quiet_zone: 10,
+    })
+}
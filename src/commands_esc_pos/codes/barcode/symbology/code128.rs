@@ -0,0 +1,440 @@
+//! CODE128 y GS1-128
+
+use super::{push_widths, Symbol};
+use crate::commands_esc_pos::codes::barcode::gs1::{self, Item};
+use crate::error::SectionError;
+
+/// Anchos de los valores 0-105 (barra, espacio, ...)
+#[rustfmt::skip]
+const PATTERNS: [[u8; 6]; 106] = [
+    [2, 1, 2, 2, 2, 2], [2, 2, 2, 1, 2, 2], [2, 2, 2, 2, 2, 1], [1, 2, 1, 2, 2, 3],
+    [1, 2, 1, 3, 2, 2], [1, 3, 1, 2, 2, 2], [1, 2, 2, 2, 1, 3], [1, 2, 2, 3, 1, 2],
+    [1, 3, 2, 2, 1, 2], [2, 2, 1, 2, 1, 3], [2, 2, 1, 3, 1, 2], [2, 3, 1, 2, 1, 2],
+    [1, 1, 2, 2, 3, 2], [1, 2, 2, 1, 3, 2], [1, 2, 2, 2, 3, 1], [1, 1, 3, 2, 2, 2],
+    [1, 2, 3, 1, 2, 2], [1, 2, 3, 2, 2, 1], [2, 2, 3, 2, 1, 1], [2, 2, 1, 1, 3, 2],
+    [2, 2, 1, 2, 3, 1], [2, 1, 3, 2, 1, 2], [2, 2, 3, 1, 1, 2], [3, 1, 2, 1, 3, 1],
+    [3, 1, 1, 2, 2, 2], [3, 2, 1, 1, 2, 2], [3, 2, 1, 2, 2, 1], [3, 1, 2, 2, 1, 2],
+    [3, 2, 2, 1, 1, 2], [3, 2, 2, 2, 1, 1], [2, 1, 2, 1, 2, 3], [2, 1, 2, 3, 2, 1],
+    [2, 3, 2, 1, 2, 1], [1, 1, 1, 3, 2, 3], [1, 3, 1, 1, 2, 3], [1, 3, 1, 3, 2, 1],
+    [1, 1, 2, 3, 1, 3], [1, 3, 2, 1, 1, 3], [1, 3, 2, 3, 1, 1], [2, 1, 1, 3, 1, 3],
+    [2, 3, 1, 1, 1, 3], [2, 3, 1, 3, 1, 1], [1, 1, 2, 1, 3, 3], [1, 1, 2, 3, 3, 1],
+    [1, 3, 2, 1, 3, 1], [1, 1, 3, 1, 2, 3], [1, 1, 3, 3, 2, 1], [1, 3, 3, 1, 2, 1],
+    [3, 1, 3, 1, 2, 1], [2, 1, 1, 3, 3, 1], [2, 3, 1, 1, 3, 1], [2, 1, 3, 1, 1, 3],
+    [2, 1, 3, 3, 1, 1], [2, 1, 3, 1, 3, 1], [3, 1, 1, 1, 2, 3], [3, 1, 1, 3, 2, 1],
+    [3, 3, 1, 1, 2, 1], [3, 1, 2, 1, 1, 3], [3, 1, 2, 3, 1, 1], [3, 3, 2, 1, 1, 1],
+    [3, 1, 4, 1, 1, 1], [2, 2, 1, 4, 1, 1], [4, 3, 1, 1, 1, 1], [1, 1, 1, 2, 2, 4],
+    [1, 1, 1, 4, 2, 2], [1, 2, 1, 1, 2, 4], [1, 2, 1, 4, 2, 1], [1, 4, 1, 1, 2, 2],
+    [1, 4, 1, 2, 2, 1], [1, 1, 2, 2, 1, 4], [1, 1, 2, 4, 1, 2], [1, 2, 2, 1, 1, 4],
+    [1, 2, 2, 4, 1, 1], [1, 4, 2, 1, 1, 2], [1, 4, 2, 2, 1, 1], [2, 4, 1, 2, 1, 1],
+    [2, 2, 1, 1, 1, 4], [4, 1, 3, 1, 1, 1], [2, 4, 1, 1, 1, 2], [1, 3, 4, 1, 1, 1],
+    [1, 1, 1, 2, 4, 2], [1, 2, 1, 1, 4, 2], [1, 2, 1, 2, 4, 1], [1, 1, 4, 2, 1, 2],
+    [1, 2, 4, 1, 1, 2], [1, 2, 4, 2, 1, 1], [4, 1, 1, 2, 1, 2], [4, 2, 1, 1, 1, 2],
+    [4, 2, 1, 2, 1, 1], [2, 1, 2, 1, 4, 1], [2, 1, 4, 1, 2, 1], [4, 1, 2, 1, 2, 1],
+    [1, 1, 1, 1, 4, 3], [1, 1, 1, 3, 4, 1], [1, 3, 1, 1, 4, 1], [1, 1, 4, 1, 1, 3],
+    [1, 1, 4, 3, 1, 1], [4, 1, 1, 1, 1, 3], [4, 1, 1, 3, 1, 1], [1, 1, 3, 1, 4, 1],
+    [1, 1, 4, 1, 3, 1], [3, 1, 1, 1, 4, 1], [4, 1, 1, 1, 3, 1], [2, 1, 1, 4, 1, 2],
+    [2, 1, 1, 2, 1, 4], [2, 1, 1, 2, 3, 2],
+];
+const STOP: [u8; 7] = [2, 3, 3, 1, 1, 1, 2];
+
+const FNC3: u8 = 96;
+const FNC2: u8 = 97;
+const SHIFT: u8 = 98;
+const CODE_C: u8 = 99;
+const CODE_B: u8 = 100; // FNC4 dentro de B
+const CODE_A: u8 = 101; // FNC4 dentro de A
+const FNC1: u8 = 102;
+const START_A: u8 = 103;
+const START_B: u8 = 104;
+const START_C: u8 = 105;
+
+/// Juego de caracteres de CODE128
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodeSet {
+    A,
+    B,
+    C,
+}
+
+impl CodeSet {
+    const ALL: [CodeSet; 3] = [CodeSet::A, CodeSet::B, CodeSet::C];
+
+    fn start(self) -> u8 {
+        match self {
+            CodeSet::A => START_A,
+            CodeSet::B => START_B,
+            CodeSet::C => START_C,
+        }
+    }
+
+    fn switch(self) -> u8 {
+        match self {
+            CodeSet::A => CODE_A,
+            CodeSet::B => CODE_B,
+            CodeSet::C => CODE_C,
+        }
+    }
+
+    /// Valor de un carácter ASCII en este juego (A o B)
+    fn value(self, ch: u8) -> Option<u8> {
+        match (self, ch) {
+            (CodeSet::A, 32..=95) | (CodeSet::B, 32..=127) => Some(ch - 32),
+            (CodeSet::A, 0..=31) => Some(ch + 64),
+            _ => None,
+        }
+    }
+
+    /// Carácter ASCII de un valor de datos en este juego (inverso de `value`)
+    fn char(self, value: u8) -> u8 {
+        match (self, value) {
+            (CodeSet::A, 64..=95) => value - 64,
+            _ => value + 32,
+        }
+    }
+
+    /// Prefijo de la sintaxis de ESC/POS que selecciona el juego
+    fn escpos_prefix(self) -> &'static [u8] {
+        match self {
+            CodeSet::A => b"{A",
+            CodeSet::B => b"{B",
+            CodeSet::C => b"{C",
+        }
+    }
+
+    /// El otro juego alfanumérico (destino de SHIFT)
+    fn shifted(self) -> CodeSet {
+        if self == CodeSet::A {
+            CodeSet::B
+        } else {
+            CodeSet::A
+        }
+    }
+}
+
+/// Paso del codificador: cómo se consumen los datos a partir de una posición
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    /// Consumir `len` elementos en el juego actual
+    Consume(usize),
+    /// Consumir un carácter del otro juego A/B con SHIFT
+    Shift,
+    /// Cambiar de juego y continuar
+    Switch(CodeSet),
+}
+
+fn index(set: CodeSet) -> usize {
+    set as usize
+}
+
+/// Elementos que consume el juego `set` en la posición `i` (sin cambiar de juego)
+fn consumable(items: &[Item], i: usize, set: CodeSet) -> Option<usize> {
+    match (set, items[i]) {
+        (_, Item::Fnc1) => Some(1),
+        (CodeSet::C, _) => {
+            let pair = items.get(i + 1).and_then(|next| next.digit());
+            (items[i].digit().is_some() && pair.is_some()).then_some(2)
+        }
+        (_, Item::Char(ch)) => set.value(ch).map(|_| 1),
+    }
+}
+
+/// Elige la secuencia de juegos que produce el menor número de símbolos
+/// (programación dinámica sobre posición y juego actual).
+pub(crate) fn encode_items(items: &[Item]) -> Result<Vec<u8>, SectionError> {
+    if let Some(Item::Char(ch)) = items
+        .iter()
+        .find(|item| matches!(item, Item::Char(ch) if *ch > 127))
+    {
+        return Err(format!("CODE128 does not support the character '{}'", *ch as char).into());
+    }
+
+    const INF: usize = usize::MAX / 2;
+    let n = items.len();
+    let mut cost = vec![[INF; 3]; n + 1];
+    let mut steps = vec![[Step::Consume(1); 3]; n + 1];
+    cost[n] = [0; 3];
+
+    for i in (0..n).rev() {
+        // Primero sin cambiar de juego
+        let mut own = [INF; 3];
+        for set in CodeSet::ALL {
+            let s = index(set);
+            if let Some(len) = consumable(items, i, set) {
+                own[s] = 1 + cost[i + len][s];
+                steps[i][s] = Step::Consume(len);
+            }
+            if set != CodeSet::C
+                && consumable(items, i, set.shifted()).is_some()
+                && 2 + cost[i + 1][s] < own[s]
+            {
+                own[s] = 2 + cost[i + 1][s];
+                steps[i][s] = Step::Shift;
+            }
+        }
+        // Después, cambiando de juego una vez antes de consumir
+        for set in CodeSet::ALL {
+            let s = index(set);
+            cost[i][s] = own[s];
+            for target in CodeSet::ALL {
+                if target != set && 1 + own[index(target)] < cost[i][s] {
+                    cost[i][s] = 1 + own[index(target)];
+                    steps[i][s] = Step::Switch(target);
+                }
+            }
+        }
+    }
+
+    // Juego inicial: el de menor coste (en empate C, luego B, luego A)
+    let mut set = [CodeSet::C, CodeSet::B, CodeSet::A]
+        .into_iter()
+        .min_by_key(|&set| cost[0][index(set)])
+        .unwrap_or(CodeSet::B);
+    let mut values = vec![set.start()];
+    let mut i = 0;
+    while i < n {
+        match steps[i][index(set)] {
+            Step::Switch(target) => {
+                values.push(target.switch());
+                set = target;
+            }
+            Step::Shift => {
+                values.push(SHIFT);
+                values.push(item_value(items, i, set.shifted()));
+                i += 1;
+            }
+            Step::Consume(len) => {
+                values.push(item_value(items, i, set));
+                i += len;
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// Valor del elemento en la posición `i` dentro del juego `set`
+fn item_value(items: &[Item], i: usize, set: CodeSet) -> u8 {
+    match (set, items[i]) {
+        (_, Item::Fnc1) => FNC1,
+        (CodeSet::C, _) => items[i].digit().unwrap_or(0) * 10 + items[i + 1].digit().unwrap_or(0),
+        (_, Item::Char(ch)) => set.value(ch).unwrap_or(0),
+    }
+}
+
+/// Interpreta la sintaxis de ESC/POS (`{A`, `{B`, `{C`, `{S`, `{1`-`{4`, `{{`).
+/// En el juego C cada byte es un valor 0-99.
+fn parse_escpos(data: &[u8]) -> Result<(Vec<u8>, String), SectionError> {
+    let mut values = Vec::new();
+    let mut hri = String::new();
+    let mut set: Option<CodeSet> = None;
+    let mut shifted = false;
+    let mut i = 0;
+
+    while i < data.len() {
+        let byte = data[i];
+        i += 1;
+        if byte == b'{' && data.get(i) != Some(&b'{') {
+            let code = data.get(i).copied().ok_or("CODE128 data ends with '{'")?;
+            i += 1;
+            let current = set;
+            let value = match code {
+                b'A' | b'B' | b'C' => {
+                    let target = match code {
+                        b'A' => CodeSet::A,
+                        b'B' => CodeSet::B,
+                        _ => CodeSet::C,
+                    };
+                    set = Some(target);
+                    match current {
+                        None => target.start(),
+                        Some(_) => target.switch(),
+                    }
+                }
+                b'S' if matches!(current, Some(CodeSet::A | CodeSet::B)) => {
+                    shifted = true;
+                    SHIFT
+                }
+                b'1' if current.is_some() => FNC1,
+                b'2' if matches!(current, Some(CodeSet::A | CodeSet::B)) => FNC2,
+                b'3' if matches!(current, Some(CodeSet::A | CodeSet::B)) => FNC3,
+                b'4' if current == Some(CodeSet::A) => CODE_A,
+                b'4' if current == Some(CodeSet::B) => CODE_B,
+                other => {
+                    return Err(format!("Invalid CODE128 function '{{{}'", other as char).into())
+                }
+            };
+            values.push(value);
+            continue;
+        }
+        if byte == b'{' {
+            i += 1; // `{{` = '{'
+        }
+
+        let current = set.ok_or("CODE128 data must start with {A, {B or {C")?;
+        let effective = match (current, shifted) {
+            (CodeSet::A, true) => CodeSet::B,
+            (CodeSet::B, true) => CodeSet::A,
+            (set, _) => set,
+        };
+        shifted = false;
+        if effective == CodeSet::C {
+            if byte > 99 {
+                return Err("CODE128 code set C only accepts values 0-99".into());
+            }
+            values.push(byte);
+            hri.push_str(&format!("{:02}", byte));
+        } else {
+            let value = effective.value(byte).ok_or_else(|| {
+                format!(
+                    "CODE128 code set {:?} does not support '{}'",
+                    effective, byte as char
+                )
+            })?;
+            values.push(value);
+            if byte >= 32 {
+                hri.push(byte as char);
+            }
+        }
+    }
+
+    if values.len() < 2 {
+        return Err("CODE128 data is empty".into());
+    }
+    Ok((values, hri))
+}
+
+/// Añade el dígito de control y el patrón de parada, y genera los módulos
+fn symbol(mut values: Vec<u8>, hri: String) -> Symbol {
+    let check = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| i.max(1) * v as usize)
+        .sum::<usize>()
+        % 103;
+    values.push(check as u8);
+
+    let mut modules = Vec::with_capacity(values.len() * 11 + 13);
+    for &value in &values {
+        push_widths(&mut modules, &PATTERNS[value as usize], true);
+    }
+    push_widths(&mut modules, &STOP, true);
+
+    Symbol {
+        modules,
+        hri,
+        quiet_zone: 10,
+    }
+}
+
+/// `true` si los datos ya usan la sintaxis de ESC/POS (`{A`, `{B` o `{C` inicial)
+fn is_escpos(data: &[u8]) -> bool {
+    matches!(data, [b'{', b'A' | b'B' | b'C', ..])
+}
+
+/// Texto libre: `{1` es FNC1 y `{{` un `{` literal
+fn parse_items(data: &[u8]) -> Vec<Item> {
+    let mut items = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        match (data[i], data.get(i + 1)) {
+            (b'{', Some(b'1')) => {
+                items.push(Item::Fnc1);
+                i += 2;
+            }
+            (b'{', Some(b'{')) => {
+                items.push(Item::Char(b'{'));
+                i += 2;
+            }
+            (byte, _) => {
+                items.push(Item::Char(byte));
+                i += 1;
+            }
+        }
+    }
+    items
+}
+
+/// Escribe los valores de un símbolo con la sintaxis de ESC/POS: `{A`/`{B`/`{C`
+/// para el juego, `{S` para SHIFT, `{1` para FNC1 y en el juego C un byte 0-99
+/// por cada par de dígitos.
+fn escpos_syntax(values: &[u8]) -> Vec<u8> {
+    let mut set = match values.first() {
+        Some(&START_A) => CodeSet::A,
+        Some(&START_C) => CodeSet::C,
+        _ => CodeSet::B,
+    };
+    let mut out = set.escpos_prefix().to_vec();
+    let mut shifted = false;
+
+    for &value in values.iter().skip(1) {
+        let effective = if shifted { set.shifted() } else { set };
+        shifted = false;
+        match (effective, value) {
+            (_, FNC1) => out.extend_from_slice(b"{1"),
+            (CodeSet::C, 0..=99) => out.push(value),
+            (_, SHIFT) => {
+                out.extend_from_slice(b"{S");
+                shifted = true;
+            }
+            (_, CODE_A | CODE_B | CODE_C) => {
+                set = match value {
+                    CODE_A => CodeSet::A,
+                    CODE_B => CodeSet::B,
+                    _ => CodeSet::C,
+                };
+                out.extend_from_slice(set.escpos_prefix());
+            }
+            (_, value) => match effective.char(value) {
+                b'{' => out.extend_from_slice(b"{{"),
+                ch => out.push(ch),
+            },
+        }
+    }
+    out
+}
+
+/// Datos para `GS k` (m = 73). Los que ya usan la sintaxis de ESC/POS se validan
+/// y se envían tal cual; el texto libre se codifica con la combinación de juegos
+/// más corta (C para series pares de dígitos, SHIFT para caracteres sueltos del
+/// otro juego) y se escribe con los prefijos `{A`/`{B`/`{C` que exige el firmware.
+pub fn to_escpos(data: &str) -> Result<String, SectionError> {
+    let bytes = data.as_bytes();
+    if is_escpos(bytes) {
+        parse_escpos(bytes)?;
+        return Ok(data.to_string());
+    }
+
+    let values = encode_items(&parse_items(bytes))?;
+    // Todos los bytes son < 128 (encode_items rechaza el resto), así que la
+    // conversión no puede fallar
+    Ok(String::from_utf8(escpos_syntax(&values)).unwrap_or_default())
+}
+
+/// Codifica CODE128. Si los datos empiezan por `{A`, `{B` o `{C` se usa la
+/// sintaxis de ESC/POS; si no, el texto se codifica eligiendo los juegos óptimos.
+pub fn encode(data: &str) -> Result<Symbol, SectionError> {
+    let bytes = data.as_bytes();
+    if is_escpos(bytes) {
+        let (values, hri) = parse_escpos(bytes)?;
+        return Ok(symbol(values, hri));
+    }
+
+    let items = parse_items(bytes);
+    let values = encode_items(&items)?;
+    let hri = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Char(ch) if !ch.is_ascii_control() => Some(*ch as char),
+            _ => None,
+        })
+        .collect();
+    Ok(symbol(values, hri))
+}
+
+/// Codifica GS1-128: FNC1 inicial y FNC1 como separador tras los AIs de longitud variable
+pub fn encode_gs1(data: &str) -> Result<Symbol, SectionError> {
+    let elements = gs1::parse(data)?;
+    let mut items = vec![Item::Fnc1];
+    items.extend(gs1::element_string(&elements));
+    let values = encode_items(&items)?;
+    Ok(symbol(values, gs1::hri(&elements)))
+}
@@ -0,0 +1,67 @@
+//! CODE39 (3 de 9)
+
+use super::{push_widths, Symbol};
+use crate::error::SectionError;
+
+const ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%";
+
+/// 9 elementos por carácter (barra, espacio, ...), bit a 1 = elemento ancho
+const PATTERNS: [u16; 43] = [
+    0x034, 0x121, 0x061, 0x160, 0x031, 0x130, 0x070, 0x025, 0x124, 0x064, 0x109, 0x049, 0x148,
+    0x019, 0x118, 0x058, 0x00D, 0x10C, 0x04C, 0x01C, 0x103, 0x043, 0x142, 0x013, 0x112, 0x052,
+    0x007, 0x106, 0x046, 0x016, 0x181, 0x0C1, 0x1C0, 0x091, 0x190, 0x0D0, 0x085, 0x184, 0x0C4,
+    0x0A8, 0x0A2, 0x08A, 0x02A,
+];
+const START_STOP: u16 = 0x094;
+
+/// Relación ancho/estrecho en módulos
+pub(crate) const WIDE: u8 = 3;
+
+/// Convierte un patrón de 9 bits ancho/estrecho en anchos de elemento
+pub(crate) fn pattern_widths(pattern: u16, elements: u32) -> Vec<u8> {
+    (0..elements)
+        .rev()
+        .map(|i| if pattern >> i & 1 == 1 { WIDE } else { 1 })
+        .collect()
+}
+
+/// Datos sin los `*` de inicio y fin, si vienen incluidos
+fn body(data: &str) -> &str {
+    data.strip_prefix('*')
+        .and_then(|rest| rest.strip_suffix('*'))
+        .unwrap_or(data)
+}
+
+/// Valida el juego de caracteres: 0-9, A-Z, espacio y `- . $ / + %`
+pub(crate) fn validate(data: &str) -> Result<(), SectionError> {
+    match body(data).bytes().find(|ch| !ALPHABET.contains(ch)) {
+        Some(ch) => Err(format!("CODE39 does not support the character '{}'", ch as char).into()),
+        None => Ok(()),
+    }
+}
+
+/// Codifica los datos; los `*` de inicio y fin se añaden si no vienen incluidos
+pub fn encode(data: &str) -> Result<Symbol, SectionError> {
+    validate(data)?;
+    let body = body(data);
+
+    let mut modules = Vec::new();
+    let mut push_char = |pattern: u16| {
+        push_widths(&mut modules, &pattern_widths(pattern, 9), true);
+        modules.push(false); // Separación entre caracteres
+    };
+
+    push_char(START_STOP);
+    for ch in body.bytes() {
+        let index = ALPHABET.iter().position(|&c| c == ch).unwrap_or_default();
+        push_char(PATTERNS[index]);
+    }
+    push_char(START_STOP);
+    modules.pop();
+
+    Ok(Symbol {
+        modules,
+        hri: format!("*{}*", body),
+        quiet_zone: 10,
+    })
+}
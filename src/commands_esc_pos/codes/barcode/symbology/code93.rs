@@ -0,0 +1,86 @@
+//! CODE93 con ASCII completo y dígitos de control C y K
+
+use super::{push_bits, Symbol};
+use crate::error::SectionError;
+
+/// Valores 0-42 como CODE39; 43-46 son los desplazamientos ($), (%), (/), (+)
+const ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%";
+
+/// 9 módulos por carácter (bit a 1 = barra)
+const PATTERNS: [u16; 47] = [
+    0x114, 0x148, 0x144, 0x142, 0x128, 0x124, 0x122, 0x150, 0x112, 0x10A, 0x1A8, 0x1A4, 0x1A2,
+    0x194, 0x192, 0x18A, 0x168, 0x164, 0x162, 0x134, 0x11A, 0x158, 0x14C, 0x146, 0x12C, 0x116,
+    0x1B4, 0x1B2, 0x1AC, 0x1A6, 0x196, 0x19A, 0x16C, 0x166, 0x136, 0x13A, 0x12E, 0x1D4, 0x1D2,
+    0x1CA, 0x16E, 0x176, 0x1AE, 0x126, 0x1DA, 0x1D6, 0x132,
+];
+const START_STOP: u16 = 0x15E;
+
+const SHIFT_DOLLAR: u8 = 43;
+const SHIFT_PERCENT: u8 = 44;
+const SHIFT_SLASH: u8 = 45;
+const SHIFT_PLUS: u8 = 46;
+
+fn letter(offset: u8) -> u8 {
+    10 + offset // Valor de 'A' + offset
+}
+
+/// Valores de un carácter ASCII (uno directo o desplazamiento + letra)
+fn values(ch: u8) -> Option<Vec<u8>> {
+    if let Some(index) = ALPHABET.iter().position(|&c| c == ch) {
+        return Some(vec![index as u8]);
+    }
+    let pair = match ch {
+        0 => (SHIFT_PERCENT, letter(20)),                       // %U
+        1..=26 => (SHIFT_DOLLAR, letter(ch - 1)),               // $A-$Z
+        27..=31 => (SHIFT_PERCENT, letter(ch - 27)),            // %A-%E
+        b'!'..=b',' => (SHIFT_SLASH, letter(ch - b'!')),        // /A-/L
+        b':' => (SHIFT_SLASH, letter(25)),                      // /Z
+        b';'..=b'?' => (SHIFT_PERCENT, letter(ch - b';' + 5)),  // %F-%J
+        b'@' => (SHIFT_PERCENT, letter(21)),                    // %V
+        b'['..=b'_' => (SHIFT_PERCENT, letter(ch - b'[' + 10)), // %K-%O
+        b'`' => (SHIFT_PERCENT, letter(22)),                    // %W
+        b'a'..=b'z' => (SHIFT_PLUS, letter(ch - b'a')),         // +A-+Z
+        b'{'..=127 => (SHIFT_PERCENT, letter(ch - b'{' + 15)),  // %P-%T
+        _ => return None,
+    };
+    Some(vec![pair.0, pair.1])
+}
+
+/// Dígito de control módulo 47 con pesos 1..=`max_weight` desde la derecha
+fn check_value(values: &[u8], max_weight: usize) -> u8 {
+    let sum: usize = values
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &v)| (i % max_weight + 1) * v as usize)
+        .sum();
+    (sum % 47) as u8
+}
+
+/// Codifica datos ASCII (0-127)
+pub fn encode(data: &str) -> Result<Symbol, SectionError> {
+    let mut encoded = Vec::new();
+    for ch in data.bytes() {
+        let ch_values = values(ch)
+            .ok_or_else(|| format!("CODE93 does not support the character '{}'", ch as char))?;
+        encoded.extend(ch_values);
+    }
+    let c = check_value(&encoded, 20);
+    encoded.push(c);
+    let k = check_value(&encoded, 15);
+    encoded.push(k);
+
+    let mut modules = Vec::new();
+    push_bits(&mut modules, START_STOP as u32, 9);
+    for &value in &encoded {
+        push_bits(&mut modules, PATTERNS[value as usize] as u32, 9);
+    }
+    push_bits(&mut modules, START_STOP as u32, 9);
+    modules.push(true); // Barra de terminación
+
+    Ok(Symbol {
+        modules,
+        hri: data.to_string(),
+        quiet_zone: 10,
+    })
+}
@@ -0,0 +1,688 @@
+//! GS1 DataBar Omnidirectional / Truncated, Limited y Expanded (ISO/IEC 24724)
+
+use super::{digits, mod10_check_digit, push_widths, Symbol};
+use crate::commands_esc_pos::codes::barcode::gs1::{self, Item};
+use crate::error::SectionError;
+
+// Omnidirectional: grupos de caracteres exteriores (0-4) e interiores (5-8)
+const G_SUM: [u32; 9] = [0, 161, 961, 2015, 2715, 0, 336, 1036, 1516];
+const T_TABLE: [u32; 9] = [1, 10, 34, 70, 126, 4, 20, 48, 81];
+const MODULES_ODD: [u32; 9] = [12, 10, 8, 6, 4, 5, 7, 9, 11];
+const MODULES_EVEN: [u32; 9] = [4, 6, 8, 10, 12, 10, 8, 6, 4];
+const WIDEST_ODD: [u32; 9] = [8, 6, 4, 3, 1, 2, 4, 6, 8];
+const WIDEST_EVEN: [u32; 9] = [1, 3, 5, 6, 8, 7, 5, 3, 1];
+const CHECKSUM_WEIGHTS: [u32; 32] = [
+    1, 3, 9, 27, 2, 6, 18, 54, 4, 12, 36, 29, 8, 24, 72, 58, 16, 48, 65, 37, 32, 17, 51, 74, 64,
+    34, 23, 69, 49, 68, 46, 59,
+];
+const FINDER_PATTERNS: [[u8; 5]; 9] = [
+    [3, 8, 2, 1, 1],
+    [3, 5, 5, 1, 1],
+    [3, 3, 7, 1, 1],
+    [3, 1, 9, 1, 1],
+    [2, 7, 4, 1, 1],
+    [2, 5, 6, 1, 1],
+    [2, 3, 8, 1, 1],
+    [1, 5, 7, 1, 1],
+    [1, 3, 9, 1, 1],
+];
+
+// Limited: 7 grupos de caracteres de 26 módulos (los pares suman 26 - impares
+// y su ancho máximo es 9 - el de los impares)
+const G_SUM_LTD: [u32; 7] = [
+    0, 183_064, 820_064, 1_000_776, 1_491_021, 1_979_845, 1_996_939,
+];
+const T_EVEN_LTD: [u32; 7] = [28, 728, 6454, 203, 2408, 1, 16632];
+const MODULES_ODD_LTD: [u32; 7] = [17, 13, 9, 15, 11, 19, 7];
+const WIDEST_ODD_LTD: [u32; 7] = [6, 5, 3, 5, 4, 8, 1];
+/// Caracteres de control de Limited (anexo C): 14 elementos, 18 módulos
+const CHECK_CHARS_LTD: [[u8; 14]; 89] = [
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 3, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 3, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 3, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 1, 1, 3, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 1, 2, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 3, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 3, 2, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 1, 2, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 2, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 3, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 1, 2, 3, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 2, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 2, 1, 2, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 3, 1, 1, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 3, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 2, 1, 1, 3, 1, 1, 1],
+    [1, 2, 1, 1, 1, 2, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 2, 1, 2, 1, 1, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 3, 1, 1, 1, 1, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 2, 1, 2, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 2, 3, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 2, 1, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 2, 1, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 2, 2, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 2, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 3, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 2, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 2, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 3, 1, 1, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 2, 2, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 2, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 2, 1, 2, 1, 1, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 3, 1, 1, 1, 1, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 3, 1, 1, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 3, 1, 1, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 2, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 1, 1, 2, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 1, 1, 3, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 2, 1, 1, 2, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 2, 2, 1, 1, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 2, 2, 1, 1, 1, 2, 1, 1, 1],
+    [1, 1, 1, 3, 1, 1, 2, 1, 1, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 2, 1, 1, 2, 2, 1, 1, 1],
+    [1, 2, 1, 2, 1, 1, 2, 1, 1, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 2, 3, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 1, 1, 2, 2, 2, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 1, 1, 3, 2, 1, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 2, 1, 1, 2, 2, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 2, 1, 2, 2, 1, 1, 1],
+    [1, 1, 1, 1, 2, 2, 1, 1, 1, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 2, 1, 1, 1, 1, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 2, 1, 1, 1, 1, 2, 2, 1, 1, 1],
+    [1, 2, 1, 1, 2, 1, 1, 2, 1, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 2, 2, 1, 1, 1, 1, 2, 1, 1, 1],
+    [1, 2, 1, 2, 2, 1, 1, 1, 1, 1, 2, 1, 1, 1],
+    [1, 3, 1, 1, 2, 1, 1, 1, 1, 1, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 3, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 2, 1, 1, 2, 2, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 2, 1, 1, 1, 1, 2, 2, 1, 1],
+    [1, 1, 2, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1, 1],
+    [1, 1, 2, 2, 1, 1, 1, 1, 1, 1, 2, 2, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 2, 1, 1, 2, 2, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 2, 1, 2, 1, 1, 2, 1, 1, 1],
+    [2, 1, 1, 2, 1, 1, 1, 1, 1, 2, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 1, 2, 2, 1, 2, 1, 1],
+];
+
+// Expanded: grupos de caracteres de 12 bits
+const G_SUM_EXP: [u32; 5] = [0, 348, 1388, 2948, 3988];
+const T_EVEN_EXP: [u32; 5] = [4, 20, 52, 104, 204];
+const MODULES_ODD_EXP: [u32; 5] = [12, 10, 8, 6, 4];
+const MODULES_EVEN_EXP: [u32; 5] = [5, 7, 9, 11, 13];
+const WIDEST_ODD_EXP: [u32; 5] = [7, 5, 4, 3, 1];
+const WIDEST_EVEN_EXP: [u32; 5] = [2, 4, 5, 6, 8];
+/// Patrones de búsqueda A-F (la variante 2 es la misma invertida)
+const FINDER_PATTERNS_EXP: [[u8; 5]; 6] = [
+    [1, 8, 4, 1, 1],
+    [3, 6, 4, 1, 1],
+    [3, 4, 6, 1, 1],
+    [3, 2, 8, 1, 1],
+    [2, 6, 5, 1, 1],
+    [2, 2, 9, 1, 1],
+];
+/// Secuencia de patrones de búsqueda según el número de pares (A=0 ... F=5);
+/// los pares en posición impar usan la variante invertida
+const FINDER_SEQUENCES: [&[u8]; 10] = [
+    &[0, 0],
+    &[0, 1, 1],
+    &[0, 2, 1, 3],
+    &[0, 4, 1, 3, 2],
+    &[0, 4, 1, 3, 3, 5],
+    &[0, 4, 1, 3, 4, 5, 5],
+    &[0, 0, 1, 1, 2, 2, 3, 3],
+    &[0, 0, 1, 1, 2, 2, 3, 4, 4],
+    &[0, 0, 1, 1, 2, 2, 3, 4, 5, 5],
+    &[0, 0, 1, 1, 2, 3, 3, 4, 4, 5, 5],
+];
+/// Máximo de caracteres de datos de 12 bits (22 con el de control)
+const MAX_DATA_CHARS: usize = 21;
+
+/// Combinaciones de n elementos tomados de r en r
+fn combinations(n: i32, r: i32) -> i32 {
+    if r < 0 || n < r {
+        return 0;
+    }
+    let (min_denom, max_denom) = if n - r > r { (r, n - r) } else { (n - r, r) };
+    let mut value: i64 = 1;
+    let mut j = 1;
+    for i in (max_denom + 1..=n).rev() {
+        value *= i as i64;
+        if j <= min_denom {
+            value /= j as i64;
+            j += 1;
+        }
+    }
+    while j <= min_denom {
+        value /= j as i64;
+        j += 1;
+    }
+    value as i32
+}
+
+/// Anchos de `elements` elementos que suman `modules` para el valor `value`
+/// (algoritmo del anexo de ISO/IEC 24724). `no_narrow` permite combinaciones
+/// sin ningún elemento de un módulo.
+fn rss_widths(
+    value: u32,
+    modules: u32,
+    elements: usize,
+    max_width: u32,
+    no_narrow: bool,
+) -> Vec<u8> {
+    let elements = elements as i32;
+    let max_width = max_width as i32;
+    let mut value = value as i32;
+    let mut n = modules as i32;
+    let mut widths = Vec::with_capacity(elements as usize);
+    let mut narrow_mask = 0u32;
+
+    for bar in 0..elements - 1 {
+        let mut width = 1;
+        narrow_mask |= 1 << bar;
+        let mut sub_value;
+        loop {
+            // Todas las combinaciones posibles con este ancho
+            sub_value = combinations(n - width - 1, elements - bar - 2);
+            // Menos las que no tendrían ningún elemento estrecho
+            if !no_narrow
+                && narrow_mask == 0
+                && n - width - (elements - bar - 1) >= elements - bar - 1
+            {
+                sub_value -= combinations(n - width - (elements - bar), elements - bar - 2);
+            }
+            // Menos las que superan el ancho máximo
+            if elements - bar - 1 > 1 {
+                let mut less = 0;
+                let mut widest = n - width - (elements - bar - 2);
+                while widest > max_width {
+                    less += combinations(n - width - widest - 1, elements - bar - 3);
+                    widest -= 1;
+                }
+                sub_value -= less * (elements - 1 - bar);
+            } else if n - width > max_width {
+                sub_value -= 1;
+            }
+            value -= sub_value;
+            if value < 0 {
+                break;
+            }
+            width += 1;
+            narrow_mask &= !(1 << bar);
+        }
+        value += sub_value;
+        n -= width;
+        widths.push(width as u8);
+    }
+    widths.push(n as u8);
+    widths
+}
+
+/// Intercala anchos impares (posiciones 0, 2, ...) y pares (1, 3, ...)
+fn interleave(odd: &[u8], even: &[u8]) -> [u8; 8] {
+    let mut widths = [0; 8];
+    for i in 0..4 {
+        widths[2 * i] = odd[i];
+        widths[2 * i + 1] = even[i];
+    }
+    widths
+}
+
+/// Valida el GTIN: 13 dígitos o 14 con dígito de control
+fn gtin(data: &str, name: &str) -> Result<(u64, Vec<u8>), SectionError> {
+    let data = data.strip_prefix("(01)").unwrap_or(data);
+    let mut values = digits(data)?;
+    match values.len() {
+        13 => values.push(mod10_check_digit(&values)),
+        14 => {}
+        _ => return Err(format!("{} requires 13 digits (or 14 with check digit)", name).into()),
+    }
+    let number = values[..13]
+        .iter()
+        .fold(0u64, |acc, &d| acc * 10 + d as u64);
+    Ok((number, values))
+}
+
+/// GS1 DataBar Omnidirectional (o Truncated, que solo cambia el alto): 96 módulos
+pub fn omnidirectional(data: &str, truncated: bool) -> Result<Symbol, SectionError> {
+    let name = if truncated {
+        "GS1-DATABAR-TRUNCATED"
+    } else {
+        "GS1-DATABAR-OMNI"
+    };
+    let (number, values) = gtin(data, name)?;
+
+    let left = number / 4_537_077;
+    let right = number % 4_537_077;
+    let characters = [
+        (left / 1597) as u32,
+        (left % 1597) as u32,
+        (right / 1597) as u32,
+        (right % 1597) as u32,
+    ];
+
+    let mut char_widths = [[0u8; 8]; 4];
+    for (i, &value) in characters.iter().enumerate() {
+        let outside = i % 2 == 0;
+        let group = if outside {
+            (0..5).rev().find(|&g| value >= G_SUM[g]).unwrap_or(0)
+        } else {
+            (5..9).rev().find(|&g| value >= G_SUM[g]).unwrap_or(5)
+        };
+        let value = value - G_SUM[group];
+        let (v_odd, v_even) = if outside {
+            (value / T_TABLE[group], value % T_TABLE[group])
+        } else {
+            (value % T_TABLE[group], value / T_TABLE[group])
+        };
+        let odd = rss_widths(v_odd, MODULES_ODD[group], 4, WIDEST_ODD[group], outside);
+        let even = rss_widths(v_even, MODULES_EVEN[group], 4, WIDEST_EVEN[group], !outside);
+        char_widths[i] = interleave(&odd, &even);
+    }
+
+    let checksum = char_widths
+        .iter()
+        .enumerate()
+        .flat_map(|(c, widths)| {
+            widths
+                .iter()
+                .enumerate()
+                .map(move |(e, &w)| w as u32 * CHECKSUM_WEIGHTS[c * 8 + e])
+        })
+        .sum::<u32>()
+        % 79;
+    // Los valores 8 y 72 no se usan (combinaciones de patrones reservadas)
+    let mut checksum = checksum;
+    if checksum >= 8 {
+        checksum += 1;
+    }
+    if checksum >= 72 {
+        checksum += 1;
+    }
+    let (check_left, check_right) = ((checksum / 9) as usize, (checksum % 9) as usize);
+
+    let mut widths = vec![1, 1];
+    widths.extend_from_slice(&char_widths[0]);
+    widths.extend_from_slice(&FINDER_PATTERNS[check_left]);
+    widths.extend(char_widths[1].iter().rev());
+    widths.extend_from_slice(&char_widths[3]);
+    widths.extend(FINDER_PATTERNS[check_right].iter().rev());
+    widths.extend(char_widths[2].iter().rev());
+    widths.extend_from_slice(&[1, 1]);
+
+    let mut modules = Vec::with_capacity(96);
+    push_widths(&mut modules, &widths, false);
+    Ok(Symbol {
+        modules,
+        hri: format!(
+            "(01){}",
+            values
+                .iter()
+                .map(|d| (b'0' + d) as char)
+                .collect::<String>()
+        ),
+        quiet_zone: 1,
+    })
+}
+
+/// GS1 DataBar Limited: 74 módulos. Solo admite GTIN que empiecen por 0 o 1.
+pub fn limited(data: &str) -> Result<Symbol, SectionError> {
+    let (number, values) = gtin(data, "GS1-DATABAR-LIMITED")?;
+    if values[0] > 1 {
+        return Err("GS1-DATABAR-LIMITED requires the first digit to be 0 or 1".into());
+    }
+
+    let characters = [(number / 2_013_571) as u32, (number % 2_013_571) as u32];
+    let mut char_widths = [[0u8; 14]; 2];
+    for (i, &value) in characters.iter().enumerate() {
+        let group = (0..7).rev().find(|&g| value >= G_SUM_LTD[g]).unwrap_or(0);
+        let value = value - G_SUM_LTD[group];
+        let odd = rss_widths(
+            value / T_EVEN_LTD[group],
+            MODULES_ODD_LTD[group],
+            7,
+            WIDEST_ODD_LTD[group],
+            false,
+        );
+        let even = rss_widths(
+            value % T_EVEN_LTD[group],
+            26 - MODULES_ODD_LTD[group],
+            7,
+            9 - WIDEST_ODD_LTD[group],
+            true,
+        );
+        for e in 0..7 {
+            char_widths[i][2 * e] = odd[e];
+            char_widths[i][2 * e + 1] = even[e];
+        }
+    }
+
+    // Pesos: potencias de 3 módulo 89; el carácter derecho empieza en 20
+    let checksum = char_widths
+        .iter()
+        .zip([1, 20])
+        .map(|(widths, first)| {
+            widths
+                .iter()
+                .fold((0, first), |(sum, weight), &w| {
+                    (sum + w as u32 * weight, weight * 3 % 89)
+                })
+                .0
+        })
+        .sum::<u32>()
+        % 89;
+
+    let mut widths = vec![1, 1];
+    widths.extend_from_slice(&char_widths[0]);
+    widths.extend_from_slice(&CHECK_CHARS_LTD[checksum as usize]);
+    widths.extend_from_slice(&char_widths[1]);
+    widths.extend_from_slice(&[1, 1]);
+
+    let mut modules = Vec::with_capacity(74);
+    push_widths(&mut modules, &widths, false);
+    Ok(Symbol {
+        modules,
+        hri: format!(
+            "(01){}",
+            values
+                .iter()
+                .map(|d| (b'0' + d) as char)
+                .collect::<String>()
+        ),
+        // Los 5 módulos de silencio que pide el estándar a la derecha
+        quiet_zone: 5,
+    })
+}
+
+/// Acumula bits (MSB primero)
+struct BitWriter(Vec<bool>);
+
+impl BitWriter {
+    fn push(&mut self, value: u32, len: u32) {
+        self.0.extend((0..len).rev().map(|i| value >> i & 1 == 1));
+    }
+}
+
+/// Modos de compactación de uso general
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Numeric,
+    Alphanumeric,
+    Iso646,
+}
+
+fn is_numeric(item: Option<&Item>) -> bool {
+    matches!(item, Some(item) if *item == Item::Fnc1 || item.digit().is_some())
+}
+
+fn numeric_value(item: Item) -> u32 {
+    item.digit().map(u32::from).unwrap_or(10) // FNC1 = 10
+}
+
+/// Valor y longitud en modo alfanumérico
+fn alphanumeric(ch: u8) -> Option<(u32, u32)> {
+    match ch {
+        b'0'..=b'9' => Some((ch as u32 - b'0' as u32 + 5, 5)),
+        b'A'..=b'Z' => Some((ch as u32 - b'A' as u32 + 32, 6)),
+        b'*' => Some((58, 6)),
+        b',' => Some((59, 6)),
+        b'-' => Some((60, 6)),
+        b'.' => Some((61, 6)),
+        b'/' => Some((62, 6)),
+        _ => None,
+    }
+}
+
+/// Valor y longitud en modo ISO/IEC 646
+fn iso646(ch: u8) -> Option<(u32, u32)> {
+    const SPECIALS: &[u8] = b"!\"%&'()*+,-./:;<=>?_ ";
+    match ch {
+        b'0'..=b'9' => Some((ch as u32 - b'0' as u32 + 5, 5)),
+        b'A'..=b'Z' => Some((ch as u32 - b'A' as u32 + 64, 7)),
+        b'a'..=b'z' => Some((ch as u32 - b'a' as u32 + 90, 7)),
+        _ => SPECIALS
+            .iter()
+            .position(|&c| c == ch)
+            .map(|i| (232 + i as u32, 8)),
+    }
+}
+
+/// Compacta el campo de uso general. FNC1 siempre se codifica en modo numérico
+/// (seguido del primer dígito del siguiente AI).
+fn encode_general(items: &[Item], bits: &mut BitWriter) -> Result<Mode, SectionError> {
+    let mut mode = Mode::Numeric;
+    let mut i = 0;
+    while i < items.len() {
+        // Conviene volver a numérico ante FNC1, cuatro caracteres numéricos o
+        // si todo lo que queda es numérico.
+        let numeric_ahead = items[i] == Item::Fnc1
+            || (i..(i + 4).min(items.len())).all(|j| is_numeric(items.get(j)))
+                && (items.len() - i >= 4 || items[i..].iter().all(|&item| is_numeric(Some(&item))));
+
+        match mode {
+            Mode::Numeric => {
+                let pair = (items[i], items.get(i + 1).copied());
+                match pair {
+                    (first, Some(second))
+                        if is_numeric(Some(&first))
+                            && is_numeric(Some(&second))
+                            && !(first == Item::Fnc1 && second == Item::Fnc1) =>
+                    {
+                        bits.push(11 * numeric_value(first) + numeric_value(second) + 8, 7);
+                        i += 2;
+                    }
+                    (first, None) if first.digit().is_some() => {
+                        // Último dígito suelto: se completa con FNC1
+                        bits.push(11 * numeric_value(first) + 10 + 8, 7);
+                        i += 1;
+                    }
+                    _ => {
+                        bits.push(0b0000, 4);
+                        mode = Mode::Alphanumeric;
+                    }
+                }
+            }
+            Mode::Alphanumeric | Mode::Iso646 if numeric_ahead => {
+                bits.push(0b000, 3);
+                mode = Mode::Numeric;
+            }
+            Mode::Alphanumeric => {
+                let Item::Char(ch) = items[i] else {
+                    unreachable!("FNC1 is always encoded in numeric mode")
+                };
+                match alphanumeric(ch) {
+                    Some((value, len)) => {
+                        bits.push(value, len);
+                        i += 1;
+                    }
+                    None => {
+                        bits.push(0b00100, 5);
+                        mode = Mode::Iso646;
+                    }
+                }
+            }
+            Mode::Iso646 => {
+                let Item::Char(ch) = items[i] else {
+                    unreachable!("FNC1 is always encoded in numeric mode")
+                };
+                // Vuelve a alfanumérico si los próximos caracteres lo permiten
+                let alpha_ahead = items[i..]
+                    .iter()
+                    .take(10)
+                    .take_while(|item| **item != Item::Fnc1)
+                    .all(|item| matches!(item, Item::Char(ch) if alphanumeric(*ch).is_some()));
+                if alpha_ahead && ch.is_ascii_uppercase() {
+                    bits.push(0b00100, 5);
+                    mode = Mode::Alphanumeric;
+                    continue;
+                }
+                let (value, len) = iso646(ch).ok_or_else(|| {
+                    format!(
+                        "GS1 DataBar Expanded does not support the character '{}'",
+                        ch as char
+                    )
+                })?;
+                bits.push(value, len);
+                i += 1;
+            }
+        }
+    }
+    Ok(mode)
+}
+
+/// Anchos de un carácter Expanded de 12 bits
+fn expanded_char(value: u32) -> [u8; 8] {
+    let group = (0..5).rev().find(|&g| value >= G_SUM_EXP[g]).unwrap_or(0);
+    let value = value - G_SUM_EXP[group];
+    let odd = rss_widths(
+        value / T_EVEN_EXP[group],
+        MODULES_ODD_EXP[group],
+        4,
+        WIDEST_ODD_EXP[group],
+        false,
+    );
+    let even = rss_widths(
+        value % T_EVEN_EXP[group],
+        MODULES_EVEN_EXP[group],
+        4,
+        WIDEST_EVEN_EXP[group],
+        true,
+    );
+    interleave(&odd, &even)
+}
+
+/// Peso de control: potencias de 3 módulo 211 (fila de 8 pesos por posición)
+fn checksum_weight(row: usize, element: usize) -> u32 {
+    (0..row * 8 + element).fold(1, |acc, _| acc * 3 % 211)
+}
+
+/// GS1 DataBar Expanded (una sola fila) a partir de `(AI)valor...`
+pub fn expanded(data: &str) -> Result<Symbol, SectionError> {
+    let elements = gs1::parse(data)?;
+
+    let mut bits = BitWriter(vec![false]); // Sin componente compuesto
+    let mut general = elements.as_slice();
+    let first = &elements[0];
+    let size_field = if first.ai == "01" && first.value.len() == 14 {
+        // Método "1": (01) comprimido y el resto en el campo general
+        let gtin = digits(&first.value)?;
+        bits.push(0b100, 3);
+        bits.push(gtin[0] as u32, 4);
+        for group in gtin[1..13].chunks(3) {
+            bits.push(group.iter().fold(0, |acc, &d| acc * 10 + d as u32), 10);
+        }
+        general = &elements[1..];
+        2
+    } else {
+        // Método "00": todo en el campo general
+        bits.push(0b0000, 4);
+        3
+    };
+
+    let mode = encode_general(&gs1::element_string(general), &mut bits)?;
+
+    let data_chars = bits.0.len().div_ceil(12).max(3);
+    if data_chars > MAX_DATA_CHARS {
+        return Err("Data is too long for GS1 DataBar Expanded".into());
+    }
+    // Relleno: cambio a alfanumérico y "00100" repetido
+    let remainder = data_chars * 12 - bits.0.len();
+    let mut padding = if mode == Mode::Numeric && !general.is_empty() {
+        vec![false; 4]
+    } else {
+        Vec::new()
+    };
+    while padding.len() < remainder {
+        padding.extend([false, false, true, false, false]);
+    }
+    padding.truncate(remainder);
+    bits.0.extend(padding);
+
+    // Campo de longitud: paridad y si hay más de 14 caracteres (con el de control)
+    let symbol_chars = data_chars + 1;
+    bits.0[size_field] = symbol_chars % 2 == 1;
+    bits.0[size_field + 1] = symbol_chars > 14;
+
+    let values: Vec<u32> = bits
+        .0
+        .chunks(12)
+        .map(|chunk| chunk.iter().fold(0, |acc, &bit| acc << 1 | bit as u32))
+        .collect();
+    let char_widths: Vec<[u8; 8]> = values.iter().map(|&v| expanded_char(v)).collect();
+
+    // Carácter de control: cada carácter pondera según su patrón de búsqueda,
+    // variante y lado (el de control, A1 izquierdo, no participa)
+    let pairs = symbol_chars.div_ceil(2);
+    let sequence = FINDER_SEQUENCES[pairs - 2];
+    let checksum: u32 = char_widths
+        .iter()
+        .enumerate()
+        .map(|(i, widths)| {
+            let position = i + 1; // 0 es el carácter de control
+            let pair = position / 2;
+            let left = position % 2 == 0;
+            let row = 4 * sequence[pair] as usize
+                + if pair % 2 == 0 { 0 } else { 2 }
+                + if left { 0 } else { 1 }
+                - 1;
+            widths
+                .iter()
+                .enumerate()
+                .map(|(e, &w)| w as u32 * checksum_weight(row, e))
+                .sum::<u32>()
+        })
+        .sum::<u32>()
+        % 211;
+    let check = expanded_char(211 * (symbol_chars as u32 - 4) + checksum);
+
+    let mut widths = vec![1, 1];
+    for pair in 0..pairs {
+        let left = if pair == 0 {
+            &check
+        } else {
+            &char_widths[2 * pair - 1]
+        };
+        widths.extend_from_slice(left);
+        let finder = &FINDER_PATTERNS_EXP[sequence[pair] as usize];
+        if pair % 2 == 0 {
+            widths.extend_from_slice(finder);
+        } else {
+            widths.extend(finder.iter().rev());
+        }
+        if let Some(right) = char_widths.get(2 * pair) {
+            widths.extend(right.iter().rev());
+        }
+    }
+    widths.extend_from_slice(&[1, 1]);
+
+    let mut modules = Vec::new();
+    push_widths(&mut modules, &widths, false);
+    Ok(Symbol {
+        modules,
+        hri: gs1::hri(&elements),
+        quiet_zone: 1,
+    })
+}
@@ -0,0 +1,251 @@
+//! EAN-13, EAN-8, UPC-A y UPC-E
+
+use super::{digits, mod10_check_digit, push_bits, Symbol};
+use crate::error::SectionError;
+
+/// Juego L (paridad impar) de 7 módulos por dígito; R es su complemento y G
+/// el inverso de R
+const L_CODES: [u32; 10] = [
+    0b0001101, 0b0011001, 0b0010011, 0b0111101, 0b0100011, 0b0110001, 0b0101111, 0b0111011,
+    0b0110111, 0b0001011,
+];
+
+/// Paridad de los 6 dígitos izquierdos de EAN-13 según el primer dígito (1 = G)
+const EAN13_PARITY: [u8; 10] = [
+    0b000000, 0b001011, 0b001101, 0b001110, 0b010011, 0b011001, 0b011100, 0b010101, 0b010110,
+    0b011010,
+];
+
+/// Paridad de los 6 dígitos de UPC-E (sistema 0) según el dígito de control (1 = G)
+const UPCE_PARITY: [u8; 10] = [
+    0b111000, 0b110100, 0b110010, 0b110001, 0b101100, 0b100110, 0b100011, 0b101010, 0b101001,
+    0b100101,
+];
+
+const GUARD: (u32, u32) = (0b101, 3);
+const CENTER: (u32, u32) = (0b01010, 5);
+const UPCE_END: (u32, u32) = (0b010101, 6);
+
+/// Zona de silencio EAN/UPC en módulos
+const QUIET_ZONE: u32 = 9;
+
+fn l_code(digit: u8) -> u32 {
+    L_CODES[digit as usize]
+}
+
+fn r_code(digit: u8) -> u32 {
+    !L_CODES[digit as usize] & 0x7F
+}
+
+fn g_code(digit: u8) -> u32 {
+    // G es R leído al revés
+    let r = r_code(digit);
+    (0..7).fold(0, |acc, i| acc << 1 | (r >> i & 1))
+}
+
+/// Completa o verifica el dígito de control: `len` dígitos sin control o
+/// `len + 1` con él.
+fn with_check_digit(data: &str, len: usize, name: &str) -> Result<Vec<u8>, SectionError> {
+    let mut values = digits(data)?;
+    if values.len() == len {
+        values.push(mod10_check_digit(&values));
+    } else if values.len() == len + 1 {
+        verify_check_digit(name, values[len], mod10_check_digit(&values[..len]))?;
+    } else {
+        return Err(format!("{} requires {} or {} digits", name, len, len + 1).into());
+    }
+    Ok(values)
+}
+
+fn verify_check_digit(name: &str, found: u8, expected: u8) -> Result<(), SectionError> {
+    if found != expected {
+        return Err(format!(
+            "{} check digit is {} but should be {}",
+            name, found, expected
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Valida datos EAN-13 (12), EAN-8 (7) o UPC-A (11): longitud y dígito de
+/// control si viene incluido. Con `auto_check_digit` devuelve los datos con el
+/// dígito de control añadido.
+pub(crate) fn normalize(
+    data: &str,
+    len: usize,
+    name: &str,
+    auto_check_digit: bool,
+) -> Result<String, SectionError> {
+    let values = with_check_digit(data, len, name)?;
+    Ok(if auto_check_digit {
+        hri(&values)
+    } else {
+        data.to_string()
+    })
+}
+
+/// Igual que [`normalize`] para UPC-E. Los datos de 6 dígitos se completan con
+/// el sistema de numeración 0.
+pub(crate) fn normalize_upc_e(data: &str, auto_check_digit: bool) -> Result<String, SectionError> {
+    let (system, _, check) = parse_upc_e(data)?;
+    Ok(match data.len() {
+        6 if auto_check_digit => format!("{}{}{}", system, data, check),
+        7 | 11 if auto_check_digit => format!("{}{}", data, check),
+        _ => data.to_string(),
+    })
+}
+
+fn hri(values: &[u8]) -> String {
+    values.iter().map(|d| (b'0' + d) as char).collect()
+}
+
+/// EAN-13: 12 dígitos (+ control)
+pub fn ean13(data: &str) -> Result<Symbol, SectionError> {
+    let values = with_check_digit(data, 12, "EAN13")?;
+    Ok(ean13_symbol(&values))
+}
+
+/// UPC-A: 11 dígitos (+ control); equivale a EAN-13 con un 0 inicial
+pub fn upc_a(data: &str) -> Result<Symbol, SectionError> {
+    let values = with_check_digit(data, 11, "UPC-A")?;
+    let mut ean = vec![0];
+    ean.extend_from_slice(&values);
+    Ok(Symbol {
+        hri: hri(&values),
+        ..ean13_symbol(&ean)
+    })
+}
+
+fn ean13_symbol(values: &[u8]) -> Symbol {
+    let mut modules = Vec::with_capacity(95);
+    push_bits(&mut modules, GUARD.0, GUARD.1);
+    let parity = EAN13_PARITY[values[0] as usize];
+    for (i, &digit) in values[1..7].iter().enumerate() {
+        let code = if parity >> (5 - i) & 1 == 1 {
+            g_code(digit)
+        } else {
+            l_code(digit)
+        };
+        push_bits(&mut modules, code, 7);
+    }
+    push_bits(&mut modules, CENTER.0, CENTER.1);
+    for &digit in &values[7..13] {
+        push_bits(&mut modules, r_code(digit), 7);
+    }
+    push_bits(&mut modules, GUARD.0, GUARD.1);
+
+    Symbol {
+        modules,
+        hri: hri(values),
+        quiet_zone: QUIET_ZONE,
+    }
+}
+
+/// EAN-8: 7 dígitos (+ control)
+pub fn ean8(data: &str) -> Result<Symbol, SectionError> {
+    let values = with_check_digit(data, 7, "EAN8")?;
+
+    let mut modules = Vec::with_capacity(67);
+    push_bits(&mut modules, GUARD.0, GUARD.1);
+    for &digit in &values[..4] {
+        push_bits(&mut modules, l_code(digit), 7);
+    }
+    push_bits(&mut modules, CENTER.0, CENTER.1);
+    for &digit in &values[4..] {
+        push_bits(&mut modules, r_code(digit), 7);
+    }
+    push_bits(&mut modules, GUARD.0, GUARD.1);
+
+    Ok(Symbol {
+        modules,
+        hri: hri(&values),
+        quiet_zone: QUIET_ZONE,
+    })
+}
+
+/// UPC-E: 6 dígitos (sistema 0), 7 (sistema + 6), 8 (sistema + 6 + control) o un
+/// UPC-A de 11/12 dígitos que admita supresión de ceros.
+pub fn upc_e(data: &str) -> Result<Symbol, SectionError> {
+    let (system, body, check) = parse_upc_e(data)?;
+
+    let mut parity = UPCE_PARITY[check as usize];
+    if system == 1 {
+        parity = !parity & 0x3F;
+    }
+
+    let mut modules = Vec::with_capacity(51);
+    push_bits(&mut modules, GUARD.0, GUARD.1);
+    for (i, &digit) in body.iter().enumerate() {
+        let code = if parity >> (5 - i) & 1 == 1 {
+            g_code(digit)
+        } else {
+            l_code(digit)
+        };
+        push_bits(&mut modules, code, 7);
+    }
+    push_bits(&mut modules, UPCE_END.0, UPCE_END.1);
+
+    let mut shown = vec![system];
+    shown.extend_from_slice(&body);
+    shown.push(check);
+    Ok(Symbol {
+        modules,
+        hri: hri(&shown),
+        quiet_zone: QUIET_ZONE,
+    })
+}
+
+/// Sistema de numeración, 6 dígitos y dígito de control (calculado o verificado)
+fn parse_upc_e(data: &str) -> Result<(u8, Vec<u8>, u8), SectionError> {
+    let values = digits(data)?;
+    let (system, body, check) = match values.len() {
+        6 => (0, values.clone(), None),
+        7 => (values[0], values[1..].to_vec(), None),
+        8 => (values[0], values[1..7].to_vec(), Some(values[7])),
+        11 | 12 => {
+            let body = compress_upc_a(&values[..11])
+                .ok_or("UPC-E: this UPC-A number cannot be zero-suppressed")?;
+            (values[0], body, values.get(11).copied())
+        }
+        _ => return Err("UPC-E requires 6, 7, 8, 11 or 12 digits".into()),
+    };
+    if system > 1 {
+        return Err("UPC-E number system must be 0 or 1".into());
+    }
+
+    let expected = mod10_check_digit(&expand_upc_e(system, &body));
+    if let Some(check) = check {
+        verify_check_digit("UPC-E", check, expected)?;
+    }
+    Ok((system, body, expected))
+}
+
+/// Expande un UPC-E (sistema + 6 dígitos) a los 11 dígitos de UPC-A sin control
+pub(crate) fn expand_upc_e(system: u8, body: &[u8]) -> Vec<u8> {
+    let d = body;
+    let mut upc_a = vec![system];
+    match d[5] {
+        0..=2 => upc_a.extend_from_slice(&[d[0], d[1], d[5], 0, 0, 0, 0, d[2], d[3], d[4]]),
+        3 => upc_a.extend_from_slice(&[d[0], d[1], d[2], 0, 0, 0, 0, 0, d[3], d[4]]),
+        4 => upc_a.extend_from_slice(&[d[0], d[1], d[2], d[3], 0, 0, 0, 0, 0, d[4]]),
+        _ => upc_a.extend_from_slice(&[d[0], d[1], d[2], d[3], d[4], 0, 0, 0, 0, d[5]]),
+    }
+    upc_a
+}
+
+/// Suprime ceros de un UPC-A (11 dígitos sin control); `None` si no es posible
+fn compress_upc_a(upc_a: &[u8]) -> Option<Vec<u8>> {
+    let (m, p) = (&upc_a[1..6], &upc_a[6..11]);
+    if m[3] == 0 && m[4] == 0 && m[2] <= 2 && p[0] == 0 && p[1] == 0 {
+        Some(vec![m[0], m[1], p[2], p[3], p[4], m[2]])
+    } else if m[3] == 0 && m[4] == 0 && p[..3] == [0, 0, 0] {
+        Some(vec![m[0], m[1], m[2], p[3], p[4], 3])
+    } else if m[4] == 0 && p[..4] == [0, 0, 0, 0] {
+        Some(vec![m[0], m[1], m[2], m[3], p[4], 4])
+    } else if p[..4] == [0, 0, 0, 0] && p[4] >= 5 {
+        Some(vec![m[0], m[1], m[2], m[3], m[4], p[4]])
+    } else {
+        None
+    }
+}
@@ -0,0 +1,51 @@
+//! ITF (Interleaved 2 of 5)
+
+use super::code39::WIDE;
+use super::{digits, push_widths, Symbol};
+use crate::error::SectionError;
+
+/// 5 elementos por dígito, bit a 1 = elemento ancho
+const PATTERNS: [u8; 10] = [
+    0b00110, 0b10001, 0b01001, 0b11000, 0b00101, 0b10100, 0b01100, 0b00011, 0b10010, 0b01010,
+];
+
+fn width(pattern: u8, element: u32) -> u8 {
+    if pattern >> (4 - element) & 1 == 1 {
+        WIDE
+    } else {
+        1
+    }
+}
+
+/// Valida que los datos sean un número par de dígitos
+pub(crate) fn validate(data: &str) -> Result<(), SectionError> {
+    if digits(data)?.len() % 2 != 0 {
+        return Err("ITF requires an even number of digits".into());
+    }
+    Ok(())
+}
+
+/// Codifica un número par de dígitos: cada par se entrelaza (barras del
+/// primero, espacios del segundo).
+pub fn encode(data: &str) -> Result<Symbol, SectionError> {
+    validate(data)?;
+    let values = digits(data)?;
+
+    let mut widths = vec![1, 1, 1, 1]; // Inicio
+    for pair in values.chunks(2) {
+        let (bars, spaces) = (PATTERNS[pair[0] as usize], PATTERNS[pair[1] as usize]);
+        for element in 0..5 {
+            widths.push(width(bars, element));
+            widths.push(width(spaces, element));
+        }
+    }
+    widths.extend_from_slice(&[WIDE, 1, 1]); // Fin
+
+    let mut modules = Vec::new();
+    push_widths(&mut modules, &widths, true);
+    Ok(Symbol {
+        modules,
+        hri: data.to_string(),
+        quiet_zone: 10,
+    })
+}
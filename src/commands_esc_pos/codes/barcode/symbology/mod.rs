@@ -0,0 +1,114 @@
+//! Codificadores de simbologías 1D para el renderizado raster de códigos de barras.
+//! Cada codificador devuelve los módulos del símbolo (sin zonas de silencio) y el
+//! texto HRI que imprimiría el firmware.
+
+pub mod codabar;
+pub mod code128;
+pub mod code39;
+pub mod code93;
+pub mod databar;
+pub mod ean_upc;
+pub mod itf;
+
+use super::barcode_type::BarcodeType;
+use super::gs1;
+use crate::error::SectionError;
+
+/// Símbolo codificado: `true` = módulo negro (barra)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub modules: Vec<bool>,
+    /// Texto legible (HRI)
+    pub hri: String,
+    /// Zona de silencio a cada lado, en módulos
+    pub quiet_zone: u32,
+}
+
+/// Codifica `data` según el tipo de código de barras
+pub fn encode(barcode_type: BarcodeType, data: &str) -> Result<Symbol, SectionError> {
+    match barcode_type {
+        BarcodeType::UpcA => ean_upc::upc_a(data),
+        BarcodeType::UpcE => ean_upc::upc_e(data),
+        BarcodeType::Ean13 => ean_upc::ean13(data),
+        BarcodeType::Ean8 => ean_upc::ean8(data),
+        BarcodeType::Code39 => code39::encode(data),
+        BarcodeType::Itf => itf::encode(data),
+        BarcodeType::Codabar => codabar::encode(data),
+        BarcodeType::Code93 => code93::encode(data),
+        BarcodeType::Code128 => code128::encode(data),
+        BarcodeType::Gs1128 => code128::encode_gs1(data),
+        BarcodeType::Gs1DatabarOmni => databar::omnidirectional(data, false),
+        BarcodeType::Gs1DatabarTruncated => databar::omnidirectional(data, true),
+        BarcodeType::Gs1DatabarLimited => databar::limited(data),
+        BarcodeType::Gs1DatabarExpanded => databar::expanded(data),
+    }
+}
+
+/// Valida los datos antes de enviarlos (también en modo nativo, donde el
+/// firmware descarta los datos incorrectos sin avisar). Con `auto_check_digit`
+/// devuelve los datos EAN/UPC con el dígito de control añadido; CODE128 se
+/// devuelve en la sintaxis de ESC/POS. Los datos GS1 en formato `(AI)valor` se
+/// validan AI por AI y se envían normalizados: el firmware usa los paréntesis
+/// para el HRI e inserta él mismo los FNC1.
+pub fn validate(
+    barcode_type: BarcodeType,
+    data: &str,
+    auto_check_digit: bool,
+) -> Result<String, SectionError> {
+    match barcode_type {
+        BarcodeType::UpcA => ean_upc::normalize(data, 11, "UPC-A", auto_check_digit),
+        BarcodeType::UpcE => ean_upc::normalize_upc_e(data, auto_check_digit),
+        BarcodeType::Ean13 => ean_upc::normalize(data, 12, "EAN13", auto_check_digit),
+        BarcodeType::Ean8 => ean_upc::normalize(data, 7, "EAN8", auto_check_digit),
+        BarcodeType::Code39 => code39::validate(data).map(|_| data.to_string()),
+        BarcodeType::Itf => itf::validate(data).map(|_| data.to_string()),
+        BarcodeType::Codabar => codabar::validate(data).map(|_| data.to_string()),
+        BarcodeType::Code128 => code128::to_escpos(data),
+        BarcodeType::Gs1128 | BarcodeType::Gs1DatabarExpanded if data.starts_with('(') => {
+            gs1::parse(data).map(|elements| gs1::hri(&elements))
+        }
+        _ => Ok(data.to_string()),
+    }
+}
+
+/// Convierte anchos de elementos alternos (barra, espacio, barra, ...) en módulos.
+/// `bar_first` indica si el primer elemento es una barra.
+pub(crate) fn push_widths(modules: &mut Vec<bool>, widths: &[u8], bar_first: bool) {
+    let mut bar = bar_first;
+    for &width in widths {
+        modules.extend(std::iter::repeat(bar).take(width as usize));
+        bar = !bar;
+    }
+}
+
+/// Convierte un patrón de bits (MSB primero) de `len` módulos
+pub(crate) fn push_bits(modules: &mut Vec<bool>, bits: u32, len: u32) {
+    modules.extend((0..len).rev().map(|i| bits >> i & 1 == 1));
+}
+
+/// Dígito de control módulo 10 con pesos 3/1 desde la derecha (EAN/UPC/ITF/GS1)
+pub(crate) fn mod10_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| d as u32 * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Convierte una cadena de dígitos ASCII a valores 0-9
+pub(crate) fn digits(data: &str) -> Result<Vec<u8>, SectionError> {
+    data.bytes()
+        .map(|b| {
+            if b.is_ascii_digit() {
+                Ok(b - b'0')
+            } else {
+                Err(SectionError::from(format!(
+                    "Only numeric digits are allowed, found '{}'",
+                    b as char
+                )))
+            }
+        })
+        .collect()
+}
@@ -1,4 +1,6 @@
+use crate::commands_esc_pos::codes::barcode::gs1;
 use crate::commands_esc_pos::text::text_type::get_styles_diff;
+use crate::error::SectionError;
 use crate::models::print_sections::{Composite as CompositeSection, GlobalStyles};
 
 /// Constructor de comandos para Composite Symbology (GS1 Composite: CC-A/CC-B/CC-C).
@@ -98,12 +100,12 @@ impl Composite {
 pub fn process_section(
     composite: &CompositeSection,
     current_styles: &GlobalStyles,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, SectionError> {
     if composite.data.is_empty() {
-        return Err("Composite data cannot be empty".to_string());
+        return Err("Composite data cannot be empty".into());
     }
 
-    let esc_pos = Composite::new(composite.data.clone())
+    let esc_pos = Composite::new(gs1::escpos_2d_data(&composite.data)?)
         .set_symbol_type(composite.symbol_type)
         .set_width(composite.width);
 
@@ -1,5 +1,9 @@
 use super::data_matrix_size::DataMatrixSize;
+use super::ecc200;
+use crate::commands_esc_pos::codes::render_mode::RenderMode;
+use crate::error::SectionError;
 use crate::models::print_sections::DataMatrixModel;
+use crate::models::printer_profile::PrinterProfile;
 
 /// Constructor de comandos para códigos DataMatrix
 ///
@@ -32,6 +36,13 @@ impl DataMatrix {
         self
     }
 
+    /// Genera el DataMatrix como imagen raster (`GS v 0`): símbolo ECC 200
+    /// cuadrado codificado en Rust, con `size` puntos por módulo.
+    pub fn get_raster_command(&self) -> Result<Vec<u8>, SectionError> {
+        let symbol = ecc200::encode(self.data.as_bytes())?;
+        Ok(symbol.raster_command(self.size.value() as u32))
+    }
+
     /// Genera el comando ESC/POS para DataMatrix
     ///
     /// NOTA: DataMatrix no es soportado por todas las impresoras térmicas.
@@ -87,8 +98,12 @@ impl DataMatrix {
     }
 }
 
-/// Procesa sección DataMatrix del modelo de impresión
-pub fn process_section(data_matrix: &DataMatrixModel) -> Result<Vec<u8>, String> {
+/// Valida una sección DataMatrix y construye el comando equivalente
+pub fn from_section(data_matrix: &DataMatrixModel) -> Result<DataMatrix, SectionError> {
+    if data_matrix.data.is_empty() {
+        return Err("DataMatrix data cannot be empty".into());
+    }
+
     let size = match data_matrix.size {
         1 => DataMatrixSize::Size1,
         2 => DataMatrixSize::Size2,
@@ -109,8 +124,23 @@ pub fn process_section(data_matrix: &DataMatrixModel) -> Result<Vec<u8>, String>
         _ => DataMatrixSize::Size6,
     };
 
-    let esc_pos_dm = DataMatrix::new(data_matrix.data.clone()).set_size(size);
-    let mut data = esc_pos_dm.get_command();
+    Ok(DataMatrix::new(data_matrix.data.clone()).set_size(size))
+}
+
+/// Procesa sección DataMatrix del modelo de impresión (nativo o raster según
+/// `render_mode` y el perfil)
+pub fn process_section(
+    data_matrix: &DataMatrixModel,
+    profile: &PrinterProfile,
+) -> Result<Vec<u8>, SectionError> {
+    let esc_pos_dm = from_section(data_matrix)?;
+    let mut data = if RenderMode::from_name(data_matrix.render_mode.as_deref())?
+        .is_raster(profile.native_data_matrix)
+    {
+        esc_pos_dm.get_raster_command()?
+    } else {
+        esc_pos_dm.get_command()
+    };
     data.extend_from_slice(b"\n");
     Ok(data)
 }
@@ -0,0 +1,378 @@
+//! Codificador DataMatrix ECC 200 (ISO/IEC 16022) para el renderizado raster.
+//! Usa la codificación ASCII (pares de dígitos compactados y Upper Shift para
+//! bytes > 127) y elige el menor símbolo cuadrado en el que caben los datos.
+
+use crate::commands_esc_pos::codes::matrix_symbol::MatrixSymbol;
+use crate::commands_esc_pos::codes::reed_solomon::GaloisField;
+use crate::error::SectionError;
+use Edge::{End, Start};
+
+/// Tamaño de símbolo cuadrado ECC 200
+struct SymbolSize {
+    side: u32,     // Lado del símbolo en módulos
+    region: u32,   // Lado de cada región de datos (sin patrón guía)
+    data: usize,   // Palabras de datos
+    ecc: usize,    // Palabras de corrección en total
+    blocks: usize, // Bloques Reed-Solomon intercalados
+}
+
+#[rustfmt::skip]
+const SIZES: [SymbolSize; 24] = [
+    SymbolSize { side: 10, region: 8, data: 3, ecc: 5, blocks: 1 },
+    SymbolSize { side: 12, region: 10, data: 5, ecc: 7, blocks: 1 },
+    SymbolSize { side: 14, region: 12, data: 8, ecc: 10, blocks: 1 },
+    SymbolSize { side: 16, region: 14, data: 12, ecc: 12, blocks: 1 },
+    SymbolSize { side: 18, region: 16, data: 18, ecc: 14, blocks: 1 },
+    SymbolSize { side: 20, region: 18, data: 22, ecc: 18, blocks: 1 },
+    SymbolSize { side: 22, region: 20, data: 30, ecc: 20, blocks: 1 },
+    SymbolSize { side: 24, region: 22, data: 36, ecc: 24, blocks: 1 },
+    SymbolSize { side: 26, region: 24, data: 44, ecc: 28, blocks: 1 },
+    SymbolSize { side: 32, region: 14, data: 62, ecc: 36, blocks: 1 },
+    SymbolSize { side: 36, region: 16, data: 86, ecc: 42, blocks: 1 },
+    SymbolSize { side: 40, region: 18, data: 114, ecc: 48, blocks: 1 },
+    SymbolSize { side: 44, region: 20, data: 144, ecc: 56, blocks: 1 },
+    SymbolSize { side: 48, region: 22, data: 174, ecc: 68, blocks: 1 },
+    SymbolSize { side: 52, region: 24, data: 204, ecc: 84, blocks: 2 },
+    SymbolSize { side: 64, region: 14, data: 280, ecc: 112, blocks: 2 },
+    SymbolSize { side: 72, region: 16, data: 368, ecc: 144, blocks: 4 },
+    SymbolSize { side: 80, region: 18, data: 456, ecc: 192, blocks: 4 },
+    SymbolSize { side: 88, region: 20, data: 576, ecc: 224, blocks: 4 },
+    SymbolSize { side: 96, region: 22, data: 696, ecc: 272, blocks: 4 },
+    SymbolSize { side: 104, region: 24, data: 816, ecc: 336, blocks: 6 },
+    SymbolSize { side: 120, region: 18, data: 1050, ecc: 408, blocks: 6 },
+    SymbolSize { side: 132, region: 20, data: 1304, ecc: 496, blocks: 8 },
+    SymbolSize { side: 144, region: 22, data: 1558, ecc: 620, blocks: 10 },
+];
+
+/// Polinomio primitivo de GF(256) en DataMatrix: x^8 + x^5 + x^3 + x^2 + 1
+const PRIMITIVE: u32 = 0x12D;
+/// Palabra de relleno
+const PAD: u8 = 129;
+/// Upper Shift: el siguiente carácter es un byte 128-255
+const UPPER_SHIFT: u8 = 235;
+
+/// Codifica `data` en el menor símbolo cuadrado ECC 200
+pub fn encode(data: &[u8]) -> Result<MatrixSymbol, SectionError> {
+    let codewords = symbol_codewords(data)?;
+    // Cada tamaño tiene un total de palabras distinto, así que el total lo identifica
+    let size = SIZES
+        .iter()
+        .find(|size| size.data + size.ecc == codewords.len())
+        .expect("symbol_codewords fills a whole symbol");
+    Ok(build_matrix(&codewords, size))
+}
+
+/// Palabras del símbolo completas (datos, relleno y corrección intercalados)
+pub fn symbol_codewords(data: &[u8]) -> Result<Vec<u8>, SectionError> {
+    if data.is_empty() {
+        return Err("DataMatrix data cannot be empty".into());
+    }
+
+    let mut codewords = ascii_codewords(data);
+    let size = SIZES
+        .iter()
+        .find(|size| size.data >= codewords.len())
+        .ok_or_else(|| {
+            format!(
+                "DataMatrix data needs {} codewords but the largest symbol holds {}",
+                codewords.len(),
+                SIZES[SIZES.len() - 1].data
+            )
+        })?;
+
+    pad(&mut codewords, size.data);
+    Ok(add_error_correction(&codewords, size))
+}
+
+/// Codificación ASCII: dos dígitos seguidos ocupan una palabra (130 + valor)
+fn ascii_codewords(data: &[u8]) -> Vec<u8> {
+    let mut codewords = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        let byte = data[i];
+        match data.get(i + 1) {
+            Some(next) if byte.is_ascii_digit() && next.is_ascii_digit() => {
+                codewords.push(130 + (byte - b'0') * 10 + (next - b'0'));
+                i += 2;
+                continue;
+            }
+            _ => {}
+        }
+        if byte > 127 {
+            codewords.push(UPPER_SHIFT);
+            codewords.push(byte - 128 + 1);
+        } else {
+            codewords.push(byte + 1);
+        }
+        i += 1;
+    }
+    codewords
+}
+
+/// Rellena hasta la capacidad: el primer relleno es 129 y los siguientes se
+/// aleatorizan con el algoritmo de 253 estados
+fn pad(codewords: &mut Vec<u8>, capacity: usize) {
+    if codewords.len() < capacity {
+        codewords.push(PAD);
+    }
+    while codewords.len() < capacity {
+        let position = codewords.len() as u32 + 1;
+        let random = (149 * position) % 253 + 1;
+        let mut value = PAD as u32 + random;
+        if value > 254 {
+            value -= 254;
+        }
+        codewords.push(value as u8);
+    }
+}
+
+/// Añade las palabras de corrección; en los símbolos grandes los datos se
+/// reparten entre bloques intercalados (la palabra i va al bloque i % bloques)
+fn add_error_correction(data: &[u8], size: &SymbolSize) -> Vec<u8> {
+    let field = GaloisField::new(PRIMITIVE, 256);
+    let ecc_per_block = size.ecc / size.blocks;
+    let mut output = data.to_vec();
+    output.resize(size.data + size.ecc, 0);
+
+    for block in 0..size.blocks {
+        let block_data: Vec<u32> = data
+            .iter()
+            .skip(block)
+            .step_by(size.blocks)
+            .map(|&word| word as u32)
+            .collect();
+        let check = field.check_words(&block_data, ecc_per_block);
+        for (k, word) in check.into_iter().enumerate() {
+            output[size.data + block + k * size.blocks] = word as u8;
+        }
+    }
+    output
+}
+
+/// Coloca las palabras en la matriz de datos (algoritmo de colocación del anexo
+/// de la norma) y añade los patrones guía de cada región
+fn build_matrix(codewords: &[u8], size: &SymbolSize) -> MatrixSymbol {
+    let regions = size.side / (size.region + 2);
+    let mapping_side = size.region * regions;
+    let placement = Placement::new(mapping_side as i32, mapping_side as i32);
+
+    let mut symbol = MatrixSymbol::new(size.side, size.side);
+    let block = size.region + 2;
+
+    // Patrones guía: borde izquierdo e inferior sólidos, superior y derecho alternos
+    for y in 0..size.side {
+        for x in 0..size.side {
+            let (bx, by) = (x % block, y % block);
+            let dark = bx == 0
+                || by == block - 1
+                || (by == 0 && bx % 2 == 0)
+                || (bx == block - 1 && by % 2 == 1);
+            if dark {
+                symbol.set(x, y);
+            }
+        }
+    }
+
+    for row in 0..mapping_side {
+        for col in 0..mapping_side {
+            let dark = match placement.cells[(row * mapping_side + col) as usize] {
+                Cell::Fixed(dark) => dark,
+                Cell::Bit { codeword, bit } => codewords[codeword] & (1 << bit) != 0,
+            };
+            if dark {
+                let y = (row / size.region) * block + 1 + row % size.region;
+                let x = (col / size.region) * block + 1 + col % size.region;
+                symbol.set(x, y);
+            }
+        }
+    }
+    symbol
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Cell {
+    Fixed(bool),
+    Bit { codeword: usize, bit: u8 },
+}
+
+/// Recorrido en diagonal que asigna a cada módulo un bit de una palabra
+struct Placement {
+    rows: i32,
+    columns: i32,
+    cells: Vec<Cell>,
+    filled: Vec<bool>,
+}
+
+impl Placement {
+    fn new(rows: i32, columns: i32) -> Self {
+        let mut placement = Self {
+            rows,
+            columns,
+            cells: vec![Cell::Fixed(false); (rows * columns) as usize],
+            filled: vec![false; (rows * columns) as usize],
+        };
+        placement.run();
+        placement
+    }
+
+    fn run(&mut self) {
+        let (nr, nc) = (self.rows, self.columns);
+        let mut codeword = 0usize;
+        let (mut r, mut c) = (4i32, 0i32);
+
+        loop {
+            // Esquinas especiales
+            if r == nr && c == 0 {
+                self.corner(codeword, &CORNER_A);
+                codeword += 1;
+            }
+            if r == nr - 2 && c == 0 && nc % 4 != 0 {
+                self.corner(codeword, &CORNER_B);
+                codeword += 1;
+            }
+            if r == nr - 2 && c == 0 && nc % 8 == 4 {
+                self.corner(codeword, &CORNER_C);
+                codeword += 1;
+            }
+            if r == nr + 4 && c == 2 && nc % 8 == 0 {
+                self.corner(codeword, &CORNER_D);
+                codeword += 1;
+            }
+
+            // Diagonal hacia arriba a la derecha
+            loop {
+                if r < nr && c >= 0 && !self.filled[(r * nc + c) as usize] {
+                    self.utah(r, c, codeword);
+                    codeword += 1;
+                }
+                r -= 2;
+                c += 2;
+                if !(r >= 0 && c < nc) {
+                    break;
+                }
+            }
+            r += 1;
+            c += 3;
+
+            // Diagonal hacia abajo a la izquierda
+            loop {
+                if r >= 0 && c < nc && !self.filled[(r * nc + c) as usize] {
+                    self.utah(r, c, codeword);
+                    codeword += 1;
+                }
+                r += 2;
+                c -= 2;
+                if !(r < nr && c >= 0) {
+                    break;
+                }
+            }
+            r += 3;
+            c += 1;
+
+            if !(r < nr || c < nc) {
+                break;
+            }
+        }
+
+        // Esquina inferior derecha sin usar: patrón fijo en damero
+        let last = (nr * nc - 1) as usize;
+        if !self.filled[last] {
+            self.cells[last] = Cell::Fixed(true);
+            self.cells[last - nc as usize - 1] = Cell::Fixed(true);
+        }
+    }
+
+    fn module(&mut self, mut r: i32, mut c: i32, codeword: usize, bit: u8) {
+        let (nr, nc) = (self.rows, self.columns);
+        if r < 0 {
+            r += nr;
+            c += 4 - ((nr + 4) % 8);
+        }
+        if c < 0 {
+            c += nc;
+            r += 4 - ((nc + 4) % 8);
+        }
+        let index = (r * nc + c) as usize;
+        self.cells[index] = Cell::Bit { codeword, bit };
+        self.filled[index] = true;
+    }
+
+    /// Forma estándar ("utah") de 8 módulos con el bit menos significativo en (r, c)
+    fn utah(&mut self, r: i32, c: i32, codeword: usize) {
+        self.module(r - 2, c - 2, codeword, 7);
+        self.module(r - 2, c - 1, codeword, 6);
+        self.module(r - 1, c - 2, codeword, 5);
+        self.module(r - 1, c - 1, codeword, 4);
+        self.module(r - 1, c, codeword, 3);
+        self.module(r, c - 2, codeword, 2);
+        self.module(r, c - 1, codeword, 1);
+        self.module(r, c, codeword, 0);
+    }
+
+    fn corner(&mut self, codeword: usize, shape: &[(Edge, Edge); 8]) {
+        for (i, (row, column)) in shape.iter().enumerate() {
+            let r = row.resolve(self.rows);
+            let c = column.resolve(self.columns);
+            self.module(r, c, codeword, 7 - i as u8);
+        }
+    }
+}
+
+/// Coordenada relativa al principio (`Start(n)`) o al final (`End(n)` = n-ésima
+/// desde el último) de una dimensión
+#[derive(Debug, Clone, Copy)]
+enum Edge {
+    Start(i32),
+    End(i32),
+}
+
+impl Edge {
+    fn resolve(self, len: i32) -> i32 {
+        match self {
+            Edge::Start(n) => n,
+            Edge::End(n) => len - 1 - n,
+        }
+    }
+}
+
+// Formas de las cuatro esquinas especiales, del bit 7 al bit 0 (fila, columna)
+const CORNER_A: [(Edge, Edge); 8] = [
+    (End(0), Start(0)),
+    (End(0), Start(1)),
+    (End(0), Start(2)),
+    (Start(0), End(1)),
+    (Start(0), End(0)),
+    (Start(1), End(0)),
+    (Start(2), End(0)),
+    (Start(3), End(0)),
+];
+const CORNER_B: [(Edge, Edge); 8] = [
+    (End(2), Start(0)),
+    (End(1), Start(0)),
+    (End(0), Start(0)),
+    (Start(0), End(3)),
+    (Start(0), End(2)),
+    (Start(0), End(1)),
+    (Start(0), End(0)),
+    (Start(1), End(0)),
+];
+const CORNER_C: [(Edge, Edge); 8] = [
+    (End(2), Start(0)),
+    (End(1), Start(0)),
+    (End(0), Start(0)),
+    (Start(0), End(1)),
+    (Start(0), End(0)),
+    (Start(1), End(0)),
+    (Start(2), End(0)),
+    (Start(3), End(0)),
+];
+const CORNER_D: [(Edge, Edge); 8] = [
+    (End(0), Start(0)),
+    (End(0), End(0)),
+    (Start(0), End(2)),
+    (Start(0), End(1)),
+    (Start(0), End(0)),
+    (Start(1), End(2)),
+    (Start(1), End(1)),
+    (Start(1), End(0)),
+];
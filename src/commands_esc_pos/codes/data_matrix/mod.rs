@@ -1,2 +1,3 @@
 pub mod data_matrix;
 pub mod data_matrix_size;
+pub mod ecc200;
@@ -1,5 +1,7 @@
 use super::gs1_databar_2d_type::Gs1Databar2dType;
+use crate::commands_esc_pos::codes::barcode::gs1;
 use crate::commands_esc_pos::text::text_type::get_styles_diff;
+use crate::error::SectionError;
 use crate::models::print_sections::{GlobalStyles, Gs1Databar2d as Gs1Databar2dSection};
 
 /// Constructor de comandos para GS1 DataBar bidimensional
@@ -85,13 +87,25 @@ impl Gs1Databar2d {
     }
 }
 
+/// Stacked y Stacked Omni codifican un GTIN sin dígito de control (13 dígitos);
+/// `(01)` con el GTIN-14 completo se valida y se convierte a ese formato.
+fn stacked_data(data: &str) -> Result<String, SectionError> {
+    if !data.starts_with('(') {
+        return Ok(data.to_string());
+    }
+    match gs1::parse(data)?.as_slice() {
+        [gtin] if gtin.ai == "01" => Ok(gtin.value[..13].to_string()),
+        _ => Err("GS1 DataBar Stacked only encodes a single (01) GTIN".into()),
+    }
+}
+
 /// Procesa sección GS1 DataBar 2D del modelo de impresión
 pub fn process_section(
     databar: &Gs1Databar2dSection,
     current_styles: &GlobalStyles,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, SectionError> {
     if databar.data.is_empty() {
-        return Err("GS1 DataBar 2D data cannot be empty".to_string());
+        return Err("GS1 DataBar 2D data cannot be empty".into());
     }
 
     let databar_type = match databar.databar_type.as_str() {
@@ -101,7 +115,11 @@ pub fn process_section(
         _ => Gs1Databar2dType::StackedOmni,
     };
 
-    let esc_pos = Gs1Databar2d::new(databar.data.clone(), databar_type).set_width(databar.width);
+    let data = match databar_type {
+        Gs1Databar2dType::ExpandedStacked => gs1::escpos_2d_data(&databar.data)?,
+        _ => stacked_data(&databar.data)?,
+    };
+    let esc_pos = Gs1Databar2d::new(data, databar_type).set_width(databar.width);
 
     let mut data = Vec::new();
     if let Some(ref align) = databar.align {
@@ -0,0 +1,54 @@
+use crate::commands_esc_pos::image_escpos::image_code::raster_command;
+use crate::commands_esc_pos::image_escpos::image_processor::ImageProcessor;
+use crate::commands_esc_pos::image_escpos::ImageMode;
+
+/// Símbolo 2D codificado en Rust: matriz de módulos por filas (`true` = negro)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixSymbol {
+    pub modules: Vec<bool>,
+    pub columns: u32,
+}
+
+impl MatrixSymbol {
+    /// Matriz vacía (todo blanco) de `columns` × `rows` módulos
+    pub fn new(columns: u32, rows: u32) -> Self {
+        Self {
+            modules: vec![false; (columns * rows) as usize],
+            columns,
+        }
+    }
+
+    /// Número de filas de módulos
+    pub fn rows(&self) -> u32 {
+        (self.modules.len() as u32)
+            .checked_div(self.columns)
+            .unwrap_or(0)
+    }
+
+    /// `true` si el módulo de la columna `x` y la fila `y` es negro
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        self.modules[(y * self.columns + x) as usize]
+    }
+
+    pub fn set(&mut self, x: u32, y: u32) {
+        self.modules[(y * self.columns + x) as usize] = true;
+    }
+
+    /// Imprime el símbolo como imagen raster (`GS v 0`) con módulos de
+    /// `module_size` × `module_size` puntos
+    pub fn raster_command(&self, module_size: u32) -> Vec<u8> {
+        self.raster_command_scaled(module_size, module_size)
+    }
+
+    /// Igual que `raster_command` con módulos de `module_width` × `module_height`
+    /// puntos (filas de PDF417 más altas que anchas)
+    pub fn raster_command_scaled(&self, module_width: u32, module_height: u32) -> Vec<u8> {
+        let binary = ImageProcessor::modules_to_image(
+            &self.modules,
+            self.columns,
+            module_width,
+            module_height,
+        );
+        raster_command(&binary, ImageMode::Normal)
+    }
+}
@@ -1,5 +1,9 @@
+use super::maxicode_encoder;
+use crate::commands_esc_pos::codes::render_mode::RenderMode;
 use crate::commands_esc_pos::text::text_type::get_styles_diff;
+use crate::error::SectionError;
 use crate::models::print_sections::{GlobalStyles, MaxiCode as MaxiCodeSection};
+use crate::models::printer_profile::PrinterProfile;
 
 /// Constructor de comandos para códigos MaxiCode
 ///
@@ -26,6 +30,13 @@ impl MaxiCode {
         self
     }
 
+    /// Genera el MaxiCode como imagen raster (`GS v 0`) codificado en Rust, con el
+    /// mismo modo que el comando nativo y su tamaño físico nominal.
+    pub fn get_raster_command(&self) -> Result<Vec<u8>, SectionError> {
+        let symbol = maxicode_encoder::encode(&self.data, self.mode)?;
+        Ok(maxicode_encoder::render(&symbol).raster_command(1))
+    }
+
     /// Genera el comando ESC/POS para MaxiCode
     ///
     /// NOTA: no soportado por todas las impresoras térmicas.
@@ -81,27 +92,41 @@ impl MaxiCode {
     }
 }
 
-/// Procesa sección MaxiCode del modelo de impresión
-pub fn process_section(
-    maxicode: &MaxiCodeSection,
-    current_styles: &GlobalStyles,
-) -> Result<Vec<u8>, String> {
+/// Valida una sección MaxiCode y construye el comando equivalente
+pub fn from_section(maxicode: &MaxiCodeSection) -> Result<MaxiCode, SectionError> {
     if maxicode.data.is_empty() {
-        return Err("MaxiCode data cannot be empty".to_string());
+        return Err("MaxiCode data cannot be empty".into());
     }
 
-    let esc_pos = MaxiCode::new(maxicode.data.clone()).set_mode(maxicode.mode);
+    Ok(MaxiCode::new(maxicode.data.clone()).set_mode(maxicode.mode))
+}
+
+/// Procesa sección MaxiCode del modelo de impresión (nativo o raster según
+/// `render_mode` y el perfil)
+pub fn process_section(
+    maxicode: &MaxiCodeSection,
+    current_styles: &GlobalStyles,
+    profile: &PrinterProfile,
+) -> Result<Vec<u8>, SectionError> {
+    let esc_pos = from_section(maxicode)?;
+    let maxicode_command = if RenderMode::from_name(maxicode.render_mode.as_deref())?
+        .is_raster(profile.native_maxicode)
+    {
+        esc_pos.get_raster_command()?
+    } else {
+        esc_pos.get_command()
+    };
 
     let mut data = Vec::new();
     if let Some(ref align) = maxicode.align {
         let mut temp_styles = current_styles.clone();
         temp_styles.align = Some(align.clone());
         data.extend_from_slice(&get_styles_diff(current_styles, &temp_styles));
-        data.extend_from_slice(&esc_pos.get_command());
+        data.extend_from_slice(&maxicode_command);
         data.extend_from_slice(b"\n");
         data.extend_from_slice(&get_styles_diff(&temp_styles, current_styles));
     } else {
-        data.extend_from_slice(&esc_pos.get_command());
+        data.extend_from_slice(&maxicode_command);
         data.extend_from_slice(b"\n");
     }
 
@@ -0,0 +1,501 @@
+//! Codificador MaxiCode (ISO/IEC 16023) para el renderizado raster.
+//!
+//! Codificación de alto nivel voraz: cada byte va en el juego de caracteres
+//! actual o en el primero que lo contenga (con shift si el siguiente no lo
+//! necesita, con latch si sí) y las series de 9 dígitos van en modo numérico.
+//! El resultado es un símbolo válido aunque no siempre el más compacto posible.
+
+use crate::commands_esc_pos::codes::matrix_symbol::MatrixSymbol;
+use crate::commands_esc_pos::codes::reed_solomon::GaloisField;
+use crate::error::SectionError;
+use crate::models::paper_size::DOTS_PER_MM;
+
+/// Rejilla de módulos: 33 filas de 30 (las impares usan 29)
+pub const COLUMNS: u32 = 30;
+pub const ROWS: u32 = 33;
+
+/// Ancho nominal del módulo (distancia entre lados del hexágono), en mm
+const MODULE_WIDTH_MM: f32 = 0.88;
+/// Fracción del módulo que ocupa cada hexágono; el resto queda como separación
+const HEXAGON_FILL: f32 = 0.9;
+
+// Juegos de caracteres como bits de `CODE_SETS`
+const SET_A: u8 = 0x01;
+const SET_B: u8 = 0x02;
+const SET_E: u8 = 0x04;
+const SET_C: u8 = 0x08;
+const SET_D: u8 = 0x10;
+/// Orden de preferencia al cambiar de juego: los que tienen PAD primero
+const SET_ORDER: [u8; 5] = [SET_A, SET_B, SET_E, SET_C, SET_D];
+
+// Palabras de control de la codificación de alto nivel
+const NUMERIC_SHIFT: u8 = 31; // NS: 9 dígitos en las 5 palabras siguientes
+const LATCH_A: u8 = 58; // desde C, D y E (63 desde B)
+const PAD: u8 = 33; // en los juegos A y B
+const PAD_E: u8 = 28;
+/// Cabecera de mensaje estructurado "[)>␞01␝" seguida de dos dígitos de versión
+const STRUCTURED_HEADER: &[u8] = b"[)>\x1e01\x1d";
+const GS: u8 = 0x1d;
+
+/// Codifica `data` en la rejilla de módulos de un MaxiCode del `mode` indicado (2-6).
+///
+/// En los modos 2 y 3 el mensaje primario sale del propio texto, como en el
+/// comando nativo: `código postal␝país␝clase de servicio␝resto`, opcionalmente
+/// precedido de la cabecera `[)>␞01␝vv`.
+pub fn encode(data: &str, mode: u8) -> Result<MatrixSymbol, SectionError> {
+    if data.is_empty() {
+        return Err("MaxiCode data cannot be empty".into());
+    }
+    if !(2..=6).contains(&mode) {
+        return Err(format!("MaxiCode mode must be 2-6, got {}", mode).into());
+    }
+
+    let bytes = latin1_bytes(data)?;
+    let mut codewords = [0u8; 144];
+
+    if mode <= 3 {
+        let (primary, message) = structured_primary(&bytes, mode)?;
+        codewords[..10].copy_from_slice(&primary);
+        let text = text_codewords(&message, 84)?;
+        codewords[20..104].copy_from_slice(&text);
+    } else {
+        // Los 9 primeros caracteres van en el mensaje primario, tras el modo
+        let capacity = if mode == 5 { 77 } else { 93 };
+        let text = text_codewords(&bytes, capacity)?;
+        codewords[0] = mode;
+        codewords[1..10].copy_from_slice(&text[..9]);
+        codewords[20..20 + capacity - 9].copy_from_slice(&text[9..]);
+    }
+
+    add_error_correction(&mut codewords, mode);
+    Ok(build_matrix(&codewords))
+}
+
+/// Texto en ISO-8859-1, el juego de caracteres por defecto de MaxiCode
+fn latin1_bytes(data: &str) -> Result<Vec<u8>, SectionError> {
+    data.chars()
+        .map(|c| {
+            u8::try_from(c as u32)
+                .map_err(|_| format!("MaxiCode cannot encode '{}' (only ISO-8859-1)", c).into())
+        })
+        .collect()
+}
+
+/// Mensaje primario estructurado de los modos 2 y 3 y el resto del texto
+fn structured_primary(bytes: &[u8], mode: u8) -> Result<([u8; 10], Vec<u8>), SectionError> {
+    let header_len = if bytes.len() >= 9
+        && bytes.starts_with(STRUCTURED_HEADER)
+        && bytes[7..9].iter().all(u8::is_ascii_digit)
+    {
+        9
+    } else {
+        0
+    };
+
+    let mut fields = bytes[header_len..].splitn(4, |&byte| byte == GS);
+    let (postcode, country, service) = match (fields.next(), fields.next(), fields.next()) {
+        (Some(postcode), Some(country), Some(service)) => (postcode, country, service),
+        _ => {
+            return Err(format!(
+                "MaxiCode mode {} data must start with \"postcode<GS>country<GS>class<GS>\"",
+                mode
+            )
+            .into())
+        }
+    };
+    let country = three_digits(country, "country code")?;
+    let service = three_digits(service, "service class")?;
+
+    let mut message = bytes[..header_len].to_vec();
+    message.extend_from_slice(fields.next().unwrap_or_default());
+
+    let primary = if mode == 2 {
+        if postcode.is_empty() || postcode.len() > 9 || !postcode.iter().all(u8::is_ascii_digit) {
+            return Err("MaxiCode mode 2 postcode must be 1-9 digits".into());
+        }
+        // Anexo B: en EE. UU. (840) el "+4" desconocido se rellena con ceros
+        let mut digits = postcode.to_vec();
+        if country == 840 && digits.len() == 5 {
+            digits.extend_from_slice(b"0000");
+        }
+        let number = digits
+            .iter()
+            .fold(0u32, |value, &digit| value * 10 + (digit - b'0') as u32);
+        primary_mode_2(number, digits.len() as u32, country, service)
+    } else {
+        if postcode.is_empty() || postcode.len() > 6 {
+            return Err("MaxiCode mode 3 postcode must be 1-6 characters".into());
+        }
+        // Seis caracteres del juego A, en mayúsculas y completados con espacios
+        let mut values = [SYMBOL_VALUES[b' ' as usize]; 6];
+        for (value, &byte) in values.iter_mut().zip(postcode) {
+            let byte = byte.to_ascii_uppercase();
+            if byte < b' ' || CODE_SETS[byte as usize] & SET_A == 0 {
+                return Err(
+                    format!("MaxiCode mode 3 postcode cannot contain '{}'", byte as char).into(),
+                );
+            }
+            *value = SYMBOL_VALUES[byte as usize];
+        }
+        primary_mode_3(&values, country, service)
+    };
+
+    Ok((primary, message))
+}
+
+fn three_digits(field: &[u8], name: &str) -> Result<u32, SectionError> {
+    if field.len() != 3 || !field.iter().all(u8::is_ascii_digit) {
+        return Err(format!("MaxiCode {} must be 3 digits", name).into());
+    }
+    Ok(field
+        .iter()
+        .fold(0, |value, &digit| value * 10 + (digit - b'0') as u32))
+}
+
+/// Primario del modo 2: código postal numérico de 30 bits y su longitud
+fn primary_mode_2(postcode: u32, length: u32, country: u32, service: u32) -> [u8; 10] {
+    [
+        (((postcode & 0x03) << 4) | 2) as u8,
+        ((postcode & 0xFC) >> 2) as u8,
+        ((postcode & 0x3F00) >> 8) as u8,
+        ((postcode & 0xFC000) >> 14) as u8,
+        ((postcode & 0x3F0_0000) >> 20) as u8,
+        (((postcode & 0x3C00_0000) >> 26) | ((length & 0x03) << 4)) as u8,
+        (((length & 0x3C) >> 2) | ((country & 0x03) << 4)) as u8,
+        ((country & 0xFC) >> 2) as u8,
+        (((country & 0x300) >> 8) | ((service & 0x0F) << 2)) as u8,
+        ((service & 0x3F0) >> 4) as u8,
+    ]
+}
+
+/// Primario del modo 3: código postal alfanumérico de 6 caracteres del juego A
+fn primary_mode_3(postcode: &[u8; 6], country: u32, service: u32) -> [u8; 10] {
+    let country_low = (country & 0x03) as u8;
+    [
+        ((postcode[5] & 0x03) << 4) | 3,
+        ((postcode[4] & 0x03) << 4) | ((postcode[5] & 0x3C) >> 2),
+        ((postcode[3] & 0x03) << 4) | ((postcode[4] & 0x3C) >> 2),
+        ((postcode[2] & 0x03) << 4) | ((postcode[3] & 0x3C) >> 2),
+        ((postcode[1] & 0x03) << 4) | ((postcode[2] & 0x3C) >> 2),
+        ((postcode[0] & 0x03) << 4) | ((postcode[1] & 0x3C) >> 2),
+        ((postcode[0] & 0x3C) >> 2) | (country_low << 4),
+        ((country & 0xFC) >> 2) as u8,
+        (((country & 0x300) >> 8) | ((service & 0x0F) << 2)) as u8,
+        ((service & 0x3F0) >> 4) as u8,
+    ]
+}
+
+/// Codificación de alto nivel de `message` en exactamente `capacity` palabras
+fn text_codewords(message: &[u8], capacity: usize) -> Result<Vec<u8>, SectionError> {
+    let mut codewords = Vec::with_capacity(capacity);
+    let mut set = SET_A;
+    let mut index = 0;
+
+    while index < message.len() {
+        let digits = message[index..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        if digits >= 9 {
+            let value = message[index..index + 9]
+                .iter()
+                .fold(0u32, |value, &digit| value * 10 + (digit - b'0') as u32);
+            codewords.push(NUMERIC_SHIFT);
+            codewords.extend([24, 18, 12, 6, 0].map(|shift| ((value >> shift) & 0x3F) as u8));
+            index += 9;
+            continue;
+        }
+
+        let byte = message[index];
+        let sets = CODE_SETS[byte as usize];
+        if sets & set == 0 {
+            let target = SET_ORDER
+                .into_iter()
+                .find(|&candidate| sets & candidate != 0)
+                .unwrap_or(SET_A);
+            // Shift para un carácter suelto; latch si el siguiente también lo necesita
+            let next_needs_target = message.get(index + 1).is_some_and(|&next| {
+                CODE_SETS[next as usize] & target != 0 && CODE_SETS[next as usize] & set == 0
+            });
+            match shift_code(set, target) {
+                Some(shift) if !next_needs_target => {
+                    codewords.push(shift);
+                    codewords.push(symbol_value(target, byte));
+                    index += 1;
+                    continue;
+                }
+                _ => {
+                    codewords.extend_from_slice(latch_codes(set, target));
+                    set = target;
+                }
+            }
+        }
+        codewords.push(symbol_value(set, byte));
+        index += 1;
+    }
+
+    if codewords.len() > capacity {
+        return Err(format!(
+            "MaxiCode data needs {} codewords, this mode holds {}",
+            codewords.len(),
+            capacity
+        )
+        .into());
+    }
+
+    // Los juegos C y D no tienen PAD: se vuelve antes al juego A
+    if codewords.len() < capacity && (set == SET_C || set == SET_D) {
+        codewords.push(LATCH_A);
+        set = SET_A;
+    }
+    codewords.resize(capacity, if set == SET_E { PAD_E } else { PAD });
+    Ok(codewords)
+}
+
+/// Valor de `byte` en el juego `set`, para los caracteres que están en varios
+fn symbol_value(set: u8, byte: u8) -> u8 {
+    if CODE_SETS[byte as usize] == set || set == SET_A {
+        return SYMBOL_VALUES[byte as usize];
+    }
+    if set == SET_B {
+        if let Some(position) = b" ,./:".iter().position(|&c| c == byte) {
+            return 47 + position as u8;
+        }
+    }
+    if set == SET_E && (28..=30).contains(&byte) {
+        // FS GS RS
+        return byte + 4;
+    }
+    // SP, CR, FS, GS y RS en los juegos C, D y E
+    if byte == b' ' {
+        59
+    } else {
+        byte
+    }
+}
+
+/// Palabra de shift de un carácter de `from` a `to`, si existe
+fn shift_code(from: u8, to: u8) -> Option<u8> {
+    match (from, to) {
+        (SET_B, SET_A) | (SET_A, SET_B) => Some(59),
+        (_, SET_C) => Some(60),
+        (_, SET_D) => Some(61),
+        (_, SET_E) => Some(62),
+        _ => None,
+    }
+}
+
+/// Palabras de latch de `from` a `to`; C, D y E se fijan con su shift y LOCK
+fn latch_codes(from: u8, to: u8) -> &'static [u8] {
+    match (from, to) {
+        (SET_B, SET_A) => &[63],
+        (_, SET_A) => &[LATCH_A],
+        (_, SET_B) => &[63],
+        (_, SET_E) => &[62, 62],
+        (_, SET_C) => &[60, 60],
+        _ => &[61, 61],
+    }
+}
+
+/// Reed-Solomon sobre GF(64): 10 palabras para el primario y, para el
+/// secundario, dos bloques intercalados (pares e impares) de 20 o 28 palabras
+fn add_error_correction(codewords: &mut [u8; 144], mode: u8) {
+    let field = GaloisField::new(0x43, 64);
+    let primary: Vec<u32> = codewords[..10].iter().map(|&c| c as u32).collect();
+    for (slot, check) in codewords[10..20]
+        .iter_mut()
+        .zip(field.check_words(&primary, 10))
+    {
+        *slot = check as u8;
+    }
+
+    let (data_len, check_len) = if mode == 5 { (68, 28) } else { (84, 20) };
+    for parity in 0..2 {
+        let block: Vec<u32> = (parity..data_len)
+            .step_by(2)
+            .map(|i| codewords[20 + i] as u32)
+            .collect();
+        for (j, check) in field.check_words(&block, check_len).into_iter().enumerate() {
+            codewords[20 + data_len + 2 * j + parity] = check as u8;
+        }
+    }
+}
+
+/// Reparte los bits de las 144 palabras por la rejilla y añade las marcas de orientación
+fn build_matrix(codewords: &[u8; 144]) -> MatrixSymbol {
+    let mut symbol = MatrixSymbol::new(COLUMNS, ROWS);
+    for y in 0..ROWS {
+        for x in 0..COLUMNS {
+            let sequence = MODULE_SEQUENCE[(y * COLUMNS + x) as usize] as usize;
+            if sequence == 0 {
+                continue;
+            }
+            let word = codewords[(sequence - 1) / 6];
+            if (word >> (5 - (sequence - 1) % 6)) & 1 == 1 {
+                symbol.set(x, y);
+            }
+        }
+    }
+
+    // Relleno superior derecho y las seis marcas de orientación alrededor de la diana
+    for (y, x) in [
+        (0, 28),
+        (0, 29),
+        (9, 10),
+        (9, 11),
+        (10, 11),
+        (15, 7),
+        (16, 8),
+        (16, 20),
+        (17, 20),
+        (22, 10),
+        (23, 10),
+        (22, 17),
+        (23, 17),
+    ] {
+        symbol.set(x, y);
+    }
+    symbol
+}
+
+/// Dibuja la rejilla como imagen a tamaño físico nominal: hexágonos con el vértice
+/// arriba, filas impares desplazadas medio módulo y la diana de tres anillos en
+/// el centro. Devuelve una matriz de puntos de impresora.
+pub fn render(symbol: &MatrixSymbol) -> MatrixSymbol {
+    let width = (MODULE_WIDTH_MM * DOTS_PER_MM).round().max(3.0);
+    let long_diameter = width * 2.0 / 3f32.sqrt();
+    let row_pitch = width * 3f32.sqrt() / 2.0;
+    let image_width = (COLUMNS as f32 * width).ceil() as u32;
+    let image_height = ((ROWS - 1) as f32 * row_pitch + long_diameter).ceil() as u32;
+    let mut image = MatrixSymbol::new(image_width, image_height);
+
+    // Semiancho y semialtura de cada hexágono dibujado
+    let half_width = width * HEXAGON_FILL / 2.0;
+    let half_height = long_diameter * HEXAGON_FILL / 2.0;
+    for row in 0..symbol.rows() {
+        let offset = if row % 2 == 1 { width / 2.0 } else { 0.0 };
+        let center_y = row as f32 * row_pitch + long_diameter / 2.0;
+        for column in 0..symbol.columns - row % 2 {
+            if !symbol.get(column, row) {
+                continue;
+            }
+            let center_x = column as f32 * width + width / 2.0 + offset;
+            let (left, right) = (center_x - half_width, center_x + half_width);
+            let (top, bottom) = (center_y - half_height, center_y + half_height);
+            for y in top.floor().max(0.0) as u32..(bottom.ceil() as u32).min(image_height) {
+                for x in left.floor().max(0.0) as u32..(right.ceil() as u32).min(image_width) {
+                    let dx = (x as f32 + 0.5 - center_x).abs();
+                    let dy = (y as f32 + 0.5 - center_y).abs();
+                    // Dentro del hexágono: entre los lados verticales y bajo las aristas inclinadas
+                    if dx <= half_width && dy <= half_height - dx / 3f32.sqrt() {
+                        image.set(x, y);
+                    }
+                }
+            }
+        }
+    }
+
+    // Diana centrada en el módulo (16, 14): círculo central claro y tres anillos
+    // oscuros alternados hasta un diámetro de 9 módulos
+    let center_x = 14.5 * width;
+    let center_y = 16.0 * row_pitch + long_diameter / 2.0;
+    let inner = long_diameter / 2.0;
+    let step = (4.5 * width - inner) / 5.0;
+    let outer = inner + 5.0 * step;
+    for y in (center_y - outer).floor() as u32..(center_y + outer).ceil() as u32 {
+        for x in (center_x - outer).floor() as u32..(center_x + outer).ceil() as u32 {
+            let distance = (x as f32 + 0.5 - center_x).hypot(y as f32 + 0.5 - center_y);
+            if distance > outer {
+                continue;
+            }
+            // Anillo 0 = círculo central; los pares son claros
+            let ring = ((distance - inner) / step).ceil().max(0.0) as u32;
+            let index = (y * image_width + x) as usize;
+            image.modules[index] = ring % 2 == 1;
+        }
+    }
+
+    image
+}
+
+/// Orden de los módulos en la rejilla de 33 filas × 30 columnas (ISO/IEC 16023,
+/// figura 5): el módulo n es el bit `5 - (n - 1) % 6` de la palabra `(n - 1) / 6`;
+/// 0 = sin datos (diana, orientación y huecos de las filas impares)
+#[rustfmt::skip]
+const MODULE_SEQUENCE: [u16; 990] = [
+    122, 121, 128, 127, 134, 133, 140, 139, 146, 145, 152, 151, 158, 157, 164, 163, 170, 169, 176, 175, 182, 181, 188, 187, 194, 193, 200, 199,   0,   0,
+    124, 123, 130, 129, 136, 135, 142, 141, 148, 147, 154, 153, 160, 159, 166, 165, 172, 171, 178, 177, 184, 183, 190, 189, 196, 195, 202, 201, 817,   0,
+    126, 125, 132, 131, 138, 137, 144, 143, 150, 149, 156, 155, 162, 161, 168, 167, 174, 173, 180, 179, 186, 185, 192, 191, 198, 197, 204, 203, 819, 818,
+    284, 283, 278, 277, 272, 271, 266, 265, 260, 259, 254, 253, 248, 247, 242, 241, 236, 235, 230, 229, 224, 223, 218, 217, 212, 211, 206, 205, 820,   0,
+    286, 285, 280, 279, 274, 273, 268, 267, 262, 261, 256, 255, 250, 249, 244, 243, 238, 237, 232, 231, 226, 225, 220, 219, 214, 213, 208, 207, 822, 821,
+    288, 287, 282, 281, 276, 275, 270, 269, 264, 263, 258, 257, 252, 251, 246, 245, 240, 239, 234, 233, 228, 227, 222, 221, 216, 215, 210, 209, 823,   0,
+    290, 289, 296, 295, 302, 301, 308, 307, 314, 313, 320, 319, 326, 325, 332, 331, 338, 337, 344, 343, 350, 349, 356, 355, 362, 361, 368, 367, 825, 824,
+    292, 291, 298, 297, 304, 303, 310, 309, 316, 315, 322, 321, 328, 327, 334, 333, 340, 339, 346, 345, 352, 351, 358, 357, 364, 363, 370, 369, 826,   0,
+    294, 293, 300, 299, 306, 305, 312, 311, 318, 317, 324, 323, 330, 329, 336, 335, 342, 341, 348, 347, 354, 353, 360, 359, 366, 365, 372, 371, 828, 827,
+    410, 409, 404, 403, 398, 397, 392, 391,  80,  79,   0,   0,  14,  13,  38,  37,   3,   0,  45,  44, 110, 109, 386, 385, 380, 379, 374, 373, 829,   0,
+    412, 411, 406, 405, 400, 399, 394, 393,  82,  81,  41,   0,  16,  15,  40,  39,   4,   0,   0,  46, 112, 111, 388, 387, 382, 381, 376, 375, 831, 830,
+    414, 413, 408, 407, 402, 401, 396, 395,  84,  83,  42,   0,   0,   0,   0,   0,   6,   5,  48,  47, 114, 113, 390, 389, 384, 383, 378, 377, 832,   0,
+    416, 415, 422, 421, 428, 427, 104, 103,  56,  55,  17,   0,   0,   0,   0,   0,   0,   0,  21,  20,  86,  85, 434, 433, 440, 439, 446, 445, 834, 833,
+    418, 417, 424, 423, 430, 429, 106, 105,  58,  57,   0,   0,   0,   0,   0,   0,   0,   0,  23,  22,  88,  87, 436, 435, 442, 441, 448, 447, 835,   0,
+    420, 419, 426, 425, 432, 431, 108, 107,  60,  59,   0,   0,   0,   0,   0,   0,   0,   0,   0,  24,  90,  89, 438, 437, 444, 443, 450, 449, 837, 836,
+    482, 481, 476, 475, 470, 469,  49,   0,  31,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   1,  54,  53, 464, 463, 458, 457, 452, 451, 838,   0,
+    484, 483, 478, 477, 472, 471,  50,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0, 466, 465, 460, 459, 454, 453, 840, 839,
+    486, 485, 480, 479, 474, 473,  52,  51,  32,   0,   0,   0,   0,   0,   0,   0,   0,   0,   0,   2,   0,  43, 468, 467, 462, 461, 456, 455, 841,   0,
+    488, 487, 494, 493, 500, 499,  98,  97,  62,  61,   0,   0,   0,   0,   0,   0,   0,   0,   0,  27,  92,  91, 506, 505, 512, 511, 518, 517, 843, 842,
+    490, 489, 496, 495, 502, 501, 100,  99,  64,  63,   0,   0,   0,   0,   0,   0,   0,   0,  29,  28,  94,  93, 508, 507, 514, 513, 520, 519, 844,   0,
+    492, 491, 498, 497, 504, 503, 102, 101,  66,  65,  18,   0,   0,   0,   0,   0,   0,   0,  19,  30,  96,  95, 510, 509, 516, 515, 522, 521, 846, 845,
+    560, 559, 554, 553, 548, 547, 542, 541,  74,  73,  33,   0,   0,   0,   0,   0,   0,  11,  68,  67, 116, 115, 536, 535, 530, 529, 524, 523, 847,   0,
+    562, 561, 556, 555, 550, 549, 544, 543,  76,  75,   0,   0,   8,   7,  36,  35,  12,   0,  70,  69, 118, 117, 538, 537, 532, 531, 526, 525, 849, 848,
+    564, 563, 558, 557, 552, 551, 546, 545,  78,  77,   0,  34,  10,   9,  26,  25,   0,   0,  72,  71, 120, 119, 540, 539, 534, 533, 528, 527, 850,   0,
+    566, 565, 572, 571, 578, 577, 584, 583, 590, 589, 596, 595, 602, 601, 608, 607, 614, 613, 620, 619, 626, 625, 632, 631, 638, 637, 644, 643, 852, 851,
+    568, 567, 574, 573, 580, 579, 586, 585, 592, 591, 598, 597, 604, 603, 610, 609, 616, 615, 622, 621, 628, 627, 634, 633, 640, 639, 646, 645, 853,   0,
+    570, 569, 576, 575, 582, 581, 588, 587, 594, 593, 600, 599, 606, 605, 612, 611, 618, 617, 624, 623, 630, 629, 636, 635, 642, 641, 648, 647, 855, 854,
+    728, 727, 722, 721, 716, 715, 710, 709, 704, 703, 698, 697, 692, 691, 686, 685, 680, 679, 674, 673, 668, 667, 662, 661, 656, 655, 650, 649, 856,   0,
+    730, 729, 724, 723, 718, 717, 712, 711, 706, 705, 700, 699, 694, 693, 688, 687, 682, 681, 676, 675, 670, 669, 664, 663, 658, 657, 652, 651, 858, 857,
+    732, 731, 726, 725, 720, 719, 714, 713, 708, 707, 702, 701, 696, 695, 690, 689, 684, 683, 678, 677, 672, 671, 666, 665, 660, 659, 654, 653, 859,   0,
+    734, 733, 740, 739, 746, 745, 752, 751, 758, 757, 764, 763, 770, 769, 776, 775, 782, 781, 788, 787, 794, 793, 800, 799, 806, 805, 812, 811, 861, 860,
+    736, 735, 742, 741, 748, 747, 754, 753, 760, 759, 766, 765, 772, 771, 778, 777, 784, 783, 790, 789, 796, 795, 802, 801, 808, 807, 814, 813, 862,   0,
+    738, 737, 744, 743, 750, 749, 756, 755, 762, 761, 768, 767, 774, 773, 780, 779, 786, 785, 792, 791, 798, 797, 804, 803, 810, 809, 816, 815, 864, 863,
+];
+
+/// Juegos de caracteres que contienen cada byte (ISO/IEC 16023, anexo A)
+#[rustfmt::skip]
+const CODE_SETS: [u8; 256] = [
+    0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x05, 0x04, 0x04,
+    0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x1F, 0x1F, 0x1F, 0x04,
+    0x1F, 0x02, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x03, 0x01, 0x03, 0x03,
+    0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x03, 0x02, 0x02, 0x02, 0x02, 0x02,
+    0x02, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+    0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x02, 0x02, 0x02, 0x02, 0x02,
+    0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+    0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+    0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+    0x10, 0x10, 0x10, 0x10, 0x10, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04,
+    0x04, 0x10, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x10, 0x04, 0x08, 0x10, 0x08, 0x04, 0x04, 0x10,
+    0x10, 0x08, 0x08, 0x08, 0x10, 0x08, 0x04, 0x10, 0x10, 0x08, 0x08, 0x10, 0x08, 0x08, 0x08, 0x10,
+    0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08,
+    0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08,
+    0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+    0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+];
+
+/// Valor de cada byte en su juego (en el juego A si está en varios)
+#[rustfmt::skip]
+const SYMBOL_VALUES: [u8; 256] = [
+     0,  1,  2,  3,  4,  5,  6,  7,  8,  9, 10, 11, 12,  0, 14, 15,
+    16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 30, 28, 29, 30, 35,
+    32, 53, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47,
+    48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 37, 38, 39, 40, 41,
+    52,  1,  2,  3,  4,  5,  6,  7,  8,  9, 10, 11, 12, 13, 14, 15,
+    16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 42, 43, 44, 45, 46,
+     0,  1,  2,  3,  4,  5,  6,  7,  8,  9, 10, 11, 12, 13, 14, 15,
+    16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 32, 54, 34, 35, 36,
+    48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 47, 48, 49, 50, 51, 52,
+    53, 54, 55, 56, 57, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 36,
+    37, 37, 38, 39, 40, 41, 42, 43, 38, 44, 37, 39, 38, 45, 46, 40,
+    41, 39, 40, 41, 42, 42, 47, 43, 44, 43, 44, 45, 45, 46, 47, 46,
+     0,  1,  2,  3,  4,  5,  6,  7,  8,  9, 10, 11, 12, 13, 14, 15,
+    16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 32, 33, 34, 35, 36,
+     0,  1,  2,  3,  4,  5,  6,  7,  8,  9, 10, 11, 12, 13, 14, 15,
+    16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 32, 33, 34, 35, 36,
+];
@@ -1,3 +1,4 @@
 pub mod maxicode;
+pub mod maxicode_encoder;
 
 pub use maxicode::MaxiCode;
@@ -3,6 +3,9 @@ pub mod barcode;
 pub mod composite;
 pub mod data_matrix;
 pub mod gs1_databar_2d;
+pub mod matrix_symbol;
 pub mod maxicode;
 pub mod pdf417;
 pub mod qr;
+pub mod reed_solomon;
+pub mod render_mode;
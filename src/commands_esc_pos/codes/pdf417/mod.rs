@@ -1,2 +1,3 @@
 pub mod pdf417;
+pub mod pdf417_encoder;
 pub mod pdf417_error_correction;
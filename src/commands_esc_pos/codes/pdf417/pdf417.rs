@@ -1,5 +1,9 @@
+use super::pdf417_encoder::{self, Pdf417Options};
 use super::pdf417_error_correction::PDF417ErrorCorrection;
+use crate::commands_esc_pos::codes::render_mode::RenderMode;
+use crate::error::SectionError;
 use crate::models::print_sections::Pdf417 as Pdf417Section;
+use crate::models::printer_profile::PrinterProfile;
 
 /// Constructor de comandos para códigos PDF417
 ///
@@ -84,6 +88,22 @@ impl PDF417 {
         self
     }
 
+    /// Genera el PDF417 como imagen raster (`GS v 0`) codificado en Rust, con las
+    /// mismas columnas, filas y corrección que el comando nativo: módulos de `width`
+    /// puntos de ancho y filas de `height` × `width` puntos de alto.
+    pub fn get_raster_command(&self) -> Result<Vec<u8>, SectionError> {
+        let symbol = pdf417_encoder::encode(
+            self.data.as_bytes(),
+            Pdf417Options {
+                columns: self.columns as u32,
+                rows: self.rows as u32,
+                error_correction: (self.error_correction.value() - 48) as u32,
+                row_height: self.height as u32,
+            },
+        )?;
+        Ok(symbol.raster_command_scaled(self.width as u32, self.height as u32 * self.width as u32))
+    }
+
     /// Genera el comando ESC/POS para PDF417
     ///
     /// NOTA: PDF417 no es soportado por todas las impresoras térmicas.
@@ -186,8 +206,12 @@ impl PDF417 {
     }
 }
 
-/// Procesa sección Pdf417 del modelo de impresión
-pub fn process_section(pdf417: &Pdf417Section) -> Result<Vec<u8>, String> {
+/// Valida una sección Pdf417 y construye el comando equivalente
+pub fn from_section(pdf417: &Pdf417Section) -> Result<PDF417, SectionError> {
+    if pdf417.data.is_empty() {
+        return Err("PDF417 data cannot be empty".into());
+    }
+
     let error_correction = match pdf417.error_correction {
         0 => PDF417ErrorCorrection::Level0,
         1 => PDF417ErrorCorrection::Level1,
@@ -201,14 +225,27 @@ pub fn process_section(pdf417: &Pdf417Section) -> Result<Vec<u8>, String> {
         _ => PDF417ErrorCorrection::Level1,
     };
 
-    let esc_pos_pdf417 = PDF417::new(pdf417.data.clone())
+    Ok(PDF417::new(pdf417.data.clone())
         .set_columns(pdf417.columns)
         .set_rows(pdf417.rows)
         .set_height(pdf417.height)
         .set_width(pdf417.width)
-        .set_error_correction(error_correction);
+        .set_error_correction(error_correction))
+}
 
-    let mut data = esc_pos_pdf417.get_command();
+/// Procesa sección Pdf417 del modelo de impresión (nativo o raster según
+/// `render_mode` y el perfil)
+pub fn process_section(
+    pdf417: &Pdf417Section,
+    profile: &PrinterProfile,
+) -> Result<Vec<u8>, SectionError> {
+    let esc_pos_pdf417 = from_section(pdf417)?;
+    let mut data =
+        if RenderMode::from_name(pdf417.render_mode.as_deref())?.is_raster(profile.native_pdf417) {
+            esc_pos_pdf417.get_raster_command()?
+        } else {
+            esc_pos_pdf417.get_command()
+        };
     data.extend_from_slice(b"\n");
     Ok(data)
 }
@@ -0,0 +1,181 @@
+//! Payload EMVCo "merchant-presented mode" (EMV QRCPS): objetos TLV con ID y
+//! longitud de dos dígitos, terminados en el CRC16 del propio payload (ID 63).
+
+use super::qr;
+use crate::error::SectionError;
+use crate::models::print_sections::{EmvField, EmvQr, GlobalStyles, Qr};
+use crate::models::printer_profile::PrinterProfile;
+
+/// Longitud máxima del payload según EMV QRCPS
+const MAX_PAYLOAD: usize = 512;
+
+/// CRC-16/CCITT-FALSE (polinomio 0x1021, valor inicial 0xFFFF)
+pub fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0xFFFF, |crc, &byte| {
+        (0..8).fold(crc ^ (byte as u16) << 8, |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// Objeto TLV `IDLLvalor`
+fn tlv(id: u8, value: &str, name: &str) -> Result<String, SectionError> {
+    if value.is_empty() {
+        return Err(format!("EMV field {} cannot be empty", name).into());
+    }
+    if !value.bytes().all(|b| (0x20..0x7F).contains(&b)) {
+        return Err(format!("EMV field {} only accepts printable ASCII", name).into());
+    }
+    if value.len() > 99 {
+        return Err(format!(
+            "EMV field {} is {} characters long (max 99)",
+            name,
+            value.len()
+        )
+        .into());
+    }
+    Ok(format!("{:02}{:02}{}", id, value.len(), value))
+}
+
+/// Campo con longitud máxima propia del estándar
+fn limited(id: u8, value: &str, name: &str, max: usize) -> Result<String, SectionError> {
+    if value.chars().count() > max {
+        return Err(format!("EMV field {} is longer than {} characters", name, max).into());
+    }
+    tlv(id, value, name)
+}
+
+/// Plantilla con subcampos TLV anidados
+fn template(id: u8, fields: &[EmvField], name: &str) -> Result<String, SectionError> {
+    let mut value = String::new();
+    for field in fields {
+        if field.id > 99 {
+            return Err(format!("EMV {} sub-field ID {} is out of range", name, field.id).into());
+        }
+        value.push_str(&tlv(
+            field.id,
+            &field.value,
+            &format!("{} {:02}", name, field.id),
+        )?);
+    }
+    tlv(id, &value, name)
+}
+
+fn digits(value: &str, length: usize, name: &str) -> Result<(), SectionError> {
+    if value.len() != length || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("EMV {} must be {} digits, got '{}'", name, length, value).into());
+    }
+    Ok(())
+}
+
+/// Importe: dígitos con punto decimal opcional, como mucho 13 caracteres
+fn validate_amount(amount: &str) -> Result<(), SectionError> {
+    let (integer, decimals) = amount.split_once('.').unwrap_or((amount, "0"));
+    let valid = amount.len() <= 13
+        && !integer.is_empty()
+        && !decimals.is_empty()
+        && integer
+            .bytes()
+            .chain(decimals.bytes())
+            .all(|b| b.is_ascii_digit());
+    if !valid {
+        return Err(format!("EMV amount must look like 10.50, got '{}'", amount).into());
+    }
+    Ok(())
+}
+
+/// Construye el payload completo, con el CRC16 final
+pub fn payload(emv: &EmvQr) -> Result<String, SectionError> {
+    let mut data = tlv(0, "01", "payload format indicator")?;
+    if let Some(dynamic) = emv.dynamic {
+        let initiation = if dynamic { "12" } else { "11" };
+        data.push_str(&tlv(1, initiation, "point of initiation")?);
+    }
+
+    if emv.merchant_accounts.is_empty() {
+        return Err("EMV QR needs at least one merchant account".into());
+    }
+    for account in &emv.merchant_accounts {
+        if !(2..=51).contains(&account.id) {
+            return Err(
+                format!("EMV merchant account ID must be 02-51, got {}", account.id).into(),
+            );
+        }
+        let name = format!("merchant account {:02}", account.id);
+        data.push_str(&match (&account.value, account.fields.is_empty()) {
+            (Some(value), true) => tlv(account.id, value, &name)?,
+            (None, false) => template(account.id, &account.fields, &name)?,
+            _ => return Err(format!("EMV {} needs either a value or fields", name).into()),
+        });
+    }
+
+    let category = emv.merchant_category_code.as_deref().unwrap_or("0000");
+    digits(category, 4, "merchant category code")?;
+    data.push_str(&tlv(52, category, "merchant category code")?);
+    digits(&emv.currency, 3, "currency")?;
+    data.push_str(&tlv(53, &emv.currency, "currency")?);
+    if let Some(amount) = &emv.amount {
+        validate_amount(amount)?;
+        data.push_str(&tlv(54, amount, "amount")?);
+    }
+    if emv.country_code.len() != 2 || !emv.country_code.bytes().all(|b| b.is_ascii_uppercase()) {
+        return Err(format!(
+            "EMV country code must be 2 uppercase letters, got '{}'",
+            emv.country_code
+        )
+        .into());
+    }
+    data.push_str(&tlv(58, &emv.country_code, "country code")?);
+    data.push_str(&limited(59, &emv.merchant_name, "merchant name", 25)?);
+    data.push_str(&limited(60, &emv.merchant_city, "merchant city", 15)?);
+    if let Some(postal_code) = &emv.postal_code {
+        data.push_str(&limited(61, postal_code, "postal code", 10)?);
+    }
+    if !emv.additional_data.is_empty() {
+        data.push_str(&template(62, &emv.additional_data, "additional data")?);
+    }
+
+    // El CRC cubre todo el payload, incluidos el ID y la longitud del propio CRC
+    data.push_str("6304");
+    data.push_str(&format!("{:04X}", crc16(data.as_bytes())));
+    if data.len() > MAX_PAYLOAD {
+        return Err(format!(
+            "EMV payload is {} characters long (max {})",
+            data.len(),
+            MAX_PAYLOAD
+        )
+        .into());
+    }
+    Ok(data)
+}
+
+/// Sección Qr equivalente con el payload generado
+pub fn to_qr(emv: &EmvQr) -> Result<Qr, SectionError> {
+    Ok(Qr {
+        data: payload(emv)?,
+        size: emv.size.unwrap_or(6),
+        error_correction: emv
+            .error_correction
+            .clone()
+            .unwrap_or_else(|| "M".to_string()),
+        model: 2,
+        align: emv.align.clone(),
+        render_mode: emv.render_mode.clone(),
+        target_width: emv.target_width,
+        target_width_mm: emv.target_width_mm,
+    })
+}
+
+/// Procesa sección EmvQr del modelo de impresión
+pub fn process_section(
+    emv: &EmvQr,
+    current_styles: &GlobalStyles,
+    profile: &PrinterProfile,
+    paper_width_pixels: i32,
+) -> Result<Vec<u8>, SectionError> {
+    qr::process_section(&to_qr(emv)?, current_styles, profile, paper_width_pixels)
+}
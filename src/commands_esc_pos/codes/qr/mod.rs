@@ -1,7 +1,9 @@
+pub mod emv;
 pub mod qr;
 pub mod qr_error_correction;
 pub mod qr_model;
 pub mod qr_size;
+pub mod sepa;
 
 pub use qr::QR;
 pub use qr_error_correction::QRErrorCorrection;
@@ -1,8 +1,16 @@
 use super::qr_error_correction::QRErrorCorrection;
 use super::qr_model::QRModel;
 use super::qr_size::QRSize;
+use crate::commands_esc_pos::codes::render_mode::RenderMode;
+use crate::commands_esc_pos::image_escpos::image_code::raster_command;
+use crate::commands_esc_pos::image_escpos::image_processor::ImageProcessor;
+use crate::commands_esc_pos::image_escpos::ImageMode;
 use crate::commands_esc_pos::text::text_type::get_styles_diff;
+use crate::error::SectionError;
+use crate::models::paper_size::DOTS_PER_MM;
 use crate::models::print_sections::{GlobalStyles, Qr};
+use crate::models::printer_profile::PrinterProfile;
+use qrcode::{Color, EcLevel, QrCode};
 
 /// Constructor de comandos para códigos QR
 #[derive(Debug, Clone)]
@@ -54,6 +62,42 @@ impl QR {
         self
     }
 
+    /// Lado del símbolo impreso en puntos (módulos × tamaño de módulo), sin zona
+    /// de silencio. En modo nativo es una estimación: la versión la elige la
+    /// impresora y puede diferir si segmenta los datos de otra forma. `None` si
+    /// los datos no caben.
+    pub fn printed_size(&self) -> Option<u32> {
+        let code = self.encode().ok()?;
+        Some(code.width() as u32 * self.size.value() as u32)
+    }
+
+    /// Codifica el símbolo en Rust (siempre Model 2)
+    fn encode(&self) -> Result<QrCode, SectionError> {
+        let level = match self.error_correction {
+            QRErrorCorrection::L => EcLevel::L,
+            QRErrorCorrection::M => EcLevel::M,
+            QRErrorCorrection::Q => EcLevel::Q,
+            QRErrorCorrection::H => EcLevel::H,
+        };
+        QrCode::with_error_correction_level(self.data.as_bytes(), level)
+            .map_err(|e| format!("QR data cannot be encoded: {}", e).into())
+    }
+
+    /// Genera el QR como imagen raster (`GS v 0`), con el mismo tamaño de módulo
+    /// que el comando nativo. Para impresoras cuyo firmware no implementa `GS ( k`.
+    pub fn get_raster_command(&self) -> Result<Vec<u8>, SectionError> {
+        let code = self.encode()?;
+        let modules: Vec<bool> = code
+            .to_colors()
+            .into_iter()
+            .map(|color| color == Color::Dark)
+            .collect();
+        let module = self.size.value() as u32;
+        let binary =
+            ImageProcessor::modules_to_image(&modules, code.width() as u32, module, module);
+        Ok(raster_command(&binary, ImageMode::Normal))
+    }
+
     /// Genera el comando ESC/POS para imprimir el código QR
     pub fn get_command(&self) -> Vec<u8> {
         let mut output = Vec::new();
@@ -128,19 +172,45 @@ impl QR {
     }
 }
 
-/// Procesa sección Qr del modelo de impresión
-pub fn process_section(qr: &Qr, current_styles: &GlobalStyles) -> Result<Vec<u8>, String> {
+/// Tamaño de módulo: `size`, o con `target_width` / `target_width_mm` el mayor
+/// con el que el símbolo (sin zona de silencio) cabe en ese ancho, limitado a
+/// `max_width`. La versión se calcula a partir de los datos y el nivel.
+fn module_size(
+    qr: &Qr,
+    error_correction: QRErrorCorrection,
+    max_width: u32,
+) -> Result<u8, SectionError> {
+    let target = match (qr.target_width, qr.target_width_mm) {
+        (Some(dots), _) => dots,
+        (None, Some(mm)) => (mm * DOTS_PER_MM).round().max(0.0) as u32,
+        (None, None) => return Ok(qr.size),
+    };
+    let target = target.min(max_width);
+
+    let version = error_correction
+        .min_version(qr.data.as_bytes())
+        .ok_or("QR data does not fit in any QR version")?;
+    let modules = 17 + 4 * version as u32;
+    if target < modules {
+        return Err(format!(
+            "QR version {} needs at least {} dots but the target width is {}",
+            version, modules, target
+        )
+        .into());
+    }
+    Ok((target / modules).min(16) as u8)
+}
+
+/// Valida una sección Qr y construye el comando equivalente
+///
+/// # Arguments
+/// * `max_width` - Ancho disponible en puntos para `target_width`
+pub fn from_section(qr: &Qr, max_width: u32) -> Result<QR, SectionError> {
     if qr.data.is_empty() {
-        return Err("QR data cannot be empty".to_string());
+        return Err("QR data cannot be empty".into());
     }
 
-    let error_correction = match qr.error_correction.as_str() {
-        "L" => QRErrorCorrection::L,
-        "M" => QRErrorCorrection::M,
-        "Q" => QRErrorCorrection::Q,
-        "H" => QRErrorCorrection::H,
-        _ => QRErrorCorrection::M,
-    };
+    let error_correction = QRErrorCorrection::from_name(&qr.error_correction);
 
     if qr.data.len() > error_correction.max_data_len() {
         return Err(format!(
@@ -148,7 +218,8 @@ pub fn process_section(qr: &Qr, current_styles: &GlobalStyles) -> Result<Vec<u8>
             qr.data.len(),
             error_correction.max_data_len(),
             qr.error_correction
-        ));
+        )
+        .into());
     }
 
     let model = if qr.model == 1 {
@@ -157,7 +228,7 @@ pub fn process_section(qr: &Qr, current_styles: &GlobalStyles) -> Result<Vec<u8>
         QRModel::Model2
     };
 
-    let size = match qr.size {
+    let size = match module_size(qr, error_correction, max_width)? {
         1 => QRSize::Size1,
         2 => QRSize::Size2,
         3 => QRSize::Size3,
@@ -177,21 +248,41 @@ pub fn process_section(qr: &Qr, current_styles: &GlobalStyles) -> Result<Vec<u8>
         _ => QRSize::Size6,
     };
 
-    let esc_pos_qr = QR::new(qr.data.clone())
+    Ok(QR::new(qr.data.clone())
         .set_model(model)
         .set_size(size)
-        .set_error_correction(error_correction);
+        .set_error_correction(error_correction))
+}
+
+/// Comando del QR (nativo o raster según `render_mode` y el perfil), sin alineación
+pub fn command(qr: &Qr, profile: &PrinterProfile, max_width: u32) -> Result<Vec<u8>, SectionError> {
+    let esc_pos_qr = from_section(qr, max_width)?;
+    if RenderMode::from_name(qr.render_mode.as_deref())?.is_raster(profile.native_qr) {
+        esc_pos_qr.get_raster_command()
+    } else {
+        Ok(esc_pos_qr.get_command())
+    }
+}
+
+/// Procesa sección Qr del modelo de impresión
+pub fn process_section(
+    qr: &Qr,
+    current_styles: &GlobalStyles,
+    profile: &PrinterProfile,
+    paper_width_pixels: i32,
+) -> Result<Vec<u8>, SectionError> {
+    let qr_command = command(qr, profile, paper_width_pixels.max(0) as u32)?;
 
     let mut data = Vec::new();
     if let Some(ref align) = qr.align {
         let mut temp_styles = current_styles.clone();
         temp_styles.align = Some(align.clone());
         data.extend_from_slice(&get_styles_diff(current_styles, &temp_styles));
-        data.extend_from_slice(&esc_pos_qr.get_command());
+        data.extend_from_slice(&qr_command);
         data.extend_from_slice(b"\n");
         data.extend_from_slice(&get_styles_diff(&temp_styles, current_styles));
     } else {
-        data.extend_from_slice(&esc_pos_qr.get_command());
+        data.extend_from_slice(&qr_command);
         data.extend_from_slice(b"\n");
     }
 
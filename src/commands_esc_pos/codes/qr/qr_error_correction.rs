@@ -13,6 +13,17 @@ impl QRErrorCorrection {
         *self as u8
     }
 
+    /// Convierte el nivel usado en el modelo (`"L"`, `"M"`, `"Q"`, `"H"`).
+    /// Los valores desconocidos se tratan como `M`.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "L" => QRErrorCorrection::L,
+            "Q" => QRErrorCorrection::Q,
+            "H" => QRErrorCorrection::H,
+            _ => QRErrorCorrection::M,
+        }
+    }
+
     /// Maximum numeric character capacity for this error correction level (QR spec)
     pub fn max_data_len(&self) -> usize {
         match self {
@@ -22,4 +33,93 @@ impl QRErrorCorrection {
             QRErrorCorrection::H => 1817,
         }
     }
+
+    /// Palabras de datos (sin corrección) de la versión 1-40 con este nivel
+    pub fn data_codewords(&self, version: u8) -> usize {
+        let column = match self {
+            QRErrorCorrection::L => 0,
+            QRErrorCorrection::M => 1,
+            QRErrorCorrection::Q => 2,
+            QRErrorCorrection::H => 3,
+        };
+        DATA_CODEWORDS[version as usize - 1][column] as usize
+    }
+
+    /// Caracteres que caben en la versión `version` codificando `data` en un
+    /// único modo (numérico, alfanumérico o byte, el más compacto posible)
+    pub fn capacity(&self, version: u8, data: &[u8]) -> usize {
+        let mode = QRDataMode::detect(data);
+        let bits = self.data_codewords(version) * 8 - 4 - mode.count_bits(version);
+        match mode {
+            QRDataMode::Numeric => {
+                // 3 dígitos en 10 bits; el resto, 2 dígitos en 7 o 1 en 4
+                let extra = match bits % 10 {
+                    7..=9 => 2,
+                    4..=6 => 1,
+                    _ => 0,
+                };
+                bits / 10 * 3 + extra
+            }
+            QRDataMode::Alphanumeric => bits / 11 * 2 + usize::from(bits % 11 >= 6),
+            QRDataMode::Byte => bits / 8,
+        }
+    }
+
+    /// Versión más pequeña (1-40) en la que cabe `data`, o `None` si no cabe
+    pub fn min_version(&self, data: &[u8]) -> Option<u8> {
+        (1..=40).find(|&version| self.capacity(version, data) >= data.len())
+    }
+}
+
+/// Modo de codificación de los datos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QRDataMode {
+    Numeric,
+    Alphanumeric,
+    Byte,
+}
+
+impl QRDataMode {
+    fn detect(data: &[u8]) -> Self {
+        if data.iter().all(u8::is_ascii_digit) {
+            QRDataMode::Numeric
+        } else if data
+            .iter()
+            .all(|&b| b.is_ascii_digit() || b.is_ascii_uppercase() || b" $%*+-./:".contains(&b))
+        {
+            QRDataMode::Alphanumeric
+        } else {
+            QRDataMode::Byte
+        }
+    }
+
+    /// Bits del indicador de longitud según la versión
+    fn count_bits(self, version: u8) -> usize {
+        let group = match version {
+            1..=9 => 0,
+            10..=26 => 1,
+            _ => 2,
+        };
+        match self {
+            QRDataMode::Numeric => [10, 12, 14][group],
+            QRDataMode::Alphanumeric => [9, 11, 13][group],
+            QRDataMode::Byte => [8, 16, 16][group],
+        }
+    }
 }
+
+/// Palabras de datos por versión (filas 1-40) y nivel (columnas L, M, Q, H),
+/// ISO/IEC 18004 tabla 7
+#[rustfmt::skip]
+const DATA_CODEWORDS: [[u16; 4]; 40] = [
+    [19, 16, 13, 9], [34, 28, 22, 16], [55, 44, 34, 26], [80, 64, 48, 36],
+    [108, 86, 62, 46], [136, 108, 76, 60], [156, 124, 88, 66], [194, 154, 110, 86],
+    [232, 182, 132, 100], [274, 216, 154, 122], [324, 254, 180, 140], [370, 290, 206, 158],
+    [428, 334, 244, 180], [461, 365, 261, 197], [523, 415, 295, 223], [589, 453, 325, 253],
+    [647, 507, 367, 283], [721, 563, 397, 313], [795, 627, 445, 341], [861, 669, 485, 385],
+    [932, 714, 512, 406], [1006, 782, 568, 442], [1094, 860, 614, 464], [1174, 914, 664, 514],
+    [1276, 1000, 718, 538], [1370, 1062, 754, 596], [1468, 1128, 808, 628], [1531, 1193, 871, 661],
+    [1631, 1267, 911, 701], [1735, 1373, 985, 745], [1843, 1455, 1033, 793], [1955, 1541, 1115, 845],
+    [2071, 1631, 1171, 901], [2191, 1725, 1231, 961], [2306, 1812, 1286, 986], [2434, 1914, 1354, 1054],
+    [2566, 1992, 1426, 1096], [2702, 2102, 1502, 1142], [2812, 2216, 1582, 1222], [2956, 2334, 1666, 1276],
+];
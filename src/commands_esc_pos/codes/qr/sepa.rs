@@ -0,0 +1,156 @@
+//! Payload EPC069-12 (SEPA Credit Transfer, "GiroCode"): líneas separadas por
+//! `\n` con versión 002, UTF-8 e identificación SCT.
+
+use super::qr;
+use crate::error::SectionError;
+use crate::models::print_sections::{GlobalStyles, Qr, SepaQr};
+use crate::models::printer_profile::PrinterProfile;
+
+/// Longitud máxima del payload según EPC069-12
+const MAX_PAYLOAD: usize = 331;
+
+fn max_length(value: &str, name: &str, max: usize) -> Result<(), SectionError> {
+    if value.chars().count() > max {
+        return Err(format!("SEPA {} is longer than {} characters", name, max).into());
+    }
+    Ok(())
+}
+
+/// IBAN sin espacios y en mayúsculas, con los dígitos de control (módulo 97) verificados
+pub fn normalize_iban(iban: &str) -> Result<String, SectionError> {
+    let iban: String = iban
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_uppercase();
+    let bytes = iban.as_bytes();
+    let well_formed = (15..=34).contains(&bytes.len())
+        && bytes.iter().all(u8::is_ascii_alphanumeric)
+        && bytes[..2].iter().all(u8::is_ascii_alphabetic)
+        && bytes[2..4].iter().all(u8::is_ascii_digit);
+    if !well_formed {
+        return Err(format!("'{}' is not a valid IBAN", iban).into());
+    }
+
+    // País y dígitos de control al final; cada letra vale 10..35
+    let remainder = bytes[4..].iter().chain(&bytes[..4]).fold(0u32, |acc, &b| {
+        if b.is_ascii_digit() {
+            (acc * 10 + (b - b'0') as u32) % 97
+        } else {
+            (acc * 100 + (b - b'A' + 10) as u32) % 97
+        }
+    });
+    if remainder != 1 {
+        return Err(format!("IBAN '{}' has invalid check digits", iban).into());
+    }
+    Ok(iban)
+}
+
+/// Importe en euros con dos decimales (`EUR12.50`)
+fn amount(value: &str) -> Result<String, SectionError> {
+    let invalid = || format!("SEPA amount must look like 12.50, got '{}'", value);
+    let (euros, cents) = value.split_once('.').unwrap_or((value, ""));
+    if euros.is_empty()
+        || euros.len() > 9
+        || cents.len() > 2
+        || !euros
+            .bytes()
+            .chain(cents.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid().into());
+    }
+    let euros: u64 = euros.parse().map_err(|_| invalid())?;
+    let cents: u64 = format!("{:0<2}", cents).parse().map_err(|_| invalid())?;
+    if euros == 0 && cents == 0 {
+        return Err("SEPA amount must be at least 0.01".into());
+    }
+    Ok(format!("EUR{}.{:02}", euros, cents))
+}
+
+/// Construye el payload; las líneas vacías del final se omiten
+pub fn payload(sepa: &SepaQr) -> Result<String, SectionError> {
+    if sepa.name.trim().is_empty() {
+        return Err("SEPA beneficiary name cannot be empty".into());
+    }
+    max_length(&sepa.name, "beneficiary name", 70)?;
+    let iban = normalize_iban(&sepa.iban)?;
+
+    let bic = sepa.bic.as_deref().unwrap_or("");
+    if !bic.is_empty()
+        && (!matches!(bic.len(), 8 | 11) || !bic.bytes().all(|b| b.is_ascii_alphanumeric()))
+    {
+        return Err(format!("'{}' is not a valid BIC", bic).into());
+    }
+    let amount = sepa.amount.as_deref().map(amount).transpose()?;
+
+    let purpose = sepa.purpose.as_deref().unwrap_or("");
+    if !purpose.is_empty()
+        && (purpose.len() != 4 || !purpose.bytes().all(|b| b.is_ascii_alphanumeric()))
+    {
+        return Err(format!("SEPA purpose must be a 4-character code, got '{}'", purpose).into());
+    }
+
+    let reference = sepa.reference.as_deref().unwrap_or("");
+    let text = sepa.text.as_deref().unwrap_or("");
+    if !reference.is_empty() && !text.is_empty() {
+        return Err("SEPA QR accepts either a reference or a text, not both".into());
+    }
+    max_length(reference, "reference", 35)?;
+    max_length(text, "text", 140)?;
+    let information = sepa.information.as_deref().unwrap_or("");
+    max_length(information, "information", 70)?;
+
+    let lines = [
+        "BCD",
+        "002",
+        "1",
+        "SCT",
+        bic,
+        &sepa.name,
+        &iban,
+        amount.as_deref().unwrap_or(""),
+        purpose,
+        reference,
+        text,
+        information,
+    ];
+    let used = lines.iter().rposition(|line| !line.is_empty()).unwrap_or(0);
+    let data = lines[..=used].join("\n");
+    if data.len() > MAX_PAYLOAD {
+        return Err(format!(
+            "SEPA payload is {} bytes long (max {})",
+            data.len(),
+            MAX_PAYLOAD
+        )
+        .into());
+    }
+    Ok(data)
+}
+
+/// Sección Qr equivalente con el payload generado
+pub fn to_qr(sepa: &SepaQr) -> Result<Qr, SectionError> {
+    Ok(Qr {
+        data: payload(sepa)?,
+        size: sepa.size.unwrap_or(6),
+        error_correction: sepa
+            .error_correction
+            .clone()
+            .unwrap_or_else(|| "M".to_string()),
+        model: 2,
+        align: sepa.align.clone(),
+        render_mode: sepa.render_mode.clone(),
+        target_width: sepa.target_width,
+        target_width_mm: sepa.target_width_mm,
+    })
+}
+
+/// Procesa sección SepaQr del modelo de impresión
+pub fn process_section(
+    sepa: &SepaQr,
+    current_styles: &GlobalStyles,
+    profile: &PrinterProfile,
+    paper_width_pixels: i32,
+) -> Result<Vec<u8>, SectionError> {
+    qr::process_section(&to_qr(sepa)?, current_styles, profile, paper_width_pixels)
+}
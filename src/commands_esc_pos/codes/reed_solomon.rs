@@ -0,0 +1,77 @@
+//! Corrección de errores Reed-Solomon sobre GF(2^m), compartida por los
+//! codificadores 2D renderizados por software (DataMatrix y Aztec).
+
+/// Cuerpo de Galois GF(2^m) definido por su polinomio primitivo
+#[derive(Debug, Clone)]
+pub struct GaloisField {
+    exp: Vec<u32>,
+    log: Vec<u32>,
+    order: u32, // 2^m - 1
+}
+
+impl GaloisField {
+    /// `primitive` incluye el bit de grado m (p. ej. 0x12D para GF(256));
+    /// `size` es 2^m.
+    pub fn new(primitive: u32, size: u32) -> Self {
+        let mut exp = vec![0; size as usize];
+        let mut log = vec![0; size as usize];
+        let mut x = 1u32;
+        for value in exp.iter_mut() {
+            *value = x;
+            x <<= 1;
+            if x >= size {
+                x ^= primitive;
+            }
+        }
+        for i in 0..size - 1 {
+            log[exp[i as usize] as usize] = i;
+        }
+        Self {
+            exp,
+            log,
+            order: size - 1,
+        }
+    }
+
+    /// α^power
+    pub fn exp(&self, power: u32) -> u32 {
+        self.exp[(power % self.order) as usize]
+    }
+
+    pub fn multiply(&self, a: u32, b: u32) -> u32 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        self.exp((self.log[a as usize] + self.log[b as usize]) % self.order)
+    }
+
+    /// Palabras de control para `data`: resto de `data(x)·x^n` entre el polinomio
+    /// generador de raíces α^1..α^n (el mismo convenio en DataMatrix y Aztec).
+    /// Se devuelven de mayor a menor grado, en el orden en que se añaden al símbolo.
+    pub fn check_words(&self, data: &[u32], n: usize) -> Vec<u32> {
+        // Generador g(x) = Π (x + α^i), coeficientes de mayor a menor grado
+        let mut generator = vec![1u32];
+        for i in 1..=n as u32 {
+            let root = self.exp(i);
+            let mut next = vec![0u32; generator.len() + 1];
+            for (j, &coefficient) in generator.iter().enumerate() {
+                next[j] ^= coefficient;
+                next[j + 1] ^= self.multiply(coefficient, root);
+            }
+            generator = next;
+        }
+
+        let mut remainder = vec![0u32; n];
+        for &word in data {
+            let factor = word ^ remainder.first().copied().unwrap_or(0);
+            if n > 0 {
+                remainder.remove(0);
+                remainder.push(0);
+            }
+            for (slot, &coefficient) in remainder.iter_mut().zip(&generator[1..]) {
+                *slot ^= self.multiply(coefficient, factor);
+            }
+        }
+        remainder
+    }
+}
@@ -0,0 +1,35 @@
+use crate::error::SectionError;
+
+/// Cómo se imprime un código: con el comando del firmware o renderizado en Rust
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    Native, // Comando ESC/POS nativo: lo codifica el firmware
+    Raster, // Se codifica en Rust y se imprime como imagen (GS v 0)
+    Auto,   // Según el perfil de la impresora
+}
+
+impl RenderMode {
+    /// Convierte el nombre usado en el modelo (`native` si no se indica)
+    pub fn from_name(name: Option<&str>) -> Result<Self, SectionError> {
+        match name.unwrap_or("native") {
+            "native" => Ok(RenderMode::Native),
+            "raster" => Ok(RenderMode::Raster),
+            "auto" => Ok(RenderMode::Auto),
+            other => Err(format!(
+                "Unknown render mode '{}' (expected native, raster or auto)",
+                other
+            )
+            .into()),
+        }
+    }
+
+    /// `true` si el código debe imprimirse como imagen. `native_supported` indica
+    /// si el firmware (según el perfil) implementa el comando nativo.
+    pub fn is_raster(&self, native_supported: bool) -> bool {
+        match self {
+            RenderMode::Native => false,
+            RenderMode::Raster => true,
+            RenderMode::Auto => !native_supported,
+        }
+    }
+}
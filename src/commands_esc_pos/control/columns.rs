@@ -0,0 +1,169 @@
+use super::page_mode::{self, element_height};
+use crate::commands_esc_pos::text::encoder::TextEncoder;
+use crate::error::SectionError;
+use crate::models::print_sections::{Columns, GlobalStyles, PageElement, PageMode, PrintSections};
+use crate::models::printer_profile::PrinterProfile;
+use crate::process::image_assets::ImageAssets;
+
+/// Separación por defecto entre columnas, en puntos
+const DEFAULT_GAP: u16 = 16;
+/// Separación vertical entre elementos apilados en una columna, en puntos
+const ELEMENT_SPACING: u16 = 8;
+
+/// Procesa sección Columns: calcula anchos y alturas de cada columna y la
+/// imprime como un único bloque en modo página.
+pub fn process_section(
+    columns: &Columns,
+    current_styles: &GlobalStyles,
+    encoder: &TextEncoder,
+    paper_width_pixels: i32,
+    profile: &PrinterProfile,
+    assets: &ImageAssets,
+) -> Result<Vec<u8>, SectionError> {
+    if columns.columns.is_empty() {
+        return Err("Columns must contain at least one column".into());
+    }
+
+    let paper_width = paper_width_pixels.max(0) as u16;
+    let widths = resolve_widths(columns, paper_width)?;
+
+    let mut page_styles = current_styles.clone();
+    page_styles.align = Some("left".to_string());
+
+    // Primero se apilan los elementos de cada columna desde y = 0; la altura de
+    // la banda es la de la columna más alta.
+    let mut stacked: Vec<(u16, Vec<PageElement>)> = Vec::new();
+    let mut x = 0u16;
+    for (col_idx, (column, &width)) in columns.columns.iter().zip(&widths).enumerate() {
+        let mut elements = Vec::new();
+        let mut y = 0u16;
+        for (idx, content) in column.content.iter().enumerate() {
+            let height = check_supported(content)
+                .and_then(|_| element_height(content, &page_styles, width, assets))
+                .map_err(|e| in_column(e, col_idx, idx, content))?;
+
+            if idx > 0 {
+                y = y.saturating_add(ELEMENT_SPACING);
+            }
+            elements.push(PageElement {
+                x,
+                y,
+                width: Some(width),
+                content: content.clone(),
+            });
+            y = y.saturating_add(height);
+        }
+        stacked.push((y, elements));
+        x = x
+            .saturating_add(width)
+            .saturating_add(columns.gap.unwrap_or(DEFAULT_GAP));
+    }
+
+    let band_height = stacked.iter().map(|(height, _)| *height).max().unwrap_or(0);
+    if band_height == 0 {
+        return Err("Columns must contain at least one element".into());
+    }
+
+    let mut elements = Vec::new();
+    for (column, (height, column_elements)) in columns.columns.iter().zip(stacked) {
+        let offset = match column.valign.as_deref() {
+            Some("middle") => (band_height - height) / 2,
+            Some("bottom") => band_height - height,
+            _ => 0,
+        };
+        elements.extend(column_elements.into_iter().map(|mut element| {
+            element.y += offset;
+            element
+        }));
+    }
+
+    let page = PageMode {
+        x: 0,
+        y: 0,
+        width: Some(paper_width),
+        height: Some(band_height),
+        direction: None,
+        elements,
+    };
+    page_mode::process_section(
+        &page,
+        current_styles,
+        encoder,
+        paper_width_pixels,
+        profile,
+        assets,
+    )
+}
+
+/// Anchos en puntos: las columnas sin `width` se reparten el espacio restante
+fn resolve_widths(columns: &Columns, paper_width: u16) -> Result<Vec<u16>, SectionError> {
+    let gap = columns.gap.unwrap_or(DEFAULT_GAP) as u32;
+    let gaps = gap * (columns.columns.len() as u32 - 1);
+    let fixed: u32 = columns
+        .columns
+        .iter()
+        .filter_map(|column| column.width)
+        .map(u32::from)
+        .sum();
+
+    if fixed + gaps > paper_width as u32 {
+        return Err(format!(
+            "Column widths ({}) plus gaps ({}) exceed the paper width ({} dots)",
+            fixed, gaps, paper_width
+        )
+        .into());
+    }
+
+    let auto_count = columns
+        .columns
+        .iter()
+        .filter(|column| column.width.is_none())
+        .count() as u32;
+    let auto_width = (paper_width as u32 - fixed - gaps)
+        .checked_div(auto_count)
+        .unwrap_or(0) as u16;
+
+    columns
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| match column.width.unwrap_or(auto_width) {
+            0 => Err(format!("columns[{}] has no room left (width 0)", i).into()),
+            width => Ok(width),
+        })
+        .collect()
+}
+
+/// Comprueba que el elemento se pueda colocar en una columna. El texto se parte
+/// y los códigos/imágenes se limitan al ancho de la columna en el modo página.
+fn check_supported(content: &PrintSections) -> Result<(), SectionError> {
+    match content {
+        PrintSections::Title(_)
+        | PrintSections::Subtitle(_)
+        | PrintSections::Text(_)
+        | PrintSections::Image(_)
+        | PrintSections::Qr(_)
+        | PrintSections::Barcode(_) => Ok(()),
+        other => Err(format!("Section '{}' is not supported inside Columns", other.kind()).into()),
+    }
+}
+
+/// Indica en el mensaje de error qué columna y elemento fallaron
+fn in_column(
+    err: SectionError,
+    column: usize,
+    index: usize,
+    content: &PrintSections,
+) -> SectionError {
+    match err {
+        SectionError::Invalid(reason) => format!(
+            "columns[{}].content[{}] ({}): {}",
+            column,
+            index,
+            content.kind(),
+            reason
+        )
+        .into(),
+        other => other,
+    }
+}
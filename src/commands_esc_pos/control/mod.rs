@@ -1 +1,4 @@
-pub mod printer_control;
+pub mod columns;
+pub mod page_direction;
+pub mod page_mode;
+pub mod printer_control;
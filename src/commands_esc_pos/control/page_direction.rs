@@ -0,0 +1,35 @@
+/// Dirección de impresión en modo página (`ESC T n`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageDirection {
+    LeftToRight = 0, // Izquierda → derecha, origen arriba a la izquierda
+    BottomToTop = 1, // Abajo → arriba, origen abajo a la izquierda (rotado 90°)
+    RightToLeft = 2, // Derecha → izquierda, origen abajo a la derecha (rotado 180°)
+    TopToBottom = 3, // Arriba → abajo, origen arriba a la derecha (rotado 270°)
+}
+
+impl PageDirection {
+    /// Obtiene el valor numérico de la dirección
+    pub fn value(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Convierte el nombre usado en el modelo (`"left_to_right"`, ...).
+    /// Devuelve `None` para nombres desconocidos.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "left_to_right" => Some(PageDirection::LeftToRight),
+            "bottom_to_top" => Some(PageDirection::BottomToTop),
+            "right_to_left" => Some(PageDirection::RightToLeft),
+            "top_to_bottom" => Some(PageDirection::TopToBottom),
+            _ => None,
+        }
+    }
+
+    /// `true` si el texto corre a lo largo del papel (rotado 90° o 270°)
+    pub fn is_vertical(&self) -> bool {
+        matches!(
+            self,
+            PageDirection::BottomToTop | PageDirection::TopToBottom
+        )
+    }
+}
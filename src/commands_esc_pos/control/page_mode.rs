@@ -0,0 +1,436 @@
+use super::page_direction::PageDirection;
+use super::printer_control::PrinterControl;
+use crate::commands_esc_pos::codes::barcode::barcode as barcode_cmd;
+use crate::commands_esc_pos::codes::barcode::barcode_raster;
+use crate::commands_esc_pos::codes::qr::qr as qr_cmd;
+use crate::commands_esc_pos::image_escpos::image_code as image_cmd;
+use crate::commands_esc_pos::image_escpos::image_options::ImageOptions;
+use crate::commands_esc_pos::text::encoder::TextEncoder;
+use crate::commands_esc_pos::text::text_type::get_styles_diff;
+use crate::error::SectionError;
+use crate::models::print_sections::{GlobalStyles, PageElement, PageMode, PrintSections, Text};
+use crate::models::printer_profile::PrinterProfile;
+use crate::process::image_assets::ImageAssets;
+use unicode_width::UnicodeWidthChar;
+
+/// Interlineado por defecto (`ESC 2`) en puntos
+const DEFAULT_LINE_PITCH: u16 = 30;
+/// Alto del texto HRI de los códigos de barras (fuente A, igual en modo raster)
+const HRI_HEIGHT: u16 = barcode_raster::HRI_BLOCK_HEIGHT as u16;
+
+/// Caja vertical de un elemento: `anchor` es la distancia desde el borde superior
+/// hasta la posición vertical que espera la impresora (la base de la primera
+/// línea de texto o el borde inferior de un código/imagen); `height` es el alto total.
+struct ElementBox {
+    anchor: u16,
+    height: u16,
+}
+
+/// Procesa sección PageMode: `ESC L`, área (`ESC W`), dirección (`ESC T`), los
+/// elementos en su posición absoluta (`ESC $` / `GS $`), `ESC FF` y `ESC S`.
+pub fn process_section(
+    page: &PageMode,
+    current_styles: &GlobalStyles,
+    encoder: &TextEncoder,
+    paper_width_pixels: i32,
+    profile: &PrinterProfile,
+    assets: &ImageAssets,
+) -> Result<Vec<u8>, SectionError> {
+    if page.elements.is_empty() {
+        return Err("PageMode must contain at least one element".into());
+    }
+
+    let direction = match page.direction.as_deref() {
+        None => PageDirection::LeftToRight,
+        Some(name) => PageDirection::from_name(name)
+            .ok_or_else(|| format!("Unknown PageMode direction '{}'", name))?,
+    };
+
+    let width = page.width.unwrap_or(paper_width_pixels.max(0) as u16);
+    if width == 0 {
+        return Err("PageMode width must be greater than 0".into());
+    }
+
+    // Dentro del modo página los elementos se posicionan con ESC $, así que la
+    // alineación se fuerza a la izquierda mientras dure la sección.
+    let mut page_styles = current_styles.clone();
+    page_styles.align = Some("left".to_string());
+
+    // En dirección vertical el eje horizontal de los elementos corre a lo largo
+    // del alto del área.
+    let line_length = |height: u16| {
+        if direction.is_vertical() {
+            height
+        } else {
+            width
+        }
+    };
+
+    let mut boxes = Vec::with_capacity(page.elements.len());
+    for (i, element) in page.elements.iter().enumerate() {
+        let max_width = element_width(element, page.height.map(line_length).unwrap_or(width));
+        let element_box = element_box(&element.content, &page_styles, max_width, assets)
+            .map_err(|e| in_element(e, i, &element.content))?;
+        boxes.push(element_box);
+    }
+
+    let height = match page.height {
+        Some(0) => return Err("PageMode height must be greater than 0".into()),
+        Some(height) => height,
+        None if direction.is_vertical() => {
+            return Err(format!(
+                "PageMode height is required for direction '{}'",
+                page.direction.as_deref().unwrap_or_default()
+            )
+            .into())
+        }
+        None => page
+            .elements
+            .iter()
+            .zip(&boxes)
+            .map(|(element, element_box)| element.y.saturating_add(element_box.height))
+            .max()
+            .unwrap_or(0),
+    };
+    let line_length = line_length(height);
+
+    let mut output = Vec::new();
+    output.extend_from_slice(&get_styles_diff(current_styles, &page_styles));
+    output.extend(PrinterControl::enable_page_mode());
+    output.extend(PrinterControl::set_page_mode_area(
+        page.x, page.y, width, height,
+    ));
+    output.extend(PrinterControl::set_page_mode_direction(direction.value()));
+
+    for (i, (element, element_box)) in page.elements.iter().zip(&boxes).enumerate() {
+        if element.x >= line_length {
+            return Err(format!(
+                "elements[{}]: x ({}) is outside the page area ({} dots)",
+                i, element.x, line_length
+            )
+            .into());
+        }
+
+        let data = render_element(
+            element,
+            element.y.saturating_add(element_box.anchor),
+            &page_styles,
+            encoder,
+            element_width(element, line_length),
+            profile,
+            assets,
+        )
+        .map_err(|e| in_element(e, i, &element.content))?;
+        output.extend(data);
+    }
+
+    output.extend(PrinterControl::print_page_mode());
+    output.extend(PrinterControl::enable_standard_mode());
+    output.extend_from_slice(&get_styles_diff(&page_styles, current_styles));
+
+    Ok(output)
+}
+
+/// Genera los bytes de un elemento, posicionado en `element.x` con su línea de
+/// referencia en `anchor_y`, sin alineación ni saltos propios del modo estándar
+fn render_element(
+    element: &PageElement,
+    anchor_y: u16,
+    page_styles: &GlobalStyles,
+    encoder: &TextEncoder,
+    max_width: u16,
+    profile: &PrinterProfile,
+    assets: &ImageAssets,
+) -> Result<Vec<u8>, SectionError> {
+    let mut output = position(element.x, anchor_y);
+    let data = match &element.content {
+        PrintSections::Title(_) | PrintSections::Subtitle(_) | PrintSections::Text(_) => {
+            // En modo página LF vuelve al borde izquierdo del área: cada línea se
+            // posiciona de nuevo en `element.x`
+            let text = wrapped_text(&element.content, page_styles, max_width);
+            let styles = text.styles.as_ref().unwrap_or(page_styles);
+            let pitch = line_pitch(styles);
+            let mut data = get_styles_diff(page_styles, styles);
+            for (n, line) in text.text.split('\n').enumerate() {
+                if n > 0 {
+                    let y = anchor_y.saturating_add((n as u16).saturating_mul(pitch));
+                    data.extend(position(element.x, y));
+                }
+                data.extend(encoder.encode_text(line)?);
+                data.extend_from_slice(b"\n");
+            }
+            data.extend(get_styles_diff(styles, page_styles));
+            Ok(data)
+        }
+        PrintSections::Qr(qr) => qr_cmd::command(qr, profile, max_width as u32),
+        PrintSections::Barcode(barcode) => {
+            let mut barcode = barcode.clone();
+            barcode.align = None;
+            barcode_cmd::process_section(&barcode, page_styles, profile, max_width as i32)
+        }
+        PrintSections::Image(image) => {
+            let mut image = image_cmd::for_profile(image, profile).into_owned();
+            image.align = "left".to_string();
+            image_cmd::process_section(&image, max_width as i32, assets)
+        }
+        other => Err(unsupported(other)),
+    };
+    output.extend(data?);
+    Ok(output)
+}
+
+/// Ancho de la caja del elemento: su `width`, sin pasar del borde del área
+fn element_width(element: &PageElement, line_length: u16) -> u16 {
+    let rest = line_length.saturating_sub(element.x);
+    element.width.map_or(rest, |width| width.min(rest))
+}
+
+/// `GS $` (vertical) y `ESC $` (horizontal) dentro del área de página
+fn position(x: u16, y: u16) -> Vec<u8> {
+    let mut output = PrinterControl::set_page_mode_vertical_position(y);
+    output.extend(PrinterControl::set_absolute_position(x));
+    output
+}
+
+/// Alto estimado en puntos de un elemento de página (ver [`element_box`])
+pub(crate) fn element_height(
+    content: &PrintSections,
+    page_styles: &GlobalStyles,
+    max_width: u16,
+    assets: &ImageAssets,
+) -> Result<u16, SectionError> {
+    element_box(content, page_styles, max_width, assets).map(|element_box| element_box.height)
+}
+
+/// Calcula la caja vertical de un elemento a partir de su contenido
+fn element_box(
+    content: &PrintSections,
+    page_styles: &GlobalStyles,
+    max_width: u16,
+    assets: &ImageAssets,
+) -> Result<ElementBox, SectionError> {
+    match content {
+        PrintSections::Title(_) | PrintSections::Subtitle(_) | PrintSections::Text(_) => {
+            let text = wrapped_text(content, page_styles, max_width);
+            let styles = text.styles.as_ref().unwrap_or(page_styles);
+            let char_height = char_height(styles);
+            let lines = text.text.split('\n').count();
+            let height = u16::try_from(lines - 1)
+                .ok()
+                .and_then(|extra| extra.checked_mul(line_pitch(styles)))
+                .and_then(|extra| extra.checked_add(char_height))
+                .ok_or_else(|| {
+                    format!(
+                        "Text has too many lines ({}) to fit in a page ({} dots max)",
+                        lines,
+                        u16::MAX
+                    )
+                })?;
+            Ok(ElementBox {
+                anchor: char_height,
+                height,
+            })
+        }
+        PrintSections::Qr(qr) => {
+            let side = qr_cmd::from_section(qr, max_width as u32)?
+                .printed_size()
+                .ok_or("QR data does not fit in any QR version")?;
+            let side = side.min(u16::MAX as u32) as u16;
+            Ok(ElementBox {
+                anchor: side,
+                height: side,
+            })
+        }
+        PrintSections::Barcode(barcode) => {
+            let bars = barcode.height as u16;
+            let (above, below) = match barcode.text_position.as_str() {
+                "above" => (HRI_HEIGHT, 0),
+                "below" => (0, HRI_HEIGHT),
+                "both" => (HRI_HEIGHT, HRI_HEIGHT),
+                _ => (0, 0),
+            };
+            Ok(ElementBox {
+                anchor: above + bars,
+                height: above + bars + below,
+            })
+        }
+        PrintSections::Image(image) => {
+            if image.data.is_empty() {
+                return Err("Image data cannot be empty".into());
+            }
+            let max_width = if image.max_width > 0 && image.max_width < max_width as i32 {
+                image.max_width as u32
+            } else {
+                max_width as u32
+            };
+            // El mapa de bits queda en caché para cuando se imprima el elemento
+            let options = ImageOptions::from_section(image)?;
+            let mut height = assets.bitmap(&image.data, max_width, &options)?.height();
+            if matches!(image.size.as_str(), "double_height" | "quadruple") {
+                height *= 2;
+            }
+            let height = height.min(u16::MAX as u32) as u16;
+            Ok(ElementBox {
+                anchor: height,
+                height,
+            })
+        }
+        other => Err(unsupported(other)),
+    }
+}
+
+/// Convierte Title/Subtitle/Text en un Text con los estilos efectivos y
+/// alineación a la izquierda (Title y Subtitle conservan su tamaño y negrita).
+fn text_element(content: &PrintSections, page_styles: &GlobalStyles) -> Text {
+    let (text, styles) = match content {
+        PrintSections::Title(title) => {
+            let mut styles = title.styles.clone().unwrap_or_else(|| page_styles.clone());
+            styles.size = Some("double".to_string());
+            (title.text.clone(), Some(styles))
+        }
+        PrintSections::Subtitle(subtitle) => {
+            let mut styles = subtitle
+                .styles
+                .clone()
+                .unwrap_or_else(|| page_styles.clone());
+            styles.size = Some("height".to_string());
+            styles.bold = Some(true);
+            (subtitle.text.clone(), Some(styles))
+        }
+        PrintSections::Text(text) => (text.text.clone(), text.styles.clone()),
+        _ => (String::new(), None),
+    };
+
+    Text {
+        text,
+        styles: styles.map(|mut styles| {
+            styles.align = Some("left".to_string());
+            styles
+        }),
+    }
+}
+
+/// Texto del elemento partido al ancho disponible: la impresora llevaría el
+/// resto de una línea larga al borde izquierdo del área, fuera de la caja medida
+fn wrapped_text(content: &PrintSections, page_styles: &GlobalStyles, max_width: u16) -> Text {
+    let mut text = text_element(content, page_styles);
+    let styles = text.styles.as_ref().unwrap_or(page_styles);
+    let max_chars = (max_width / char_width(styles)).max(1) as usize;
+    text.text = wrap_words(&text.text, max_chars);
+    text
+}
+
+/// Parte el texto por palabras para que ninguna línea supere `max_chars`
+/// columnas; las palabras más largas que la línea se cortan por carácter. Las
+/// líneas que ya caben se dejan tal cual (con sus espacios).
+fn wrap_words(text: &str, max_chars: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    for paragraph in text.split('\n') {
+        if text_width(paragraph) <= max_chars {
+            lines.push(paragraph.to_string());
+            continue;
+        }
+        let mut line = String::new();
+        let mut line_width = 0usize;
+
+        for word in paragraph.split(' ').filter(|word| !word.is_empty()) {
+            let word_width = text_width(word);
+            let needed = if line.is_empty() {
+                word_width
+            } else {
+                line_width + 1 + word_width
+            };
+
+            if needed <= max_chars {
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                line_width = needed;
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            for ch in word.chars() {
+                let ch_width = ch.width().unwrap_or(0);
+                if line_width + ch_width > max_chars && !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push(ch);
+                line_width += ch_width;
+            }
+        }
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+/// Ancho de un texto en columnas de carácter
+fn text_width(text: &str) -> usize {
+    text.chars().map(|ch| ch.width().unwrap_or(0)).sum()
+}
+
+/// Alto de un carácter en puntos según fuente y tamaño
+fn char_height(styles: &GlobalStyles) -> u16 {
+    let base = match styles
+        .font
+        .as_deref()
+        .unwrap_or("A")
+        .to_uppercase()
+        .as_str()
+    {
+        "B" | "C" => 17,
+        _ => 24,
+    };
+    match styles.size.as_deref().unwrap_or("normal") {
+        "height" | "double" => base * 2,
+        _ => base,
+    }
+}
+
+/// Distancia en puntos entre las bases de dos líneas de texto seguidas
+fn line_pitch(styles: &GlobalStyles) -> u16 {
+    DEFAULT_LINE_PITCH.max(char_height(styles))
+}
+
+/// Ancho de un carácter en puntos según fuente y tamaño
+fn char_width(styles: &GlobalStyles) -> u16 {
+    let base = match styles
+        .font
+        .as_deref()
+        .unwrap_or("A")
+        .to_uppercase()
+        .as_str()
+    {
+        "B" | "C" => 9,
+        _ => 12,
+    };
+    match styles.size.as_deref().unwrap_or("normal") {
+        "width" | "double" => base * 2,
+        _ => base,
+    }
+}
+
+fn unsupported(content: &PrintSections) -> SectionError {
+    format!(
+        "Section '{}' is not supported inside PageMode",
+        content.kind()
+    )
+    .into()
+}
+
+/// Indica en el mensaje de error qué elemento falló
+fn in_element(err: SectionError, index: usize, content: &PrintSections) -> SectionError {
+    match err {
+        SectionError::Invalid(reason) => {
+            format!("elements[{}] ({}): {}", index, content.kind(), reason).into()
+        }
+        other => other,
+    }
+}
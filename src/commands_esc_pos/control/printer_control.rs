@@ -1,3 +1,4 @@
+use crate::error::SectionError;
 use crate::models::print_sections::{
     Beep, CharSpacing, Cut, Drawer, Feed, LeftMargin, LineSpacing, Position, PrintAreaWidth,
     TabStops,
@@ -87,17 +88,17 @@ impl PrinterControl {
     }
 
     /// Procesa sección Feed
-    pub fn process_feed(feed: &Feed) -> Result<Vec<u8>, String> {
+    pub fn process_feed(feed: &Feed) -> Result<Vec<u8>, SectionError> {
         match feed.feed_type.as_str() {
             "lines" => Ok(Self::feed_paper(feed.value)),
             "dots" => Ok(Self::feed_paper_dots(feed.value)),
             "line_feed" => Ok(Self::line_feed_multiple(feed.value as usize)),
-            _ => Err("Unknown feed type".to_string()),
+            _ => Err("Unknown feed type".into()),
         }
     }
 
     /// Procesa sección Cut
-    pub fn process_cut(cut: &Cut) -> Result<Vec<u8>, String> {
+    pub fn process_cut(cut: &Cut) -> Result<Vec<u8>, SectionError> {
         let mode = match cut.mode.as_str() {
             "full" | "partial_alt2" => CUT_MODE_FULL,
             _ => CUT_MODE_PARTIAL,
@@ -106,7 +107,7 @@ impl PrinterControl {
     }
 
     /// Procesa sección Beep (Epson `ESC ( A`)
-    pub fn process_beep(beep: &Beep) -> Result<Vec<u8>, String> {
+    pub fn process_beep(beep: &Beep) -> Result<Vec<u8>, SectionError> {
         let times = if beep.times == 0 { 1 } else { beep.times };
         let duration = if beep.duration == 0 {
             100
@@ -120,7 +121,7 @@ impl PrinterControl {
     ///
     /// Alternativa a [`Self::process_beep`] para impresoras genéricas/clones que
     /// ignoran el `ESC ( A` de Epson.
-    pub fn process_beep2(beep: &Beep) -> Result<Vec<u8>, String> {
+    pub fn process_beep2(beep: &Beep) -> Result<Vec<u8>, SectionError> {
         let times = if beep.times == 0 { 1 } else { beep.times };
         let duration = if beep.duration == 0 { 3 } else { beep.duration };
         Ok(Self::beep_generic(times, duration))
@@ -184,40 +185,40 @@ impl PrinterControl {
     }
 
     /// Procesa sección LineSpacing
-    pub fn process_line_spacing(ls: &LineSpacing) -> Result<Vec<u8>, String> {
+    pub fn process_line_spacing(ls: &LineSpacing) -> Result<Vec<u8>, SectionError> {
         Ok(Self::set_line_spacing(ls.value))
     }
 
     /// Procesa sección CharSpacing
-    pub fn process_char_spacing(cs: &CharSpacing) -> Result<Vec<u8>, String> {
+    pub fn process_char_spacing(cs: &CharSpacing) -> Result<Vec<u8>, SectionError> {
         Ok(Self::set_char_spacing(cs.value))
     }
 
     /// Procesa sección Position
-    pub fn process_position(p: &Position) -> Result<Vec<u8>, String> {
+    pub fn process_position(p: &Position) -> Result<Vec<u8>, SectionError> {
         Ok(Self::set_absolute_position(p.value))
     }
 
     /// Procesa sección TabStops
-    pub fn process_tab_stops(t: &TabStops) -> Result<Vec<u8>, String> {
+    pub fn process_tab_stops(t: &TabStops) -> Result<Vec<u8>, SectionError> {
         if t.positions.is_empty() {
-            return Err("Tab stops cannot be empty".to_string());
+            return Err("Tab stops cannot be empty".into());
         }
         Ok(Self::set_tab_stops(&t.positions))
     }
 
     /// Procesa sección LeftMargin
-    pub fn process_left_margin(m: &LeftMargin) -> Result<Vec<u8>, String> {
+    pub fn process_left_margin(m: &LeftMargin) -> Result<Vec<u8>, SectionError> {
         Ok(Self::set_left_margin(m.value))
     }
 
     /// Procesa sección PrintAreaWidth
-    pub fn process_print_area_width(w: &PrintAreaWidth) -> Result<Vec<u8>, String> {
+    pub fn process_print_area_width(w: &PrintAreaWidth) -> Result<Vec<u8>, SectionError> {
         Ok(Self::set_print_area_width(w.value))
     }
 
     /// Procesa sección Drawer
-    pub fn process_drawer(drawer: &Drawer) -> Result<Vec<u8>, String> {
+    pub fn process_drawer(drawer: &Drawer) -> Result<Vec<u8>, SectionError> {
         if drawer.pin == 2 {
             Ok(Self::open_cash_drawer_pin2(drawer.pulse_time))
         } else {
@@ -281,38 +282,50 @@ impl PrinterControl {
     //     Self::feed_paper(lines)
     // }
 
-    // /// Modo de página (permite posicionamiento absoluto)
-    // /// ESC L
-    // pub fn enable_page_mode() -> Vec<u8> {
-    //     vec![0x1B, 0x4C]
-    // }
+    /// Modo de página (permite posicionamiento absoluto)
+    /// ESC L
+    pub fn enable_page_mode() -> Vec<u8> {
+        vec![0x1B, 0x4C]
+    }
 
-    // /// Modo estándar (desactiva modo página)
-    // /// ESC S
-    // pub fn enable_standard_mode() -> Vec<u8> {
-    //     vec![0x1B, 0x53]
-    // }
+    /// Modo estándar (desactiva modo página)
+    /// ESC S
+    pub fn enable_standard_mode() -> Vec<u8> {
+        vec![0x1B, 0x53]
+    }
 
-    // /// Establece el área de impresión en modo página
-    // /// ESC W xL xH yL yH dxL dxH dyL dyH
-    // pub fn set_page_mode_area(x: u16, y: u16, width: u16, height: u16) -> Vec<u8> {
-    //     vec![
-    //         0x1B,
-    //         0x57,
-    //         (x & 0xFF) as u8,
-    //         ((x >> 8) & 0xFF) as u8,
-    //         (y & 0xFF) as u8,
-    //         ((y >> 8) & 0xFF) as u8,
-    //         (width & 0xFF) as u8,
-    //         ((width >> 8) & 0xFF) as u8,
-    //         (height & 0xFF) as u8,
-    //         ((height >> 8) & 0xFF) as u8,
-    //     ]
-    // }
+    /// Establece el área de impresión en modo página
+    /// ESC W xL xH yL yH dxL dxH dyL dyH
+    pub fn set_page_mode_area(x: u16, y: u16, width: u16, height: u16) -> Vec<u8> {
+        vec![
+            0x1B,
+            0x57,
+            (x & 0xFF) as u8,
+            ((x >> 8) & 0xFF) as u8,
+            (y & 0xFF) as u8,
+            ((y >> 8) & 0xFF) as u8,
+            (width & 0xFF) as u8,
+            ((width >> 8) & 0xFF) as u8,
+            (height & 0xFF) as u8,
+            ((height >> 8) & 0xFF) as u8,
+        ]
+    }
 
-    // /// Imprime el contenido del buffer en modo página
-    // /// ESC FF
-    // pub fn print_page_mode() -> Vec<u8> {
-    //     vec![0x1B, 0x0C]
-    // }
+    /// Dirección de impresión en modo página
+    /// ESC T n (0 = izq→der, 1 = abajo→arriba, 2 = der→izq, 3 = arriba→abajo)
+    pub fn set_page_mode_direction(direction: u8) -> Vec<u8> {
+        vec![0x1B, 0x54, direction]
+    }
+
+    /// Posición vertical absoluta en modo página
+    /// GS $ nL nH
+    pub fn set_page_mode_vertical_position(pos: u16) -> Vec<u8> {
+        vec![0x1D, 0x24, (pos & 0xFF) as u8, ((pos >> 8) & 0xFF) as u8]
+    }
+
+    /// Imprime el contenido del buffer en modo página
+    /// ESC FF
+    pub fn print_page_mode() -> Vec<u8> {
+        vec![0x1B, 0x0C]
+    }
 }
@@ -0,0 +1,248 @@
+//! Binarización de imágenes en escala de grises: umbral, difusión de error
+//! (Floyd-Steinberg, Atkinson, Jarvis-Judice-Ninke, Stucki, Sierra) y tramado
+//! ordenado Bayer, a dos niveles o a varios tonos de gris. Todo es aritmética entera para que el resultado sea idéntico
+//! en cualquier plataforma.
+
+use crate::error::SectionError;
+use image::{ImageBuffer, Luma};
+
+/// Umbral por defecto: valores < 127 = negro
+pub const DEFAULT_THRESHOLD: u8 = 127;
+
+/// Algoritmo de binarización
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DitherAlgorithm {
+    /// Umbral simple, sin tramado
+    Threshold,
+    FloydSteinberg,
+    Atkinson,
+    JarvisJudiceNinke,
+    Stucki,
+    Sierra,
+    /// Tramado ordenado con matriz de Bayer 4×4
+    Bayer4,
+    /// Tramado ordenado con matriz de Bayer 8×8
+    Bayer8,
+}
+
+/// Reparto del error a los vecinos: `(dx, dy, peso)` y divisor
+struct Kernel {
+    weights: &'static [(i32, u32, i32)],
+    divisor: i32,
+}
+
+const FLOYD_STEINBERG: Kernel = Kernel {
+    weights: &[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)],
+    divisor: 16,
+};
+
+/// Atkinson solo reparte 6/8 del error: más contraste, menos ruido
+#[rustfmt::skip]
+const ATKINSON: Kernel = Kernel {
+    weights: &[(1, 0, 1), (2, 0, 1), (-1, 1, 1), (0, 1, 1), (1, 1, 1), (0, 2, 1)],
+    divisor: 8,
+};
+
+#[rustfmt::skip]
+const JARVIS_JUDICE_NINKE: Kernel = Kernel {
+    weights: &[
+                                (1, 0, 7), (2, 0, 5),
+        (-2, 1, 3), (-1, 1, 5), (0, 1, 7), (1, 1, 5), (2, 1, 3),
+        (-2, 2, 1), (-1, 2, 3), (0, 2, 5), (1, 2, 3), (2, 2, 1),
+    ],
+    divisor: 48,
+};
+
+#[rustfmt::skip]
+const STUCKI: Kernel = Kernel {
+    weights: &[
+                                (1, 0, 8), (2, 0, 4),
+        (-2, 1, 2), (-1, 1, 4), (0, 1, 8), (1, 1, 4), (2, 1, 2),
+        (-2, 2, 1), (-1, 2, 2), (0, 2, 4), (1, 2, 2), (2, 2, 1),
+    ],
+    divisor: 42,
+};
+
+#[rustfmt::skip]
+const SIERRA: Kernel = Kernel {
+    weights: &[
+                                (1, 0, 5), (2, 0, 3),
+        (-2, 1, 2), (-1, 1, 4), (0, 1, 5), (1, 1, 4), (2, 1, 2),
+                    (-1, 2, 2), (0, 2, 3), (1, 2, 2),
+    ],
+    divisor: 32,
+};
+
+#[rustfmt::skip]
+const BAYER4: [[u8; 4]; 4] = [
+    [0, 8, 2, 10],
+    [12, 4, 14, 6],
+    [3, 11, 1, 9],
+    [15, 7, 13, 5],
+];
+
+#[rustfmt::skip]
+const BAYER8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+impl DitherAlgorithm {
+    /// Convierte el nombre usado en el modelo. Sin nombre: Floyd-Steinberg si
+    /// `dithering` es `true`, umbral simple si es `false`.
+    pub fn from_name(name: Option<&str>, dithering: bool) -> Result<Self, SectionError> {
+        if !dithering {
+            return Ok(DitherAlgorithm::Threshold);
+        }
+        let algorithm = match name.unwrap_or("floyd_steinberg") {
+            "threshold" => DitherAlgorithm::Threshold,
+            "floyd_steinberg" => DitherAlgorithm::FloydSteinberg,
+            "atkinson" => DitherAlgorithm::Atkinson,
+            "jarvis_judice_ninke" => DitherAlgorithm::JarvisJudiceNinke,
+            "stucki" => DitherAlgorithm::Stucki,
+            "sierra" => DitherAlgorithm::Sierra,
+            "bayer4" => DitherAlgorithm::Bayer4,
+            "bayer8" => DitherAlgorithm::Bayer8,
+            other => {
+                return Err(format!(
+                    "Unknown dithering algorithm '{}' (expected threshold, floyd_steinberg, atkinson, jarvis_judice_ninke, stucki, sierra, bayer4 or bayer8)",
+                    other
+                )
+                .into())
+            }
+        };
+        Ok(algorithm)
+    }
+
+    /// Binariza `grayscale` (0 = negro, 255 = blanco). `threshold` es el punto de
+    /// corte del umbral y de la difusión de error, y desplaza la matriz de Bayer.
+    pub fn apply(
+        self,
+        grayscale: &ImageBuffer<Luma<u8>, Vec<u8>>,
+        threshold: u8,
+    ) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+        match self {
+            DitherAlgorithm::Threshold => {
+                ImageBuffer::from_fn(grayscale.width(), grayscale.height(), |x, y| {
+                    binary(grayscale.get_pixel(x, y)[0] as i32, threshold)
+                })
+            }
+            DitherAlgorithm::Bayer4 => ordered(grayscale, threshold, &BAYER4),
+            DitherAlgorithm::Bayer8 => ordered(grayscale, threshold, &BAYER8),
+            _ => diffuse(grayscale, self.kernel(), |value| binary(value, threshold)),
+        }
+    }
+
+    /// Reduce `grayscale` a `levels` grises repartidos entre 0 y 255, para los
+    /// gráficos de tonos múltiples. El umbral no interviene: cada píxel va al
+    /// tono más cercano y el error se reparte igual que al binarizar.
+    pub fn apply_levels(
+        self,
+        grayscale: &ImageBuffer<Luma<u8>, Vec<u8>>,
+        levels: u8,
+    ) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+        let steps = levels.max(2) as i32 - 1;
+        let level = |index: i32| Luma([(index.clamp(0, steps) * 255 / steps) as u8]);
+        let nearest = |value: i32| level((value * steps + 127).div_euclid(255));
+        match self {
+            DitherAlgorithm::Threshold => {
+                ImageBuffer::from_fn(grayscale.width(), grayscale.height(), |x, y| {
+                    nearest(grayscale.get_pixel(x, y)[0] as i32)
+                })
+            }
+            DitherAlgorithm::Bayer4 => ordered_levels(grayscale, steps, &BAYER4),
+            DitherAlgorithm::Bayer8 => ordered_levels(grayscale, steps, &BAYER8),
+            _ => diffuse(grayscale, self.kernel(), nearest),
+        }
+    }
+
+    fn kernel(self) -> &'static Kernel {
+        match self {
+            DitherAlgorithm::Atkinson => &ATKINSON,
+            DitherAlgorithm::JarvisJudiceNinke => &JARVIS_JUDICE_NINKE,
+            DitherAlgorithm::Stucki => &STUCKI,
+            DitherAlgorithm::Sierra => &SIERRA,
+            _ => &FLOYD_STEINBERG,
+        }
+    }
+}
+
+fn binary(value: i32, threshold: u8) -> Luma<u8> {
+    if value < threshold as i32 {
+        Luma([0])
+    } else {
+        Luma([255])
+    }
+}
+
+/// Difusión de error de izquierda a derecha y de arriba abajo; `quantize` da el
+/// valor de salida de cada píxel
+fn diffuse(
+    grayscale: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    kernel: &Kernel,
+    quantize: impl Fn(i32) -> Luma<u8>,
+) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+    let (width, height) = grayscale.dimensions();
+    let mut pixels: Vec<i32> = grayscale.pixels().map(|p| p[0] as i32).collect();
+    let mut output = ImageBuffer::new(width, height);
+
+    for y in 0..height {
+        for x in 0..width {
+            let old_pixel = pixels[(y * width + x) as usize];
+            let new_pixel = quantize(old_pixel);
+            output.put_pixel(x, y, new_pixel);
+
+            let error = old_pixel - new_pixel[0] as i32;
+            for &(dx, dy, weight) in kernel.weights {
+                let (nx, ny) = (x as i32 + dx, y + dy);
+                if nx >= 0 && (nx as u32) < width && ny < height {
+                    pixels[(ny * width + nx as u32) as usize] += error * weight / kernel.divisor;
+                }
+            }
+        }
+    }
+    output
+}
+
+/// Tramado ordenado: cada píxel se compara con su celda de la matriz
+fn ordered<const N: usize>(
+    grayscale: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    threshold: u8,
+    matrix: &[[u8; N]; N],
+) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+    let cells = (N * N) as i32;
+    let offset = threshold as i32 - DEFAULT_THRESHOLD as i32;
+    ImageBuffer::from_fn(grayscale.width(), grayscale.height(), |x, y| {
+        let rank = matrix[y as usize % N][x as usize % N] as i32;
+        let limit = (2 * rank + 1) * 128 / cells + offset;
+        if (grayscale.get_pixel(x, y)[0] as i32) < limit {
+            Luma([0])
+        } else {
+            Luma([255])
+        }
+    })
+}
+
+/// Tramado ordenado entre los dos tonos vecinos de cada píxel (`steps` + 1 tonos)
+fn ordered_levels<const N: usize>(
+    grayscale: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    steps: i32,
+    matrix: &[[u8; N]; N],
+) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+    let cells = (N * N) as i32;
+    ImageBuffer::from_fn(grayscale.width(), grayscale.height(), |x, y| {
+        let rank = matrix[y as usize % N][x as usize % N] as i32;
+        // Posición del píxel entre tonos en unidades de 1/255 de tono
+        let scaled = grayscale.get_pixel(x, y)[0] as i32 * steps;
+        let (lower, fraction) = (scaled / 255, scaled % 255);
+        let limit = (2 * rank + 1) * 255 / (2 * cells);
+        let index = if fraction > limit { lower + 1 } else { lower };
+        Luma([(index.min(steps) * 255 / steps) as u8])
+    })
+}
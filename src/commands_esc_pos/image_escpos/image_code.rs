@@ -1,30 +1,51 @@
 use super::image_alignment::ImageAlignment;
+use super::image_command::{color_graphics_command, ImageCommandMode};
 use super::image_mode::ImageMode;
+use super::image_options::{ImageOptions, ImagePlane};
 use super::image_processor::ImageProcessor;
+use super::nv_graphics;
+use crate::error::SectionError;
 use crate::models::print_sections::Image as ImageSection;
+use crate::models::printer_profile::PrinterProfile;
+use crate::process::image_assets::{Bitmap, ImageAssets};
+use image::{imageops, ImageBuffer, Luma};
+use std::borrow::Cow;
 
 /// Constructor de comandos para imágenes
 #[derive(Debug, Clone)]
 pub struct Image {
-    base64_image: String,
+    data: String,
     alignment: ImageAlignment,
     mode: ImageMode,
     max_width: u32,
-    use_dithering: bool,
+    options: ImageOptions,
+    command_mode: ImageCommandMode,
+    band_height: Option<u32>,
+    assets: ImageAssets,
 }
 
 impl Image {
-    /// Crea una nueva imagen
-    pub fn new(base64_image: &str, max_width: u32) -> Result<Self, String> {
+    /// Crea una nueva imagen. `data` es base64 o cualquier otra forma que acepte
+    /// [`ImageAssets`] (`file://`, `resource://`, `asset:`).
+    pub fn new(data: &str, max_width: u32) -> Result<Self, String> {
         Ok(Self {
-            base64_image: base64_image.to_string(),
+            data: data.to_string(),
             alignment: ImageAlignment::Center,
             mode: ImageMode::Normal,
             max_width: max_width,
-            use_dithering: true,
+            options: ImageOptions::default(),
+            command_mode: ImageCommandMode::Raster,
+            band_height: None,
+            assets: ImageAssets::default(),
         })
     }
 
+    /// Establece de dónde se leen las imágenes que no son base64, y su caché
+    pub fn set_assets(mut self, assets: ImageAssets) -> Self {
+        self.assets = assets;
+        self
+    }
+
     /// Establece la alineación
     pub fn set_alignment(mut self, alignment: ImageAlignment) -> Self {
         self.alignment = alignment;
@@ -37,58 +58,189 @@ impl Image {
         self
     }
 
-    /// Establece si usar dithering
+    /// Establece si usar dithering (Floyd-Steinberg, sin ajustes)
     pub fn set_use_dithering(mut self, use_dithering: bool) -> Self {
-        self.use_dithering = use_dithering;
+        self.options = ImageOptions::dithered(use_dithering);
+        self
+    }
+
+    /// Establece el algoritmo de tramado y los ajustes de imagen
+    pub fn set_options(mut self, options: ImageOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Establece el comando de imagen (`GS v 0`, `ESC *` o `GS ( L`)
+    pub fn set_command_mode(mut self, command_mode: ImageCommandMode) -> Self {
+        self.command_mode = command_mode;
+        self
+    }
+
+    /// Divide la imagen en franjas de `band_height` filas, cada una con su propio
+    /// comando (`None` = un solo comando)
+    pub fn set_band_height(mut self, band_height: Option<u32>) -> Self {
+        self.band_height = band_height;
         self
     }
 
     /// Genera el comando ESC/POS para imprimir la imagen
-    /// Usa el comando GS v 0 (raster bit image)
-    pub fn get_command(&self) -> Result<Vec<u8>, String> {
-        let mut output = Vec::new();
+    /// Por defecto usa el comando GS v 0 (raster bit image)
+    pub fn get_command(&self) -> Result<Vec<u8>, SectionError> {
+        Ok(self.get_bands()?.concat())
+    }
 
+    /// Igual que `get_command` pero separado por franjas. La primera incluye la
+    /// alineación y la última la restaura a la izquierda.
+    pub fn get_bands(&self) -> Result<Vec<Vec<u8>>, SectionError> {
         // Procesar la imagen
-        let processed_image =
-            ImageProcessor::process_image(&self.base64_image, self.max_width, self.use_dithering)?;
+        let processed_image = self
+            .assets
+            .bitmap(&self.data, self.max_width, &self.options)?;
+        // En dos colores `processed_image` es el plano negro y este el rojo
+        let red_plane = match self.command_mode {
+            ImageCommandMode::TwoColor => Some(self.assets.bitmap(
+                &self.data,
+                self.max_width,
+                &ImageOptions {
+                    plane: ImagePlane::Red,
+                    ..self.options
+                },
+            )?),
+            _ => None,
+        };
 
         let (width, height) = processed_image.dimensions();
+        let rows = match (self.band_height, self.command_mode) {
+            (None, _) => height.max(1),
+            // Las franjas de ESC * ocupan 24 filas de origen en ambos modos
+            (Some(rows), ImageCommandMode::BitImage8 | ImageCommandMode::BitImage24) => {
+                (rows / 24 * 24).max(24)
+            }
+            (Some(rows), _) => rows.max(1),
+        };
 
-        // Convertir imagen a bytes
-        let image_data = ImageProcessor::image_to_bytes(&processed_image);
+        let mut bands: Vec<Vec<u8>> = (0..height.max(1))
+            .step_by(rows as usize)
+            .map(|top| {
+                let crop = |plane: &Bitmap| {
+                    imageops::crop_imm(plane, 0, top, width, rows.min(height.saturating_sub(top)))
+                        .to_image()
+                };
+                let band = crop(&processed_image);
+                match &red_plane {
+                    Some(red) => color_graphics_command(&[&band, &crop(red)], self.mode),
+                    None => self.command_mode.command(&band, self.mode),
+                }
+            })
+            .collect();
 
         // Establecer alineación
-        output.extend_from_slice(&[0x1B, 0x61, self.alignment.value()]);
+        bands[0].splice(0..0, [0x1B, 0x61, self.alignment.value()]);
+        // Restaurar alineación a la izquierda
+        if let Some(last) = bands.last_mut() {
+            last.extend_from_slice(&[0x1B, 0x61, 0x00]);
+        }
 
-        // Calcular ancho en bytes (múltiplo de 8)
-        let width_bytes = ((width + 7) / 8) as u16;
-        let x_l = (width_bytes & 0xFF) as u8;
-        let x_h = ((width_bytes >> 8) & 0xFF) as u8;
-        let y_l = (height & 0xFF) as u8;
-        let y_h = ((height >> 8) & 0xFF) as u8;
-
-        // Comando para imprimir imagen raster: GS v 0 m xL xH yL yH d1...dk
-        output.push(0x1D); // GS
-        output.push(0x76); // v
-        output.push(0x30); // 0 (ASCII '0', no 0x00)
-        output.push(self.mode.value()); // m
-        output.push(x_l);
-        output.push(x_h);
-        output.push(y_l);
-        output.push(y_h);
-        output.extend_from_slice(&image_data);
+        Ok(bands)
+    }
 
-        // Restaurar alineación a la izquierda
+    /// Imprime la imagen como gráfico descargado `key` (`GS ( L` fn 85), con la
+    /// alineación como `get_command`. Si `define` es `true` antes la guarda en
+    /// la memoria volátil (fn 83); las siguientes copias solo envían la clave.
+    pub fn get_download_command(
+        &self,
+        key: [u8; 2],
+        define: bool,
+    ) -> Result<Vec<u8>, SectionError> {
+        let mut output = Vec::new();
+        if define {
+            let processed_image = self
+                .assets
+                .bitmap(&self.data, self.max_width, &self.options)?;
+            output.extend(nv_graphics::define_download_command(&processed_image, key)?);
+        }
+        output.extend_from_slice(&[0x1B, 0x61, self.alignment.value()]);
+        output.extend(nv_graphics::print_download_command(key, self.mode));
         output.extend_from_slice(&[0x1B, 0x61, 0x00]);
-
         Ok(output)
     }
 }
 
+/// Comando de imagen raster `GS v 0 m xL xH yL yH d1...dk` para una imagen ya
+/// binarizada. No cambia la alineación.
+pub fn raster_command(binary: &ImageBuffer<Luma<u8>, Vec<u8>>, mode: ImageMode) -> Vec<u8> {
+    let (width, height) = binary.dimensions();
+
+    // Calcular ancho en bytes (múltiplo de 8)
+    let width_bytes = width.div_ceil(8) as u16;
+    let x_l = (width_bytes & 0xFF) as u8;
+    let x_h = ((width_bytes >> 8) & 0xFF) as u8;
+    let y_l = (height & 0xFF) as u8;
+    let y_h = ((height >> 8) & 0xFF) as u8;
+
+    let mut output = vec![
+        0x1D,         // GS
+        0x76,         // v
+        0x30,         // 0 (ASCII '0', no 0x00)
+        mode.value(), // m
+        x_l,
+        x_h,
+        y_l,
+        y_h,
+    ];
+    output.extend(ImageProcessor::image_to_bytes(binary));
+    output
+}
+
 /// Procesa sección Image del modelo de impresión
-pub fn process_section(imagen: &ImageSection, paper_width_pixels: i32) -> Result<Vec<u8>, String> {
+pub fn process_section(
+    imagen: &ImageSection,
+    paper_width_pixels: i32,
+    assets: &ImageAssets,
+) -> Result<Vec<u8>, SectionError> {
+    Ok(process_section_bands(imagen, paper_width_pixels, assets)?.concat())
+}
+
+/// Igual que `process_section` pero separado por franjas (`band_height`), para
+/// poder pausar el envío entre ellas
+pub fn process_section_bands(
+    imagen: &ImageSection,
+    paper_width_pixels: i32,
+    assets: &ImageAssets,
+) -> Result<Vec<Vec<u8>>, SectionError> {
+    if imagen.band_height == Some(0) {
+        return Err("Image band_height must be greater than 0".into());
+    }
+
+    let mut bands = from_section(imagen, paper_width_pixels, assets)?.get_bands()?;
+    if let Some(last) = bands.last_mut() {
+        last.extend_from_slice(b"\n");
+    }
+    Ok(bands)
+}
+
+/// Igual que `process_section` pero como gráfico descargado `key`, definiéndolo
+/// antes si `define` es `true` (ver `Image::get_download_command`)
+pub fn process_section_download(
+    imagen: &ImageSection,
+    paper_width_pixels: i32,
+    key: [u8; 2],
+    define: bool,
+    assets: &ImageAssets,
+) -> Result<Vec<u8>, SectionError> {
+    let mut output =
+        from_section(imagen, paper_width_pixels, assets)?.get_download_command(key, define)?;
+    output.extend_from_slice(b"\n");
+    Ok(output)
+}
+
+fn from_section(
+    imagen: &ImageSection,
+    paper_width_pixels: i32,
+    assets: &ImageAssets,
+) -> Result<Image, SectionError> {
     if imagen.data.is_empty() {
-        return Err("Image data cannot be empty".to_string());
+        return Err("Image data cannot be empty".into());
     }
 
     let alignment = match imagen.align.as_str() {
@@ -112,61 +264,43 @@ pub fn process_section(imagen: &ImageSection, paper_width_pixels: i32) -> Result
         imagen.max_width as u32
     };
 
-    let image = Image::new(&imagen.data, max_width)
+    let command_mode = ImageCommandMode::from_name(imagen.command_mode.as_deref())?;
+    let options = ImageOptions {
+        levels: command_mode.levels(),
+        plane: match command_mode {
+            ImageCommandMode::TwoColor => ImagePlane::Black,
+            _ => ImagePlane::Gray,
+        },
+        ..ImageOptions::from_section(imagen)?
+    };
+
+    Ok(Image::new(&imagen.data, max_width)
         .map_err(|e| format!("Failed to create image: {}", e))?
         .set_alignment(alignment)
         .set_mode(mode)
-        .set_use_dithering(imagen.dithering);
-
-    let mut cmd = image.get_command()?;
-    cmd.extend_from_slice(b"\n");
-    Ok(cmd)
+        .set_options(options)
+        .set_command_mode(command_mode)
+        .set_band_height(imagen.band_height)
+        .set_assets(assets.clone()))
 }
 
-impl Image {
-    // /// Método alternativo usando modo bit image (ESC *)
-    // /// Útil para impresoras más antiguas que no soportan GS v 0
-    // /// NOTA: Este método puede no ser compatible con todas las impresoras móviles
-    // pub fn get_command_bit_image(&self) -> Result<Vec<u8>, String> {
-    //     let mut output = Vec::new();
-
-    //     let processed_image = ImageProcessor::process_image(
-    //         &self.base64_image,
-    //         self.max_width,
-    //         self.use_dithering
-    //     )?;
-
-    //     let (width, height) = processed_image.dimensions();
-    //     let width_bytes = ((width + 7) / 8) as usize;
-    //     let image_data = ImageProcessor::image_to_bytes(&processed_image);
-
-    //     // Establecer alineación
-    //     output.extend_from_slice(&[0x1B, 0x61, self.alignment.value()]);
-
-    //     // Imprimir línea por línea usando ESC * (Select bit-image mode)
-    //     for y in 0..height {
-    //         // ESC * m nL nH d1...dk
-    //         output.push(0x1B); // ESC
-    //         output.push(0x2A); // *
-    //         output.push(33);   // m = 33 (24-dot double-density)
-
-    //         let n_l = (width & 0xFF) as u8;
-    //         let n_h = ((width >> 8) & 0xFF) as u8;
-    //         output.push(n_l);
-    //         output.push(n_h);
-
-    //         // Escribir datos de la línea
-    //         let start_index = (y as usize) * width_bytes;
-    //         let end_index = (start_index + width_bytes).min(image_data.len());
-    //         output.extend_from_slice(&image_data[start_index..end_index]);
-
-    //         // Nueva línea
-    //         output.push(0x0A); // LF
-    //     }
-
-    //     // Restaurar alineación
-    //     output.extend_from_slice(&[0x1B, 0x61, 0x00]);
-
-    //     Ok(output)
-    // }
+/// La sección tal como la puede imprimir `profile`: sin gráficos de tonos
+/// múltiples o de dos colores, una imagen `multi_tone` o `two_color` se trama en
+/// blanco y negro y se envía con el comando por defecto (`GS v 0`).
+pub fn for_profile<'a>(
+    imagen: &'a ImageSection,
+    profile: &PrinterProfile,
+) -> Cow<'a, ImageSection> {
+    let supported = match imagen.command_mode.as_deref() {
+        Some("multi_tone") => profile.multi_tone,
+        Some("two_color") => profile.two_color,
+        _ => true,
+    };
+    if supported {
+        return Cow::Borrowed(imagen);
+    }
+    let mut fallback = imagen.clone();
+    fallback.command_mode = None;
+    fallback.dithering = true;
+    Cow::Owned(fallback)
 }
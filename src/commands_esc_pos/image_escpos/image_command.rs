@@ -0,0 +1,232 @@
+//! Comandos ESC/POS para enviar una imagen ya binarizada: raster `GS v 0`,
+//! imagen de bits por columnas `ESC *` (8 o 24 puntos), gráficos `GS ( L` o
+//! gráficos de tonos múltiples o de dos colores. Ninguno cambia la alineación.
+
+use super::image_code::raster_command;
+use super::image_mode::ImageMode;
+use super::image_processor::ImageProcessor;
+use crate::error::SectionError;
+use image::{ImageBuffer, Luma};
+
+/// Interlineado (en unidades de movimiento vertical) que avanza exactamente una
+/// franja de `ESC *`: 24 puntos en modo 24, u 8 puntos de triple altura en modo 8.
+const BAND_LINE_SPACING: u8 = 24;
+
+/// Tonos de los gráficos de tonos múltiples (dos planos de bits)
+pub const MULTI_TONE_LEVELS: u8 = 4;
+
+/// Comando usado para enviar la imagen a la impresora
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageCommandMode {
+    /// `GS v 0` (por defecto)
+    Raster,
+    /// `ESC * 1`: franjas de 8 puntos, densidad vertical baja
+    BitImage8,
+    /// `ESC * 33`: franjas de 24 puntos
+    BitImage24,
+    /// `GS ( L` fn 112 + fn 50: gráfico en el buffer de impresión
+    Graphics,
+    /// `GS ( L` fn 112 con tonos múltiples (`a` = 52) + fn 50
+    MultiTone,
+    /// `GS ( L` fn 112 con un plano negro (color 1) y otro rojo (color 2) + fn 50
+    TwoColor,
+}
+
+impl ImageCommandMode {
+    /// Convierte el nombre usado en el modelo (`raster` si no se indica)
+    pub fn from_name(name: Option<&str>) -> Result<Self, SectionError> {
+        match name.unwrap_or("raster") {
+            "raster" => Ok(ImageCommandMode::Raster),
+            "bit_image_8" => Ok(ImageCommandMode::BitImage8),
+            "bit_image_24" => Ok(ImageCommandMode::BitImage24),
+            "graphics" => Ok(ImageCommandMode::Graphics),
+            "multi_tone" => Ok(ImageCommandMode::MultiTone),
+            "two_color" => Ok(ImageCommandMode::TwoColor),
+            other => Err(format!(
+                "Unknown image command mode '{}' (expected raster, bit_image_8, bit_image_24, graphics, multi_tone or two_color)",
+                other
+            )
+            .into()),
+        }
+    }
+
+    /// Tonos que necesita la imagen procesada para este comando
+    pub fn levels(self) -> u8 {
+        match self {
+            ImageCommandMode::MultiTone => MULTI_TONE_LEVELS,
+            _ => 2,
+        }
+    }
+
+    /// Comando completo para una imagen binarizada (o reducida a `levels` tonos).
+    /// En `TwoColor` es el plano negro; `Image` añade el rojo con
+    /// [`color_graphics_command`].
+    pub fn command(self, binary: &ImageBuffer<Luma<u8>, Vec<u8>>, mode: ImageMode) -> Vec<u8> {
+        match self {
+            ImageCommandMode::Raster => raster_command(binary, mode),
+            ImageCommandMode::BitImage8 => bit_image_command(binary, mode, 8),
+            ImageCommandMode::BitImage24 => bit_image_command(binary, mode, 24),
+            ImageCommandMode::Graphics | ImageCommandMode::TwoColor => {
+                graphics_command(binary, mode)
+            }
+            ImageCommandMode::MultiTone => multi_tone_command(binary, mode, MULTI_TONE_LEVELS),
+        }
+    }
+}
+
+/// Escalas horizontal y vertical (1 o 2) de un modo de impresión
+pub fn mode_scale(mode: ImageMode) -> (u8, u8) {
+    match mode {
+        ImageMode::Normal => (1, 1),
+        ImageMode::DoubleWidth => (2, 1),
+        ImageMode::DoubleHeight => (1, 2),
+        ImageMode::Quadruple => (2, 2),
+    }
+}
+
+/// Duplica píxeles para reproducir el modo de impresión en comandos sin escala
+fn scale(
+    binary: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    mode: ImageMode,
+) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+    let (sx, sy) = mode_scale(mode);
+    if (sx, sy) == (1, 1) {
+        return binary.clone();
+    }
+    let (sx, sy) = (sx as u32, sy as u32);
+    ImageBuffer::from_fn(binary.width() * sx, binary.height() * sy, |x, y| {
+        *binary.get_pixel(x / sx, y / sy)
+    })
+}
+
+/// En modo de 8 puntos cada punto mide tres puntos de alto: se agrupan las filas
+/// de tres en tres (negro si alguna lo es) para conservar las proporciones.
+fn compress_rows(binary: &ImageBuffer<Luma<u8>, Vec<u8>>) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+    let (width, height) = binary.dimensions();
+    ImageBuffer::from_fn(width, height.div_ceil(3), |x, y| {
+        let black = (y * 3..(y * 3 + 3).min(height))
+            .any(|row| binary.get_pixel(x, row)[0] < ImageProcessor::THRESHOLD);
+        Luma([if black { 0 } else { 255 }])
+    })
+}
+
+/// `ESC * m nL nH d1...dk` por franjas de `dots` filas (8 o 24), cada una seguida
+/// de LF. El interlineado se fija a la altura de la franja para que no queden
+/// líneas blancas entre ellas y se restaura al valor por defecto (`ESC 2`) al final.
+pub fn bit_image_command(
+    binary: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    mode: ImageMode,
+    dots: u32,
+) -> Vec<u8> {
+    let mut binary = scale(binary, mode);
+    if dots == 8 {
+        binary = compress_rows(&binary);
+    }
+    let (width, height) = binary.dimensions();
+    let m = if dots == 24 { 33 } else { 1 };
+
+    let mut output = vec![0x1B, 0x33, BAND_LINE_SPACING]; // ESC 3 n
+    for top in (0..height).step_by(dots as usize) {
+        output.extend_from_slice(&[0x1B, 0x2A, m, (width & 0xFF) as u8, (width >> 8) as u8]);
+        output.extend(ImageProcessor::column_bytes(&binary, top, dots / 8));
+        output.push(0x0A); // LF
+    }
+    output.extend_from_slice(&[0x1B, 0x32]); // ESC 2
+    output
+}
+
+/// Cabecera `GS ( L pL pH m fn` o, si los parámetros no caben en dos bytes,
+/// `GS 8 L p1 p2 p3 p4 m fn`. `len` cuenta los bytes que siguen a `fn`.
+pub fn graphics_header(function: u8, len: usize) -> Vec<u8> {
+    let p = len + 2;
+    if p <= 0xFFFF {
+        vec![
+            0x1D, // GS
+            0x28, // (
+            0x4C, // L
+            (p & 0xFF) as u8,
+            (p >> 8) as u8,
+            0x30, // m
+            function,
+        ]
+    } else {
+        let mut header = vec![0x1D, 0x38, 0x4C];
+        header.extend_from_slice(&(p as u32).to_le_bytes());
+        header.extend_from_slice(&[0x30, function]);
+        header
+    }
+}
+
+/// `GS ( L` fn 112: guarda la imagen en el buffer de impresión (monocromo,
+/// color 1) con la escala del modo; fn 50 la imprime.
+pub fn graphics_command(binary: &ImageBuffer<Luma<u8>, Vec<u8>>, mode: ImageMode) -> Vec<u8> {
+    color_graphics_command(&[binary], mode)
+}
+
+/// `GS ( L` fn 112 una vez por plano (color 1, 2, ...: en una impresora de dos
+/// colores, negro y rojo) y fn 50 para imprimirlos juntos. Los planos deben
+/// tener el mismo tamaño.
+pub fn color_graphics_command(
+    planes: &[&ImageBuffer<Luma<u8>, Vec<u8>>],
+    mode: ImageMode,
+) -> Vec<u8> {
+    let (bx, by) = mode_scale(mode);
+
+    let mut output = Vec::new();
+    for (color, binary) in planes.iter().enumerate() {
+        let (width, height) = binary.dimensions();
+        let data = ImageProcessor::image_to_bytes(binary);
+        output.extend(graphics_header(0x70, 8 + data.len()));
+        output.extend_from_slice(&[
+            0x30, // a: monocromo
+            bx,
+            by,
+            0x31 + color as u8, // c: color 1, 2, ...
+            (width & 0xFF) as u8,
+            (width >> 8) as u8,
+            (height & 0xFF) as u8,
+            (height >> 8) as u8,
+        ]);
+        output.extend(data);
+    }
+    output.extend(graphics_header(0x32, 0)); // fn 50: imprimir
+    output
+}
+
+/// `GS ( L` fn 112 con `a` = 52 (tonos múltiples): la imagen de `levels` tonos se
+/// envía como un plano de bits por color (`c` = 49 lleva el bit más
+/// significativo del tono, 0 = blanco) y fn 50 la imprime.
+pub fn multi_tone_command(
+    tones: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    mode: ImageMode,
+    levels: u8,
+) -> Vec<u8> {
+    let (width, height) = tones.dimensions();
+    let (bx, by) = mode_scale(mode);
+    let steps = levels.max(2) as u32 - 1;
+    let planes = 32 - steps.leading_zeros();
+
+    let mut output = Vec::new();
+    for plane in 0..planes {
+        let bit = planes - 1 - plane;
+        let binary = ImageBuffer::from_fn(width, height, |x, y| {
+            let tone = ((255 - tones.get_pixel(x, y)[0] as u32) * steps + 127) / 255;
+            Luma([if tone >> bit & 1 == 1 { 0u8 } else { 255 }])
+        });
+        let data = ImageProcessor::image_to_bytes(&binary);
+        output.extend(graphics_header(0x70, 8 + data.len()));
+        output.extend_from_slice(&[
+            0x34, // a: tonos múltiples
+            bx,
+            by,
+            0x31 + plane as u8, // c: color 1, 2, ...
+            (width & 0xFF) as u8,
+            (width >> 8) as u8,
+            (height & 0xFF) as u8,
+            (height >> 8) as u8,
+        ]);
+        output.extend(data);
+    }
+    output.extend(graphics_header(0x32, 0)); // fn 50: imprimir
+    output
+}
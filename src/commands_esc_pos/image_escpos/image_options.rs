@@ -0,0 +1,182 @@
+//! Ajustes de imagen previos a la binarización (brillo, contraste, gamma,
+//! enfoque e inversión), algoritmo de tramado y transformaciones geométricas de
+//! una sección Image.
+
+use super::dithering::{DitherAlgorithm, DEFAULT_THRESHOLD};
+use super::image_transform::ImageTransform;
+use crate::error::SectionError;
+use crate::models::print_sections::Image as ImageSection;
+use image::{ImageBuffer, Luma};
+
+/// Qué parte de la imagen en color se pasa a escala de grises
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImagePlane {
+    /// Luminosidad de todos los colores (impresoras de un color)
+    #[default]
+    Gray,
+    /// Plano negro de una impresora de dos colores: los tonos rojos cuentan
+    /// como blanco
+    Black,
+    /// Plano rojo: solo los tonos rojos
+    Red,
+}
+
+/// Cómo se convierte la imagen en escala de grises a blanco y negro
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageOptions {
+    pub algorithm: DitherAlgorithm,
+    /// Punto de corte 0-255 (por defecto 127)
+    pub threshold: u8,
+    /// -100 (negro) a 100 (blanco)
+    pub brightness: i32,
+    /// -100 (gris plano) a 100 (el doble de contraste)
+    pub contrast: i32,
+    /// > 1 aclara los tonos medios, < 1 los oscurece
+    pub gamma: f32,
+    /// Intensidad de la máscara de enfoque (0 = sin enfoque)
+    pub sharpen: f32,
+    pub invert: bool,
+    /// Recorte, giro, márgenes y relleno
+    pub transform: ImageTransform,
+    /// Tonos del resultado: 2 (blanco y negro) o más para los gráficos de tonos
+    /// múltiples
+    pub levels: u8,
+    /// Plano de color que se imprime
+    pub plane: ImagePlane,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self::dithered(true)
+    }
+}
+
+impl ImageOptions {
+    /// Sin ajustes: Floyd-Steinberg o umbral simple
+    pub fn dithered(use_dithering: bool) -> Self {
+        Self {
+            algorithm: if use_dithering {
+                DitherAlgorithm::FloydSteinberg
+            } else {
+                DitherAlgorithm::Threshold
+            },
+            threshold: DEFAULT_THRESHOLD,
+            brightness: 0,
+            contrast: 0,
+            gamma: 1.0,
+            sharpen: 0.0,
+            invert: false,
+            transform: ImageTransform::default(),
+            levels: 2,
+            plane: ImagePlane::Gray,
+        }
+    }
+
+    /// Lee y valida los ajustes de la sección
+    pub fn from_section(image: &ImageSection) -> Result<Self, SectionError> {
+        let percent = |value: Option<f32>, name: &str| -> Result<i32, SectionError> {
+            let value = value.unwrap_or(0.0);
+            if !(-100.0..=100.0).contains(&value) {
+                return Err(format!("Image {} must be between -100 and 100", name).into());
+            }
+            Ok(value.round() as i32)
+        };
+
+        let gamma = image.gamma.unwrap_or(1.0);
+        if !(0.1..=10.0).contains(&gamma) {
+            return Err("Image gamma must be between 0.1 and 10".into());
+        }
+        let sharpen = image.sharpen.unwrap_or(0.0);
+        if !(0.0..=10.0).contains(&sharpen) {
+            return Err("Image sharpen must be between 0 and 10".into());
+        }
+
+        Ok(Self {
+            algorithm: DitherAlgorithm::from_name(
+                image.dithering_algorithm.as_deref(),
+                image.dithering,
+            )?,
+            threshold: image.threshold.unwrap_or(DEFAULT_THRESHOLD),
+            brightness: percent(image.brightness, "brightness")?,
+            contrast: percent(image.contrast, "contrast")?,
+            gamma,
+            sharpen,
+            invert: image.invert.unwrap_or(false),
+            transform: ImageTransform::from_section(image)?,
+            levels: 2,
+            plane: ImagePlane::Gray,
+        })
+    }
+
+    /// Aplica los ajustes y binariza (0 = negro, 255 = blanco), o reduce a
+    /// `levels` tonos si son más de dos
+    pub fn binarize(
+        &self,
+        grayscale: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    ) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+        let adjusted = self.adjust(grayscale);
+        if self.levels > 2 {
+            return self.algorithm.apply_levels(&adjusted, self.levels);
+        }
+        self.algorithm.apply(&adjusted, self.threshold)
+    }
+
+    /// Brillo, contraste y gamma (una tabla de 256 valores), enfoque e inversión
+    pub fn adjust(
+        &self,
+        grayscale: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    ) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+        let lut = self.tone_curve();
+        let mut adjusted = grayscale.clone();
+        for pixel in adjusted.pixels_mut() {
+            pixel[0] = lut[pixel[0] as usize];
+        }
+        if self.sharpen > 0.0 {
+            adjusted = sharpen(&adjusted, self.sharpen);
+        }
+        if self.invert {
+            for pixel in adjusted.pixels_mut() {
+                pixel[0] = 255 - pixel[0];
+            }
+        }
+        adjusted
+    }
+
+    fn tone_curve(&self) -> [u8; 256] {
+        let mut lut = [0u8; 256];
+        for (value, slot) in lut.iter_mut().enumerate() {
+            let mut v = value as i32 + self.brightness * 255 / 100;
+            v = (v - 128) * (100 + self.contrast) / 100 + 128;
+            let v = v.clamp(0, 255);
+            *slot = if self.gamma == 1.0 {
+                v as u8
+            } else {
+                // Redondeo a entero: la tabla es la misma en cualquier plataforma
+                (255.0 * (v as f64 / 255.0).powf(1.0 / self.gamma as f64)).round() as u8
+            };
+        }
+        lut
+    }
+}
+
+/// Máscara de enfoque: `v + amount · (v - media 3×3)`, en aritmética entera
+fn sharpen(
+    grayscale: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    amount: f32,
+) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+    let (width, height) = grayscale.dimensions();
+    let amount = (amount * 256.0).round() as i32;
+    ImageBuffer::from_fn(width, height, |x, y| {
+        let mut sum = 0;
+        for dy in -1i32..=1 {
+            for dx in -1i32..=1 {
+                let nx = (x as i32 + dx).clamp(0, width as i32 - 1) as u32;
+                let ny = (y as i32 + dy).clamp(0, height as i32 - 1) as u32;
+                sum += grayscale.get_pixel(nx, ny)[0] as i32;
+            }
+        }
+        let value = grayscale.get_pixel(x, y)[0] as i32;
+        let sharpened = value + amount * (value - sum / 9) / 256;
+        Luma([sharpened.clamp(0, 255) as u8])
+    })
+}
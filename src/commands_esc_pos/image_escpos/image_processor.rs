@@ -1,13 +1,18 @@
+use super::dithering::DitherAlgorithm;
+use super::image_options::{ImageOptions, ImagePlane};
+use super::image_transform::ImageTransform;
+use crate::error::SectionError;
 use base64::{engine::general_purpose, Engine as _};
 use exif::{In, Reader, Tag};
-use image::{DynamicImage, GenericImageView, ImageBuffer, Luma, Pixel};
+use image::{DynamicImage, GenericImageView, ImageBuffer, Luma, Pixel, RgbaImage};
+use resvg::{tiny_skia, usvg};
 use std::io::Cursor;
 
 /// Procesador de imágenes para impresoras térmicas
 pub struct ImageProcessor;
 
 impl ImageProcessor {
-    const THRESHOLD: u8 = 127; // Umbral para convertir a blanco y negro
+    pub const THRESHOLD: u8 = 127; // Umbral para convertir a blanco y negro
 
     fn apply_exif_orientation(img: DynamicImage, image_bytes: &[u8]) -> DynamicImage {
         let mut cursor = Cursor::new(image_bytes);
@@ -29,24 +34,81 @@ impl ImageProcessor {
         }
     }
 
-    /// Convierte una imagen base64 a DynamicImage
-    pub fn base64_to_image(base64_string: &str) -> Result<DynamicImage, String> {
-        // Remover el prefijo data:image si existe
-        let image_data = if base64_string.contains(',') {
+    /// Decodifica base64, quitando el prefijo `data:...;base64,` si existe
+    pub fn decode_base64(base64_string: &str) -> Result<Vec<u8>, base64::DecodeError> {
+        let data = if base64_string.contains(',') {
             base64_string.split(',').nth(1).unwrap_or(base64_string)
         } else {
             base64_string
         };
+        general_purpose::STANDARD.decode(data)
+    }
 
-        // Decodificar base64
-        let image_bytes = general_purpose::STANDARD
-            .decode(image_data)
-            .map_err(|e| format!("Error decoding base64: {}", e))?;
+    /// Decodifica un archivo de imagen (PNG, JPEG, ...) y corrige la orientación
+    /// EXIF cuando está disponible. Los SVG se rasterizan a su tamaño propio.
+    pub fn decode_image(image_bytes: &[u8]) -> Result<DynamicImage, SectionError> {
+        if Self::is_svg(image_bytes) {
+            return Self::render_svg(&Self::parse_svg(image_bytes)?, 1.0);
+        }
+        image::load_from_memory(image_bytes)
+            .map(|img| Self::apply_exif_orientation(img, image_bytes))
+            .map_err(|e| SectionError::ImageDecode(format!("Error loading image: {}", e)))
+    }
 
-        // Cargar imagen y corregir orientación EXIF cuando esté disponible.
-        image::load_from_memory(&image_bytes)
-            .map(|img| Self::apply_exif_orientation(img, &image_bytes))
-            .map_err(|e| format!("Error loading image: {}", e))
+    /// Decodifica la imagen, aplica `transform` (recorte, giro, márgenes) y la
+    /// ajusta a `max_width`. Los SVG se rasterizan directamente a la escala que
+    /// deja el resultado en ese ancho en lugar de escalar un mapa de bits, para
+    /// que los bordes queden nítidos.
+    pub fn decode_image_at(
+        image_bytes: &[u8],
+        max_width: u32,
+        transform: &ImageTransform,
+    ) -> Result<DynamicImage, SectionError> {
+        let transformed = if Self::is_svg(image_bytes) {
+            let tree = Self::parse_svg(image_bytes)?;
+            let size = tree.size();
+            let (width, _) = transform.oriented_size(size.width(), size.height());
+            let scale = max_width as f32 / width;
+            transform.apply(Self::render_svg(&tree, scale)?, scale)?
+        } else {
+            transform.apply(Self::decode_image(image_bytes)?, 1.0)?
+        };
+        Ok(Self::resize_image(&transformed, max_width))
+    }
+
+    /// Indica si el archivo es un SVG (documento XML cuyo elemento raíz es `<svg>`)
+    pub fn is_svg(image_bytes: &[u8]) -> bool {
+        let head = &image_bytes[..image_bytes.len().min(1024)];
+        let head = String::from_utf8_lossy(head);
+        let head = head.trim_start_matches('\u{feff}').trim_start();
+        head.starts_with('<') && head.contains("<svg")
+    }
+
+    fn parse_svg(image_bytes: &[u8]) -> Result<usvg::Tree, SectionError> {
+        usvg::Tree::from_data(image_bytes, &usvg::Options::default())
+            .map_err(|e| SectionError::ImageDecode(format!("Error loading SVG: {}", e)))
+    }
+
+    /// Rasteriza un SVG sobre fondo blanco a `scale` veces su tamaño propio
+    fn render_svg(tree: &usvg::Tree, scale: f32) -> Result<DynamicImage, SectionError> {
+        let size = tree.size();
+        let width = (size.width() * scale).round().max(1.0) as u32;
+        let height = (size.height() * scale).round().max(1.0) as u32;
+
+        let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or_else(|| {
+            SectionError::ImageDecode(format!("Invalid SVG size {}x{}", width, height))
+        })?;
+        pixmap.fill(tiny_skia::Color::WHITE);
+        resvg::render(
+            tree,
+            tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+
+        // El fondo es opaco, así que los píxeles premultiplicados ya son RGBA normal
+        let rgba = RgbaImage::from_raw(width, height, pixmap.take())
+            .expect("pixmap buffer matches its size");
+        Ok(DynamicImage::ImageRgba8(rgba))
     }
 
     /// Redimensiona la imagen manteniendo la relación de aspecto
@@ -122,73 +184,34 @@ impl ImageProcessor {
         grayscale
     }
 
-    /// Convierte la imagen a blanco y negro usando dithering Floyd-Steinberg
-    pub fn to_binary_with_dithering(
-        grayscale: &ImageBuffer<Luma<u8>, Vec<u8>>,
-    ) -> ImageBuffer<Luma<u8>, Vec<u8>> {
-        let (width, height) = grayscale.dimensions();
-        let mut pixels: Vec<Vec<i32>> = vec![vec![0; width as usize]; height as usize];
-
-        // Copiar valores de píxeles
-        for y in 0..height {
-            for x in 0..width {
-                let pixel = grayscale.get_pixel(x, y)[0];
-                pixels[y as usize][x as usize] = pixel as i32;
+    /// Escala de grises de un plano de una impresora de dos colores. El rojo de un
+    /// píxel es lo que `r` supera al mayor de `g` y `b`: en el plano rojo es la
+    /// tinta, y en el negro se suma a la luminosidad para que el rojo puro quede en
+    /// blanco. La transparencia cuenta como blanco en ambos.
+    pub fn to_plane(img: &DynamicImage, plane: ImagePlane) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+        let rgba = img.to_rgba8();
+        ImageBuffer::from_fn(rgba.width(), rgba.height(), |x, y| {
+            let [r, g, b, a] = rgba.get_pixel(x, y).0;
+            if a < 128 {
+                return Luma([255u8]);
             }
-        }
-
-        let mut binary = ImageBuffer::new(width, height);
-
-        // Aplicar Floyd-Steinberg dithering
-        for y in 0..height {
-            for x in 0..width {
-                let old_pixel = pixels[y as usize][x as usize];
-                let new_pixel = if old_pixel < Self::THRESHOLD as i32 {
-                    0
-                } else {
-                    255
-                };
-
-                binary.put_pixel(x, y, Luma([new_pixel as u8]));
-
-                let error = old_pixel - new_pixel;
-
-                // Distribuir el error a píxeles vecinos
-                if x + 1 < width {
-                    pixels[y as usize][(x + 1) as usize] += error * 7 / 16;
+            let red = r.saturating_sub(g.max(b));
+            let gray = match plane {
+                ImagePlane::Red => 255 - red,
+                _ => {
+                    let luma = (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32) as u8;
+                    luma.saturating_add(red)
                 }
-                if y + 1 < height {
-                    if x > 0 {
-                        pixels[(y + 1) as usize][(x - 1) as usize] += error * 3 / 16;
-                    }
-                    pixels[(y + 1) as usize][x as usize] += error * 5 / 16;
-                    if x + 1 < width {
-                        pixels[(y + 1) as usize][(x + 1) as usize] += error / 16;
-                    }
-                }
-            }
-        }
-
-        binary
+            };
+            Luma([gray])
+        })
     }
 
     /// Convierte la imagen a blanco y negro sin dithering (umbral simple)
     pub fn to_binary_simple(
         grayscale: &ImageBuffer<Luma<u8>, Vec<u8>>,
     ) -> ImageBuffer<Luma<u8>, Vec<u8>> {
-        let (width, height) = grayscale.dimensions();
-        let mut binary = ImageBuffer::new(width, height);
-
-        for y in 0..height {
-            for x in 0..width {
-                let pixel = grayscale.get_pixel(x, y)[0];
-                // Umbral: valores oscuros (< 127) = negro (0), valores claros (>= 127) = blanco (255)
-                let binary_value = if pixel < Self::THRESHOLD { 0 } else { 255 };
-                binary.put_pixel(x, y, Luma([binary_value]));
-            }
-        }
-
-        binary
+        DitherAlgorithm::Threshold.apply(grayscale, Self::THRESHOLD)
     }
 
     /// Convierte la imagen binaria a bytes para ESC/POS
@@ -220,22 +243,63 @@ impl ImageProcessor {
         image_data
     }
 
-    /// Procesa una imagen base64 completa: resize, grayscale, binary
+    /// Convierte la imagen binaria a formato de columnas: para cada columna, de
+    /// izquierda a derecha, `band_bytes` bytes verticales a partir de la fila `top`
+    /// (8 puntos por byte, MSB = punto superior). Las filas fuera de la imagen van
+    /// en blanco.
+    pub fn column_bytes(
+        binary: &ImageBuffer<Luma<u8>, Vec<u8>>,
+        top: u32,
+        band_bytes: u32,
+    ) -> Vec<u8> {
+        let (width, height) = binary.dimensions();
+        let mut data = Vec::with_capacity((width * band_bytes) as usize);
+        for x in 0..width {
+            for band in 0..band_bytes {
+                let mut byte = 0u8;
+                for bit in 0..8 {
+                    let y = top + band * 8 + bit;
+                    if y < height && binary.get_pixel(x, y)[0] < Self::THRESHOLD {
+                        byte |= 1 << (7 - bit);
+                    }
+                }
+                data.push(byte);
+            }
+        }
+        data
+    }
+
+    /// Construye una imagen binaria a partir de una matriz de módulos (`true` = negro),
+    /// escalando cada módulo a `module_width` × `module_height` puntos. Lo usan los
+    /// códigos renderizados por software.
+    pub fn modules_to_image(
+        modules: &[bool],
+        columns: u32,
+        module_width: u32,
+        module_height: u32,
+    ) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+        let rows = (modules.len() as u32).checked_div(columns).unwrap_or(0);
+        ImageBuffer::from_fn(columns * module_width, rows * module_height, |x, y| {
+            let index = (y / module_height * columns + x / module_width) as usize;
+            if modules[index] {
+                Luma([0])
+            } else {
+                Luma([255])
+            }
+        })
+    }
+
+    /// Procesa una imagen completa: resize, grayscale, ajustes y binarización
     pub fn process_image(
-        base64_image: &str,
+        original: &DynamicImage,
         max_width: u32,
-        use_dithering: bool,
-    ) -> Result<ImageBuffer<Luma<u8>, Vec<u8>>, String> {
-        let original = Self::base64_to_image(base64_image)?;
-        let resized = Self::resize_image(&original, max_width);
-        let grayscale = Self::to_grayscale(&resized);
-
-        let binary = if use_dithering {
-            Self::to_binary_with_dithering(&grayscale)
-        } else {
-            Self::to_binary_simple(&grayscale)
+        options: &ImageOptions,
+    ) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+        let resized = Self::resize_image(original, max_width);
+        let grayscale = match options.plane {
+            ImagePlane::Gray => Self::to_grayscale(&resized),
+            plane => Self::to_plane(&resized, plane),
         };
-
-        Ok(binary)
+        options.binarize(&grayscale)
     }
 }
@@ -0,0 +1,156 @@
+//! Transformaciones geométricas de una sección Image: recorte, giro, recorte
+//! automático de márgenes blancos y relleno alrededor de la imagen impresa.
+
+use super::image_processor::ImageProcessor;
+use crate::error::SectionError;
+use crate::models::print_sections::{Image as ImageSection, ImageCrop, ImagePadding};
+use image::{imageops, DynamicImage, GenericImageView, ImageBuffer, Luma};
+
+/// Gris a partir del cual un píxel cuenta como margen blanco en `trim`
+const TRIM_WHITE: u8 = 240;
+
+/// Recorte y giro (antes de redimensionar) y relleno (después de binarizar)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImageTransform {
+    pub crop: Option<ImageCrop>,
+    /// Grados en sentido horario: 0, 90, 180 o 270
+    pub rotate: u16,
+    pub trim: bool,
+    pub padding: ImagePadding,
+}
+
+impl ImageTransform {
+    /// Lee y valida las transformaciones de la sección
+    pub fn from_section(image: &ImageSection) -> Result<Self, SectionError> {
+        let rotate = image.rotate.unwrap_or(0);
+        if !matches!(rotate, 0 | 90 | 180 | 270) {
+            return Err(format!("Image rotate must be 0, 90, 180 or 270 (got {})", rotate).into());
+        }
+        if let Some(crop) = image.crop {
+            if crop.width == 0 || crop.height == 0 {
+                return Err("Image crop width and height must be greater than 0".into());
+            }
+        }
+        Ok(Self {
+            crop: image.crop,
+            rotate,
+            trim: image.trim.unwrap_or(false),
+            padding: image.padding.unwrap_or_default(),
+        })
+    }
+
+    /// Ancho que le queda a la imagen dentro de `max_width` quitando el relleno
+    /// izquierdo y derecho
+    pub fn content_width(&self, max_width: u32) -> Result<u32, SectionError> {
+        let horizontal = self.padding.left.saturating_add(self.padding.right);
+        match max_width.checked_sub(horizontal) {
+            Some(width) if width > 0 => Ok(width),
+            _ => Err(format!(
+                "Image padding ({} dots left and right) leaves no room in {} dots",
+                horizontal, max_width
+            )
+            .into()),
+        }
+    }
+
+    /// Tamaño de una imagen de `width`×`height` después de recortarla y girarla
+    /// (sin contar `trim`)
+    pub fn oriented_size(&self, width: f32, height: f32) -> (f32, f32) {
+        let (width, height) = match self.crop {
+            Some(crop) => (crop.width as f32, crop.height as f32),
+            None => (width, height),
+        };
+        if self.rotate % 180 == 90 {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Recorta, gira y quita los márgenes blancos. `scale` pasa las coordenadas
+    /// de `crop` (píxeles de la imagen original) a las de `image`, para los SVG
+    /// rasterizados a otro tamaño.
+    pub fn apply(&self, image: DynamicImage, scale: f32) -> Result<DynamicImage, SectionError> {
+        let mut image = match self.crop {
+            Some(crop) => crop_image(&image, crop, scale)?,
+            None => image,
+        };
+        image = match self.rotate {
+            90 => image.rotate90(),
+            180 => image.rotate180(),
+            270 => image.rotate270(),
+            _ => image,
+        };
+        if self.trim {
+            image = trim(image);
+        }
+        Ok(image)
+    }
+
+    /// Rodea la imagen binarizada con el relleno en blanco
+    pub fn pad(&self, bitmap: ImageBuffer<Luma<u8>, Vec<u8>>) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+        let padding = self.padding;
+        if padding == ImagePadding::default() {
+            return bitmap;
+        }
+        let (width, height) = bitmap.dimensions();
+        let mut padded = ImageBuffer::from_pixel(
+            width + padding.left + padding.right,
+            height + padding.top + padding.bottom,
+            Luma([255u8]),
+        );
+        imageops::replace(
+            &mut padded,
+            &bitmap,
+            padding.left as i64,
+            padding.top as i64,
+        );
+        padded
+    }
+}
+
+fn crop_image(
+    image: &DynamicImage,
+    crop: ImageCrop,
+    scale: f32,
+) -> Result<DynamicImage, SectionError> {
+    let (width, height) = image.dimensions();
+    let scaled = |value: u32| (value as f32 * scale).round() as u32;
+    let (left, top) = (scaled(crop.x), scaled(crop.y));
+    let right = scaled(crop.x.saturating_add(crop.width));
+    let bottom = scaled(crop.y.saturating_add(crop.height));
+    if right > width || bottom > height || right <= left || bottom <= top {
+        return Err(format!(
+            "Image crop {}x{} at ({}, {}) is outside the {}x{} image",
+            crop.width,
+            crop.height,
+            crop.x,
+            crop.y,
+            (width as f32 / scale).round(),
+            (height as f32 / scale).round()
+        )
+        .into());
+    }
+    Ok(image.crop_imm(left, top, right - left, bottom - top))
+}
+
+/// Quita las filas y columnas exteriores blancas o transparentes. Una imagen
+/// completamente blanca se deja como está.
+fn trim(image: DynamicImage) -> DynamicImage {
+    let grayscale = ImageProcessor::to_grayscale(&image);
+    let ink = grayscale
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel[0] < TRIM_WHITE);
+    let bounds = ink.fold(None, |bounds, (x, y, _)| match bounds {
+        None => Some((x, y, x, y)),
+        Some((left, top, right, bottom)) => {
+            Some((left.min(x), top.min(y), right.max(x), bottom.max(y)))
+        }
+    });
+    match bounds {
+        Some((left, top, right, bottom)) => {
+            image.crop_imm(left, top, right - left + 1, bottom - top + 1)
+        }
+        None => image,
+    }
+}
@@ -1,13 +1,42 @@
 use super::image_mode::ImageMode;
 use super::image_processor::ImageProcessor;
+use super::nv_graphics;
+use crate::error::SectionError;
 use crate::models::print_sections::Logo as LogoSection;
+use crate::process::image_assets::ImageAssets;
+
+/// Comandos usados para guardar e imprimir el logo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogoCommandMode {
+    /// `FS q` / `FS p` (por defecto)
+    NvBitImage,
+    /// `GS ( L` fn 67 / fn 69: gráficos NV con clave de dos caracteres
+    Graphics,
+}
+
+impl LogoCommandMode {
+    /// Convierte el nombre usado en el modelo (`nv_bit_image` si no se indica)
+    pub fn from_name(name: Option<&str>) -> Result<Self, SectionError> {
+        match name.unwrap_or("nv_bit_image") {
+            "nv_bit_image" => Ok(LogoCommandMode::NvBitImage),
+            "graphics" => Ok(LogoCommandMode::Graphics),
+            other => Err(format!(
+                "Unknown logo command mode '{}' (expected nv_bit_image or graphics)",
+                other
+            )
+            .into()),
+        }
+    }
+}
 
 /// Clase para manejar logos guardados en la memoria de la impresora.
 /// Algunas impresoras ESC/POS permiten guardar logos en memoria NV (Non-Volatile).
 #[derive(Debug, Clone)]
 pub struct Logo {
     key_code: u8,
+    graphics_key: [u8; 2],
     mode: ImageMode,
+    command_mode: LogoCommandMode,
 }
 
 impl Logo {
@@ -15,7 +44,9 @@ impl Logo {
     pub fn new(key_code: u8) -> Self {
         Self {
             key_code,
+            graphics_key: [b'0' + key_code / 10 % 10, b'0' + key_code % 10],
             mode: ImageMode::Normal,
+            command_mode: LogoCommandMode::NvBitImage,
         }
     }
 
@@ -25,15 +56,32 @@ impl Logo {
         self
     }
 
+    /// Establece los comandos usados (`FS p` o `GS ( L`)
+    pub fn set_command_mode(mut self, command_mode: LogoCommandMode) -> Self {
+        self.command_mode = command_mode;
+        self
+    }
+
+    /// Establece la clave de dos caracteres usada en modo `Graphics`
+    /// (por defecto los dos dígitos de `key_code`)
+    pub fn set_graphics_key(mut self, graphics_key: [u8; 2]) -> Self {
+        self.graphics_key = graphics_key;
+        self
+    }
+
     /// Comando para imprimir logo guardado en memoria NV
     /// FS p n m - Print NV bit image
+    /// GS ( L pL pH m fn kc1 kc2 x y - Print NV graphics (fn 69)
     pub fn get_print_command(&self) -> Vec<u8> {
-        vec![
-            0x1C,              // FS
-            0x70,              // p
-            self.key_code,     // n (key code)
-            self.mode.value(), // m (mode)
-        ]
+        match self.command_mode {
+            LogoCommandMode::NvBitImage => vec![
+                0x1C,              // FS
+                0x70,              // p
+                self.key_code,     // n (key code)
+                self.mode.value(), // m (mode)
+            ],
+            LogoCommandMode::Graphics => nv_graphics::print_command(self.graphics_key, self.mode),
+        }
     }
 }
 
@@ -45,10 +93,30 @@ pub const NV_LOGO_KEY_CODE: u8 = 1;
 /// Si `set_logo` está presente, tiene prioridad: se descarga la imagen a la memoria
 /// NV de la impresora (`FS q`) y se ignoran `key_code`/`mode`. En caso contrario se
 /// imprime el logo previamente guardado (`FS p`).
-pub fn process_section(logo: &LogoSection, paper_width_pixels: i32) -> Result<Vec<u8>, String> {
+pub fn process_section(
+    logo: &LogoSection,
+    paper_width_pixels: i32,
+    assets: &ImageAssets,
+) -> Result<Vec<u8>, SectionError> {
+    let command_mode = LogoCommandMode::from_name(logo.command_mode.as_deref())?;
+    let key_code = logo.key_code.unwrap_or(NV_LOGO_KEY_CODE);
+    // En modo `Graphics` la clave es `key` o, si falta, los dos dígitos de `key_code`
+    let graphics_key = match (command_mode, &logo.key) {
+        (LogoCommandMode::NvBitImage, _) => [0; 2],
+        (LogoCommandMode::Graphics, Some(key)) => nv_graphics::key_bytes(key)?,
+        (LogoCommandMode::Graphics, None) => nv_graphics::key_from_code(key_code)?,
+    };
+
     // `set_logo` tiene prioridad: guardar en memoria NV e ignorar el resto de campos.
     if let Some(image) = &logo.set_logo {
-        return Logo::get_define_command(image, paper_width_pixels);
+        return match command_mode {
+            LogoCommandMode::NvBitImage => {
+                Logo::get_define_command(image, paper_width_pixels, assets)
+            }
+            LogoCommandMode::Graphics => {
+                Logo::get_define_graphics_command(image, paper_width_pixels, graphics_key, assets)
+            }
+        };
     }
 
     let mode = match logo.mode.as_deref().unwrap_or("normal") {
@@ -59,7 +127,10 @@ pub fn process_section(logo: &LogoSection, paper_width_pixels: i32) -> Result<Ve
         _ => ImageMode::Normal,
     };
 
-    let esc_pos_logo = Logo::new(logo.key_code.unwrap_or(NV_LOGO_KEY_CODE)).set_mode(mode);
+    let esc_pos_logo = Logo::new(key_code)
+        .set_mode(mode)
+        .set_command_mode(command_mode)
+        .set_graphics_key(graphics_key);
     let mut data = esc_pos_logo.get_print_command();
     data.extend_from_slice(b"\n");
     Ok(data)
@@ -82,24 +153,15 @@ impl Logo {
     pub fn get_define_command(
         image: &crate::models::print_sections::Image,
         paper_width_pixels: i32,
-    ) -> Result<Vec<u8>, String> {
-        if image.data.is_empty() {
-            return Err("Logo image data cannot be empty".to_string());
-        }
-
-        let max_width = if image.max_width > paper_width_pixels || image.max_width <= 0 {
-            paper_width_pixels as u32
-        } else {
-            image.max_width as u32
-        };
-
-        let binary = ImageProcessor::process_image(&image.data, max_width, image.dithering)?;
+        assets: &ImageAssets,
+    ) -> Result<Vec<u8>, SectionError> {
+        let binary = nv_graphics::binarize(image, paper_width_pixels, assets)?;
         let (width, height) = (binary.width(), binary.height());
 
         let x_bytes = ((width + 7) / 8) as u16;
         let y_bytes = ((height + 7) / 8) as u16;
         if x_bytes == 0 || y_bytes == 0 {
-            return Err("Logo image has no printable content".to_string());
+            return Err("Logo image has no printable content".into());
         }
 
         let mut output = vec![
@@ -112,25 +174,25 @@ impl Logo {
             (y_bytes >> 8) as u8,
         ];
 
-        // Formato de columnas: para cada columna de puntos (padded a múltiplo de 8),
-        // emitir `y_bytes` bytes verticales (8 puntos por byte, MSB = punto superior).
-        let padded_width = x_bytes as u32 * 8;
-        for col in 0..padded_width {
-            for band in 0..y_bytes as u32 {
-                let mut byte = 0u8;
-                for bit in 0..8u32 {
-                    let row = band * 8 + bit;
-                    if col < width && row < height {
-                        // Píxel negro (< umbral) => punto impreso (bit 1).
-                        if binary.get_pixel(col, row)[0] < 127 {
-                            byte |= 1 << (7 - bit);
-                        }
-                    }
-                }
-                output.push(byte);
-            }
-        }
+        // Formato de columnas; las columnas de relleno hasta múltiplo de 8 van en blanco.
+        output.extend(ImageProcessor::column_bytes(&binary, 0, y_bytes as u32));
+        output.resize(
+            output.len() + (x_bytes as usize * 8 - width as usize) * y_bytes as usize,
+            0,
+        );
 
         Ok(output)
     }
+
+    /// Genera el comando `GS ( L` fn 67 para guardar la imagen como gráfico NV
+    /// bajo `graphics_key` (ver `nv_graphics::define_command`).
+    pub fn get_define_graphics_command(
+        image: &crate::models::print_sections::Image,
+        paper_width_pixels: i32,
+        graphics_key: [u8; 2],
+        assets: &ImageAssets,
+    ) -> Result<Vec<u8>, SectionError> {
+        let binary = nv_graphics::binarize(image, paper_width_pixels, assets)?;
+        nv_graphics::define_command(&binary, graphics_key)
+    }
 }
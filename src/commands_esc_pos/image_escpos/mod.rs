@@ -1,8 +1,13 @@
+pub mod dithering;
 pub mod image_alignment;
 pub mod image_code;
+pub mod image_command;
 pub mod image_mode;
+pub mod image_options;
 pub mod image_processor;
+pub mod image_transform;
 pub mod logo;
+pub mod nv_graphics;
 
 pub use image_alignment::ImageAlignment;
 pub use image_code::Image;
@@ -0,0 +1,236 @@
+//! Gráficos NV (`GS ( L` / `GS 8 L`): guardar, imprimir, listar y borrar imágenes
+//! en la memoria no volátil de la impresora con claves de dos caracteres, y leer
+//! la capacidad. Las respuestas de la impresora tienen la forma
+//! `37H <identificador> <datos> NUL`.
+//!
+//! También los gráficos descargados (fn 83/85), con el mismo formato pero en
+//! memoria volátil: se usan para enviar una sola vez las imágenes repetidas de un
+//! trabajo.
+
+use super::image_command::{graphics_header, mode_scale};
+use super::image_mode::ImageMode;
+use super::image_options::ImageOptions;
+use super::image_processor::ImageProcessor;
+use crate::error::{Error, SectionError};
+use crate::models::print_sections::Image as ImageSection;
+use crate::process::image_assets::{Bitmap, ImageAssets};
+use image::{ImageBuffer, Luma};
+use std::sync::Arc;
+
+/// Tamaño máximo de un gráfico NV en puntos
+pub const MAX_WIDTH: u32 = 8192;
+pub const MAX_HEIGHT: u32 = 2304;
+
+/// Identificadores de las respuestas a fn 48, fn 51 y fn 64
+pub const CAPACITY_IDENTIFIER: u8 = 0x30;
+pub const REMAINING_CAPACITY_IDENTIFIER: u8 = 0x31;
+pub const KEY_LIST_IDENTIFIER: u8 = 0x72;
+
+/// Valida una clave: exactamente dos caracteres ASCII imprimibles (32-126)
+pub fn key_bytes(key: &str) -> Result<[u8; 2], SectionError> {
+    match key.as_bytes() {
+        &[kc1, kc2] if (0x20..=0x7E).contains(&kc1) && (0x20..=0x7E).contains(&kc2) => {
+            Ok([kc1, kc2])
+        }
+        _ => Err(format!(
+            "NV graphics key must be 2 printable ASCII characters, got '{}'",
+            key
+        )
+        .into()),
+    }
+}
+
+/// Clave de dos dígitos para un `key_code` numérico (`7` → `"07"`)
+pub fn key_from_code(key_code: u8) -> Result<[u8; 2], SectionError> {
+    if !(1..=99).contains(&key_code) {
+        return Err(format!(
+            "Logo key_code must be 1-99 in graphics mode, got {}",
+            key_code
+        )
+        .into());
+    }
+    Ok([b'0' + key_code / 10, b'0' + key_code % 10])
+}
+
+/// Redimensiona al ancho indicado (o al del papel), pasa a escala de grises y binariza
+pub fn binarize(
+    image: &ImageSection,
+    paper_width_pixels: i32,
+    assets: &ImageAssets,
+) -> Result<Arc<Bitmap>, SectionError> {
+    if image.data.is_empty() {
+        return Err("Image data cannot be empty".into());
+    }
+
+    let max_width = if image.max_width > paper_width_pixels || image.max_width <= 0 {
+        paper_width_pixels as u32
+    } else {
+        image.max_width as u32
+    };
+
+    let options = ImageOptions::from_section(image)?;
+    assets.bitmap(&image.data, max_width, &options)
+}
+
+/// `GS ( L` fn 67: guarda la imagen (monocromo, color 1) bajo `key`.
+///
+/// `GS ( L pL pH m fn a kc1 kc2 b xL xH yL yH c d1...dk`, con `x`/`y` en puntos y
+/// los datos en formato raster (filas de `ceil(x / 8)` bytes, MSB a la izquierda).
+/// Si los datos no caben en `pL pH` se usa `GS 8 L`.
+pub fn define_command(
+    binary: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    key: [u8; 2],
+) -> Result<Vec<u8>, SectionError> {
+    define(0x43, binary, key)
+}
+
+/// `GS ( L` fn 83: igual que fn 67 pero como gráfico descargado (volátil)
+pub fn define_download_command(
+    binary: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    key: [u8; 2],
+) -> Result<Vec<u8>, SectionError> {
+    define(0x53, binary, key)
+}
+
+fn define(
+    function: u8,
+    binary: &ImageBuffer<Luma<u8>, Vec<u8>>,
+    key: [u8; 2],
+) -> Result<Vec<u8>, SectionError> {
+    let (width, height) = binary.dimensions();
+    if width == 0 || height == 0 {
+        return Err("Image has no printable content".into());
+    }
+    if width > MAX_WIDTH || height > MAX_HEIGHT {
+        return Err(format!(
+            "Image is {}x{} dots; GS ( L graphics accept at most {}x{}",
+            width, height, MAX_WIDTH, MAX_HEIGHT
+        )
+        .into());
+    }
+
+    let data = ImageProcessor::image_to_bytes(binary);
+    let mut output = graphics_header(function, 9 + data.len());
+    output.push(0x30); // a: monocromo
+    output.extend_from_slice(&key);
+    output.extend_from_slice(&[
+        0x01, // b: un color
+        (width & 0xFF) as u8,
+        (width >> 8) as u8,
+        (height & 0xFF) as u8,
+        (height >> 8) as u8,
+        0x31, // c: color 1
+    ]);
+    output.extend(data);
+    Ok(output)
+}
+
+/// `GS ( L` fn 69: imprime el gráfico `key` con la escala del modo
+pub fn print_command(key: [u8; 2], mode: ImageMode) -> Vec<u8> {
+    let (x, y) = mode_scale(mode);
+    let mut output = graphics_header(0x45, 4);
+    output.extend_from_slice(&key);
+    output.extend_from_slice(&[x, y]);
+    output
+}
+
+/// `GS ( L` fn 85: imprime el gráfico descargado `key` con la escala del modo
+pub fn print_download_command(key: [u8; 2], mode: ImageMode) -> Vec<u8> {
+    let (x, y) = mode_scale(mode);
+    let mut output = graphics_header(0x55, 4);
+    output.extend_from_slice(&key);
+    output.extend_from_slice(&[x, y]);
+    output
+}
+
+/// `GS ( L` fn 81: borra todos los gráficos descargados
+pub fn delete_all_download_command() -> Vec<u8> {
+    let mut output = graphics_header(0x51, 3);
+    output.extend_from_slice(b"CLR");
+    output
+}
+
+/// `GS ( L` fn 66: borra el gráfico `key`
+pub fn delete_command(key: [u8; 2]) -> Vec<u8> {
+    let mut output = graphics_header(0x42, 2);
+    output.extend_from_slice(&key);
+    output
+}
+
+/// `GS ( L` fn 65: borra todos los gráficos NV
+pub fn delete_all_command() -> Vec<u8> {
+    let mut output = graphics_header(0x41, 3);
+    output.extend_from_slice(b"CLR");
+    output
+}
+
+/// `GS ( L` fn 64: pide la lista de claves definidas
+pub fn key_list_request() -> Vec<u8> {
+    let mut output = graphics_header(0x40, 2);
+    output.extend_from_slice(b"KC");
+    output
+}
+
+/// `GS ( L` fn 48: pide la capacidad total de la memoria NV de gráficos
+pub fn capacity_request() -> Vec<u8> {
+    graphics_header(0x30, 0)
+}
+
+/// `GS ( L` fn 51: pide la capacidad libre de la memoria NV de gráficos
+pub fn remaining_capacity_request() -> Vec<u8> {
+    graphics_header(0x33, 0)
+}
+
+/// Datos de la respuesta `37H <identifier> ... NUL`, ignorando bytes previos
+/// (p. ej. estados automáticos)
+fn response_data(response: &[u8], identifier: u8) -> Result<&[u8], Error> {
+    let missing = || invalid_response("The printer did not answer the NV graphics request".into());
+    let start = response
+        .windows(2)
+        .position(|w| w == [0x37, identifier])
+        .ok_or_else(missing)?
+        + 2;
+    let len = response[start..]
+        .iter()
+        .position(|&b| b == 0x00)
+        .ok_or_else(missing)?;
+    Ok(&response[start..start + len])
+}
+
+fn invalid_response(message: String) -> Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message).into()
+}
+
+/// Capacidad en bytes de la respuesta a fn 48 (identificador 30H) o fn 51 (31H)
+pub fn parse_capacity(response: &[u8], identifier: u8) -> Result<u32, Error> {
+    let data = response_data(response, identifier)?;
+    std::str::from_utf8(data)
+        .ok()
+        .and_then(|digits| digits.parse().ok())
+        .ok_or_else(|| {
+            invalid_response(format!("Invalid NV graphics capacity answer {:02X?}", data))
+        })
+}
+
+/// Claves de la respuesta a fn 64 (identificador 72H). El byte de estado es 40H
+/// si la lista termina en este bloque o 41H si la impresora tiene más; en ese caso
+/// el segundo valor es `false`.
+pub fn parse_key_list(response: &[u8]) -> Result<(Vec<String>, bool), Error> {
+    let data = response_data(response, KEY_LIST_IDENTIFIER)?;
+    let (status, keys) = match data.split_first() {
+        Some((&status, keys)) if matches!(status, 0x40 | 0x41) && keys.len() % 2 == 0 => {
+            (status, keys)
+        }
+        _ => {
+            return Err(invalid_response(format!(
+                "Invalid NV graphics key list answer {:02X?}",
+                data
+            )))
+        }
+    };
+    let keys = keys
+        .chunks(2)
+        .map(|key| String::from_utf8_lossy(key).into_owned())
+        .collect();
+    Ok((keys, status == 0x40))
+}
@@ -9,6 +9,11 @@ pub struct CodePage {
     pub encode: Encode,
     #[serde(default)]
     pub use_gbk: bool,
+    /// Cuando es `true`, un carácter que la codificación elegida no puede
+    /// representar hace fallar el trabajo (`ENCODING_FAILED`) en lugar de enviarse
+    /// como bytes UTF-8.
+    #[serde(default)]
+    pub strict: bool,
 }
 
 impl Default for CodePage {
@@ -17,6 +22,7 @@ impl Default for CodePage {
             code_page: 0,
             encode: Encode::AccentRemover,
             use_gbk: false,
+            strict: false,
         }
     }
 }
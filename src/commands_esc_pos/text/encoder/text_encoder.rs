@@ -1,4 +1,5 @@
 use crate::commands_esc_pos::text::code_page::CodePage;
+use crate::error::SectionError;
 use unicode_width::UnicodeWidthChar;
 
 use super::accent_remover::accent_remover_bytes;
@@ -8,6 +9,7 @@ use super::encode::Encode;
 pub struct TextEncoder {
     encode: Encode,
     use_gbk: bool,
+    strict: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -21,10 +23,11 @@ impl TextEncoder {
         Self {
             encode: code_page.encode,
             use_gbk: code_page.use_gbk,
+            strict: code_page.strict,
         }
     }
 
-    pub fn encode_text(&self, text: &str) -> Result<Vec<u8>, String> {
+    pub fn encode_text(&self, text: &str) -> Result<Vec<u8>, SectionError> {
         let mut output = Vec::with_capacity(text.len());
 
         for ch in text.chars() {
@@ -34,14 +37,28 @@ impl TextEncoder {
         Ok(output)
     }
 
-    pub(crate) fn encode_char(&self, ch: char) -> Result<EncodedChar, String> {
+    pub(crate) fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Indica si `ch` se puede representar con la codificación elegida (o con GBK
+    /// si `use_gbk` está activo) sin recurrir al envío en UTF-8.
+    pub(crate) fn can_encode(&self, ch: char) -> bool {
+        let strict = Self {
+            strict: true,
+            ..self.clone()
+        };
+        strict.encode_char(ch).is_ok()
+    }
+
+    pub(crate) fn encode_char(&self, ch: char) -> Result<EncodedChar, SectionError> {
         match self.encode.encoding() {
             Some(encoding) => self.encode_with_encoding(ch, encoding),
             None => self.encode_accent_remover(ch),
         }
     }
 
-    fn encode_accent_remover(&self, ch: char) -> Result<EncodedChar, String> {
+    fn encode_accent_remover(&self, ch: char) -> Result<EncodedChar, SectionError> {
         if let Some(mapped) = accent_remover_bytes(ch) {
             return Ok(multi_byte_char(mapped));
         }
@@ -57,7 +74,7 @@ impl TextEncoder {
         &self,
         ch: char,
         encoding: &'static encoding_rs::Encoding,
-    ) -> Result<EncodedChar, String> {
+    ) -> Result<EncodedChar, SectionError> {
         let value = ch.to_string();
         let (encoded, _, had_errors) = encoding.encode(&value);
 
@@ -71,12 +88,24 @@ impl TextEncoder {
         })
     }
 
-    fn encode_with_optional_gbk(&self, ch: char) -> Result<EncodedChar, String> {
-        if !self.use_gbk {
-            return Ok(passthrough_char(ch));
+    fn encode_with_optional_gbk(&self, ch: char) -> Result<EncodedChar, SectionError> {
+        if self.use_gbk {
+            if let Some(encoded) = encode_with_gbk(ch) {
+                return Ok(encoded);
+            }
+        }
+
+        self.passthrough_or_fail(ch)
+    }
+
+    /// Último recurso para un carácter no representable: en modo `strict` falla,
+    /// si no se envía tal cual en UTF-8.
+    fn passthrough_or_fail(&self, ch: char) -> Result<EncodedChar, SectionError> {
+        if self.strict {
+            return Err(SectionError::EncodingFailed(ch));
         }
 
-        encode_with_gbk(ch)
+        Ok(passthrough_char(ch))
     }
 }
 
@@ -94,15 +123,15 @@ fn multi_byte_char(bytes: &'static [u8]) -> EncodedChar {
     }
 }
 
-fn encode_with_gbk(ch: char) -> Result<EncodedChar, String> {
+fn encode_with_gbk(ch: char) -> Option<EncodedChar> {
     let value = ch.to_string();
     let (encoded, _, had_errors) = encoding_rs::GBK.encode(&value);
 
     if had_errors {
-        return Ok(passthrough_char(ch));
+        return None;
     }
 
-    Ok(EncodedChar {
+    Some(EncodedChar {
         bytes: encoded.into_owned(),
         width: display_width(ch),
     })
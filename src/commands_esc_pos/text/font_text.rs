@@ -0,0 +1,552 @@
+//! Texto renderizado como imagen con una fuente TrueType/OpenType, para escrituras
+//! que las páginas de código de la impresora no cubren (árabe, hebreo, tailandés,
+//! devanagari, emoji monocromo...). El texto se conforma con `rustybuzz` (ligaduras,
+//! formas contextuales, marcas combinantes), se reordena con el algoritmo bidi de
+//! Unicode y se rasteriza al ancho del papel.
+
+use crate::commands_esc_pos::image_escpos::image_alignment::ImageAlignment;
+use crate::commands_esc_pos::image_escpos::image_code::raster_command;
+use crate::commands_esc_pos::image_escpos::image_processor::ImageProcessor;
+use crate::commands_esc_pos::image_escpos::ImageMode;
+use crate::error::SectionError;
+use crate::models::paper_size::DOTS_PER_MM;
+use crate::models::print_sections::{FontText as FontTextSection, FontTextFont, TextFont};
+use crate::process::font_registry::FontRegistry;
+use ab_glyph_rasterizer::{point, Point, Rasterizer};
+use image::{GrayImage, Luma};
+use rustybuzz::ttf_parser::{GlyphId, OutlineBuilder, Tag};
+use rustybuzz::{Direction, Face, UnicodeBuffer};
+use std::ops::Range;
+use std::sync::Arc;
+use unicode_bidi::{BidiInfo, Level, ParagraphInfo};
+
+/// Tamaño por defecto en puntos: la altura de la fuente A (12×24)
+pub const DEFAULT_SIZE: f32 = 24.0;
+
+/// Tamaño por defecto en puntos de imprenta de Title y Subtitle con fuente
+pub const TITLE_SIZE: f32 = 18.0;
+pub const SUBTITLE_SIZE: f32 = 14.0;
+
+/// Peso normal (CSS/OpenType): 400 regular, 700 negrita
+pub const NORMAL_WEIGHT: u16 = 400;
+
+/// Eje de peso de las fuentes variables
+const WEIGHT_AXIS: Tag = Tag::from_bytes(b"wght");
+
+/// Puntos de imprenta (1/72") a puntos del cabezal de 203 DPI
+const DOTS_PER_POINT: f32 = DOTS_PER_MM * 25.4 / 72.0;
+
+const INVALID_FONT: &str = "Invalid font data (expected a TrueType or OpenType font)";
+
+/// Dirección base del párrafo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextDirection {
+    /// Según el primer carácter con dirección fuerte (por defecto)
+    Auto,
+    Ltr,
+    Rtl,
+}
+
+impl TextDirection {
+    pub fn from_name(name: Option<&str>) -> Result<Self, SectionError> {
+        match name.unwrap_or("auto") {
+            "auto" => Ok(TextDirection::Auto),
+            "ltr" => Ok(TextDirection::Ltr),
+            "rtl" => Ok(TextDirection::Rtl),
+            other => Err(format!(
+                "Unknown text direction '{}' (expected auto, ltr or rtl)",
+                other
+            )
+            .into()),
+        }
+    }
+
+    fn level(self) -> Option<Level> {
+        match self {
+            TextDirection::Auto => None,
+            TextDirection::Ltr => Some(Level::ltr()),
+            TextDirection::Rtl => Some(Level::rtl()),
+        }
+    }
+}
+
+/// Glifo ya posicionado en la línea (en puntos, `y` hacia arriba desde la base)
+struct PlacedGlyph {
+    id: GlyphId,
+    x: f32,
+    y: f32,
+}
+
+/// Constructor de texto renderizado con fuente
+#[derive(Debug, Clone)]
+pub struct FontText {
+    font: Arc<Vec<u8>>,
+    size: f32,
+    alignment: Option<ImageAlignment>,
+    direction: TextDirection,
+    line_spacing: u32,
+    weight: u16,
+    letter_spacing: f32,
+}
+
+impl FontText {
+    /// Crea el renderizador con el archivo de fuente (TTF/OTF) ya decodificado
+    pub fn new(font: impl Into<Arc<Vec<u8>>>) -> Result<Self, SectionError> {
+        let font = font.into();
+        if Face::from_slice(&font, 0).is_none() {
+            return Err(INVALID_FONT.into());
+        }
+        Ok(Self {
+            font,
+            size: DEFAULT_SIZE,
+            alignment: None,
+            direction: TextDirection::Auto,
+            line_spacing: 0,
+            weight: NORMAL_WEIGHT,
+            letter_spacing: 0.0,
+        })
+    }
+
+    /// Establece el tamaño (altura del em) en puntos
+    pub fn set_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Establece la alineación (`None`: según la dirección del párrafo)
+    pub fn set_alignment(mut self, alignment: Option<ImageAlignment>) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Establece la dirección base de los párrafos
+    pub fn set_direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Establece el espacio extra entre líneas, en puntos
+    pub fn set_line_spacing(mut self, line_spacing: u32) -> Self {
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    /// Establece el peso (100-900). En fuentes variables se usa el eje `wght`; en
+    /// las demás, los pesos por encima de 400 engrosan los trazos.
+    pub fn set_weight(mut self, weight: u16) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Establece el espacio extra entre caracteres, en puntos (puede ser negativo)
+    pub fn set_letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = letter_spacing;
+        self
+    }
+
+    /// Renderiza `text` en una imagen de `width` puntos de ancho (blanco = 255),
+    /// partiendo las líneas por palabras. `\n` fuerza un salto de línea.
+    pub fn render(&self, text: &str, width: u32) -> Result<GrayImage, SectionError> {
+        if !self.size.is_finite() || self.size <= 0.0 {
+            return Err("Font size must be greater than 0".into());
+        }
+        let (face, bold) = self.face()?;
+        let spacing = self.letter_spacing + bold as f32;
+        let scale = self.size / face.units_per_em() as f32;
+        let ascent = face.ascender() as f32 * scale;
+        let line_height = ((face.ascender() - face.descender() + face.line_gap()) as f32 * scale)
+            .ceil() as u32
+            + self.line_spacing;
+
+        let bidi = BidiInfo::new(text, self.direction.level());
+        let mut lines = Vec::new();
+        for paragraph in &bidi.paragraphs {
+            let alignment = self.alignment.unwrap_or(if paragraph.level.is_rtl() {
+                ImageAlignment::Right
+            } else {
+                ImageAlignment::Left
+            });
+            for range in self.wrap(&face, &bidi, paragraph, spacing, width as f32) {
+                let (glyphs, line_width) = self.shape_line(&face, &bidi, paragraph, range, spacing);
+                let offset = match alignment {
+                    ImageAlignment::Left => 0.0,
+                    ImageAlignment::Center => ((width as f32 - line_width) / 2.0).max(0.0),
+                    ImageAlignment::Right => (width as f32 - line_width).max(0.0),
+                };
+                lines.push((glyphs, offset));
+            }
+        }
+        if lines.is_empty() {
+            return Err("FontText text cannot be empty".into());
+        }
+
+        let mut canvas =
+            GrayImage::from_pixel(width, line_height * lines.len() as u32, Luma([255]));
+        for (index, (glyphs, offset)) in lines.iter().enumerate() {
+            let baseline = index as f32 * line_height as f32 + ascent;
+            for glyph in glyphs {
+                draw_glyph(
+                    &face,
+                    glyph,
+                    offset + glyph.x,
+                    baseline - glyph.y,
+                    scale,
+                    &mut canvas,
+                );
+            }
+        }
+        embolden(&mut canvas, bold);
+        Ok(canvas)
+    }
+
+    /// Comando raster (`GS v 0`) del texto renderizado al ancho `width`
+    pub fn get_command(&self, text: &str, width: u32) -> Result<Vec<u8>, SectionError> {
+        let binary = ImageProcessor::to_binary_simple(&self.render(text, width)?);
+        Ok(raster_command(&binary, ImageMode::Normal))
+    }
+
+    /// Parte un párrafo en líneas (rangos lógicos, sin el espacio final) que caben
+    /// en `width`. Corta entre palabras y, si una palabra sola no cabe, entre
+    /// caracteres.
+    fn wrap(
+        &self,
+        face: &Face,
+        bidi: &BidiInfo,
+        paragraph: &ParagraphInfo,
+        spacing: f32,
+        width: f32,
+    ) -> Vec<Range<usize>> {
+        let text = bidi.text;
+        let end = text[paragraph.range.clone()]
+            .trim_end_matches(['\n', '\r', '\u{2029}'])
+            .len()
+            + paragraph.range.start;
+        let fits =
+            |range: Range<usize>| self.shape_line(face, bidi, paragraph, range, spacing).1 <= width;
+        let trimmed = |start: usize, stop: usize| start..start + text[start..stop].trim_end().len();
+
+        let mut lines = Vec::new();
+        let mut start = paragraph.range.start;
+        while start < end {
+            // Siguiente fin de palabra (incluyendo los espacios que la siguen)
+            let mut stop = start;
+            let mut best = None;
+            while stop < end {
+                let word_end = next_word_end(text, stop, end);
+                if best.is_some() && !fits(trimmed(start, word_end)) {
+                    break;
+                }
+                if best.is_none() && !fits(trimmed(start, word_end)) {
+                    // La primera palabra no cabe: cortar por caracteres
+                    let mut cut = start;
+                    for (offset, ch) in text[start..word_end].char_indices() {
+                        let next = start + offset + ch.len_utf8();
+                        if cut > start && !fits(start..next) {
+                            break;
+                        }
+                        cut = next;
+                    }
+                    best = Some(cut);
+                    break;
+                }
+                best = Some(word_end);
+                stop = word_end;
+            }
+            let stop = best.unwrap_or(end);
+            lines.push(trimmed(start, stop));
+            start = stop;
+        }
+        if lines.is_empty() {
+            lines.push(paragraph.range.start..paragraph.range.start);
+        }
+        lines
+    }
+
+    /// Carga la fuente con el peso pedido. Devuelve también los puntos de
+    /// engrosado sintético (0 en fuentes variables o de peso normal).
+    fn face(&self) -> Result<(Face<'_>, u32), SectionError> {
+        let mut face = Face::from_slice(&self.font, 0).ok_or(INVALID_FONT)?;
+        if face
+            .variation_axes()
+            .into_iter()
+            .any(|axis| axis.tag == WEIGHT_AXIS)
+        {
+            face.set_variation(WEIGHT_AXIS, self.weight as f32);
+            return Ok((face, 0));
+        }
+        // Unos 0,8 puntos por cada 300 de peso a 24 puntos, proporcional al tamaño
+        let bold = self.weight.saturating_sub(NORMAL_WEIGHT) as f32 / 300.0 * self.size / 30.0;
+        Ok((face, bold.round() as u32))
+    }
+
+    /// Conforma una línea: cada tramo bidi en orden visual con su dirección, con
+    /// `spacing` puntos extra entre grupos de caracteres. Devuelve los glifos
+    /// posicionados y el ancho total.
+    fn shape_line(
+        &self,
+        face: &Face,
+        bidi: &BidiInfo,
+        paragraph: &ParagraphInfo,
+        range: Range<usize>,
+        spacing: f32,
+    ) -> (Vec<PlacedGlyph>, f32) {
+        let scale = self.size / face.units_per_em() as f32;
+        let mut glyphs = Vec::new();
+        let mut pen = 0.0;
+        if range.is_empty() {
+            return (glyphs, pen);
+        }
+
+        let (levels, runs) = bidi.visual_runs(paragraph, range);
+        for run in runs {
+            let mut buffer = UnicodeBuffer::new();
+            buffer.push_str(&bidi.text[run.clone()]);
+            buffer.set_direction(if levels[run.start].is_rtl() {
+                Direction::RightToLeft
+            } else {
+                Direction::LeftToRight
+            });
+            buffer.guess_segment_properties();
+            let shaped = rustybuzz::shape(face, &[], buffer);
+            let mut cluster = None;
+            for (info, position) in shaped.glyph_infos().iter().zip(shaped.glyph_positions()) {
+                // El espaciado va entre grupos: las marcas y ligaduras no se separan
+                if cluster.is_some_and(|cluster| cluster != info.cluster) {
+                    pen += spacing;
+                }
+                cluster = Some(info.cluster);
+                glyphs.push(PlacedGlyph {
+                    id: GlyphId(info.glyph_id as u16),
+                    x: pen + position.x_offset as f32 * scale,
+                    y: position.y_offset as f32 * scale,
+                });
+                pen += position.x_advance as f32 * scale;
+            }
+        }
+        (glyphs, pen)
+    }
+}
+
+/// Engrosa los trazos `amount` puntos hacia la derecha (negrita sintética)
+fn embolden(canvas: &mut GrayImage, amount: u32) {
+    if amount == 0 {
+        return;
+    }
+    for y in 0..canvas.height() {
+        for x in (0..canvas.width()).rev() {
+            let darkest = (x.saturating_sub(amount)..=x)
+                .map(|sx| canvas.get_pixel(sx, y)[0])
+                .min()
+                .unwrap_or(255);
+            canvas.put_pixel(x, y, Luma([darkest]));
+        }
+    }
+}
+
+/// Alineación por nombre (`left`/`center`/`right`)
+fn alignment_from_name(name: &str) -> Option<ImageAlignment> {
+    match name {
+        "left" => Some(ImageAlignment::Left),
+        "center" => Some(ImageAlignment::Center),
+        "right" => Some(ImageAlignment::Right),
+        _ => None,
+    }
+}
+
+/// Fin de la palabra que empieza en `start`, incluyendo los espacios siguientes
+fn next_word_end(text: &str, start: usize, end: usize) -> usize {
+    let slice = &text[start..end];
+    let word = slice.find(char::is_whitespace).unwrap_or(slice.len());
+    let spaces = slice[word..]
+        .find(|ch: char| !ch.is_whitespace())
+        .unwrap_or(slice.len() - word);
+    start + word + spaces
+}
+
+/// Rasteriza el contorno de un glifo con origen en (`x`, `baseline`) y lo funde
+/// con el lienzo (se queda el valor más oscuro)
+fn draw_glyph(
+    face: &Face,
+    glyph: &PlacedGlyph,
+    x: f32,
+    baseline: f32,
+    scale: f32,
+    canvas: &mut GrayImage,
+) {
+    let Some(bbox) = face.glyph_bounding_box(glyph.id) else {
+        return; // Glifo sin contorno (espacio)
+    };
+    let left = (x + bbox.x_min as f32 * scale).floor();
+    let top = (baseline - bbox.y_max as f32 * scale).floor();
+    let width = (x + bbox.x_max as f32 * scale).ceil() - left;
+    let height = (baseline - bbox.y_min as f32 * scale).ceil() - top;
+    if width < 1.0 || height < 1.0 {
+        return;
+    }
+
+    let mut outline = GlyphOutline {
+        rasterizer: Rasterizer::new(width as usize, height as usize),
+        origin: point(x - left, baseline - top),
+        scale,
+        start: point(0.0, 0.0),
+        last: point(0.0, 0.0),
+    };
+    if face.outline_glyph(glyph.id, &mut outline).is_none() {
+        return;
+    }
+    outline.rasterizer.for_each_pixel_2d(|px, py, coverage| {
+        let (cx, cy) = (left as i64 + px as i64, top as i64 + py as i64);
+        if cx < 0 || cy < 0 || cx >= canvas.width() as i64 || cy >= canvas.height() as i64 {
+            return;
+        }
+        let shade = 255 - (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let pixel = canvas.get_pixel_mut(cx as u32, cy as u32);
+        pixel[0] = pixel[0].min(shade);
+    });
+}
+
+/// Traduce el contorno (unidades de fuente, `y` hacia arriba) a puntos del
+/// rasterizador del glifo
+struct GlyphOutline {
+    rasterizer: Rasterizer,
+    origin: Point,
+    scale: f32,
+    start: Point,
+    last: Point,
+}
+
+impl GlyphOutline {
+    fn map(&self, x: f32, y: f32) -> Point {
+        point(
+            self.origin.x + x * self.scale,
+            self.origin.y - y * self.scale,
+        )
+    }
+}
+
+impl OutlineBuilder for GlyphOutline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = self.map(x, y);
+        self.last = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.map(x, y);
+        self.rasterizer.draw_line(self.last, to);
+        self.last = to;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let to = self.map(x, y);
+        self.rasterizer.draw_quad(self.last, self.map(x1, y1), to);
+        self.last = to;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let to = self.map(x, y);
+        self.rasterizer
+            .draw_cubic(self.last, self.map(x1, y1), self.map(x2, y2), to);
+        self.last = to;
+    }
+
+    fn close(&mut self) {
+        if self.last != self.start {
+            self.rasterizer.draw_line(self.last, self.start);
+        }
+        self.last = self.start;
+    }
+}
+
+/// Procesa sección FontText del modelo de impresión. La fuente viene en base64
+/// o es una registrada en `fonts`. `size`, como el de la registrada, va en puntos
+/// de imprenta y manda sobre él; sin ninguno se usa `DEFAULT_SIZE` (puntos del
+/// cabezal).
+pub fn process_section(
+    section: &FontTextSection,
+    fonts: &FontRegistry,
+    paper_width_pixels: i32,
+) -> Result<Vec<u8>, SectionError> {
+    if section.text.is_empty() {
+        return Err("FontText text cannot be empty".into());
+    }
+    let (font, size, weight, letter_spacing) = match &section.font {
+        FontTextFont::Data(data) => {
+            let font = ImageProcessor::decode_base64(data)
+                .map_err(|e| format!("Error decoding font base64: {}", e))?;
+            (Arc::new(font), None, NORMAL_WEIGHT, 0.0)
+        }
+        FontTextFont::Registered(font) => (
+            registered_font(fonts, &font.name)?,
+            font.size,
+            font.weight.unwrap_or(NORMAL_WEIGHT),
+            font.letter_spacing.unwrap_or(0.0),
+        ),
+    };
+
+    let alignment = match section.align.as_deref() {
+        None => None,
+        Some(name) => Some(alignment_from_name(name).ok_or_else(|| {
+            format!(
+                "Unknown FontText align '{}' (expected left, center or right)",
+                name
+            )
+        })?),
+    };
+
+    let font_text = FontText::new(font)?
+        .set_size(
+            section
+                .size
+                .or(size)
+                .map_or(DEFAULT_SIZE, |size| size * DOTS_PER_POINT),
+        )
+        .set_weight(weight)
+        .set_letter_spacing(letter_spacing)
+        .set_alignment(alignment)
+        .set_direction(TextDirection::from_name(section.direction.as_deref())?)
+        .set_line_spacing(section.line_spacing.unwrap_or(0));
+
+    let mut output = font_text.get_command(&section.text, paper_width_pixels.max(0) as u32)?;
+    output.extend_from_slice(b"\n");
+    Ok(output)
+}
+
+/// Procesa un Title/Subtitle con `font`: el texto se renderiza con la fuente
+/// registrada y se imprime como imagen. `align` viene de los estilos (`None`:
+/// según la dirección del texto), `bold` da peso 700 si la fuente no fija otro y
+/// `default_size` (en puntos de imprenta) se usa si no fija tamaño.
+pub fn process_heading(
+    text: &str,
+    font: &TextFont,
+    align: Option<&str>,
+    bold: bool,
+    default_size: f32,
+    fonts: &FontRegistry,
+    paper_width_pixels: i32,
+) -> Result<Vec<u8>, SectionError> {
+    if text.is_empty() {
+        return Err("Text cannot be empty".into());
+    }
+    let data = registered_font(fonts, &font.name)?;
+    let default_weight = if bold { 700 } else { NORMAL_WEIGHT };
+
+    let font_text = FontText::new(data)?
+        .set_size(font.size.unwrap_or(default_size) * DOTS_PER_POINT)
+        .set_weight(font.weight.unwrap_or(default_weight))
+        .set_letter_spacing(font.letter_spacing.unwrap_or(0.0))
+        .set_alignment(align.and_then(alignment_from_name));
+
+    let mut output = font_text.get_command(text, paper_width_pixels.max(0) as u32)?;
+    output.extend_from_slice(b"\n");
+    Ok(output)
+}
+
+fn registered_font(fonts: &FontRegistry, name: &str) -> Result<Arc<Vec<u8>>, SectionError> {
+    fonts.get(name).ok_or_else(|| {
+        format!(
+            "Font '{}' is not registered (call register_font first)",
+            name
+        )
+        .into()
+    })
+}
@@ -1,5 +1,6 @@
 pub mod code_page;
 pub mod encoder;
+pub mod font_text;
 pub mod table;
 pub mod table_render;
 pub mod text_type;
@@ -1,12 +1,13 @@
 use crate::commands_esc_pos::text::encoder::TextEncoder;
 use crate::commands_esc_pos::text::table_render::{render_row, RenderedLine};
+use crate::error::SectionError;
 use crate::models::print_sections::{Table, Text};
 
 pub fn process_section(
     table: &Table,
     chars_per_line: i32,
     encoder: &TextEncoder,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, SectionError> {
     validate_table(table, chars_per_line)?;
     process_table(table, chars_per_line, table.truncate, encoder)
 }
@@ -16,7 +17,7 @@ pub fn process_table(
     max_width: i32,
     truncate: bool,
     encoder: &TextEncoder,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, SectionError> {
     if table.columns == 0 {
         return Ok(Vec::new());
     }
@@ -55,27 +56,28 @@ pub fn process_table(
     Ok(output)
 }
 
-fn validate_table(table: &Table, chars_per_line: i32) -> Result<(), String> {
+fn validate_table(table: &Table, chars_per_line: i32) -> Result<(), SectionError> {
     validate_column_widths(table, chars_per_line)?;
     validate_header(table)?;
     validate_rows(table)
 }
 
-fn validate_column_widths(table: &Table, chars_per_line: i32) -> Result<(), String> {
+fn validate_column_widths(table: &Table, chars_per_line: i32) -> Result<(), SectionError> {
     if let Some(widths) = &table.column_widths {
         let total: i32 = widths.iter().map(|&width| i32::from(width)).sum();
         if total != chars_per_line {
             return Err(format!(
                 "column_widths sum ({}) must equal paper chars_per_line ({})",
                 total, chars_per_line
-            ));
+            )
+            .into());
         }
     }
 
     Ok(())
 }
 
-fn validate_header(table: &Table) -> Result<(), String> {
+fn validate_header(table: &Table) -> Result<(), SectionError> {
     if let Some(header) = &table.header {
         let num_columns = table.columns as usize;
         if !header.is_empty() && header.len() != num_columns {
@@ -83,14 +85,15 @@ fn validate_header(table: &Table) -> Result<(), String> {
                 "Table header has {} cells but {} columns declared",
                 header.len(),
                 num_columns
-            ));
+            )
+            .into());
         }
     }
 
     Ok(())
 }
 
-fn validate_rows(table: &Table) -> Result<(), String> {
+fn validate_rows(table: &Table) -> Result<(), SectionError> {
     let num_columns = table.columns as usize;
 
     for (row_idx, row) in table.body.iter().enumerate() {
@@ -100,7 +103,8 @@ fn validate_rows(table: &Table) -> Result<(), String> {
                 row_idx,
                 row.len(),
                 num_columns
-            ));
+            )
+            .into());
         }
     }
 
@@ -168,7 +172,7 @@ fn write_row_groups(
     truncate: bool,
     word_wrap: bool,
     encoder: &TextEncoder,
-) -> Result<(), String> {
+) -> Result<(), SectionError> {
     for group in column_groups {
         let widths = group_widths(group, column_widths);
         let cells = group_cells(group, row);
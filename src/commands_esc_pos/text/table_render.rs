@@ -1,4 +1,5 @@
 use crate::commands_esc_pos::text::encoder::{EncodedChar, TextEncoder};
+use crate::error::SectionError;
 use crate::models::print_sections::Text;
 
 #[derive(Debug, Clone, Default)]
@@ -13,7 +14,7 @@ pub fn render_row(
     truncate: bool,
     word_wrap: bool,
     encoder: &TextEncoder,
-) -> Result<Vec<RenderedLine>, String> {
+) -> Result<Vec<RenderedLine>, SectionError> {
     let rendered_cells = render_cells(row, column_widths, truncate, word_wrap, encoder)?;
     let max_lines = rendered_cells.iter().map(Vec::len).max().unwrap_or(1);
     let mut output = Vec::new();
@@ -31,7 +32,7 @@ fn render_cells(
     truncate: bool,
     word_wrap: bool,
     encoder: &TextEncoder,
-) -> Result<Vec<Vec<RenderedLine>>, String> {
+) -> Result<Vec<Vec<RenderedLine>>, SectionError> {
     let mut rendered = Vec::new();
 
     for (index, cell) in row.iter().enumerate() {
@@ -72,7 +73,7 @@ fn render_cell(
     truncate: bool,
     word_wrap: bool,
     encoder: &TextEncoder,
-) -> Result<Vec<RenderedLine>, String> {
+) -> Result<Vec<RenderedLine>, SectionError> {
     if truncate {
         return Ok(vec![truncate_text(text, width, encoder)?]);
     }
@@ -84,7 +85,11 @@ fn render_cell(
     }
 }
 
-fn truncate_text(text: &str, width: usize, encoder: &TextEncoder) -> Result<RenderedLine, String> {
+fn truncate_text(
+    text: &str,
+    width: usize,
+    encoder: &TextEncoder,
+) -> Result<RenderedLine, SectionError> {
     if width == 0 {
         return Ok(RenderedLine::default());
     }
@@ -109,7 +114,7 @@ fn wrap_text_char(
     text: &str,
     width: usize,
     encoder: &TextEncoder,
-) -> Result<Vec<RenderedLine>, String> {
+) -> Result<Vec<RenderedLine>, SectionError> {
     if width == 0 {
         return Ok(vec![RenderedLine::default()]);
     }
@@ -138,7 +143,7 @@ fn push_wrapped_char(
     ch: char,
     width: usize,
     encoder: &TextEncoder,
-) -> Result<(), String> {
+) -> Result<(), SectionError> {
     let encoded = encoder.encode_char(ch)?;
 
     if current.width > 0 && current.width + encoded.width > width {
@@ -168,7 +173,7 @@ fn wrap_text_word(
     text: &str,
     width: usize,
     encoder: &TextEncoder,
-) -> Result<Vec<RenderedLine>, String> {
+) -> Result<Vec<RenderedLine>, SectionError> {
     if width == 0 {
         return Ok(vec![RenderedLine::default()]);
     }
@@ -1,4 +1,5 @@
 use crate::commands_esc_pos::text::encoder::TextEncoder;
+use crate::error::SectionError;
 use crate::models::print_sections::{GlobalStyles, Line, Subtitle, Text, Title};
 
 #[derive(Debug, Clone, Copy)]
@@ -34,6 +35,10 @@ pub enum TextType {
     RotateOff,
     UpsideDownOn,
     UpsideDownOff,
+
+    // Color (impresoras de dos colores)
+    ColorBlack,
+    ColorRed,
 }
 
 impl TextType {
@@ -78,6 +83,10 @@ impl TextType {
             Self::RotateOff => &[0x1B, 0x56, 0x00],
             Self::UpsideDownOn => &[0x1B, 0x7B, 0x01],
             Self::UpsideDownOff => &[0x1B, 0x7B, 0x00],
+
+            // Color de impresión (ESC r)
+            Self::ColorBlack => &[0x1B, 0x72, 0x00],
+            Self::ColorRed => &[0x1B, 0x72, 0x01],
         }
     }
 }
@@ -171,6 +180,16 @@ pub fn get_styles_diff(old: &GlobalStyles, new: &GlobalStyles) -> Vec<u8> {
         }
     }
 
+    let old_color = get_string(&old.color, "black");
+    let new_color = get_string(&new.color, "black");
+    if old_color != new_color {
+        match new_color.as_str() {
+            "black" => output.extend_from_slice(TextType::ColorBlack.command()),
+            "red" => output.extend_from_slice(TextType::ColorRed.command()),
+            _ => {}
+        }
+    }
+
     let old_size = get_string(&old.size, "normal");
     let new_size = get_string(&new.size, "normal");
     if old_size != new_size {
@@ -202,7 +221,7 @@ pub fn process_title(
     title: &Title,
     current_styles: &GlobalStyles,
     encoder: &TextEncoder,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, SectionError> {
     let mut output = Vec::new();
 
     let base_styles = title.styles.as_ref().unwrap_or(current_styles).clone();
@@ -223,7 +242,7 @@ pub fn process_subtitle(
     subtitle: &Subtitle,
     current_styles: &GlobalStyles,
     encoder: &TextEncoder,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, SectionError> {
     let mut output = Vec::new();
 
     let base_styles = subtitle.styles.as_ref().unwrap_or(current_styles).clone();
@@ -244,7 +263,7 @@ pub fn process_text(
     text: &Text,
     current_styles: &GlobalStyles,
     encoder: &TextEncoder,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, SectionError> {
     let mut output = Vec::new();
 
     let effective_styles = text.styles.as_ref().unwrap_or(current_styles).clone();
@@ -262,7 +281,7 @@ pub fn process_line(
     line: &Line,
     current_styles: &GlobalStyles,
     chars_per_line: i32,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, SectionError> {
     let size = current_styles
         .size
         .as_deref()
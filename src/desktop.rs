@@ -1,20 +1,26 @@
 use serde::de::DeserializeOwned;
-use tauri::{plugin::PluginApi, AppHandle, Runtime};
+use tauri::{path::BaseDirectory, plugin::PluginApi, AppHandle, Manager, Runtime};
 
 use crate::error::{Error, Result};
 use crate::models::*;
+use crate::process::font_registry::FontRegistry;
+use crate::process::image_assets::ImageAssets;
+use crate::process::process_nv_graphics::ProcessNvGraphics;
 use crate::process::process_print::ProcessPrint;
 use crate::process::process_print_test::TestPrinter;
+use crate::process::process_validate::ProcessValidate;
 
 pub fn init<R: Runtime, C: DeserializeOwned>(
     app: &AppHandle<R>,
     _api: PluginApi<R, C>,
 ) -> Result<ThermalPrinter<R>> {
-    Ok(ThermalPrinter(app.clone()))
+    let assets = ImageAssets::default();
+    assets.set_resource_dir(app.path().resource_dir().ok());
+    Ok(ThermalPrinter(app.clone(), FontRegistry::default(), assets))
 }
 
 /// Access to the thermal-printer APIs.
-pub struct ThermalPrinter<R: Runtime>(AppHandle<R>);
+pub struct ThermalPrinter<R: Runtime>(AppHandle<R>, FontRegistry, ImageAssets);
 
 impl<R: Runtime> ThermalPrinter<R> {
     pub fn list_thermal_printers(&self) -> Result<Vec<PrinterInfo>> {
@@ -36,25 +42,35 @@ impl<R: Runtime> ThermalPrinter<R> {
     }
 
     pub fn print_thermal_printer(&self, print_job_request: PrintJobRequest) -> Result<()> {
-        let mut process_print = ProcessPrint::new();
+        let mut process_print = ProcessPrint::new()
+            .set_fonts(self.1.clone())
+            .set_assets(self.2.clone());
         let data = process_print
             .generate_document(&print_job_request)
             .map_err(|err| {
                 log::error!("Error generating document: {}", err);
-                Error::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, err))
+                err
             })?;
         #[cfg(target_os = "windows")]
         {
-            crate::desktop_printers::windows::print_raw_data_win(&print_job_request.printer, &data)
-                .map_err(|err| {
-                    log::error!("Error printing raw data: {}", err);
-                    Error::Io(err)
-                })?;
+            crate::desktop_printers::windows::print_raw_data_win(
+                &print_job_request.printer,
+                &data,
+                process_print.pauses(),
+            )
+            .map_err(|err| {
+                log::error!("Error printing raw data: {}", err);
+                err
+            })?;
         }
         #[cfg(not(target_os = "windows"))]
         {
-            crate::desktop_printers::unix_base::print_raw_data(&print_job_request.printer, &data)
-                .map_err(|err| {
+            crate::desktop_printers::unix_base::print_raw_data(
+                &print_job_request.printer,
+                &data,
+                process_print.pauses(),
+            )
+            .map_err(|err| {
                 log::error!("Error printing raw data: {}", err);
                 err
             })?;
@@ -68,17 +84,18 @@ impl<R: Runtime> ThermalPrinter<R> {
             .generate_test_document(&print_job_request)
             .map_err(|err| {
                 log::error!("Error generating test document: {}", err);
-                Error::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, err))
+                Error::InvalidJob(err)
             })?;
         #[cfg(target_os = "windows")]
         {
             crate::desktop_printers::windows::print_raw_data_win(
                 &print_job_request.printer_info.printer,
                 &data,
+                &[],
             )
             .map_err(|err| {
                 log::error!("Error printing raw data: {}", err);
-                Error::Io(err)
+                err
             })?;
         }
         #[cfg(not(target_os = "windows"))]
@@ -86,6 +103,7 @@ impl<R: Runtime> ThermalPrinter<R> {
             crate::desktop_printers::unix_base::print_raw_data(
                 &print_job_request.printer_info.printer,
                 &data,
+                &[],
             )
             .map_err(|err| {
                 log::error!("Error printing raw data: {}", err);
@@ -94,4 +112,91 @@ impl<R: Runtime> ThermalPrinter<R> {
         }
         Ok(())
     }
+
+    pub fn validate_print_job(
+        &self,
+        print_job_request: PrintJobRequest,
+    ) -> Result<ValidationReport> {
+        Ok(ProcessValidate::new()
+            .set_fonts(self.1.clone())
+            .set_assets(self.2.clone())
+            .validate_document(&print_job_request))
+    }
+
+    pub fn register_font(&self, request: RegisterFontRequest) -> Result<()> {
+        self.1.register_request(&request, |path| {
+            self.0
+                .path()
+                .resolve(path, BaseDirectory::Resource)
+                .map_err(|err| {
+                    std::io::Error::new(std::io::ErrorKind::NotFound, err.to_string()).into()
+                })
+        })
+    }
+
+    pub fn register_image_asset(&self, request: RegisterImageAssetRequest) -> Result<()> {
+        self.2.register(&request.name, &request.data)
+    }
+
+    /// Allows `Image.data` to read `file://` paths. Off by default, because any
+    /// webview content allowed to print could otherwise read local files.
+    pub fn allow_file_images(&self, allow: bool) {
+        self.2.set_allow_files(allow)
+    }
+
+    pub fn store_nv_graphic(&self, request: NvGraphicsRequest) -> Result<()> {
+        let data = ProcessNvGraphics::store_document(&request, &self.2)?;
+        self.send_raw_data(&request.printer, &data)
+    }
+
+    pub fn print_nv_graphic(&self, request: NvGraphicsRequest) -> Result<()> {
+        let data = ProcessNvGraphics::print_document(&request)?;
+        self.send_raw_data(&request.printer, &data)
+    }
+
+    pub fn delete_nv_graphic(&self, request: NvGraphicsRequest) -> Result<()> {
+        let data = ProcessNvGraphics::delete_document(&request)?;
+        self.send_raw_data(&request.printer, &data)
+    }
+
+    pub fn list_nv_graphics(&self, request: NvGraphicsRequest) -> Result<NvGraphicsKeyList> {
+        ProcessNvGraphics::list_keys(|data, responses| {
+            self.query_raw_data(&request.printer, data, responses)
+        })
+    }
+
+    pub fn nv_graphics_capacity(&self, request: NvGraphicsRequest) -> Result<NvGraphicsCapacity> {
+        let (data, responses) = ProcessNvGraphics::capacity_query();
+        let response = self.query_raw_data(&request.printer, &data, responses)?;
+        ProcessNvGraphics::capacity(&response)
+    }
+
+    fn send_raw_data(&self, printer: &str, data: &[u8]) -> Result<()> {
+        #[cfg(target_os = "windows")]
+        let result = crate::desktop_printers::windows::print_raw_data_win(printer, data, &[]);
+        #[cfg(not(target_os = "windows"))]
+        let result = crate::desktop_printers::unix_base::print_raw_data(printer, data, &[]);
+        result.map_err(|err| {
+            log::error!("Error printing raw data: {}", err);
+            err
+        })
+    }
+
+    /// Reading back from the printer is only available for CUPS network queues.
+    fn query_raw_data(&self, printer: &str, data: &[u8], responses: usize) -> Result<Vec<u8>> {
+        #[cfg(target_os = "windows")]
+        {
+            let _ = (printer, data, responses);
+            Err(Error::UnsupportedPlatform)
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            crate::desktop_printers::unix_base::query_raw_data(printer, data, responses).map_err(
+                |err| {
+                    log::error!("Error querying printer: {}", err);
+                    err
+                },
+            )
+        }
+    }
 }
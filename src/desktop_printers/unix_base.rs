@@ -1,9 +1,15 @@
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::models::print_job_request::PrinterInfo;
+use crate::models::print_pause::{paced_segments, PrintPause};
 use std::collections::HashMap;
-use std::io::Write;
+use std::io::{Read, Write};
+use std::net::TcpStream;
 use std::process::Command;
 use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+/// Tiempo máximo para conectar y recibir la respuesta de `query_raw_data`
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
 
 fn lpstat(args: &[&str]) -> std::io::Result<String> {
     let output = Command::new("lpstat")
@@ -91,24 +97,115 @@ fn parse_device_line(line: &str) -> Option<(String, (String, String))> {
     ))
 }
 
-pub fn print_raw_data(printer_name: &str, data: &[u8]) -> std::io::Result<()> {
+/// Envía `data` a `lp`, esperando en cada pausa antes de seguir escribiendo
+pub fn print_raw_data(printer_name: &str, data: &[u8], pauses: &[PrintPause]) -> Result<()> {
     let mut child = Command::new("lp")
         .args(["-d", printer_name, "-o", "raw"])
+        .env("LANG", "C")
+        .env("LC_ALL", "C")
         .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
         .spawn()?;
 
-    child
-        .stdin
-        .take()
-        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "Failed to open stdin"))?
-        .write_all(data)?;
+    {
+        let mut stdin = child.stdin.take().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "Failed to open stdin")
+        })?;
+        for (segment, delay_ms) in paced_segments(data, pauses) {
+            stdin.write_all(segment)?;
+            if delay_ms > 0 {
+                stdin.flush()?;
+                std::thread::sleep(std::time::Duration::from_millis(delay_ms as u64));
+            }
+        }
+    }
+
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    // Con LANG=C, CUPS responde "... does not exist" cuando el destino no existe
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("does not exist") {
+        return Err(Error::PrinterNotFound(printer_name.to_string()));
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        format!(
+            "lp command failed with status: {} {}",
+            output.status,
+            stderr.trim()
+        ),
+    )
+    .into())
+}
 
-    let status = child.wait()?;
+/// Envía `data` y lee hasta recibir `responses` respuestas terminadas en NUL.
+///
+/// `lp` no devuelve datos de la impresora, así que solo funciona con colas CUPS
+/// cuyo dispositivo es `socket://host[:puerto]` (RAW/JetDirect), conectando
+/// directamente a la impresora. Con otros dispositivos (USB, serie...) devuelve
+/// `UnsupportedPlatform`.
+pub fn query_raw_data(printer_name: &str, data: &[u8], responses: usize) -> Result<Vec<u8>> {
+    let devices = get_printer_devices()?;
+    let (interface_type, identifier) = devices
+        .get(printer_name)
+        .ok_or_else(|| Error::PrinterNotFound(printer_name.to_string()))?;
+    let address = socket_address(interface_type, identifier).ok_or_else(|| {
+        log::warn!(
+            "Reading from printer '{}' requires a socket:// (network) device (found {})",
+            printer_name,
+            interface_type
+        );
+        Error::UnsupportedPlatform
+    })?;
+
+    let timeout = |err: std::io::Error| match err.kind() {
+        std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => {
+            Error::TransportTimeout(format!("{} ({})", address, err))
+        }
+        _ => Error::Io(err),
+    };
+    let addr = std::net::ToSocketAddrs::to_socket_addrs(address.as_str())?
+        .next()
+        .ok_or_else(|| Error::PrinterNotFound(printer_name.to_string()))?;
+    let mut stream = TcpStream::connect_timeout(&addr, QUERY_TIMEOUT).map_err(timeout)?;
+    stream.write_all(data)?;
+
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    let mut response = Vec::new();
+    let mut buffer = [0u8; 256];
+    while response.iter().filter(|&&b| b == 0x00).count() < responses {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(Error::TransportTimeout(format!(
+                "{} did not answer in time",
+                address
+            )));
+        }
+        stream.set_read_timeout(Some(remaining))?;
+        match stream.read(&mut buffer).map_err(timeout)? {
+            0 => break,
+            n => response.extend_from_slice(&buffer[..n]),
+        }
+    }
+    Ok(response)
+}
 
-    status.success().then_some(()).ok_or_else(|| {
-        std::io::Error::new(
-            std::io::ErrorKind::Other,
-            format!("lp command failed with status: {}", status),
-        )
-    })
+/// `host:puerto` de un dispositivo `socket://host[:puerto][/...]` (9100 por defecto)
+fn socket_address(interface_type: &str, identifier: &str) -> Option<String> {
+    if interface_type != "socket" {
+        return None;
+    }
+    let host = identifier
+        .trim_start_matches("//")
+        .split(['/', '?'])
+        .next()
+        .filter(|host| !host.is_empty())?;
+    if host.contains(':') {
+        Some(host.to_string())
+    } else {
+        Some(format!("{}:9100", host))
+    }
 }
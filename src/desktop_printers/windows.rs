@@ -10,8 +10,16 @@ use winapi::um::winspool::{
     StartPagePrinter, WritePrinter, PRINTER_ENUM_LOCAL, PRINTER_INFO_2W,
 };
 
+use crate::error::Error;
+use crate::models::print_pause::{paced_segments, PrintPause};
 use crate::PrinterInfo;
 
+// Códigos Win32 que se traducen a errores estructurados.
+const ERROR_OUT_OF_PAPER: i32 = 28;
+const ERROR_SEM_TIMEOUT: i32 = 121;
+const ERROR_TIMEOUT: i32 = 1460;
+const ERROR_INVALID_PRINTER_NAME: i32 = 1801;
+
 // Struct intermedia para deserializar el JSON de PowerShell
 #[allow(non_snake_case)]
 #[derive(Debug, Deserialize)]
@@ -193,7 +201,23 @@ fn wide_to_string(wide: LPWSTR) -> String {
     }
 }
 
-pub fn print_raw_data_win(printer_name: &str, data: &[u8]) -> std::io::Result<()> {
+/// Convierte el último error del sistema en el `Error` estructurado correspondiente.
+fn last_print_error(printer_name: &str) -> Error {
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(ERROR_INVALID_PRINTER_NAME) => Error::PrinterNotFound(printer_name.to_string()),
+        Some(ERROR_OUT_OF_PAPER) => Error::PaperOut,
+        Some(ERROR_SEM_TIMEOUT) | Some(ERROR_TIMEOUT) => Error::TransportTimeout(err.to_string()),
+        _ => Error::Io(err),
+    }
+}
+
+/// Envía `data` como trabajo RAW; en cada pausa espera antes del siguiente `WritePrinter`
+pub fn print_raw_data_win(
+    printer_name: &str,
+    data: &[u8],
+    pauses: &[PrintPause],
+) -> crate::error::Result<()> {
     log::debug!(
         "Sending raw data to printer '{}' ({} bytes)",
         printer_name,
@@ -219,7 +243,7 @@ pub fn print_raw_data_win(printer_name: &str, data: &[u8]) -> std::io::Result<()
 
         if result == 0 {
             log::error!("Error opening printer '{}'", printer_name);
-            return Err(std::io::Error::last_os_error());
+            return Err(last_print_error(printer_name));
         }
 
         log::debug!("Opened printer '{}'", printer_name);
@@ -246,7 +270,7 @@ pub fn print_raw_data_win(printer_name: &str, data: &[u8]) -> std::io::Result<()
         if job_id == 0 {
             log::error!("Error starting document on printer '{}'", printer_name);
             ClosePrinter(h_printer);
-            return Err(std::io::Error::last_os_error());
+            return Err(last_print_error(printer_name));
         }
 
         log::debug!("Started print job {}", job_id);
@@ -257,29 +281,37 @@ pub fn print_raw_data_win(printer_name: &str, data: &[u8]) -> std::io::Result<()
             log::error!("Error starting page on printer '{}'", printer_name);
             EndDocPrinter(h_printer);
             ClosePrinter(h_printer);
-            return Err(std::io::Error::last_os_error());
+            return Err(last_print_error(printer_name));
         }
 
-        // Escribir los datos
-        let mut bytes_written: DWORD = 0;
-        let write_result = WritePrinter(
-            h_printer,
-            data.as_ptr() as LPVOID,
-            data.len() as DWORD,
-            &mut bytes_written,
-        );
+        // Escribir los datos, un WritePrinter por segmento
+        let mut total_written: usize = 0;
+        for (segment, delay_ms) in paced_segments(data, pauses) {
+            let mut bytes_written: DWORD = 0;
+            let write_result = WritePrinter(
+                h_printer,
+                segment.as_ptr() as LPVOID,
+                segment.len() as DWORD,
+                &mut bytes_written,
+            );
+
+            if write_result == 0 {
+                log::error!("Error writing to printer '{}'", printer_name);
+                EndPagePrinter(h_printer);
+                EndDocPrinter(h_printer);
+                ClosePrinter(h_printer);
+                return Err(last_print_error(printer_name));
+            }
+            total_written += bytes_written as usize;
 
-        if write_result == 0 {
-            log::error!("Error writing to printer '{}'", printer_name);
-            EndPagePrinter(h_printer);
-            EndDocPrinter(h_printer);
-            ClosePrinter(h_printer);
-            return Err(std::io::Error::last_os_error());
+            if delay_ms > 0 {
+                std::thread::sleep(std::time::Duration::from_millis(delay_ms as u64));
+            }
         }
 
         log::debug!(
             "Wrote {} bytes to printer '{}'",
-            bytes_written,
+            total_written,
             printer_name
         );
 
@@ -1,4 +1,4 @@
-use serde::{ser::Serializer, Serialize};
+use serde::{ser::SerializeMap, ser::Serializer, Serialize};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -11,13 +11,141 @@ pub enum Error {
     #[cfg(mobile)]
     #[error(transparent)]
     PluginInvoke(#[from] tauri::plugin::mobile::PluginInvokeError),
+    /// The job as a whole is unusable (no sections, no printer, ...).
+    #[error("{0}")]
+    InvalidJob(String),
+    /// A section could not be converted to ESC/POS.
+    #[error("Section {index} ({kind}): {reason}")]
+    InvalidSection {
+        index: usize,
+        kind: String,
+        reason: String,
+    },
+    /// A character of a text section cannot be represented with the selected
+    /// `Encode` (only raised when `CodePage.strict` is enabled).
+    #[error("Section {index}: character '{char}' cannot be encoded with the selected code page")]
+    EncodingFailed { index: usize, char: char },
+    /// The image of an `Image`/`Logo` section could not be decoded.
+    #[error("Section {index}: {reason}")]
+    ImageDecode { index: usize, reason: String },
+    #[error("Printer '{0}' not found")]
+    PrinterNotFound(String),
+    #[error("Timed out talking to the printer: {0}")]
+    TransportTimeout(String),
+    #[error("Printer is out of paper")]
+    PaperOut,
 }
 
+impl Error {
+    /// Stable, machine-readable error code. The frontend keys localized messages
+    /// and retry decisions on it, so existing values must never change.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Io(_) => "IO",
+            Error::UnsupportedPlatform => "UNSUPPORTED_PLATFORM",
+            #[cfg(mobile)]
+            Error::PluginInvoke(_) => "PLUGIN_INVOKE",
+            Error::InvalidJob(_) => "INVALID_JOB",
+            Error::InvalidSection { .. } => "INVALID_SECTION",
+            Error::EncodingFailed { .. } => "ENCODING_FAILED",
+            Error::ImageDecode { .. } => "IMAGE_DECODE",
+            Error::PrinterNotFound(_) => "PRINTER_NOT_FOUND",
+            Error::TransportTimeout(_) => "TRANSPORT_TIMEOUT",
+            Error::PaperOut => "PAPER_OUT",
+        }
+    }
+
+    /// Whether re-sending the same job may succeed without changing it.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::Io(_) | Error::TransportTimeout(_) | Error::PaperOut
+        )
+    }
+}
+
+/// Serialized as `{ code, message, retryable, ...details }` so the frontend can
+/// localize the message and decide whether to retry.
 impl Serialize for Error {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(self.to_string().as_ref())
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("message", &self.to_string())?;
+        map.serialize_entry("retryable", &self.is_retryable())?;
+        match self {
+            Error::InvalidSection {
+                index,
+                kind,
+                reason,
+            } => {
+                map.serialize_entry("index", index)?;
+                map.serialize_entry("kind", kind)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Error::EncodingFailed { index, char } => {
+                map.serialize_entry("index", index)?;
+                map.serialize_entry("char", char)?;
+            }
+            Error::ImageDecode { index, reason } => {
+                map.serialize_entry("index", index)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Error::PrinterNotFound(printer) => {
+                map.serialize_entry("printer", printer)?;
+            }
+            _ => {}
+        }
+        map.end()
+    }
+}
+
+/// Error returned by the section processors.
+///
+/// Processors don't know where their section sits in the job; `ProcessPrint`
+/// attaches the index and kind with [`SectionError::at`]. Converts from/into
+/// `String` so it interoperates with the helpers that return `Result<_, String>`.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub(crate) enum SectionError {
+    #[error("{0}")]
+    Invalid(String),
+    #[error("Character '{0}' cannot be encoded with the selected code page")]
+    EncodingFailed(char),
+    #[error("{0}")]
+    ImageDecode(String),
+}
+
+impl SectionError {
+    /// Attaches the section position, producing the public [`Error`].
+    pub(crate) fn at(self, index: usize, kind: &str) -> Error {
+        match self {
+            SectionError::Invalid(reason) => Error::InvalidSection {
+                index,
+                kind: kind.to_string(),
+                reason,
+            },
+            SectionError::EncodingFailed(char) => Error::EncodingFailed { index, char },
+            SectionError::ImageDecode(reason) => Error::ImageDecode { index, reason },
+        }
+    }
+}
+
+impl From<String> for SectionError {
+    fn from(reason: String) -> Self {
+        SectionError::Invalid(reason)
+    }
+}
+
+impl From<&str> for SectionError {
+    fn from(reason: &str) -> Self {
+        SectionError::Invalid(reason.to_string())
+    }
+}
+
+impl From<SectionError> for String {
+    fn from(err: SectionError) -> Self {
+        err.to_string()
     }
 }
@@ -16,11 +16,16 @@ mod desktop_printers;
 mod error;
 mod models;
 mod process;
+mod receipt;
 
 #[cfg(test)]
 mod test;
 
 pub use commands::*;
+pub use commands_esc_pos::text::code_page::CodePage;
+pub use commands_esc_pos::text::encoder::Encode;
+pub use error::{Error, Result};
+pub use receipt::Receipt;
 
 #[cfg(desktop)]
 use desktop::ThermalPrinter;
@@ -44,7 +49,15 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
         .invoke_handler(tauri::generate_handler![
             commands::print_thermal_printer,
             commands::list_thermal_printers,
-            commands::test_thermal_printer
+            commands::test_thermal_printer,
+            commands::validate_print_job,
+            commands::store_nv_graphic,
+            commands::print_nv_graphic,
+            commands::delete_nv_graphic,
+            commands::list_nv_graphics,
+            commands::nv_graphics_capacity,
+            commands::register_font,
+            commands::register_image_asset
         ])
         .setup(|app, api| {
             #[cfg(mobile)]
@@ -1,14 +1,19 @@
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use serde::de::DeserializeOwned;
 use tauri::{
-    plugin::{PluginApi, PluginHandle},
-    AppHandle, Runtime,
+    path::BaseDirectory,
+    plugin::{mobile::PluginInvokeError, PluginApi, PluginHandle},
+    AppHandle, Manager, Runtime,
 };
 
 use crate::error::{Error, Result};
 use crate::models::*;
+use crate::process::font_registry::FontRegistry;
+use crate::process::image_assets::ImageAssets;
+use crate::process::process_nv_graphics::ProcessNvGraphics;
 use crate::process::process_print::ProcessPrint;
 use crate::process::process_print_test::TestPrinter;
+use crate::process::process_validate::ProcessValidate;
 
 pub const OS_NAME: &str = std::env::consts::OS;
 
@@ -29,6 +34,24 @@ struct PrintRawRequest {
     /// Final ESC/POS payload, Base64-encoded for a compact bridge transport.
     /// The Kotlin side decodes it back to the exact same bytes before sending.
     data: String,
+    /// Byte offsets where the native side waits before writing the rest (image
+    /// bands with `band_delay_ms`). Empty for most jobs.
+    pauses: Vec<PrintPause>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct QueryRawRequest {
+    identifier: String,
+    /// Request bytes, Base64-encoded like `PrintRawRequest::data`.
+    data: String,
+    /// Number of NUL-terminated answers to read before resolving.
+    responses: usize,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct QueryRawResponse {
+    /// Bytes received from the printer, Base64-encoded.
+    data: String,
 }
 
 #[cfg(target_os = "ios")]
@@ -36,7 +59,7 @@ tauri::ios_plugin_binding!(init_plugin_thermal_printer);
 
 // initializes the Kotlin or Swift plugin classes
 pub fn init<R: Runtime, C: DeserializeOwned>(
-    _app: &AppHandle<R>,
+    app: &AppHandle<R>,
     api: PluginApi<R, C>,
 ) -> Result<ThermalPrinter<R>> {
     #[cfg(target_os = "android")]
@@ -44,11 +67,17 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
         api.register_android_plugin("com.luis3132.thermal_printer", "Thermal_Printer_Plugin")?;
     #[cfg(target_os = "ios")]
     let handle = api.register_ios_plugin(init_plugin_thermal_printer)?;
-    Ok(ThermalPrinter(handle))
+    // En Android los recursos van dentro del APK y no se pueden leer como
+    // archivos, así que `resource://` solo se resuelve en iOS.
+    let assets = ImageAssets::default();
+    if OS_NAME != "android" {
+        assets.set_resource_dir(app.path().resource_dir().ok());
+    }
+    Ok(ThermalPrinter(handle, FontRegistry::default(), assets))
 }
 
 /// Access to the thermal-printer APIs.
-pub struct ThermalPrinter<R: Runtime>(PluginHandle<R>);
+pub struct ThermalPrinter<R: Runtime>(PluginHandle<R>, FontRegistry, ImageAssets);
 
 impl<R: Runtime> ThermalPrinter<R> {
     pub fn list_thermal_printers(&self) -> Result<Vec<PrinterInfo>> {
@@ -65,17 +94,11 @@ impl<R: Runtime> ThermalPrinter<R> {
     pub fn print_thermal_printer(&self, print_job_request: PrintJobRequest) -> Result<()> {
         if is_supported_mobile() {
             let identifier = print_job_request.printer.clone();
-            let data = ProcessPrint::new()
-                .generate_document(&print_job_request)
-                .map_err(|e| Error::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, e)))?;
-            let _: () = self.0.run_mobile_plugin(
-                "print_raw_data",
-                PrintRawRequest {
-                    identifier,
-                    data: STANDARD.encode(&data),
-                },
-            )?;
-            Ok(())
+            let mut process_print = ProcessPrint::new()
+                .set_fonts(self.1.clone())
+                .set_assets(self.2.clone());
+            let data = process_print.generate_document(&print_job_request)?;
+            self.print_raw_data(identifier, &data, process_print.pauses())
         } else {
             Err(Error::UnsupportedPlatform)
         }
@@ -86,17 +109,129 @@ impl<R: Runtime> ThermalPrinter<R> {
             let identifier = print_job_request.printer_info.printer.clone();
             let data = TestPrinter::new()
                 .generate_test_document(&print_job_request)
-                .map_err(|e| Error::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, e)))?;
-            let _: () = self.0.run_mobile_plugin(
-                "print_raw_data",
-                PrintRawRequest {
-                    identifier,
-                    data: STANDARD.encode(&data),
-                },
-            )?;
-            Ok(())
+                .map_err(Error::InvalidJob)?;
+            self.print_raw_data(identifier, &data, &[])
+        } else {
+            Err(Error::UnsupportedPlatform)
+        }
+    }
+
+    /// Validation runs in Rust only, so it is available on every mobile target.
+    pub fn validate_print_job(
+        &self,
+        print_job_request: PrintJobRequest,
+    ) -> Result<ValidationReport> {
+        Ok(ProcessValidate::new()
+            .set_fonts(self.1.clone())
+            .set_assets(self.2.clone())
+            .validate_document(&print_job_request))
+    }
+
+    /// Resource paths work on iOS; Android packs resources into the APK's assets,
+    /// which can't be read as files, so fonts must be passed as `data` there.
+    pub fn register_font(&self, request: RegisterFontRequest) -> Result<()> {
+        self.1.register_request(&request, |path| {
+            if OS_NAME == "android" {
+                return Err(Error::UnsupportedPlatform);
+            }
+            self.0
+                .app()
+                .path()
+                .resolve(path, BaseDirectory::Resource)
+                .map_err(|err| {
+                    std::io::Error::new(std::io::ErrorKind::NotFound, err.to_string()).into()
+                })
+        })
+    }
+
+    /// Assets live in memory, so `data` works everywhere; `resource://` sources
+    /// are not available on Android (see `register_font`).
+    pub fn register_image_asset(&self, request: RegisterImageAssetRequest) -> Result<()> {
+        self.2.register(&request.name, &request.data)
+    }
+
+    /// Allows `Image.data` to read `file://` paths. Off by default, because any
+    /// webview content allowed to print could otherwise read local files.
+    pub fn allow_file_images(&self, allow: bool) {
+        self.2.set_allow_files(allow)
+    }
+
+    pub fn store_nv_graphic(&self, request: NvGraphicsRequest) -> Result<()> {
+        if is_supported_mobile() {
+            let data = ProcessNvGraphics::store_document(&request, &self.2)?;
+            self.print_raw_data(request.printer, &data, &[])
+        } else {
+            Err(Error::UnsupportedPlatform)
+        }
+    }
+
+    pub fn print_nv_graphic(&self, request: NvGraphicsRequest) -> Result<()> {
+        if is_supported_mobile() {
+            let data = ProcessNvGraphics::print_document(&request)?;
+            self.print_raw_data(request.printer, &data, &[])
+        } else {
+            Err(Error::UnsupportedPlatform)
+        }
+    }
+
+    pub fn delete_nv_graphic(&self, request: NvGraphicsRequest) -> Result<()> {
+        if is_supported_mobile() {
+            let data = ProcessNvGraphics::delete_document(&request)?;
+            self.print_raw_data(request.printer, &data, &[])
         } else {
             Err(Error::UnsupportedPlatform)
         }
     }
+
+    pub fn list_nv_graphics(&self, request: NvGraphicsRequest) -> Result<NvGraphicsKeyList> {
+        if is_supported_mobile() {
+            ProcessNvGraphics::list_keys(|data, responses| {
+                self.query_raw_data(request.printer.clone(), data, responses)
+            })
+        } else {
+            Err(Error::UnsupportedPlatform)
+        }
+    }
+
+    pub fn nv_graphics_capacity(&self, request: NvGraphicsRequest) -> Result<NvGraphicsCapacity> {
+        if is_supported_mobile() {
+            let (data, responses) = ProcessNvGraphics::capacity_query();
+            let response = self.query_raw_data(request.printer, &data, responses)?;
+            ProcessNvGraphics::capacity(&response)
+        } else {
+            Err(Error::UnsupportedPlatform)
+        }
+    }
+
+    fn print_raw_data(&self, identifier: String, data: &[u8], pauses: &[PrintPause]) -> Result<()> {
+        self.0
+            .run_mobile_plugin::<()>(
+                "print_raw_data",
+                PrintRawRequest {
+                    identifier: identifier.clone(),
+                    data: STANDARD.encode(data),
+                    pauses: pauses.to_vec(),
+                },
+            )
+            .map_err(|err| map_invoke_error(&identifier, err))
+    }
+
+    /// Sends `data` and returns what the printer answers (network and Bluetooth
+    /// printers; USB and BLE reject with `UNSUPPORTED_PLATFORM`).
+    fn query_raw_data(&self, identifier: String, data: &[u8], responses: usize) -> Result<Vec<u8>> {
+        let response: QueryRawResponse = self
+            .0
+            .run_mobile_plugin(
+                "query_raw_data",
+                QueryRawRequest {
+                    identifier: identifier.clone(),
+                    data: STANDARD.encode(data),
+                    responses,
+                },
+            )
+            .map_err(|err| map_invoke_error(&identifier, err))?;
+        STANDARD.decode(response.data).map_err(|err| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()).into()
+        })
+    }
 }
@@ -1,7 +1,19 @@
+pub mod nv_graphics_request;
 pub mod paper_size;
 pub mod print_job_request;
+pub mod print_pause;
 pub mod print_sections;
+pub mod printer_profile;
+pub mod register_font_request;
+pub mod register_image_asset_request;
+pub mod validation;
 
+pub use nv_graphics_request::*;
 pub use paper_size::PaperSize;
 pub use print_job_request::*;
+pub use print_pause::PrintPause;
 pub use print_sections::*;
+pub use printer_profile::PrinterProfile;
+pub use register_font_request::RegisterFontRequest;
+pub use register_image_asset_request::RegisterImageAssetRequest;
+pub use validation::*;
@@ -0,0 +1,42 @@
+use crate::models::paper_size::PaperSize;
+use crate::models::print_sections::Image;
+use serde::{Deserialize, Serialize};
+
+/// Request for the NV graphics commands (`store_nv_graphic`, `print_nv_graphic`,
+/// `delete_nv_graphic`, `list_nv_graphics`, `nv_graphics_capacity`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NvGraphicsRequest {
+    /// Printer name (for system printing) or connection configuration
+    pub printer: String,
+    /// Two-character key (ASCII 32-126, e.g. `"LG"`). Required to store and print;
+    /// `delete_nv_graphic` without a key deletes every stored graphic.
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Image to store (`store_nv_graphic` only). Processed like an `Image` section:
+    /// resized to `max_width` or the paper width, then binarized.
+    #[serde(default)]
+    pub image: Option<Image>,
+    /// Print mode (`normal`/`double_width`/`double_height`/`quadruple`) for
+    /// `print_nv_graphic`. Defaults to `normal`.
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// Paper used to size the stored image. Defaults to 80mm.
+    #[serde(default)]
+    pub paper_size: Option<PaperSize>,
+}
+
+/// Keys defined in the printer's NV graphics memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NvGraphicsKeyList {
+    pub keys: Vec<String>,
+    /// `false` when the printer kept reporting more keys (41H) after the follow-up
+    /// requests; `keys` holds the ones read so far.
+    pub complete: bool,
+}
+
+/// NV graphics memory size in bytes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct NvGraphicsCapacity {
+    pub total: u32,
+    pub remaining: u32,
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+/// Dots per millimetre of a 203 DPI thermal head
+pub const DOTS_PER_MM: f32 = 8.0;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PaperSize {
     /// 40mm paper — print width ~32mm, 256 dots @ 203 DPI
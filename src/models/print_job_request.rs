@@ -1,5 +1,6 @@
 use crate::models::paper_size::PaperSize;
 use crate::models::print_sections::PrintSections;
+use crate::models::printer_profile::PrinterProfile;
 use crate::commands_esc_pos::text::code_page::CodePage;
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +11,10 @@ pub struct PrintJobRequest {
     pub sections: Vec<PrintSections>,
     pub options: CodePage,
     pub paper_size: PaperSize,
+    /// Firmware capabilities used by `render_mode: "auto"`. Defaults to a printer
+    /// that supports every native command.
+    #[serde(default)]
+    pub profile: PrinterProfile,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
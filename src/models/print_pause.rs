@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Pause requested while sending a document: after the first `offset` bytes the
+/// transport waits `delay_ms` before writing the rest. Used to pace tall images on
+/// slow links (see `Image::band_delay_ms`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrintPause {
+    pub offset: usize,
+    pub delay_ms: u32,
+}
+
+/// Splits `data` at the pauses: each segment is followed by its delay in ms
+/// (0 for the last one). Pauses must be sorted by offset.
+pub fn paced_segments<'a>(data: &'a [u8], pauses: &[PrintPause]) -> Vec<(&'a [u8], u32)> {
+    let mut segments = Vec::with_capacity(pauses.len() + 1);
+    let mut start = 0;
+    for pause in pauses {
+        let end = pause.offset.clamp(start, data.len());
+        segments.push((&data[start..end], pause.delay_ms));
+        start = end;
+    }
+    segments.push((&data[start..], 0));
+    segments
+}
@@ -13,6 +13,8 @@ pub enum PrintSections {
     Drawer(Drawer),
     GlobalStyles(GlobalStyles),
     Qr(Qr),
+    EmvQr(EmvQr),
+    SepaQr(SepaQr),
     Barcode(Barcode),
     Table(Table),
     DataMatrix(DataMatrixModel),
@@ -22,6 +24,8 @@ pub enum PrintSections {
     MaxiCode(MaxiCode),
     Composite(Composite),
     Image(Image),
+    /// Text shaped with a TrueType/OpenType font and printed as an image.
+    FontText(FontText),
     Logo(Logo),
     Line(Line),
     LineSpacing(LineSpacing),
@@ -30,18 +34,88 @@ pub enum PrintSections {
     TabStops(TabStops),
     LeftMargin(LeftMargin),
     PrintAreaWidth(PrintAreaWidth),
+    PageMode(PageMode),
+    Columns(Columns),
+}
+
+impl PrintSections {
+    /// Variant name as it appears in the JSON (`"Qr"`, `"Table"`, ...). Used to tag
+    /// errors with the kind of section that failed.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PrintSections::Title(_) => "Title",
+            PrintSections::Subtitle(_) => "Subtitle",
+            PrintSections::Text(_) => "Text",
+            PrintSections::Feed(_) => "Feed",
+            PrintSections::Cut(_) => "Cut",
+            PrintSections::Beep(_) => "Beep",
+            PrintSections::Beep2(_) => "Beep2",
+            PrintSections::Drawer(_) => "Drawer",
+            PrintSections::GlobalStyles(_) => "GlobalStyles",
+            PrintSections::Qr(_) => "Qr",
+            PrintSections::EmvQr(_) => "EmvQr",
+            PrintSections::SepaQr(_) => "SepaQr",
+            PrintSections::Barcode(_) => "Barcode",
+            PrintSections::Table(_) => "Table",
+            PrintSections::DataMatrix(_) => "DataMatrix",
+            PrintSections::Pdf417(_) => "Pdf417",
+            PrintSections::Aztec(_) => "Aztec",
+            PrintSections::Gs1Databar2d(_) => "Gs1Databar2d",
+            PrintSections::MaxiCode(_) => "MaxiCode",
+            PrintSections::Composite(_) => "Composite",
+            PrintSections::Image(_) => "Image",
+            PrintSections::FontText(_) => "FontText",
+            PrintSections::Logo(_) => "Logo",
+            PrintSections::Line(_) => "Line",
+            PrintSections::LineSpacing(_) => "LineSpacing",
+            PrintSections::CharSpacing(_) => "CharSpacing",
+            PrintSections::Position(_) => "Position",
+            PrintSections::TabStops(_) => "TabStops",
+            PrintSections::LeftMargin(_) => "LeftMargin",
+            PrintSections::PrintAreaWidth(_) => "PrintAreaWidth",
+            PrintSections::PageMode(_) => "PageMode",
+            PrintSections::Columns(_) => "Columns",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Title {
     pub text: String,
     pub styles: Option<GlobalStyles>,
+    /// Render with a registered font as a bitmap instead of the printer font.
+    #[serde(default)]
+    pub font: Option<TextFont>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Subtitle {
     pub text: String,
     pub styles: Option<GlobalStyles>,
+    /// Render with a registered font as a bitmap instead of the printer font.
+    #[serde(default)]
+    pub font: Option<TextFont>,
+}
+
+/// Font for a `Title` or `Subtitle` rendered as a bitmap. The text follows
+/// `styles.align` (Title defaults to `center`); the other styles don't apply.
+/// Ignored inside `PageMode` and `Columns`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextFont {
+    /// Name the font was registered under with `register_font`.
+    pub name: String,
+    /// Size in typographic points (1/72 inch). Defaults to 18 for Title and 14
+    /// for Subtitle; FontText defaults to 24 dots.
+    #[serde(default)]
+    pub size: Option<f32>,
+    /// 100-900. Variable fonts use their `wght` axis; other fonts are
+    /// emboldened above 400. Title and Subtitle default to 700 unless their
+    /// `styles.bold` is `false` (then 400); FontText defaults to 400.
+    #[serde(default)]
+    pub weight: Option<u16>,
+    /// Extra space between characters, in dots (negative tightens).
+    #[serde(default)]
+    pub letter_spacing: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,6 +162,9 @@ pub struct GlobalStyles {
     /// Double-strike / double-print (`ESC G`). Reinforces bold on printers that
     /// render `ESC E` weakly. Widely supported on generic printers.
     pub double_strike: Option<bool>,
+    /// Print color (`ESC r`) on two-color printers: `black` or `red`.
+    #[serde(default)]
+    pub color: Option<String>,
     /// When `true`, emits `ESC @` (initialize) and re-applies the code page,
     /// resetting the printer to defaults. Takes priority: all other style fields
     /// in the same section are ignored.
@@ -107,6 +184,7 @@ impl Default for GlobalStyles {
             upside_down: Some(false),
             size: Some("normal".to_string()),
             double_strike: Some(false),
+            color: Some("black".to_string()),
             reset: Some(false),
         }
     }
@@ -133,6 +211,127 @@ pub struct Qr {
     pub error_correction: String,
     pub model: u8,
     pub align: Option<String>,
+    /// `native` (default) sends `GS ( k` to the firmware; `raster` encodes the QR
+    /// in Rust and prints it as an image (always Model 2); `auto` uses `raster`
+    /// when the job's `profile.native_qr` is `false`.
+    #[serde(default)]
+    pub render_mode: Option<String>,
+    /// Target symbol width in dots. When set, `size` is ignored and the largest
+    /// module size whose symbol fits (and never wider than the paper) is used.
+    #[serde(default)]
+    pub target_width: Option<u32>,
+    /// Same as `target_width` in millimetres (8 dots/mm, 203 DPI).
+    #[serde(default)]
+    pub target_width_mm: Option<f32>,
+}
+
+/// EMVCo merchant-presented payment QR (PIX, UPI, ...). The payload is built from
+/// the typed fields as TLV data objects ending with a CRC16 and printed through the
+/// `Qr` pipeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmvQr {
+    /// Merchant account information templates (IDs 02-51), e.g. PIX is ID 26 with
+    /// `00 = br.gov.bcb.pix` and `01 = <key>`.
+    pub merchant_accounts: Vec<EmvTemplate>,
+    /// ISO 18245 merchant category code (4 digits). Defaults to `0000`.
+    #[serde(default)]
+    pub merchant_category_code: Option<String>,
+    /// ISO 4217 numeric currency code, e.g. `986` (BRL) or `356` (INR).
+    pub currency: String,
+    /// Transaction amount, e.g. `10.50`. Omitted lets the payer enter it.
+    #[serde(default)]
+    pub amount: Option<String>,
+    /// ISO 3166-1 alpha-2 country code, e.g. `BR`.
+    pub country_code: String,
+    pub merchant_name: String,
+    pub merchant_city: String,
+    #[serde(default)]
+    pub postal_code: Option<String>,
+    /// Additional data field template (ID 62), e.g. `05` = reference label.
+    #[serde(default)]
+    pub additional_data: Vec<EmvField>,
+    /// Point of initiation: `true` = dynamic (`12`, single use), `false` = static
+    /// (`11`). Omitted when not set.
+    #[serde(default)]
+    pub dynamic: Option<bool>,
+    /// QR module size (1-16). Defaults to 6.
+    #[serde(default)]
+    pub size: Option<u8>,
+    /// `L`, `M`, `Q` or `H`. Defaults to `M`.
+    #[serde(default)]
+    pub error_correction: Option<String>,
+    #[serde(default)]
+    pub align: Option<String>,
+    /// Same as `Qr.render_mode`.
+    #[serde(default)]
+    pub render_mode: Option<String>,
+    /// Same as `Qr.target_width`.
+    #[serde(default)]
+    pub target_width: Option<u32>,
+    /// Same as `Qr.target_width_mm`.
+    #[serde(default)]
+    pub target_width_mm: Option<f32>,
+}
+
+/// EMVCo template: either a primitive `value` or nested `fields`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmvTemplate {
+    pub id: u8,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<EmvField>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmvField {
+    pub id: u8,
+    pub value: String,
+}
+
+/// EPC069-12 SEPA credit transfer QR ("GiroCode"), printed through the `Qr`
+/// pipeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SepaQr {
+    /// Beneficiary name (max 70 characters).
+    pub name: String,
+    /// Beneficiary IBAN. Spaces are removed and the check digits verified.
+    pub iban: String,
+    /// Beneficiary BIC (8 or 11 characters). Optional inside the EEA.
+    #[serde(default)]
+    pub bic: Option<String>,
+    /// Amount in euros, e.g. `12.50` (0.01-999999999.99).
+    #[serde(default)]
+    pub amount: Option<String>,
+    /// ISO 20022 purpose code (4 characters).
+    #[serde(default)]
+    pub purpose: Option<String>,
+    /// Structured creditor reference (max 35). Exclusive with `text`.
+    #[serde(default)]
+    pub reference: Option<String>,
+    /// Unstructured remittance text (max 140). Exclusive with `reference`.
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Beneficiary-to-originator information (max 70).
+    #[serde(default)]
+    pub information: Option<String>,
+    /// QR module size (1-16). Defaults to 6.
+    #[serde(default)]
+    pub size: Option<u8>,
+    /// `L`, `M`, `Q` or `H`. Defaults to `M` (the EPC guideline's level).
+    #[serde(default)]
+    pub error_correction: Option<String>,
+    #[serde(default)]
+    pub align: Option<String>,
+    /// Same as `Qr.render_mode`.
+    #[serde(default)]
+    pub render_mode: Option<String>,
+    /// Same as `Qr.target_width`.
+    #[serde(default)]
+    pub target_width: Option<u32>,
+    /// Same as `Qr.target_width_mm`.
+    #[serde(default)]
+    pub target_width_mm: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -143,12 +342,29 @@ pub struct Barcode {
     pub height: u8,
     pub text_position: String,
     pub align: Option<String>,
+    /// `native` (default) sends `GS k` to the firmware; `raster` encodes the
+    /// barcode in Rust and prints it as an image with quiet zones and a bitmap HRI
+    /// font, so it looks the same on every printer; `auto` uses `raster` when the
+    /// job's profile says the firmware lacks the symbology.
+    #[serde(default)]
+    pub render_mode: Option<String>,
+    /// EAN-13, EAN-8, UPC-A and UPC-E: append the computed check digit when the
+    /// data omits it instead of leaving it to the printer. A check digit that is
+    /// present is always verified.
+    #[serde(default)]
+    pub auto_check_digit: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataMatrixModel {
     pub data: String,
     pub size: u8,
+    /// `native` (default) sends `GS ( k`; `raster` encodes an ECC 200 symbol in
+    /// Rust (smallest square size, `size` dots per module) and prints it as an
+    /// image; `auto` uses `raster` when the job's `profile.native_data_matrix`
+    /// is `false`.
+    #[serde(default)]
+    pub render_mode: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -159,6 +375,12 @@ pub struct Pdf417 {
     pub width: u8,
     pub height: u8,
     pub error_correction: u8,
+    /// `native` (default) sends `GS ( k`; `raster` encodes the symbol in Rust
+    /// honoring `columns`, `rows` and `error_correction` (modules `width` dots
+    /// wide, rows `height` × `width` dots tall) and prints it as an image; `auto`
+    /// uses `raster` when the job's `profile.native_pdf417` is `false`.
+    #[serde(default)]
+    pub render_mode: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -169,6 +391,12 @@ pub struct Aztec {
     pub size: u8,
     pub error_correction: u8,
     pub align: Option<String>,
+    /// `native` (default) sends `GS ( k`; `raster` encodes the symbol in Rust
+    /// honoring `mode`, `layers` and `error_correction` (`size` dots per module)
+    /// and prints it as an image; `auto` uses `raster` when the job's
+    /// `profile.native_aztec` is `false`.
+    #[serde(default)]
+    pub render_mode: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -184,6 +412,12 @@ pub struct MaxiCode {
     pub data: String,
     pub mode: u8,
     pub align: Option<String>,
+    /// `native` (default) sends `GS ( k`; `raster` encodes the symbol in Rust
+    /// with the same `mode` and prints the hexagonal grid as an image at its
+    /// nominal size (about 1 inch); `auto` uses `raster` when the job's
+    /// `profile.native_maxicode` is `false`.
+    #[serde(default)]
+    pub render_mode: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -194,13 +428,123 @@ pub struct Composite {
     pub align: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Image {
     pub data: String,
     pub max_width: i32,
     pub align: String,
     pub dithering: bool,
     pub size: String,
+    /// Algorithm used when `dithering` is `true`: `floyd_steinberg` (default),
+    /// `atkinson`, `jarvis_judice_ninke`, `stucki`, `sierra`, `bayer4`, `bayer8`
+    /// or `threshold`.
+    #[serde(default)]
+    pub dithering_algorithm: Option<String>,
+    /// Black/white cut-off 0-255 (default 127). Also shifts the Bayer matrices.
+    #[serde(default)]
+    pub threshold: Option<u8>,
+    /// -100 to 100 (default 0).
+    #[serde(default)]
+    pub brightness: Option<f32>,
+    /// -100 to 100 (default 0).
+    #[serde(default)]
+    pub contrast: Option<f32>,
+    /// 0.1 to 10 (default 1). Values above 1 lighten the midtones.
+    #[serde(default)]
+    pub gamma: Option<f32>,
+    /// Unsharp mask strength 0 to 10 (default 0).
+    #[serde(default)]
+    pub sharpen: Option<f32>,
+    /// Swap black and white before dithering.
+    #[serde(default)]
+    pub invert: Option<bool>,
+    /// Keep only this rectangle of the source image, in source pixels. Applied
+    /// first.
+    #[serde(default)]
+    pub crop: Option<ImageCrop>,
+    /// Rotate clockwise by `90`, `180` or `270` degrees, after `crop`. Use 90 or
+    /// 270 to print wide images (signatures, charts) down the roll.
+    #[serde(default)]
+    pub rotate: Option<u16>,
+    /// Remove the white (or transparent) margins around the content, after
+    /// `rotate`.
+    #[serde(default)]
+    pub trim: Option<bool>,
+    /// White space added around the printed image, in dots. The image is scaled
+    /// to fit `max_width` minus the left and right padding.
+    #[serde(default)]
+    pub padding: Option<ImagePadding>,
+    /// Command used to send the image: `raster` (`GS v 0`, default),
+    /// `bit_image_8` / `bit_image_24` (`ESC *` columns) or `graphics` (`GS ( L`).
+    /// Ignored inside `set_logo`; use the logo's own `command_mode` there.
+    #[serde(default)]
+    pub command_mode: Option<String>,
+    /// Split the image into bands of this many rows, each sent as its own
+    /// command. Rounded down to a multiple of 24 in the `bit_image_*` modes.
+    /// Printed output is identical. Default: one command for the whole image.
+    #[serde(default)]
+    pub band_height: Option<u32>,
+    /// Pause in milliseconds after each band, for slow transports. Requires
+    /// `band_height`.
+    #[serde(default)]
+    pub band_delay_ms: Option<u32>,
+}
+
+/// Rectangle of an `Image` to keep, in pixels of the source image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageCrop {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// White space around an `Image`, in dots. Missing sides default to 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImagePadding {
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+    pub left: u32,
+}
+
+/// Text rendered with a TrueType/OpenType font and printed as a raster image, for
+/// scripts the printer's code pages cannot print (Arabic, Hebrew, Thai,
+/// Devanagari, ...). Shaping handles ligatures, contextual forms and combining
+/// marks; right-to-left and mixed-direction text is reordered with the Unicode
+/// bidi algorithm. Lines wrap at word boundaries to the paper width.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontText {
+    pub text: String,
+    /// Font file (TTF/OTF) as base64, with or without a `data:` prefix, or a
+    /// font registered with `register_font`.
+    pub font: FontTextFont,
+    /// Font size in typographic points (1/72 inch), like the registered font's
+    /// `size`, which it overrides. Default 24 dots (about 8.5 pt), the height of
+    /// native font A.
+    #[serde(default)]
+    pub size: Option<f32>,
+    /// `left`, `center` or `right`. Default: `right` for right-to-left
+    /// paragraphs, `left` otherwise.
+    #[serde(default)]
+    pub align: Option<String>,
+    /// Base paragraph direction: `auto` (default, from the first strong
+    /// character), `ltr` or `rtl`.
+    #[serde(default)]
+    pub direction: Option<String>,
+    /// Extra space between lines, in dots (default 0).
+    #[serde(default)]
+    pub line_spacing: Option<u32>,
+}
+
+/// Font of a FontText section: a JSON string is the font file in base64, an
+/// object is a registered font as in Title/Subtitle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FontTextFont {
+    Data(String),
+    Registered(TextFont),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -217,6 +561,15 @@ pub struct Logo {
     /// can later be printed with a `Logo` section without `set_logo`.
     #[serde(default)]
     pub set_logo: Option<Image>,
+    /// `nv_bit_image` (`FS q` / `FS p`, default) or `graphics` (`GS ( L` NV
+    /// graphics). In `graphics` mode `key_code` (1-99) also selects the key
+    /// `set_logo` stores under, sent as two ASCII digits.
+    #[serde(default)]
+    pub command_mode: Option<String>,
+    /// Two-character NV graphics key (ASCII 32-126, e.g. `"LG"`) used instead of
+    /// `key_code` in `graphics` mode. Same keys as `store_nv_graphic`.
+    #[serde(default)]
+    pub key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -265,3 +618,66 @@ pub struct PrintAreaWidth {
     /// Printable area width, in dots.
     pub value: u16,
 }
+
+/// Page mode block (`ESC L` ... `ESC FF`): child elements are placed at absolute
+/// dot positions inside a page area, optionally rotated with `ESC T`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageMode {
+    /// Page area origin, in dots from the left edge of the printable area.
+    #[serde(default)]
+    pub x: u16,
+    /// Page area origin, in dots from the top of the page.
+    #[serde(default)]
+    pub y: u16,
+    /// Page area width in dots. Defaults to the paper width.
+    #[serde(default)]
+    pub width: Option<u16>,
+    /// Page area height in dots. Defaults to the bottom edge of the lowest element;
+    /// required for `bottom_to_top` and `top_to_bottom`.
+    #[serde(default)]
+    pub height: Option<u16>,
+    /// `left_to_right` (default), `bottom_to_top`, `right_to_left` or `top_to_bottom`.
+    /// Element coordinates are relative to the rotated page.
+    #[serde(default)]
+    pub direction: Option<String>,
+    pub elements: Vec<PageElement>,
+}
+
+/// A child of a [`PageMode`] section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageElement {
+    /// Left edge, in dots from the page area origin.
+    pub x: u16,
+    /// Top edge, in dots from the page area origin.
+    pub y: u16,
+    /// Width of the element's box in dots: text wraps to it and codes/images are
+    /// limited to it. Defaults to the rest of the page area after `x`.
+    #[serde(default)]
+    pub width: Option<u16>,
+    /// `Title`, `Subtitle`, `Text`, `Qr`, `Barcode` or `Image`.
+    pub content: PrintSections,
+}
+
+/// Side-by-side layout, e.g. a QR code on the left and order details on the right.
+/// Printed as a single page mode band whose height is the tallest column.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Columns {
+    pub columns: Vec<Column>,
+    /// Space between columns in dots. Defaults to 16.
+    #[serde(default)]
+    pub gap: Option<u16>,
+}
+
+/// A column of a [`Columns`] section. Its content is stacked top to bottom.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Column {
+    /// Width in dots. Columns without a width share the remaining paper width.
+    #[serde(default)]
+    pub width: Option<u16>,
+    /// Vertical alignment inside the band: `top` (default), `middle` or `bottom`.
+    #[serde(default)]
+    pub valign: Option<String>,
+    /// `Title`, `Subtitle`, `Text`, `Qr`, `Barcode` or `Image`. Text is wrapped
+    /// to the column width and images are scaled down to it.
+    pub content: Vec<PrintSections>,
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Capabilities of the target printer's firmware. Sections with
+/// `render_mode: "auto"` use it to choose between the native ESC/POS command and
+/// software rendering (a raster image that every printer can print).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrinterProfile {
+    /// The firmware implements QR codes (`GS ( k` cn=49). Many cheap 58 mm
+    /// printers don't and print garbage instead.
+    #[serde(default = "default_true")]
+    pub native_qr: bool,
+    /// The firmware implements the classic 1D barcodes (`GS k` 65-73).
+    #[serde(default = "default_true")]
+    pub native_barcodes: bool,
+    /// The firmware implements the GS1 barcodes (`GS k` 74-78: GS1-128 and
+    /// GS1 DataBar), which many printers lack.
+    #[serde(default = "default_true")]
+    pub native_gs1_barcodes: bool,
+    /// The firmware implements DataMatrix (`GS ( k` cn=54).
+    #[serde(default = "default_true")]
+    pub native_data_matrix: bool,
+    /// The firmware implements Aztec Code (`GS ( k` cn=53).
+    #[serde(default = "default_true")]
+    pub native_aztec: bool,
+    /// The firmware implements PDF417 (`GS ( k` cn=48).
+    #[serde(default = "default_true")]
+    pub native_pdf417: bool,
+    /// The firmware implements MaxiCode (`GS ( k` cn=50).
+    #[serde(default = "default_true")]
+    pub native_maxicode: bool,
+    /// The firmware implements download graphics (`GS ( L` fn 83/85). Identical
+    /// `Image` sections repeated in a job are then sent once and printed by key.
+    /// Off by default: many cheap printers don't implement these functions.
+    #[serde(default)]
+    pub download_graphics: bool,
+    /// The firmware implements multiple-tone graphics (`GS ( L` fn 112 with
+    /// `a = 52`), e.g. some Epson TM-T88 models. `Image` sections with
+    /// `command_mode: "multi_tone"` are dithered to black and white otherwise,
+    /// which is the default.
+    #[serde(default)]
+    pub multi_tone: bool,
+    /// The printer has a second (red) color for graphics (`GS ( L` fn 112 with
+    /// `c = 50`). `Image` sections with `command_mode: "two_color"` are sent as
+    /// black and red planes, and dithered to black and white otherwise, which is
+    /// the default.
+    #[serde(default)]
+    pub two_color: bool,
+}
+
+impl Default for PrinterProfile {
+    fn default() -> Self {
+        Self {
+            native_qr: true,
+            native_barcodes: true,
+            native_gs1_barcodes: true,
+            native_data_matrix: true,
+            native_aztec: true,
+            native_pdf417: true,
+            native_maxicode: true,
+            download_graphics: false,
+            multi_tone: false,
+            two_color: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Request for `register_font`. Exactly one of `path` or `data` must be set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterFontRequest {
+    /// Name used by `TextFont.name` in print jobs. Registering the same name
+    /// again replaces the font.
+    pub name: String,
+    /// Font file (TTF/OTF) relative to the app's resource directory, e.g.
+    /// `"fonts/Brand-Bold.ttf"`. Not available on Android; use `data` there.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Font file as base64, with or without a `data:` prefix.
+    #[serde(default)]
+    pub data: Option<String>,
+}
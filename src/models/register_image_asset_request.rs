@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Request for `register_image_asset`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterImageAssetRequest {
+    /// Name used as `Image.data = "asset:<name>"` in print jobs. Registering the
+    /// same name again replaces the image.
+    pub name: String,
+    /// The image in any form `Image.data` accepts: base64, `file://` or
+    /// `resource://`. It is read once and kept in memory.
+    pub data: String,
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Result of `validate_print_job`: every problem found in the job, without printing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// `true` when there are no errors (warnings do not make a job invalid).
+    pub valid: bool,
+    /// Problems that make `print_thermal_printer` fail.
+    pub errors: Vec<ValidationIssue>,
+    /// Problems that still print, but probably not as intended.
+    pub warnings: Vec<ValidationIssue>,
+}
+
+/// A single validation finding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// Stable code: the same codes as the print errors (`INVALID_SECTION`,
+    /// `ENCODING_FAILED`, ...) plus warning-only codes such as `UNENCODABLE_CHAR`.
+    pub code: String,
+    /// Zero-based section index. `None` for job-level findings (`printer`, `sections`).
+    pub index: Option<usize>,
+    /// Section variant (`"Qr"`, `"Table"`, ...). `None` for job-level findings.
+    pub kind: Option<String>,
+    /// Location of the offending value, e.g. `sections[2].body[1][0].text`.
+    pub path: String,
+    pub message: String,
+}
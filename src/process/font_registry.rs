@@ -0,0 +1,64 @@
+use crate::commands_esc_pos::image_escpos::image_processor::ImageProcessor;
+use crate::commands_esc_pos::text::font_text::FontText;
+use crate::error::{Error, Result};
+use crate::models::register_font_request::RegisterFontRequest;
+use crate::process::image_assets::resource_path;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+/// Fuentes registradas con `register_font`, por nombre. El plugin guarda una
+/// instancia como estado y cada `ProcessPrint` recibe una copia (comparten los
+/// datos), para que Title y Subtitle las usen sin enviar la fuente en cada trabajo.
+#[derive(Debug, Clone, Default)]
+pub struct FontRegistry(Arc<RwLock<HashMap<String, Arc<Vec<u8>>>>>);
+
+impl FontRegistry {
+    /// Registra (o reemplaza) la fuente `name`. Falla si no es TTF/OTF válida.
+    pub fn register(&self, name: &str, font: Vec<u8>) -> Result<()> {
+        if name.is_empty() {
+            return Err(Error::InvalidJob("Font name cannot be empty".to_string()));
+        }
+        let font = Arc::new(font);
+        FontText::new(font.clone()).map_err(|err| {
+            Error::InvalidJob(format!("Font '{}' could not be loaded: {}", name, err))
+        })?;
+        self.0
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(name.to_string(), font);
+        Ok(())
+    }
+
+    /// Registra la fuente de `request`: `data` en base64 o `path`, que `resolve`
+    /// convierte en ruta dentro de los recursos de la app (depende de la plataforma).
+    /// `path` no puede salir de los recursos.
+    pub fn register_request(
+        &self,
+        request: &RegisterFontRequest,
+        resolve: impl FnOnce(&str) -> Result<PathBuf>,
+    ) -> Result<()> {
+        let font = match (&request.path, &request.data) {
+            (Some(path), None) => {
+                resource_path(path).map_err(Error::InvalidJob)?;
+                std::fs::read(resolve(path)?)?
+            }
+            (None, Some(data)) => ImageProcessor::decode_base64(data)
+                .map_err(|e| Error::InvalidJob(format!("Error decoding font base64: {}", e)))?,
+            _ => {
+                return Err(Error::InvalidJob(
+                    "register_font requires either path or data".to_string(),
+                ))
+            }
+        };
+        self.register(&request.name, font)
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<Vec<u8>>> {
+        self.0
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(name)
+            .cloned()
+    }
+}
@@ -0,0 +1,213 @@
+use crate::commands_esc_pos::image_escpos::image_options::ImageOptions;
+use crate::commands_esc_pos::image_escpos::image_processor::ImageProcessor;
+use crate::error::{Error, Result, SectionError};
+use image::{DynamicImage, ImageBuffer, Luma};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Prefijos de `Image.data` además de base64
+pub const FILE_PREFIX: &str = "file://";
+pub const RESOURCE_PREFIX: &str = "resource://";
+pub const ASSET_PREFIX: &str = "asset:";
+
+/// Máximo de mapas de bits procesados en caché; al superarlo se descarta el más
+/// antiguo
+const MAX_CACHED_BITMAPS: usize = 32;
+
+/// Imagen ya redimensionada y binarizada (0 = negro, 255 = blanco)
+pub type Bitmap = ImageBuffer<Luma<u8>, Vec<u8>>;
+
+/// Origen de las imágenes: resuelve `Image.data` (base64, `file://`,
+/// `resource://`, `asset:<nombre>` o SVG en línea) y guarda en caché los mapas de bits ya
+/// redimensionados y binarizados. El plugin guarda una instancia como estado y
+/// cada `ProcessPrint` recibe una copia (comparten caché y recursos).
+#[derive(Debug, Clone, Default)]
+pub struct ImageAssets(Arc<Inner>);
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// Carpeta de recursos de la app (`None` si la plataforma no la expone)
+    resource_dir: RwLock<Option<PathBuf>>,
+    /// Si se pueden leer rutas `file://` (desactivado por defecto)
+    allow_files: AtomicBool,
+    /// Imágenes registradas con `register_image_asset`, por nombre
+    assets: RwLock<HashMap<String, Arc<Vec<u8>>>>,
+    cache: Mutex<BitmapCache>,
+}
+
+/// Caché por huella (contenido + ancho + ajustes), con orden de inserción
+#[derive(Debug, Default)]
+struct BitmapCache {
+    bitmaps: HashMap<String, Arc<Bitmap>>,
+    order: VecDeque<String>,
+}
+
+impl ImageAssets {
+    /// Carpeta contra la que se resuelven las rutas `resource://`
+    pub fn set_resource_dir(&self, resource_dir: Option<PathBuf>) {
+        *self
+            .0
+            .resource_dir
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = resource_dir;
+    }
+
+    /// Permite leer imágenes `file://`. Desactivado por defecto: cualquier
+    /// contenido de la webview con permiso para imprimir podría leer archivos del
+    /// equipo, así que solo lo activa el código Rust de la app.
+    pub fn set_allow_files(&self, allow: bool) {
+        self.0.allow_files.store(allow, Ordering::Relaxed);
+    }
+
+    /// Registra (o reemplaza) la imagen `name`. `data` acepta las mismas formas
+    /// que `Image.data`; la imagen se lee una vez y se guarda en memoria.
+    pub fn register(&self, name: &str, data: &str) -> Result<()> {
+        if name.is_empty() {
+            return Err(Error::InvalidJob("Asset name cannot be empty".to_string()));
+        }
+        let invalid = |err: SectionError| {
+            Error::InvalidJob(format!(
+                "Image asset '{}' could not be loaded: {}",
+                name, err
+            ))
+        };
+        let bytes = self.bytes(data).map_err(invalid)?;
+        ImageProcessor::decode_image(&bytes).map_err(invalid)?;
+        self.0
+            .assets
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(name.to_string(), bytes);
+        Ok(())
+    }
+
+    /// Contenido del archivo de imagen al que apunta `data`
+    pub fn bytes(&self, data: &str) -> std::result::Result<Arc<Vec<u8>>, SectionError> {
+        if let Some(name) = data.strip_prefix(ASSET_PREFIX) {
+            return self
+                .0
+                .assets
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .get(name)
+                .cloned()
+                .ok_or_else(|| {
+                    SectionError::ImageDecode(format!(
+                        "Image asset '{}' is not registered (call register_image_asset first)",
+                        name
+                    ))
+                });
+        }
+
+        let path = if let Some(path) = data.strip_prefix(FILE_PREFIX) {
+            if !self.0.allow_files.load(Ordering::Relaxed) {
+                return Err(SectionError::ImageDecode(
+                    "file:// images are disabled (enable them with allow_file_images)".to_string(),
+                ));
+            }
+            file_path(path)
+        } else if let Some(path) = data.strip_prefix(RESOURCE_PREFIX) {
+            let resource_dir = self
+                .0
+                .resource_dir
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone()
+                .ok_or_else(|| {
+                    SectionError::ImageDecode(
+                        "resource:// images are not available on this platform".to_string(),
+                    )
+                })?;
+            resource_dir.join(resource_path(path).map_err(SectionError::ImageDecode)?)
+        } else if data.trim_start().starts_with('<') {
+            // SVG en línea: el texto del documento tal cual
+            return Ok(Arc::new(data.as_bytes().to_vec()));
+        } else {
+            return ImageProcessor::decode_base64(data)
+                .map(Arc::new)
+                .map_err(|e| SectionError::ImageDecode(format!("Error decoding base64: {}", e)));
+        };
+
+        std::fs::read(&path).map(Arc::new).map_err(|e| {
+            SectionError::ImageDecode(format!("Error reading image '{}': {}", path.display(), e))
+        })
+    }
+
+    /// Decodifica la imagen de `data` (con la orientación EXIF aplicada)
+    pub fn load(&self, data: &str) -> std::result::Result<DynamicImage, SectionError> {
+        ImageProcessor::decode_image(&self.bytes(data)?)
+    }
+
+    /// Imagen de `data` transformada, redimensionada a `max_width` y binarizada con
+    /// `options`. El resultado se guarda en caché por contenido, ancho y ajustes, así que
+    /// repetir la misma imagen (aunque llegue de otra forma) no la vuelve a procesar.
+    pub fn bitmap(
+        &self,
+        data: &str,
+        max_width: u32,
+        options: &ImageOptions,
+    ) -> std::result::Result<Arc<Bitmap>, SectionError> {
+        let bytes = self.bytes(data)?;
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        let key = format!("{:016x}:{}:{:?}", hasher.finish(), max_width, options);
+
+        if let Some(bitmap) = self.cache().bitmaps.get(&key) {
+            return Ok(bitmap.clone());
+        }
+
+        let width = options.transform.content_width(max_width)?;
+        let resized = ImageProcessor::decode_image_at(&bytes, width, &options.transform)?;
+        let processed = ImageProcessor::process_image(&resized, width, options);
+        let bitmap = Arc::new(options.transform.pad(processed));
+
+        let mut cache = self.cache();
+        if cache.bitmaps.insert(key.clone(), bitmap.clone()).is_none() {
+            cache.order.push_back(key);
+            while cache.order.len() > MAX_CACHED_BITMAPS {
+                if let Some(oldest) = cache.order.pop_front() {
+                    cache.bitmaps.remove(&oldest);
+                }
+            }
+        }
+        Ok(bitmap)
+    }
+
+    fn cache(&self) -> std::sync::MutexGuard<'_, BitmapCache> {
+        self.0
+            .cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Comprueba que `path` sea relativo y no salga de la carpeta de recursos: sin
+/// `..`, raíz ni unidad (`join` con una ruta absoluta reemplazaría la carpeta).
+pub fn resource_path(path: &str) -> std::result::Result<&Path, String> {
+    let relative = Path::new(path);
+    let inside = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if path.is_empty() || !inside {
+        return Err(format!(
+            "Resource path '{}' must be relative to the resource directory, without '..'",
+            path
+        ));
+    }
+    Ok(relative)
+}
+
+/// Ruta de una URL `file://` sin el esquema. En Windows `file:///C:/logo.png`
+/// deja `/C:/logo.png`, así que se quita la barra inicial ante una letra de unidad.
+fn file_path(path: &str) -> PathBuf {
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[0] == b'/' && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+        PathBuf::from(&path[1..])
+    } else {
+        PathBuf::from(path)
+    }
+}
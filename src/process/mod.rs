@@ -1,2 +1,6 @@
+pub mod font_registry;
+pub mod image_assets;
+pub mod process_nv_graphics;
 pub mod process_print;
 pub mod process_print_test;
+pub mod process_validate;
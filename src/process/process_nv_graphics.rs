@@ -0,0 +1,137 @@
+use crate::commands_esc_pos::control::printer_control::PrinterControl;
+use crate::commands_esc_pos::image_escpos::nv_graphics;
+use crate::commands_esc_pos::image_escpos::ImageMode;
+use crate::error::{Error, Result, SectionError};
+use crate::models::nv_graphics_request::{
+    NvGraphicsCapacity, NvGraphicsKeyList, NvGraphicsRequest,
+};
+use crate::models::paper_size::PaperSize;
+use crate::process::image_assets::ImageAssets;
+
+/// Máximo de bloques de la lista de claves que se piden antes de rendirse
+const MAX_KEY_LIST_BLOCKS: usize = 64;
+
+/// Genera los documentos de los comandos de gráficos NV (`GS ( L`), que se envían
+/// a la impresora fuera de un trabajo de impresión.
+pub struct ProcessNvGraphics;
+
+impl ProcessNvGraphics {
+    /// Guarda `request.image` bajo `request.key` (fn 67)
+    pub fn store_document(request: &NvGraphicsRequest, assets: &ImageAssets) -> Result<Vec<u8>> {
+        let key = Self::key(request)?;
+        let image = request
+            .image
+            .as_ref()
+            .ok_or_else(|| Error::InvalidJob("store_nv_graphic requires an image".to_string()))?;
+        let paper_size = request.paper_size.as_ref().unwrap_or(&PaperSize::DEFAULT);
+
+        let binary =
+            nv_graphics::binarize(image, paper_size.pixels_width(), assets).map_err(invalid)?;
+        let mut document = PrinterControl::initialize();
+        document.extend(nv_graphics::define_command(&binary, key).map_err(invalid)?);
+        Ok(document)
+    }
+
+    /// Imprime el gráfico `request.key` con el modo indicado (fn 69)
+    pub fn print_document(request: &NvGraphicsRequest) -> Result<Vec<u8>> {
+        let key = Self::key(request)?;
+        let mode = match request.mode.as_deref().unwrap_or("normal") {
+            "normal" => ImageMode::Normal,
+            "double_width" => ImageMode::DoubleWidth,
+            "double_height" => ImageMode::DoubleHeight,
+            "quadruple" => ImageMode::Quadruple,
+            other => {
+                return Err(Error::InvalidJob(format!(
+                    "Unknown NV graphics print mode '{}'",
+                    other
+                )))
+            }
+        };
+
+        let mut document = PrinterControl::initialize();
+        document.extend(nv_graphics::print_command(key, mode));
+        document.extend(PrinterControl::line_feed());
+        Ok(document)
+    }
+
+    /// Borra el gráfico `request.key` (fn 66) o, sin clave, todos (fn 65)
+    pub fn delete_document(request: &NvGraphicsRequest) -> Result<Vec<u8>> {
+        match &request.key {
+            Some(key) => Ok(nv_graphics::delete_command(
+                nv_graphics::key_bytes(key).map_err(invalid)?,
+            )),
+            None => Ok(nv_graphics::delete_all_command()),
+        }
+    }
+
+    /// Petición de la lista de claves y número de respuestas (terminadas en NUL)
+    /// que hay que leer
+    pub fn key_list_query() -> (Vec<u8>, usize) {
+        (nv_graphics::key_list_request(), 1)
+    }
+
+    pub fn key_list(response: &[u8]) -> Result<NvGraphicsKeyList> {
+        let (keys, complete) = nv_graphics::parse_key_list(response)?;
+        Ok(NvGraphicsKeyList { keys, complete })
+    }
+
+    /// Lista completa de claves: mientras la impresora responda 41H (quedan
+    /// claves) se vuelve a pedir fn 64 con `query`, que envía la petición y lee
+    /// el número de respuestas indicado. Si un bloque no trae claves nuevas o se
+    /// llega a `MAX_KEY_LIST_BLOCKS`, devuelve lo leído con `complete: false`.
+    pub fn list_keys(
+        mut query: impl FnMut(&[u8], usize) -> Result<Vec<u8>>,
+    ) -> Result<NvGraphicsKeyList> {
+        let (data, responses) = Self::key_list_query();
+        let mut list = NvGraphicsKeyList {
+            keys: Vec::new(),
+            complete: false,
+        };
+        for _ in 0..MAX_KEY_LIST_BLOCKS {
+            let block = Self::key_list(&query(&data, responses)?)?;
+            let before = list.keys.len();
+            for key in block.keys {
+                if !list.keys.contains(&key) {
+                    list.keys.push(key);
+                }
+            }
+            if block.complete {
+                list.complete = true;
+                break;
+            }
+            if list.keys.len() == before {
+                break;
+            }
+        }
+        Ok(list)
+    }
+
+    /// Peticiones de capacidad total y libre, que se leen juntas
+    pub fn capacity_query() -> (Vec<u8>, usize) {
+        let mut request = nv_graphics::capacity_request();
+        request.extend(nv_graphics::remaining_capacity_request());
+        (request, 2)
+    }
+
+    pub fn capacity(response: &[u8]) -> Result<NvGraphicsCapacity> {
+        Ok(NvGraphicsCapacity {
+            total: nv_graphics::parse_capacity(response, nv_graphics::CAPACITY_IDENTIFIER)?,
+            remaining: nv_graphics::parse_capacity(
+                response,
+                nv_graphics::REMAINING_CAPACITY_IDENTIFIER,
+            )?,
+        })
+    }
+
+    fn key(request: &NvGraphicsRequest) -> Result<[u8; 2]> {
+        let key = request
+            .key
+            .as_deref()
+            .ok_or_else(|| Error::InvalidJob("NV graphics key is required".to_string()))?;
+        nv_graphics::key_bytes(key).map_err(invalid)
+    }
+}
+
+fn invalid(err: SectionError) -> Error {
+    Error::InvalidJob(err.to_string())
+}
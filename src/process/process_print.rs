@@ -5,21 +5,58 @@ use crate::commands_esc_pos::codes::data_matrix::data_matrix as data_matrix_cmd;
 use crate::commands_esc_pos::codes::gs1_databar_2d::gs1_databar_2d as gs1_databar_2d_cmd;
 use crate::commands_esc_pos::codes::maxicode::maxicode as maxicode_cmd;
 use crate::commands_esc_pos::codes::pdf417::pdf417 as pdf417_cmd;
+use crate::commands_esc_pos::codes::qr::emv as emv_qr_cmd;
 use crate::commands_esc_pos::codes::qr::qr as qr_cmd;
+use crate::commands_esc_pos::codes::qr::sepa as sepa_qr_cmd;
+use crate::commands_esc_pos::control::columns as columns_cmd;
+use crate::commands_esc_pos::control::page_mode as page_mode_cmd;
 use crate::commands_esc_pos::control::printer_control::PrinterControl;
 use crate::commands_esc_pos::image_escpos::image_code as image_cmd;
+use crate::commands_esc_pos::image_escpos::image_command::ImageCommandMode;
 use crate::commands_esc_pos::image_escpos::logo as logo_cmd;
+use crate::commands_esc_pos::image_escpos::nv_graphics;
 use crate::commands_esc_pos::text::encoder::TextEncoder;
+use crate::commands_esc_pos::text::font_text as font_text_cmd;
 use crate::commands_esc_pos::text::table as table_cmd;
 use crate::commands_esc_pos::text::text_type::{
     get_styles_diff, process_line, process_subtitle, process_text, process_title,
 };
+use crate::error::{Error, Result, SectionError};
 use crate::models::print_job_request::PrintJobRequest;
-use crate::models::print_sections::{GlobalStyles, PrintSections};
+use crate::models::print_pause::PrintPause;
+use crate::models::print_sections::{GlobalStyles, Image, PrintSections};
+use crate::models::printer_profile::PrinterProfile;
+use crate::process::font_registry::FontRegistry;
+use crate::process::image_assets::ImageAssets;
+use std::collections::HashMap;
+
+/// Máximo de imágenes distintas enviadas como gráficos descargados (claves "00"-"99")
+const MAX_DOWNLOAD_GRAPHICS: usize = 100;
+
+/// Imagen repetida en el trabajo que se envía como gráfico descargado
+#[derive(Debug, Clone, Copy)]
+enum DownloadGraphic {
+    /// Aún no se ha definido: la primera copia envía fn 83 con esta clave
+    Pending([u8; 2]),
+    /// Ya definida: las copias solo envían fn 85
+    Defined([u8; 2]),
+    /// No se pudo definir (p. ej. demasiado alta): se imprime como imagen normal
+    Fallback,
+}
 
 pub struct ProcessPrint {
     current_styles: GlobalStyles,
     print_job_context: PrintJobRequest,
+    /// Pausas del último documento generado, con su posición en el documento
+    pauses: Vec<PrintPause>,
+    /// Pausas de la sección en curso, relativas al inicio de la sección
+    section_pauses: Vec<PrintPause>,
+    /// Imágenes repetidas del trabajo, por su JSON (ver `plan_download_graphics`)
+    download_graphics: HashMap<String, DownloadGraphic>,
+    /// Fuentes registradas para Title/Subtitle con `font`
+    fonts: FontRegistry,
+    /// Imágenes registradas, recursos de la app y caché de mapas de bits
+    assets: ImageAssets,
 }
 
 impl ProcessPrint {
@@ -31,45 +68,138 @@ impl ProcessPrint {
                 sections: Vec::new(),
                 options: Default::default(),
                 paper_size: crate::PaperSize::DEFAULT,
+                profile: Default::default(),
             },
+            pauses: Vec::new(),
+            section_pauses: Vec::new(),
+            download_graphics: HashMap::new(),
+            fonts: FontRegistry::default(),
+            assets: ImageAssets::default(),
         }
     }
 
-    pub fn generate_document(&mut self, print_job: &PrintJobRequest) -> Result<Vec<u8>, String> {
-        if print_job.sections.is_empty() {
-            return Err("No sections to print".to_string());
-        }
+    /// Fuentes registradas con `register_font` que pueden usar Title y Subtitle
+    pub fn set_fonts(mut self, fonts: FontRegistry) -> Self {
+        self.fonts = fonts;
+        self
+    }
+
+    /// Origen de las imágenes que no llegan en base64 (`file://`, `resource://`,
+    /// `asset:`) y caché compartida de imágenes procesadas
+    pub fn set_assets(mut self, assets: ImageAssets) -> Self {
+        self.assets = assets;
+        self
+    }
+
+    /// Pausas pedidas por el último documento generado (`band_delay_ms`), para que
+    /// el transporte espere entre franjas de imagen. Ordenadas por posición.
+    pub fn pauses(&self) -> &[PrintPause] {
+        &self.pauses
+    }
+
+    pub fn generate_document(&mut self, print_job: &PrintJobRequest) -> Result<Vec<u8>> {
         if print_job.printer.is_empty() {
-            return Err("Printer not specified".to_string());
+            return Err(Error::InvalidJob("Printer not specified".to_string()));
+        }
+
+        self.generate_bytes(print_job)
+    }
+
+    /// Igual que `generate_document` pero sin exigir impresora: solo produce los
+    /// bytes ESC/POS (lo usa `Receipt::to_bytes`).
+    pub(crate) fn generate_bytes(&mut self, print_job: &PrintJobRequest) -> Result<Vec<u8>> {
+        if print_job.sections.is_empty() {
+            return Err(Error::InvalidJob("No sections to print".to_string()));
         }
 
         self.print_job_context = print_job.clone();
+        self.pauses.clear();
         let encoder = TextEncoder::from_code_page(&print_job.options);
 
         let mut document: Vec<u8> = Vec::new();
         document.extend(PrinterControl::initialize());
         document.extend(print_job.options.escpos_command());
+        self.plan_download_graphics(print_job);
+        if !self.download_graphics.is_empty() {
+            // Liberar la memoria de gráficos descargados de trabajos anteriores
+            document.extend(nv_graphics::delete_all_download_command());
+        }
 
-        for section in &print_job.sections {
-            let section_data = self.process_print_section(section, &encoder)?;
+        for (index, section) in print_job.sections.iter().enumerate() {
+            let section_data = self
+                .process_print_section(section, &encoder)
+                .map_err(|e| e.at(index, section.kind()))?;
+            let start = document.len();
+            self.pauses
+                .extend(self.section_pauses.drain(..).map(|pause| PrintPause {
+                    offset: start + pause.offset,
+                    ..pause
+                }));
             document.extend(section_data);
         }
 
         Ok(document)
     }
 
+    /// Procesa todas las secciones sin detenerse en la primera que falla y devuelve
+    /// el error de cada sección fallida junto a su índice. No genera documento.
+    pub(crate) fn collect_section_errors(
+        &mut self,
+        print_job: &PrintJobRequest,
+    ) -> Vec<(usize, SectionError)> {
+        self.print_job_context = print_job.clone();
+        self.download_graphics.clear();
+        let encoder = TextEncoder::from_code_page(&print_job.options);
 
+        print_job
+            .sections
+            .iter()
+            .enumerate()
+            .filter_map(|(index, section)| {
+                let result = self.process_print_section(section, &encoder);
+                self.section_pauses.clear();
+                result.err().map(|err| (index, err))
+            })
+            .collect()
+    }
 
     fn process_print_section(
         &mut self,
         section: &PrintSections,
         encoder: &TextEncoder,
-    ) -> Result<Vec<u8>, String> {
+    ) -> std::result::Result<Vec<u8>, SectionError> {
         match section {
-            PrintSections::Title(title) => process_title(title, &self.current_styles, encoder),
-            PrintSections::Subtitle(subtitle) => {
-                process_subtitle(subtitle, &self.current_styles, encoder)
-            }
+            PrintSections::Title(title) => match &title.font {
+                // Con fuente se respeta `styles.align` de la sección (por defecto centrado)
+                Some(font) => {
+                    let align = title.styles.as_ref().and_then(|s| s.align.as_deref());
+                    font_text_cmd::process_heading(
+                        &title.text,
+                        font,
+                        Some(align.unwrap_or("center")),
+                        heading_bold(title.styles.as_ref()),
+                        font_text_cmd::TITLE_SIZE,
+                        &self.fonts,
+                        self.print_job_context.paper_size.pixels_width(),
+                    )
+                }
+                None => process_title(title, &self.current_styles, encoder),
+            },
+            PrintSections::Subtitle(subtitle) => match &subtitle.font {
+                Some(font) => {
+                    let styles = subtitle.styles.as_ref().unwrap_or(&self.current_styles);
+                    font_text_cmd::process_heading(
+                        &subtitle.text,
+                        font,
+                        styles.align.as_deref(),
+                        heading_bold(subtitle.styles.as_ref()),
+                        font_text_cmd::SUBTITLE_SIZE,
+                        &self.fonts,
+                        self.print_job_context.paper_size.pixels_width(),
+                    )
+                }
+                None => process_subtitle(subtitle, &self.current_styles, encoder),
+            },
             PrintSections::Text(text) => process_text(text, &self.current_styles, encoder),
             PrintSections::Line(line) => process_line(
                 line,
@@ -95,28 +225,63 @@ impl ProcessPrint {
                     Ok(diff)
                 }
             }
-            PrintSections::Qr(qr) => qr_cmd::process_section(qr, &self.current_styles),
-            PrintSections::Barcode(barcode) => {
-                barcode_cmd::process_section(barcode, &self.current_styles)
+            PrintSections::Qr(qr) => qr_cmd::process_section(
+                qr,
+                &self.current_styles,
+                &self.print_job_context.profile,
+                self.print_job_context.paper_size.pixels_width(),
+            ),
+            PrintSections::EmvQr(emv) => emv_qr_cmd::process_section(
+                emv,
+                &self.current_styles,
+                &self.print_job_context.profile,
+                self.print_job_context.paper_size.pixels_width(),
+            ),
+            PrintSections::SepaQr(sepa) => sepa_qr_cmd::process_section(
+                sepa,
+                &self.current_styles,
+                &self.print_job_context.profile,
+                self.print_job_context.paper_size.pixels_width(),
+            ),
+            PrintSections::Barcode(barcode) => barcode_cmd::process_section(
+                barcode,
+                &self.current_styles,
+                &self.print_job_context.profile,
+                self.print_job_context.paper_size.pixels_width(),
+            ),
+            PrintSections::Pdf417(pdf417) => {
+                pdf417_cmd::process_section(pdf417, &self.print_job_context.profile)
+            }
+            PrintSections::DataMatrix(data_matrix) => {
+                data_matrix_cmd::process_section(data_matrix, &self.print_job_context.profile)
             }
-            PrintSections::Pdf417(pdf417) => pdf417_cmd::process_section(pdf417),
-            PrintSections::DataMatrix(data_matrix) => data_matrix_cmd::process_section(data_matrix),
-            PrintSections::Aztec(aztec) => aztec_cmd::process_section(aztec, &self.current_styles),
+            PrintSections::Aztec(aztec) => aztec_cmd::process_section(
+                aztec,
+                &self.current_styles,
+                &self.print_job_context.profile,
+            ),
             PrintSections::Gs1Databar2d(databar) => {
                 gs1_databar_2d_cmd::process_section(databar, &self.current_styles)
             }
-            PrintSections::MaxiCode(maxicode) => {
-                maxicode_cmd::process_section(maxicode, &self.current_styles)
-            }
+            PrintSections::MaxiCode(maxicode) => maxicode_cmd::process_section(
+                maxicode,
+                &self.current_styles,
+                &self.print_job_context.profile,
+            ),
             PrintSections::Composite(composite) => {
                 composite_cmd::process_section(composite, &self.current_styles)
             }
-            PrintSections::Image(imagen) => {
-                image_cmd::process_section(imagen, self.print_job_context.paper_size.pixels_width())
-            }
-            PrintSections::Logo(logo) => {
-                logo_cmd::process_section(logo, self.print_job_context.paper_size.pixels_width())
-            }
+            PrintSections::Image(imagen) => self.process_image(imagen),
+            PrintSections::FontText(font_text) => font_text_cmd::process_section(
+                font_text,
+                &self.fonts,
+                self.print_job_context.paper_size.pixels_width(),
+            ),
+            PrintSections::Logo(logo) => logo_cmd::process_section(
+                logo,
+                self.print_job_context.paper_size.pixels_width(),
+                &self.assets,
+            ),
             PrintSections::Table(table) => table_cmd::process_section(
                 table,
                 self.print_job_context.paper_size.chars_per_line(),
@@ -128,6 +293,141 @@ impl ProcessPrint {
             PrintSections::TabStops(t) => PrinterControl::process_tab_stops(t),
             PrintSections::LeftMargin(m) => PrinterControl::process_left_margin(m),
             PrintSections::PrintAreaWidth(w) => PrinterControl::process_print_area_width(w),
+            PrintSections::PageMode(page) => page_mode_cmd::process_section(
+                page,
+                &self.current_styles,
+                encoder,
+                self.print_job_context.paper_size.pixels_width(),
+                &self.print_job_context.profile,
+                &self.assets,
+            ),
+            PrintSections::Columns(columns) => columns_cmd::process_section(
+                columns,
+                &self.current_styles,
+                encoder,
+                self.print_job_context.paper_size.pixels_width(),
+                &self.print_job_context.profile,
+                &self.assets,
+            ),
+        }
+    }
+
+    /// Busca las secciones `Image` idénticas que aparecen más de una vez para
+    /// enviarlas como gráficos descargados (`GS ( L` fn 83/85): la imagen se define
+    /// una vez y las copias solo envían la clave. Requiere
+    /// `profile.download_graphics`; las imágenes por franjas y las de `ESC *` se
+    /// envían siempre completas.
+    fn plan_download_graphics(&mut self, print_job: &PrintJobRequest) {
+        self.download_graphics.clear();
+        if !print_job.profile.download_graphics {
+            return;
+        }
+
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for section in &print_job.sections {
+            let PrintSections::Image(imagen) = section else {
+                continue;
+            };
+            let printed = image_cmd::for_profile(imagen, &print_job.profile);
+            let eligible = printed.band_height.is_none()
+                && matches!(
+                    ImageCommandMode::from_name(printed.command_mode.as_deref()),
+                    Ok(ImageCommandMode::Raster | ImageCommandMode::Graphics)
+                );
+            let Some(id) = eligible
+                .then(|| download_graphic_id(imagen, &print_job.profile))
+                .flatten()
+            else {
+                continue;
+            };
+            match counts.iter_mut().find(|(known, _)| *known == id) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((id, 1)),
+            }
+        }
+
+        let repeated = counts.into_iter().filter(|(_, count)| *count > 1);
+        for (index, (id, _)) in repeated.take(MAX_DOWNLOAD_GRAPHICS).enumerate() {
+            let key = [b'0' + (index / 10) as u8, b'0' + (index % 10) as u8];
+            self.download_graphics
+                .insert(id, DownloadGraphic::Pending(key));
         }
     }
+
+    fn process_image(&mut self, imagen: &Image) -> std::result::Result<Vec<u8>, SectionError> {
+        let id = if self.download_graphics.is_empty() {
+            None
+        } else {
+            download_graphic_id(imagen, &self.print_job_context.profile)
+        };
+        let imagen = &*image_cmd::for_profile(imagen, &self.print_job_context.profile);
+        let paper_width = self.print_job_context.paper_size.pixels_width();
+
+        let graphic = id.and_then(|id| {
+            let graphic = self.download_graphics.get(&id).copied();
+            graphic.map(|graphic| (id, graphic))
+        });
+        match graphic {
+            Some((_, DownloadGraphic::Defined(key))) => {
+                return image_cmd::process_section_download(
+                    imagen,
+                    paper_width,
+                    key,
+                    false,
+                    &self.assets,
+                );
+            }
+            Some((id, DownloadGraphic::Pending(key))) => {
+                let result = image_cmd::process_section_download(
+                    imagen,
+                    paper_width,
+                    key,
+                    true,
+                    &self.assets,
+                );
+                let next = match result {
+                    Ok(_) => DownloadGraphic::Defined(key),
+                    Err(_) => DownloadGraphic::Fallback,
+                };
+                self.download_graphics.insert(id, next);
+                if result.is_ok() {
+                    return result;
+                }
+            }
+            Some((_, DownloadGraphic::Fallback)) | None => {}
+        }
+
+        image_cmd::process_section_bands(imagen, paper_width, &self.assets)
+            .map(|bands| self.pace(bands, imagen.band_delay_ms.unwrap_or(0)))
+    }
+
+    /// Une las franjas de una sección y, si hay retardo, anota una pausa tras
+    /// cada franja salvo la última
+    fn pace(&mut self, bands: Vec<Vec<u8>>, delay_ms: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        let count = bands.len();
+        for (index, band) in bands.into_iter().enumerate() {
+            data.extend(band);
+            if delay_ms > 0 && index + 1 < count {
+                self.section_pauses.push(PrintPause {
+                    offset: data.len(),
+                    delay_ms,
+                });
+            }
+        }
+        data
+    }
+}
+
+/// Negrita de un Title/Subtitle con fuente: como los encabezados nativos, salvo
+/// que la sección pida `bold: false`
+fn heading_bold(styles: Option<&GlobalStyles>) -> bool {
+    styles.and_then(|styles| styles.bold).unwrap_or(true)
+}
+
+/// Identifica una sección `Image` por el JSON de la imagen que se imprimirá de
+/// verdad (tras `for_profile`): dos secciones con el mismo JSON generan
+/// exactamente los mismos bytes. Lo usan tanto el plan como el proceso.
+fn download_graphic_id(imagen: &Image, profile: &PrinterProfile) -> Option<String> {
+    serde_json::to_string(&*image_cmd::for_profile(imagen, profile)).ok()
 }
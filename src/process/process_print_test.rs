@@ -12,6 +12,7 @@ use crate::commands_esc_pos::text::table;
 use crate::commands_esc_pos::text::text_type::TextType;
 use crate::models::print_job_request::PrintJobRequest;
 use crate::models::print_sections::{Image as ImageSection, Table, Text};
+use crate::process::image_assets::ImageAssets;
 use crate::TestPrintRequest;
 
 pub struct TestPrinter {
@@ -27,6 +28,7 @@ impl TestPrinter {
                     sections: vec![],
                     options: Default::default(),
                     paper_size: crate::PaperSize::DEFAULT,
+                    profile: Default::default(),
                 },
                 include_text: true,
                 include_custom_text: false,
@@ -513,7 +515,7 @@ impl TestPrinter {
             .set_height(60)
             .set_width(2)
             .set_text_position(BarcodeTextPosition::Below);
-        document.extend(barcode.get_command());
+        document.extend(barcode.get_command().map_err(|e| e.to_string())?);
 
         document.extend(b"\n");
 
@@ -524,7 +526,7 @@ impl TestPrinter {
             .set_height(60)
             .set_width(2)
             .set_text_position(BarcodeTextPosition::Below);
-        document.extend(barcode.get_command());
+        document.extend(barcode.get_command().map_err(|e| e.to_string())?);
 
         document.extend(b"\n");
 
@@ -534,7 +536,7 @@ impl TestPrinter {
             .set_height(60)
             .set_width(2)
             .set_text_position(BarcodeTextPosition::Below);
-        document.extend(barcode.get_command());
+        document.extend(barcode.get_command().map_err(|e| e.to_string())?);
 
         document.extend(b"\n\n");
         document.extend(TextType::AlignLeft.command());
@@ -550,7 +552,7 @@ impl TestPrinter {
             .set_height(50)
             .set_width(2)
             .set_text_position(BarcodeTextPosition::Below);
-        document.extend(ean13.get_command());
+        document.extend(ean13.get_command().map_err(|e| e.to_string())?);
         document.extend(b"\n\n");
 
         // CODE39
@@ -559,7 +561,7 @@ impl TestPrinter {
             .set_height(50)
             .set_width(2)
             .set_text_position(BarcodeTextPosition::Below);
-        document.extend(code39.get_command());
+        document.extend(code39.get_command().map_err(|e| e.to_string())?);
         document.extend(b"\n\n");
 
         // GS1-128 (requiere firmware compatible)
@@ -568,7 +570,7 @@ impl TestPrinter {
             .set_height(50)
             .set_width(2)
             .set_text_position(BarcodeTextPosition::Below);
-        document.extend(gs1_128.get_command());
+        document.extend(gs1_128.get_command().map_err(|e| e.to_string())?);
         document.extend(b"\n\n");
 
         // GS1 DataBar Omnidirectional (requiere firmware compatible)
@@ -577,7 +579,7 @@ impl TestPrinter {
             .set_height(50)
             .set_width(2)
             .set_text_position(BarcodeTextPosition::Below);
-        document.extend(databar.get_command());
+        document.extend(databar.get_command().map_err(|e| e.to_string())?);
         document.extend(b"\n\n");
 
         document.extend(TextType::AlignLeft.command());
@@ -693,10 +695,11 @@ impl TestPrinter {
             align: "center".to_string(),
             dithering: true,
             size: "normal".to_string(),
+            ..Default::default()
         };
 
         // 1) Guardar en memoria NV con la clave 1 (FS q).
-        match NvLogo::get_define_command(&logo_image, paper_width) {
+        match NvLogo::get_define_command(&logo_image, paper_width, &ImageAssets::default()) {
             Ok(define_cmd) => {
                 document.extend(define_cmd);
 
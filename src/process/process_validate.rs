@@ -0,0 +1,496 @@
+use crate::commands_esc_pos::codes::barcode::BarcodeType;
+use crate::commands_esc_pos::codes::qr::{emv, sepa, QRErrorCorrection};
+use crate::commands_esc_pos::image_escpos::image_command::ImageCommandMode;
+use crate::commands_esc_pos::image_escpos::image_options::ImageOptions;
+use crate::commands_esc_pos::image_escpos::logo::LogoCommandMode;
+use crate::commands_esc_pos::image_escpos::nv_graphics;
+use crate::commands_esc_pos::text::encoder::TextEncoder;
+use crate::models::print_job_request::PrintJobRequest;
+use crate::models::print_sections::{Barcode, Image, PrintSections, Qr, Table};
+use crate::models::validation::{ValidationIssue, ValidationReport};
+use crate::process::font_registry::FontRegistry;
+use crate::process::image_assets::ImageAssets;
+use crate::process::process_print::ProcessPrint;
+
+/// Aviso: el carácter no se puede codificar y se enviará como UTF-8.
+pub const WARNING_UNENCODABLE_CHAR: &str = "UNENCODABLE_CHAR";
+/// Aviso: la imagen es más ancha que el papel y se reducirá o recortará.
+pub const WARNING_IMAGE_TOO_WIDE: &str = "IMAGE_TOO_WIDE";
+/// Aviso: `column_widths` no coincide con `columns` y se ignora.
+pub const WARNING_COLUMN_WIDTHS_IGNORED: &str = "COLUMN_WIDTHS_IGNORED";
+
+const CODE_INVALID_JOB: &str = "INVALID_JOB";
+const CODE_INVALID_SECTION: &str = "INVALID_SECTION";
+const CODE_ENCODING_FAILED: &str = "ENCODING_FAILED";
+const CODE_IMAGE_DECODE: &str = "IMAGE_DECODE";
+
+/// Valida un trabajo completo sin imprimir: recorre todas las secciones y
+/// acumula todos los errores y avisos en lugar de parar en el primero.
+pub struct ProcessValidate {
+    report: ValidationReport,
+    encoder: TextEncoder,
+    chars_per_line: i32,
+    pixels_width: i32,
+    fonts: FontRegistry,
+    assets: ImageAssets,
+}
+
+/// Sección que se está validando: índice y tipo para etiquetar los hallazgos.
+#[derive(Clone, Copy)]
+struct SectionRef {
+    index: usize,
+    kind: &'static str,
+}
+
+impl SectionRef {
+    fn path(&self, field: &str) -> String {
+        if field.is_empty() {
+            format!("sections[{}]", self.index)
+        } else {
+            format!("sections[{}].{}", self.index, field)
+        }
+    }
+}
+
+impl ProcessValidate {
+    pub fn new() -> Self {
+        Self {
+            report: ValidationReport::default(),
+            encoder: TextEncoder::from_code_page(&Default::default()),
+            chars_per_line: crate::PaperSize::DEFAULT.chars_per_line(),
+            pixels_width: crate::PaperSize::DEFAULT.pixels_width(),
+            fonts: FontRegistry::default(),
+            assets: ImageAssets::default(),
+        }
+    }
+
+    /// Fuentes registradas, para comprobar los Title/Subtitle con `font`
+    pub fn set_fonts(mut self, fonts: FontRegistry) -> Self {
+        self.fonts = fonts;
+        self
+    }
+
+    /// Imágenes registradas y recursos, para leer las Image que no son base64
+    pub fn set_assets(mut self, assets: ImageAssets) -> Self {
+        self.assets = assets;
+        self
+    }
+
+    pub fn validate_document(&mut self, print_job: &PrintJobRequest) -> ValidationReport {
+        self.report = ValidationReport::default();
+        self.encoder = TextEncoder::from_code_page(&print_job.options);
+        self.chars_per_line = print_job.paper_size.chars_per_line();
+        self.pixels_width = print_job.paper_size.pixels_width();
+
+        if print_job.printer.is_empty() {
+            self.job_error("printer", "Printer not specified");
+        }
+        if print_job.sections.is_empty() {
+            self.job_error("sections", "No sections to print");
+        }
+
+        for (index, section) in print_job.sections.iter().enumerate() {
+            let section_ref = SectionRef {
+                index,
+                kind: section.kind(),
+            };
+            self.validate_section(section_ref, section);
+        }
+
+        // Cualquier otro fallo de generación que las comprobaciones específicas no
+        // detecten se reporta a nivel de sección.
+        for (index, err) in ProcessPrint::new()
+            .set_fonts(self.fonts.clone())
+            .set_assets(self.assets.clone())
+            .collect_section_errors(print_job)
+        {
+            let already_reported = self.report.errors.iter().any(|e| e.index == Some(index));
+            if !already_reported {
+                let section_ref = SectionRef {
+                    index,
+                    kind: print_job.sections[index].kind(),
+                };
+                let code = err.clone().at(index, section_ref.kind).code();
+                self.error(section_ref, code, "", err.to_string());
+            }
+        }
+
+        self.report.errors.sort_by_key(|issue| issue.index);
+        self.report.warnings.sort_by_key(|issue| issue.index);
+        self.report.valid = self.report.errors.is_empty();
+        std::mem::take(&mut self.report)
+    }
+
+    fn validate_section(&mut self, section_ref: SectionRef, section: &PrintSections) {
+        match section {
+            // Con `font` el texto se renderiza como imagen: no pasa por la página de códigos
+            PrintSections::Title(title) if title.font.is_none() => {
+                self.check_text(section_ref, "text", &title.text)
+            }
+            PrintSections::Subtitle(subtitle) if subtitle.font.is_none() => {
+                self.check_text(section_ref, "text", &subtitle.text)
+            }
+            PrintSections::Text(text) => self.check_text(section_ref, "text", &text.text),
+            PrintSections::Line(line) => self.check_text(section_ref, "character", &line.character),
+            PrintSections::Qr(qr) => self.check_qr(section_ref, qr),
+            PrintSections::EmvQr(emv) => match emv::to_qr(emv) {
+                Ok(qr) => self.check_qr(section_ref, &qr),
+                Err(e) => self.error(section_ref, CODE_INVALID_SECTION, "", e.to_string()),
+            },
+            PrintSections::SepaQr(sepa) => match sepa::to_qr(sepa) {
+                Ok(qr) => self.check_qr(section_ref, &qr),
+                Err(e) => self.error(section_ref, CODE_INVALID_SECTION, "", e.to_string()),
+            },
+            PrintSections::Barcode(barcode) => self.check_barcode(section_ref, barcode),
+            PrintSections::Table(table) => self.check_table(section_ref, table),
+            PrintSections::Image(image) => self.check_image(section_ref, "", image),
+            PrintSections::Logo(logo) => {
+                if let Err(err) = LogoCommandMode::from_name(logo.command_mode.as_deref()) {
+                    self.error(
+                        section_ref,
+                        CODE_INVALID_SECTION,
+                        "command_mode",
+                        err.to_string(),
+                    );
+                }
+                if let Some(Err(err)) = logo.key.as_deref().map(nv_graphics::key_bytes) {
+                    self.error(section_ref, CODE_INVALID_SECTION, "key", err.to_string());
+                }
+                if let Some(image) = &logo.set_logo {
+                    self.check_image(section_ref, "set_logo", image);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_text(&mut self, section_ref: SectionRef, field: &str, text: &str) {
+        let mut reported: Vec<char> = Vec::new();
+        for ch in text.chars() {
+            if reported.contains(&ch) || self.encoder.can_encode(ch) {
+                continue;
+            }
+            reported.push(ch);
+
+            if self.encoder.is_strict() {
+                self.error(
+                    section_ref,
+                    CODE_ENCODING_FAILED,
+                    field,
+                    format!(
+                        "Character '{}' cannot be encoded with the selected code page",
+                        ch
+                    ),
+                );
+            } else {
+                self.warning(
+                    section_ref,
+                    WARNING_UNENCODABLE_CHAR,
+                    field,
+                    format!(
+                        "Character '{}' cannot be encoded with the selected code page and will be sent as UTF-8",
+                        ch
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_qr(&mut self, section_ref: SectionRef, qr: &Qr) {
+        if qr.data.is_empty() {
+            self.error(
+                section_ref,
+                CODE_INVALID_SECTION,
+                "data",
+                "QR data cannot be empty",
+            );
+            return;
+        }
+
+        let error_correction = QRErrorCorrection::from_name(&qr.error_correction);
+        if qr.data.len() > error_correction.max_data_len() {
+            self.error(
+                section_ref,
+                CODE_INVALID_SECTION,
+                "data",
+                format!(
+                    "QR data length {} exceeds maximum {} for error correction level '{}'",
+                    qr.data.len(),
+                    error_correction.max_data_len(),
+                    qr.error_correction
+                ),
+            );
+        }
+    }
+
+    fn check_barcode(&mut self, section_ref: SectionRef, barcode: &Barcode) {
+        if barcode.data.is_empty() {
+            self.error(
+                section_ref,
+                CODE_INVALID_SECTION,
+                "data",
+                "Barcode data cannot be empty",
+            );
+        }
+        if barcode.height == 0 {
+            self.error(
+                section_ref,
+                CODE_INVALID_SECTION,
+                "height",
+                "Barcode height must be greater than 0",
+            );
+        }
+
+        let barcode_type = BarcodeType::from_name(&barcode.barcode_type);
+        if barcode_type.requires_numeric_data() && !barcode.data.chars().all(|c| c.is_ascii_digit())
+        {
+            self.error(
+                section_ref,
+                CODE_INVALID_SECTION,
+                "data",
+                format!(
+                    "Barcode type '{}' only accepts numeric digits",
+                    barcode.barcode_type
+                ),
+            );
+        }
+    }
+
+    fn check_table(&mut self, section_ref: SectionRef, table: &Table) {
+        let num_columns = table.columns as usize;
+
+        if let Some(widths) = &table.column_widths {
+            let total: i32 = widths.iter().map(|&width| i32::from(width)).sum();
+            if total != self.chars_per_line {
+                self.error(
+                    section_ref,
+                    CODE_INVALID_SECTION,
+                    "column_widths",
+                    format!(
+                        "column_widths sum ({}) must equal paper chars_per_line ({})",
+                        total, self.chars_per_line
+                    ),
+                );
+            }
+            if widths.len() != num_columns {
+                self.warning(
+                    section_ref,
+                    WARNING_COLUMN_WIDTHS_IGNORED,
+                    "column_widths",
+                    format!(
+                        "column_widths has {} entries but {} columns declared; columns will be split evenly",
+                        widths.len(),
+                        num_columns
+                    ),
+                );
+            }
+        }
+
+        if let Some(header) = &table.header {
+            if !header.is_empty() && header.len() != num_columns {
+                self.error(
+                    section_ref,
+                    CODE_INVALID_SECTION,
+                    "header",
+                    format!(
+                        "Table header has {} cells but {} columns declared",
+                        header.len(),
+                        num_columns
+                    ),
+                );
+            }
+            for (col_idx, cell) in header.iter().enumerate() {
+                self.check_text(
+                    section_ref,
+                    &format!("header[{}].text", col_idx),
+                    &cell.text,
+                );
+            }
+        }
+
+        for (row_idx, row) in table.body.iter().enumerate() {
+            if row.len() != num_columns {
+                self.error(
+                    section_ref,
+                    CODE_INVALID_SECTION,
+                    &format!("body[{}]", row_idx),
+                    format!(
+                        "Table row {} has {} cells but {} columns declared",
+                        row_idx,
+                        row.len(),
+                        num_columns
+                    ),
+                );
+            }
+            for (col_idx, cell) in row.iter().enumerate() {
+                self.check_text(
+                    section_ref,
+                    &format!("body[{}][{}].text", row_idx, col_idx),
+                    &cell.text,
+                );
+            }
+        }
+    }
+
+    fn check_image(&mut self, section_ref: SectionRef, prefix: &str, image: &Image) {
+        let field = |name: &str| {
+            if prefix.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", prefix, name)
+            }
+        };
+
+        if image.data.is_empty() {
+            self.error(
+                section_ref,
+                CODE_INVALID_SECTION,
+                &field("data"),
+                "Image data cannot be empty",
+            );
+            return;
+        }
+
+        if prefix.is_empty() && image.band_height == Some(0) {
+            self.error(
+                section_ref,
+                CODE_INVALID_SECTION,
+                "band_height",
+                "Image band_height must be greater than 0",
+            );
+        }
+        if prefix.is_empty() {
+            if let Err(err) = ImageCommandMode::from_name(image.command_mode.as_deref()) {
+                self.error(
+                    section_ref,
+                    CODE_INVALID_SECTION,
+                    "command_mode",
+                    err.to_string(),
+                );
+            }
+        }
+        let transform = match ImageOptions::from_section(image) {
+            Ok(options) => options.transform,
+            Err(err) => {
+                self.error(
+                    section_ref,
+                    CODE_INVALID_SECTION,
+                    &field("data"),
+                    err.to_string(),
+                );
+                Default::default()
+            }
+        };
+
+        let decoded = match self.assets.load(&image.data) {
+            Ok(decoded) => decoded,
+            Err(err) => {
+                self.error(
+                    section_ref,
+                    CODE_IMAGE_DECODE,
+                    &field("data"),
+                    err.to_string(),
+                );
+                return;
+            }
+        };
+        // Los avisos de ancho se refieren a la imagen ya recortada y girada
+        let decoded = match transform.apply(decoded, 1.0) {
+            Ok(decoded) => decoded,
+            Err(err) => {
+                self.error(
+                    section_ref,
+                    CODE_INVALID_SECTION,
+                    &field("crop"),
+                    err.to_string(),
+                );
+                return;
+            }
+        };
+
+        if image.max_width > self.pixels_width {
+            self.warning(
+                section_ref,
+                WARNING_IMAGE_TOO_WIDE,
+                &field("max_width"),
+                format!(
+                    "max_width {} exceeds the paper width of {} dots and will be ignored",
+                    image.max_width, self.pixels_width
+                ),
+            );
+        }
+
+        let width = decoded.width() as i32;
+        if width > self.pixels_width {
+            self.warning(
+                section_ref,
+                WARNING_IMAGE_TOO_WIDE,
+                &field("data"),
+                format!(
+                    "Image is {} dots wide but the paper is {} dots; it will be scaled down",
+                    width, self.pixels_width
+                ),
+            );
+        }
+
+        // Los modos de doble ancho duplican cada punto al imprimir.
+        let printed_width = width.min(self.pixels_width);
+        if matches!(image.size.as_str(), "double_width" | "quadruple")
+            && printed_width * 2 > self.pixels_width
+        {
+            self.warning(
+                section_ref,
+                WARNING_IMAGE_TOO_WIDE,
+                &field("size"),
+                format!(
+                    "Size '{}' prints the image {} dots wide, wider than the paper ({} dots); it will be clipped",
+                    image.size,
+                    printed_width * 2,
+                    self.pixels_width
+                ),
+            );
+        }
+    }
+
+    fn job_error(&mut self, field: &str, message: &str) {
+        self.report.errors.push(ValidationIssue {
+            code: CODE_INVALID_JOB.to_string(),
+            index: None,
+            kind: None,
+            path: field.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    fn error(
+        &mut self,
+        section_ref: SectionRef,
+        code: &str,
+        field: &str,
+        message: impl Into<String>,
+    ) {
+        let issue = Self::issue(section_ref, code, field, message.into());
+        self.report.errors.push(issue);
+    }
+
+    fn warning(
+        &mut self,
+        section_ref: SectionRef,
+        code: &str,
+        field: &str,
+        message: impl Into<String>,
+    ) {
+        let issue = Self::issue(section_ref, code, field, message.into());
+        self.report.warnings.push(issue);
+    }
+
+    fn issue(section_ref: SectionRef, code: &str, field: &str, message: String) -> ValidationIssue {
+        ValidationIssue {
+            code: code.to_string(),
+            index: Some(section_ref.index),
+            kind: Some(section_ref.kind.to_string()),
+            path: section_ref.path(field),
+            message,
+        }
+    }
+}
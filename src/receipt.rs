@@ -0,0 +1,458 @@
+//! Fluent builder to create print jobs from Rust without spelling out every section.
+//!
+//! ```rust,no_run
+//! use tauri_plugin_thermal_printer::{PaperSize, Receipt};
+//!
+//! let bytes = Receipt::new(PaperSize::Mm80)
+//!     .title("My Shop")
+//!     .text("Thanks for your purchase")
+//!     .bold(true)
+//!     .align("center")
+//!     .line('=')
+//!     .table([["Coffee", "2.50"], ["Cake", "3.00"]])
+//!     .column_widths([38, 10])
+//!     .qr("https://example.com")
+//!     .cut()
+//!     .to_bytes()
+//!     .expect("valid receipt");
+//! # let _ = bytes;
+//! ```
+//!
+//! Style modifiers (`bold`, `align`, ...) apply to the section added last; table and
+//! code modifiers (`header`, `qr_size`, ...) apply to the last table or code. A
+//! modifier that does not fit the last section makes [`Receipt::build`] and
+//! [`Receipt::to_bytes`] fail with `INVALID_JOB`.
+
+use crate::commands_esc_pos::text::code_page::CodePage;
+use crate::error::{Error, Result};
+use crate::models::print_job_request::PrintJobRequest;
+use crate::models::print_sections::*;
+use crate::models::{PaperSize, PrinterProfile};
+use crate::process::process_print::ProcessPrint;
+
+/// Fluent builder for a [`PrintJobRequest`].
+#[derive(Debug, Clone)]
+pub struct Receipt {
+    printer: String,
+    paper_size: PaperSize,
+    options: CodePage,
+    profile: PrinterProfile,
+    sections: Vec<PrintSections>,
+    /// Styles in effect after the last `GlobalStyles` section; used as the base
+    /// for per-section style modifiers.
+    current_styles: GlobalStyles,
+    /// First modifier that did not fit the last section, reported by `build`.
+    misplaced: Option<String>,
+}
+
+impl Receipt {
+    /// Creates an empty receipt for the given paper size, with the default code page.
+    pub fn new(paper_size: PaperSize) -> Self {
+        Self {
+            printer: String::new(),
+            paper_size,
+            options: CodePage::default(),
+            profile: PrinterProfile::default(),
+            sections: Vec::new(),
+            current_styles: GlobalStyles::default(),
+            misplaced: None,
+        }
+    }
+
+    /// Sets the printer name. Required by [`Receipt::build`] consumers that print;
+    /// not needed for [`Receipt::to_bytes`].
+    pub fn printer(mut self, printer: impl Into<String>) -> Self {
+        self.printer = printer.into();
+        self
+    }
+
+    /// Sets the code page and host-side encoding.
+    pub fn code_page(mut self, options: CodePage) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the printer capabilities used by sections with `render_mode: "auto"`.
+    pub fn profile(mut self, profile: PrinterProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    // ─── Sections ───────────────────────────────────────────────────────────
+
+    /// Appends any section as-is.
+    pub fn section(mut self, section: PrintSections) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Appends a title (double size, centered).
+    pub fn title(self, text: impl Into<String>) -> Self {
+        self.section(PrintSections::Title(Title {
+            text: text.into(),
+            styles: None,
+            font: None,
+        }))
+    }
+
+    /// Appends a subtitle (double height, bold).
+    pub fn subtitle(self, text: impl Into<String>) -> Self {
+        self.section(PrintSections::Subtitle(Subtitle {
+            text: text.into(),
+            styles: None,
+            font: None,
+        }))
+    }
+
+    /// Appends a line of text using the current styles.
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.section(PrintSections::Text(Text {
+            text: text.into(),
+            styles: None,
+        }))
+    }
+
+    /// Appends a separator line made of `character`.
+    pub fn line(self, character: char) -> Self {
+        self.section(PrintSections::Line(Line {
+            character: character.to_string(),
+        }))
+    }
+
+    /// Appends a `GlobalStyles` section; it applies to every following section.
+    pub fn styles(mut self, styles: GlobalStyles) -> Self {
+        self.current_styles = styles.clone();
+        self.section(PrintSections::GlobalStyles(styles))
+    }
+
+    /// Resets the printer (`ESC @`) and the current styles.
+    pub fn reset(mut self) -> Self {
+        self.current_styles = GlobalStyles::default();
+        self.section(PrintSections::GlobalStyles(GlobalStyles {
+            reset: Some(true),
+            ..GlobalStyles::default()
+        }))
+    }
+
+    /// Feeds `lines` lines.
+    pub fn feed(self, lines: u8) -> Self {
+        self.section(PrintSections::Feed(Feed {
+            feed_type: "lines".to_string(),
+            value: lines,
+        }))
+    }
+
+    /// Partial cut after feeding 4 lines (same defaults as the TypeScript `cut()`).
+    pub fn cut(self) -> Self {
+        self.cut_with("partial", 4)
+    }
+
+    /// Cut with an explicit mode (`full`, `partial`, ...) and feed.
+    pub fn cut_with(self, mode: impl Into<String>, feed: u8) -> Self {
+        self.section(PrintSections::Cut(Cut {
+            mode: mode.into(),
+            feed,
+        }))
+    }
+
+    /// Single beep.
+    pub fn beep(self) -> Self {
+        self.section(PrintSections::Beep(Beep {
+            times: 1,
+            duration: 3,
+        }))
+    }
+
+    /// Opens the cash drawer on pin 2.
+    pub fn drawer(self) -> Self {
+        self.section(PrintSections::Drawer(Drawer {
+            pin: 2,
+            pulse_time: 120,
+        }))
+    }
+
+    /// Appends a table. The number of columns is taken from the widest row;
+    /// rows wrap instead of being truncated.
+    pub fn table<R, C, S>(self, rows: R) -> Self
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let body: Vec<Vec<Text>> = rows.into_iter().map(cells).collect();
+        let columns = body.iter().map(Vec::len).max().unwrap_or(0) as u8;
+        self.section(PrintSections::Table(Table {
+            columns,
+            column_widths: None,
+            header: None,
+            body,
+            truncate: false,
+            word_wrap: None,
+        }))
+    }
+
+    /// Appends a QR code (module size 6, error correction `M`, model 2).
+    pub fn qr(self, data: impl Into<String>) -> Self {
+        self.section(PrintSections::Qr(Qr {
+            data: data.into(),
+            size: 6,
+            error_correction: "M".to_string(),
+            model: 2,
+            align: None,
+            render_mode: None,
+            target_width: None,
+            target_width_mm: None,
+        }))
+    }
+
+    /// Appends a 1D barcode, e.g. `barcode("EAN13", "5901234123457")`.
+    pub fn barcode(self, barcode_type: impl Into<String>, data: impl Into<String>) -> Self {
+        self.section(PrintSections::Barcode(Barcode {
+            data: data.into(),
+            barcode_type: barcode_type.into(),
+            width: 3,
+            height: 80,
+            text_position: "below".to_string(),
+            align: None,
+            render_mode: None,
+            auto_check_digit: None,
+        }))
+    }
+
+    /// Appends a Base64 image scaled to the paper width, centered and dithered.
+    pub fn image(self, base64: impl Into<String>) -> Self {
+        self.section(PrintSections::Image(Image {
+            data: base64.into(),
+            max_width: 0,
+            align: "center".to_string(),
+            dithering: true,
+            size: "normal".to_string(),
+            ..Default::default()
+        }))
+    }
+
+    // ─── Style modifiers (last Title / Subtitle / Text) ─────────────────────
+
+    /// Bold (`ESC E`).
+    pub fn bold(self, value: bool) -> Self {
+        self.with_styles("bold", |styles| styles.bold = Some(value))
+    }
+
+    /// Underline (`ESC -`).
+    pub fn underline(self, value: bool) -> Self {
+        self.with_styles("underline", |styles| styles.underline = Some(value))
+    }
+
+    /// Italic (`ESC 4`), where supported.
+    pub fn italic(self, value: bool) -> Self {
+        self.with_styles("italic", |styles| styles.italic = Some(value))
+    }
+
+    /// White on black (`GS B`).
+    pub fn invert(self, value: bool) -> Self {
+        self.with_styles("invert", |styles| styles.invert = Some(value))
+    }
+
+    /// Double-strike (`ESC G`).
+    pub fn double_strike(self, value: bool) -> Self {
+        self.with_styles("double_strike", |styles| styles.double_strike = Some(value))
+    }
+
+    /// `black` or `red` (`ESC r`) on two-color printers.
+    pub fn color(self, color: impl Into<String>) -> Self {
+        let color = color.into();
+        self.with_styles("color", |styles| styles.color = Some(color))
+    }
+
+    /// `normal`, `height`, `width` or `double`.
+    pub fn size(self, size: impl Into<String>) -> Self {
+        let size = size.into();
+        self.with_styles("size", |styles| styles.size = Some(size))
+    }
+
+    /// `A`, `B` or `C`.
+    pub fn font(self, font: impl Into<String>) -> Self {
+        let font = font.into();
+        self.with_styles("font", |styles| styles.font = Some(font))
+    }
+
+    /// `left`, `center` or `right`. Also applies to the last code or image.
+    pub fn align(mut self, align: impl Into<String>) -> Self {
+        let align = align.into();
+        match self.sections.last_mut() {
+            Some(PrintSections::Qr(qr)) => qr.align = Some(align),
+            Some(PrintSections::EmvQr(emv)) => emv.align = Some(align),
+            Some(PrintSections::SepaQr(sepa)) => sepa.align = Some(align),
+            Some(PrintSections::Barcode(barcode)) => barcode.align = Some(align),
+            Some(PrintSections::Aztec(aztec)) => aztec.align = Some(align),
+            Some(PrintSections::Gs1Databar2d(databar)) => databar.align = Some(align),
+            Some(PrintSections::MaxiCode(maxicode)) => maxicode.align = Some(align),
+            Some(PrintSections::Composite(composite)) => composite.align = Some(align),
+            Some(PrintSections::Image(image)) => image.align = align,
+            _ => return self.with_styles("align", |styles| styles.align = Some(align)),
+        }
+        self
+    }
+
+    // ─── Table modifiers (last Table) ───────────────────────────────────────
+
+    /// Sets the header row of the last table.
+    pub fn header<C, S>(self, header: C) -> Self
+    where
+        C: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let header = cells(header);
+        self.with_table("header", |table| table.header = Some(header))
+    }
+
+    /// Sets explicit column widths (in characters) for the last table. They must
+    /// add up to the paper's characters per line.
+    pub fn column_widths(self, widths: impl IntoIterator<Item = u8>) -> Self {
+        let widths: Vec<u8> = widths.into_iter().collect();
+        self.with_table("column_widths", |table| table.column_widths = Some(widths))
+    }
+
+    /// Truncates cells that don't fit instead of wrapping them.
+    pub fn truncate(self, value: bool) -> Self {
+        self.with_table("truncate", |table| table.truncate = value)
+    }
+
+    /// Wraps cells by word instead of by character.
+    pub fn word_wrap(self, value: bool) -> Self {
+        self.with_table("word_wrap", |table| table.word_wrap = Some(value))
+    }
+
+    // ─── Code modifiers (last Qr / Barcode) ─────────────────────────────────
+
+    /// QR module size (1-16).
+    pub fn qr_size(self, size: u8) -> Self {
+        self.with_qr("qr_size", |qr| qr.size = size)
+    }
+
+    /// Fits the QR to `width_mm` millimetres (largest module size that fits).
+    pub fn qr_width_mm(self, width_mm: f32) -> Self {
+        self.with_qr("qr_width_mm", |qr| qr.target_width_mm = Some(width_mm))
+    }
+
+    /// QR error correction level (`L`, `M`, `Q`, `H`).
+    pub fn error_correction(self, level: impl Into<String>) -> Self {
+        let level = level.into();
+        self.with_qr("error_correction", |qr| qr.error_correction = level)
+    }
+
+    /// Barcode module width and height in dots.
+    pub fn barcode_size(self, width: u8, height: u8) -> Self {
+        self.with_barcode("barcode_size", |barcode| {
+            barcode.width = width;
+            barcode.height = height;
+        })
+    }
+
+    /// Appends the computed check digit to EAN/UPC data that omits it.
+    pub fn auto_check_digit(self, value: bool) -> Self {
+        self.with_barcode("auto_check_digit", |barcode| {
+            barcode.auto_check_digit = Some(value)
+        })
+    }
+
+    // ─── Output ─────────────────────────────────────────────────────────────
+
+    /// Sections added so far.
+    pub fn sections(&self) -> &[PrintSections] {
+        &self.sections
+    }
+
+    /// Produces the request accepted by `print_thermal_printer`. Fails if a
+    /// modifier was called when the last section could not take it.
+    pub fn build(self) -> Result<PrintJobRequest> {
+        if let Some(reason) = self.misplaced {
+            return Err(Error::InvalidJob(reason));
+        }
+        Ok(PrintJobRequest {
+            printer: self.printer,
+            sections: self.sections,
+            options: self.options,
+            paper_size: self.paper_size,
+            profile: self.profile,
+        })
+    }
+
+    /// Generates the raw ESC/POS bytes, e.g. to send them over your own transport.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let request = self.clone().build()?;
+        ProcessPrint::new().generate_bytes(&request)
+    }
+
+    fn with_styles(mut self, modifier: &str, apply: impl FnOnce(&mut GlobalStyles)) -> Self {
+        let base = self.current_styles.clone();
+        let styles = match self.sections.last_mut() {
+            Some(PrintSections::Title(title)) => &mut title.styles,
+            Some(PrintSections::Subtitle(subtitle)) => &mut subtitle.styles,
+            Some(PrintSections::Text(text)) => &mut text.styles,
+            _ => return self.misplaced(modifier, "a Title, Subtitle or Text"),
+        };
+        apply(styles.get_or_insert(base));
+        self
+    }
+
+    fn with_table(mut self, modifier: &str, apply: impl FnOnce(&mut Table)) -> Self {
+        match self.sections.last_mut() {
+            Some(PrintSections::Table(table)) => apply(table),
+            _ => return self.misplaced(modifier, "a Table"),
+        }
+        self
+    }
+
+    fn with_qr(mut self, modifier: &str, apply: impl FnOnce(&mut Qr)) -> Self {
+        match self.sections.last_mut() {
+            Some(PrintSections::Qr(qr)) => apply(qr),
+            _ => return self.misplaced(modifier, "a Qr"),
+        }
+        self
+    }
+
+    fn with_barcode(mut self, modifier: &str, apply: impl FnOnce(&mut Barcode)) -> Self {
+        match self.sections.last_mut() {
+            Some(PrintSections::Barcode(barcode)) => apply(barcode),
+            _ => return self.misplaced(modifier, "a Barcode"),
+        }
+        self
+    }
+
+    /// Records the first modifier that had nothing to apply to.
+    fn misplaced(mut self, modifier: &str, expected: &str) -> Self {
+        if self.misplaced.is_none() {
+            let last = self
+                .sections
+                .last()
+                .map_or("no section", PrintSections::kind);
+            self.misplaced = Some(format!(
+                "Receipt::{}() needs {} as the last section (found {})",
+                modifier, expected, last
+            ));
+        }
+        self
+    }
+}
+
+impl TryFrom<Receipt> for PrintJobRequest {
+    type Error = Error;
+
+    fn try_from(receipt: Receipt) -> Result<Self> {
+        receipt.build()
+    }
+}
+
+fn cells<C, S>(row: C) -> Vec<Text>
+where
+    C: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    row.into_iter()
+        .map(|cell| Text {
+            text: cell.into(),
+            styles: None,
+        })
+        .collect()
+}
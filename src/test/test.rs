@@ -12,11 +12,32 @@
 //! TEST_SECTIONS=test_spacing,test_positioning,test_beep2 cargo test configurable_test_document -- --nocapture
 //! ```
 
+use crate::commands_esc_pos::codes::aztec::aztec_encoder::{self, AztecOptions};
+use crate::commands_esc_pos::codes::barcode::symbology::{self, code128, Symbol};
+use crate::commands_esc_pos::codes::barcode::{Barcode as BarcodeBuilder, BarcodeType};
+use crate::commands_esc_pos::codes::data_matrix::ecc200;
+use crate::commands_esc_pos::codes::maxicode::maxicode_encoder;
+use crate::commands_esc_pos::codes::pdf417::pdf417_encoder::{self, Pdf417Options};
+use crate::commands_esc_pos::codes::qr::{emv, sepa};
+use crate::commands_esc_pos::codes::reed_solomon::GaloisField;
+use crate::commands_esc_pos::control::printer_control::PrinterControl;
+use crate::commands_esc_pos::image_escpos::dithering::DitherAlgorithm;
+use crate::commands_esc_pos::image_escpos::image_options::ImageOptions;
+use crate::commands_esc_pos::image_escpos::image_transform::ImageTransform;
 use crate::commands_esc_pos::text::code_page::CodePage;
+use crate::error::SectionError;
+use crate::models::nv_graphics_request::NvGraphicsRequest;
 use crate::models::print_job_request::PrintJobRequest;
+use crate::models::print_pause::paced_segments;
 use crate::models::print_sections::*;
+use crate::models::validation::ValidationReport;
+use crate::process::font_registry::FontRegistry;
+use crate::process::image_assets::ImageAssets;
+use crate::process::process_nv_graphics::ProcessNvGraphics;
 use crate::process::process_print::ProcessPrint;
 use crate::process::process_print_test::TestPrinter;
+use crate::process::process_validate::ProcessValidate;
+use crate::Receipt;
 use crate::TestPrintRequest;
 use serde_json::{json, Value};
 use std::collections::HashSet;
@@ -29,6 +50,7 @@ fn job(sections: Vec<PrintSections>) -> PrintJobRequest {
         sections,
         options: CodePage::default(),
         paper_size: crate::PaperSize::DEFAULT,
+        profile: Default::default(),
     }
 }
 
@@ -130,6 +152,28 @@ fn double_strike_style_emits_esc_g() {
     assert!(contains(&out, &[0x1B, 0x47, 0x01]));
 }
 
+#[test]
+fn color_style_switches_to_red_and_back() {
+    let red = GlobalStyles {
+        color: Some("red".into()),
+        ..Default::default()
+    };
+    let out = gen(vec![PrintSections::Text(Text {
+        text: "NO NUTS".into(),
+        styles: Some(red),
+    })]);
+    let expected = [&[0x1B, 0x72, 0x01][..], b"NO NUTS\n", &[0x1B, 0x72, 0x00]].concat();
+    assert!(contains(&out, &expected), "red only around the text");
+
+    let out = Receipt::new(crate::PaperSize::Mm80)
+        .text("Burger")
+        .text("+ extra cheese")
+        .color("red")
+        .to_bytes()
+        .unwrap();
+    assert_eq!(count(&out, &[0x1B, 0x72, 0x01]), 1);
+}
+
 // ─── Reset ──────────────────────────────────────────────────────────────────
 
 #[test]
@@ -184,6 +228,7 @@ fn title_forces_double_size_and_center() {
     let out = gen(vec![PrintSections::Title(Title {
         text: "Hi".to_string(),
         styles: None,
+        font: None,
     })]);
     assert!(contains(&out, &[0x1B, 0x21, 0x30]), "double size (ESC ! 0x30)");
     assert!(contains(&out, &[0x1B, 0x61, 0x01]), "center (ESC a 1)");
@@ -195,6 +240,7 @@ fn subtitle_forces_double_height_and_bold() {
     let out = gen(vec![PrintSections::Subtitle(Subtitle {
         text: "Sub".to_string(),
         styles: None,
+        font: None,
     })]);
     assert!(contains(&out, &[0x1B, 0x21, 0x10]), "double height (ESC ! 0x10)");
     assert!(contains(&out, &[0x1B, 0x45, 0x01]), "bold (ESC E 1)");
@@ -234,6 +280,7 @@ fn global_styles_emit_expected_commands() {
         (GlobalStyles { size: Some("width".into()), ..Default::default() }, &[0x1B, 0x21, 0x20], "double width"),
         (GlobalStyles { size: Some("height".into()), ..Default::default() }, &[0x1B, 0x21, 0x10], "double height"),
         (GlobalStyles { size: Some("double".into()), ..Default::default() }, &[0x1B, 0x21, 0x30], "double size"),
+        (GlobalStyles { color: Some("red".into()), ..Default::default() }, &[0x1B, 0x72, 0x01], "color red"),
     ];
     for (style, expected, label) in cases {
         let out = gen(vec![styled(style.clone())]);
@@ -284,10 +331,206 @@ fn qr_uses_gs_paren_k_with_cn_49() {
         error_correction: "M".into(),
         model: 2,
         align: None,
+        render_mode: None,
+        target_width: None,
+        target_width_mm: None,
     })]);
     assert!(contains(&out, &[0x1D, 0x28, 0x6B, 0x04, 0x00, 0x31]), "QR model fn (cn=49)");
 }
 
+fn qr_with_mode(render_mode: &str) -> PrintSections {
+    PrintSections::Qr(Qr {
+        data: "HELLO".into(),
+        size: 4,
+        error_correction: "M".into(),
+        model: 2,
+        align: Some("center".into()),
+        render_mode: Some(render_mode.into()),
+        target_width: None,
+        target_width_mm: None,
+    })
+}
+
+#[test]
+fn qr_raster_mode_prints_gs_v_0_with_native_module_size() {
+    let out = gen(vec![qr_with_mode("raster")]);
+    // Versión 1: 21 módulos × 4 puntos = 84 → 11 bytes por fila, 84 filas
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 11, 0x00, 84, 0x00]));
+    assert!(!contains(&out, &[0x1D, 0x28, 0x6B]), "no GS ( k");
+    assert!(contains(&out, &[0x1B, 0x61, 0x01]), "alignment kept");
+}
+
+#[test]
+fn qr_auto_mode_follows_printer_profile() {
+    let native = gen(vec![qr_with_mode("auto")]);
+    assert!(contains(&native, &[0x1D, 0x28, 0x6B]));
+
+    let mut request = job(vec![qr_with_mode("auto")]);
+    request.profile.native_qr = false;
+    let raster = ProcessPrint::new().generate_document(&request).unwrap();
+    assert!(!contains(&raster, &[0x1D, 0x28, 0x6B]));
+    assert!(contains(&raster, &[0x1D, 0x76, 0x30]));
+}
+
+#[test]
+fn unknown_render_mode_is_rejected() {
+    let native = gen(vec![qr_with_mode("native")]);
+    assert!(contains(&native, &[0x1D, 0x28, 0x6B]));
+
+    for section in [qr_with_mode("rastr"), barcode_with_mode("CODE128", "ABC", "Raster")] {
+        let err = ProcessPrint::new().generate_document(&job(vec![section])).unwrap_err();
+        assert_eq!(err.code(), "INVALID_SECTION");
+        assert!(err.to_string().contains("Unknown render mode"), "{err}");
+    }
+}
+
+#[test]
+fn qr_capacity_table_matches_the_spec() {
+    use crate::commands_esc_pos::codes::qr::QRErrorCorrection as Ec;
+    assert_eq!(Ec::L.capacity(40, b"1"), 7089);
+    assert_eq!(Ec::L.capacity(40, b"A"), 4296);
+    assert_eq!(Ec::L.capacity(40, b"a"), 2953);
+    assert_eq!(Ec::H.capacity(40, b"a"), 1273);
+    assert_eq!(Ec::H.capacity(1, b"1"), 17);
+    assert_eq!(Ec::M.capacity(10, b"a"), 213);
+    assert_eq!(Ec::M.min_version(b"https://example.com"), Some(2));
+    assert_eq!(Ec::H.min_version(&[b'a'; 1274]), None);
+}
+
+fn qr_fit(data: &str, target_width: Option<u32>, target_width_mm: Option<f32>) -> PrintSections {
+    PrintSections::Qr(Qr {
+        data: data.into(),
+        size: 3,
+        error_correction: "M".into(),
+        model: 2,
+        align: None,
+        render_mode: None,
+        target_width,
+        target_width_mm,
+    })
+}
+
+#[test]
+fn qr_target_width_picks_the_largest_module_size_that_fits() {
+    // Versión 2 (25 módulos): 200 / 25 = 8 puntos por módulo
+    let out = gen(vec![qr_fit("https://example.com", Some(200), None)]);
+    assert!(contains(&out, &[0x31, 0x43, 8]), "module size 8");
+    // 30 mm = 240 puntos → 9
+    let out = gen(vec![qr_fit("https://example.com", None, Some(30.0))]);
+    assert!(contains(&out, &[0x31, 0x43, 9]), "module size 9");
+    // Limitado al papel: 58 mm = 384 puntos → 15
+    let mut request = job(vec![qr_fit("https://example.com", Some(1000), None)]);
+    request.paper_size = crate::PaperSize::Mm58;
+    let out = ProcessPrint::new().generate_document(&request).unwrap();
+    assert!(contains(&out, &[0x31, 0x43, 15]), "module size 15");
+    // Nunca más de 16
+    let out = gen(vec![qr_fit("1", Some(576), None)]);
+    assert!(contains(&out, &[0x31, 0x43, 16]), "module size 16");
+
+    // El raster usa el mismo tamaño: 25 × 8 = 200 puntos = 25 bytes por fila
+    let mut raster = qr_fit("https://example.com", Some(200), None);
+    if let PrintSections::Qr(qr) = &mut raster {
+        qr.render_mode = Some("raster".into());
+    }
+    assert!(contains(&gen(vec![raster]), &[0x1D, 0x76, 0x30, 0x00, 25, 0x00, 200, 0x00]));
+
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![qr_fit("https://example.com", Some(20), None)]))
+        .unwrap_err();
+    assert!(err.to_string().contains("QR version 2 needs at least 25 dots"), "{err}");
+}
+
+fn pix_qr() -> EmvQr {
+    serde_json::from_value(json!({
+        "merchant_accounts": [{
+            "id": 26,
+            "fields": [
+                { "id": 0, "value": "br.gov.bcb.pix" },
+                { "id": 1, "value": "123e4567-e12b-12d1-a456-426655440000" }
+            ]
+        }],
+        "currency": "986",
+        "country_code": "BR",
+        "merchant_name": "Fulano de Tal",
+        "merchant_city": "BRASILIA",
+        "additional_data": [{ "id": 5, "value": "***" }]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn emv_qr_builds_tlv_payload_with_crc16() {
+    assert_eq!(emv::crc16(b"123456789"), 0x29B1);
+    // Ejemplo del manual de BR Code (PIX) del Banco Central do Brasil
+    let payload = "00020126580014br.gov.bcb.pix0136123e4567-e12b-12d1-a456-4266554400005204000053039865802BR5913Fulano de Tal6008BRASILIA62070503***63041D3D";
+    assert_eq!(emv::payload(&pix_qr()).unwrap(), payload);
+
+    let out = gen(vec![PrintSections::EmvQr(pix_qr())]);
+    assert!(contains(&out, payload.as_bytes()), "printed through the Qr pipeline");
+
+    let mut dynamic = pix_qr();
+    dynamic.dynamic = Some(true);
+    dynamic.amount = Some("10.50".into());
+    let payload = emv::payload(&dynamic).unwrap();
+    assert!(payload.starts_with("000201010212"), "{payload}");
+    assert!(payload.contains("540510.50"), "{payload}");
+
+    for (edit, reason) in [
+        (json!({ "merchant_name": "A merchant name that is too long" }), "merchant name is longer than 25"),
+        (json!({ "currency": "BRL" }), "currency must be 3 digits"),
+        (json!({ "country_code": "br" }), "country code must be 2 uppercase letters"),
+        (json!({ "amount": "10,50" }), "amount must look like 10.50"),
+        (json!({ "merchant_accounts": [{ "id": 62, "value": "x" }] }), "merchant account ID must be 02-51"),
+        (json!({ "merchant_accounts": [] }), "at least one merchant account"),
+    ] {
+        let mut value = serde_json::to_value(pix_qr()).unwrap();
+        for (key, field) in edit.as_object().unwrap() {
+            value[key] = field.clone();
+        }
+        let emv: EmvQr = serde_json::from_value(value).unwrap();
+        let err = emv::payload(&emv).unwrap_err().to_string();
+        assert!(err.contains(reason), "{err}");
+    }
+}
+
+fn sepa_qr(iban: &str) -> SepaQr {
+    serde_json::from_value(json!({
+        "name": "Red Cross",
+        "iban": iban,
+        "bic": "BPOTBEB1",
+        "amount": "12.5",
+        "text": "Donation"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn sepa_qr_builds_epc069_12_payload() {
+    let sepa = sepa_qr("de89 3704 0044 0532 0130 00");
+    let payload = "BCD\n002\n1\nSCT\nBPOTBEB1\nRed Cross\nDE89370400440532013000\nEUR12.50\n\n\nDonation";
+    assert_eq!(sepa::payload(&sepa).unwrap(), payload);
+    let out = gen(vec![PrintSections::SepaQr(sepa)]);
+    assert!(contains(&out, payload.as_bytes()));
+
+    let err = sepa::payload(&sepa_qr("DE88370400440532013000")).unwrap_err();
+    assert!(err.to_string().contains("invalid check digits"), "{err}");
+
+    let mut both = sepa_qr("DE89370400440532013000");
+    both.reference = Some("RF18539007547034".into());
+    assert!(sepa::payload(&both).is_err(), "reference and text are exclusive");
+
+    let mut no_amount = sepa_qr("DE89370400440532013000");
+    no_amount.amount = Some("0.00".into());
+    assert!(sepa::payload(&no_amount).is_err());
+    no_amount.amount = None;
+    no_amount.text = None;
+    assert!(sepa::payload(&no_amount).unwrap().ends_with("DE89370400440532013000"));
+
+    // La validación sin imprimir informa del mismo error
+    let report = validate(&job(vec![PrintSections::SepaQr(sepa_qr("DE00"))]));
+    assert!(!report.valid);
+}
+
 #[test]
 fn barcode_uses_gs_k() {
     let out = gen(vec![PrintSections::Barcode(Barcode {
@@ -297,6 +540,8 @@ fn barcode_uses_gs_k() {
         height: 60,
         text_position: "below".into(),
         align: None,
+        render_mode: None,
+        auto_check_digit: None,
     })]);
     assert!(contains(&out, &[0x1D, 0x6B]), "GS k (barcode)");
     assert!(contains(&out, &[0x1D, 0x68, 60]), "GS h (height)");
@@ -312,6 +557,8 @@ fn gs1_128_uses_gs_k_m_74() {
         height: 60,
         text_position: "below".into(),
         align: None,
+        render_mode: None,
+        auto_check_digit: None,
     })]);
     // GS k m n data — m=74 (GS1-128)
     assert!(contains(&out, &[0x1D, 0x6B, 74, 8]), "GS k m=74 (GS1-128)");
@@ -331,6 +578,8 @@ fn gs1_databar_variants_use_gs_k_m_75_to_78() {
             height: 60,
             text_position: "below".into(),
             align: None,
+            render_mode: None,
+            auto_check_digit: None,
         })]);
         assert!(contains(&out, &[0x1D, 0x6B, m, 13]), "GS k m={m} ({ty})");
     }
@@ -343,15 +592,323 @@ fn gs1_databar_variants_use_gs_k_m_75_to_78() {
         height: 60,
         text_position: "below".into(),
         align: None,
+        render_mode: None,
+        auto_check_digit: None,
     })]);
     assert!(contains(&out, &[0x1D, 0x6B, 78]), "GS k m=78 (GS1 DataBar Expanded)");
 }
 
+fn barcode_with_mode(barcode_type: &str, data: &str, render_mode: &str) -> PrintSections {
+    PrintSections::Barcode(Barcode {
+        data: data.into(),
+        barcode_type: barcode_type.into(),
+        width: 2,
+        height: 60,
+        text_position: "below".into(),
+        align: Some("center".into()),
+        render_mode: Some(render_mode.into()),
+        auto_check_digit: None,
+    })
+}
+
+fn encode_barcode(barcode_type: &str, data: &str) -> Result<Symbol, SectionError> {
+    symbology::encode(BarcodeType::from_name(barcode_type), data)
+}
+
+#[test]
+fn barcode_raster_mode_prints_gs_v_0_with_quiet_zones_and_hri() {
+    let out = gen(vec![barcode_with_mode("EAN13", "590123412345", "raster")]);
+    // (95 módulos + 2 × 9 de zona de silencio) × 2 puntos = 226 → 29 bytes;
+    // 60 de barras + 24 del bloque HRI = 84 filas
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 29, 0x00, 84, 0x00]));
+    assert!(!contains(&out, &[0x1D, 0x6B]), "no GS k");
+    assert!(contains(&out, &[0x1B, 0x61, 0x01]), "alignment kept");
+}
+
+#[test]
+fn barcode_raster_renders_every_type() {
+    for (ty, data) in [
+        ("UPC-A", "03600029145"),
+        ("UPC-E", "0425261"),
+        ("EAN13", "590123412345"),
+        ("EAN8", "9638507"),
+        ("CODE39", "CODE-39"),
+        ("ITF", "123456"),
+        ("CODABAR", "A40156B"),
+        ("CODE93", "Code 93!"),
+        ("CODE128", "{BNo.{C\x0c\x22"),
+        ("CODE128", "Order 12345678"),
+        ("GS1-128", "(01)09501101530003(10)ABC123"),
+        ("GS1-DATABAR-OMNI", "0950110153000"),
+        ("GS1-DATABAR-TRUNCATED", "0950110153000"),
+        ("GS1-DATABAR-LIMITED", "1501234567890"),
+        ("GS1-DATABAR-EXPANDED", "(01)09501101530003(3103)001750"),
+    ] {
+        let out = gen(vec![barcode_with_mode(ty, data, "raster")]);
+        assert!(contains(&out, &[0x1D, 0x76, 0x30]), "{ty} rendered as raster");
+    }
+}
+
+#[test]
+fn barcode_raster_encoders_compute_check_digits_and_hri() {
+    assert_eq!(encode_barcode("EAN13", "590123412345").unwrap().hri, "5901234123457");
+    assert_eq!(encode_barcode("UPC-A", "03600029145").unwrap().hri, "036000291452");
+    assert_eq!(encode_barcode("EAN8", "9638507").unwrap().hri, "96385074");
+    // UPC-A 04210000526 se comprime a UPC-E 0 425261, control 4
+    assert_eq!(encode_barcode("UPC-E", "04210000526").unwrap().hri, "04252614");
+    assert_eq!(encode_barcode("CODE39", "ABC").unwrap().hri, "*ABC*");
+    assert_eq!(
+        encode_barcode("GS1-DATABAR-OMNI", "0950110153000").unwrap().hri,
+        "(01)09501101530003"
+    );
+
+    let ean13 = encode_barcode("EAN13", "590123412345").unwrap();
+    assert_eq!(ean13.modules.len(), 95);
+    assert_eq!(ean13.modules[..3], [true, false, true], "start guard");
+    assert_eq!(encode_barcode("UPC-E", "0425261").unwrap().modules.len(), 51);
+    assert_eq!(encode_barcode("EAN8", "9638507").unwrap().modules.len(), 67);
+    assert_eq!(encode_barcode("GS1-DATABAR-OMNI", "0950110153000").unwrap().modules.len(), 96);
+}
+
+#[test]
+fn barcode_raster_code128_picks_the_shortest_code_sets() {
+    // START C, 12, 34, 56, control y parada: 5 × 11 + 13 módulos
+    assert_eq!(encode_barcode("CODE128", "123456").unwrap().modules.len(), 68);
+    // START B, A, B, C, control y parada
+    assert_eq!(encode_barcode("CODE128", "ABC").unwrap().modules.len(), 68);
+    // START C + FNC1 + 01 + 7 pares + control y parada = 11 símbolos
+    let gs1 = encode_barcode("GS1-128", "(01)09501101530003").unwrap();
+    assert_eq!(gs1.modules.len(), 11 * 11 + 13);
+    assert_eq!(gs1.hri, "(01)09501101530003");
+}
+
+#[test]
+fn barcode_raster_rejects_invalid_data() {
+    assert!(encode_barcode("ITF", "12345").is_err(), "odd ITF length");
+    assert!(encode_barcode("CODE39", "abc").is_err(), "lowercase CODE39");
+    assert!(encode_barcode("CODABAR", "12345").is_err(), "CODABAR without start/stop");
+    assert!(encode_barcode("UPC-E", "03600029145").is_err(), "not zero-suppressible");
+    assert!(encode_barcode("GS1-128", "0109501101530003").is_err(), "GS1 without (AI)");
+}
+
+fn native_barcode(
+    barcode_type: &str,
+    data: &str,
+    auto_check_digit: bool,
+) -> Result<Vec<u8>, String> {
+    let mut section = barcode_with_mode(barcode_type, data, "native");
+    if let PrintSections::Barcode(barcode) = &mut section {
+        barcode.auto_check_digit = Some(auto_check_digit);
+    }
+    ProcessPrint::new()
+        .generate_document(&job(vec![section]))
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn barcode_check_digits_are_verified_and_optionally_appended() {
+    let err = native_barcode("EAN13", "5901234123458", false).unwrap_err();
+    assert!(err.contains("EAN13 check digit is 8 but should be 7"), "{err}");
+    let err = native_barcode("UPC-E", "04252615", false).unwrap_err();
+    assert!(err.contains("UPC-E check digit is 5 but should be 4"), "{err}");
+
+    // Sin auto_check_digit los datos se envían tal cual (la impresora calcula el control)
+    let out = native_barcode("EAN13", "590123412345", false).unwrap();
+    assert!(contains(&out, &[0x1D, 0x6B, 67, 12]));
+    assert!(contains(&out, b"590123412345"));
+
+    for (ty, data, complete) in [
+        ("EAN13", "590123412345", "5901234123457"),
+        ("EAN8", "9638507", "96385074"),
+        ("UPC-A", "03600029145", "036000291452"),
+        ("UPC-E", "425261", "04252614"),
+        ("UPC-E", "0425261", "04252614"),
+        ("UPC-E", "04210000526", "042100005264"),
+        ("EAN13", "5901234123457", "5901234123457"),
+    ] {
+        let out = native_barcode(ty, data, true).unwrap();
+        let mut expected = vec![complete.len() as u8];
+        expected.extend_from_slice(complete.as_bytes());
+        assert!(contains(&out, &expected), "{ty} {data} → {complete}");
+    }
+}
+
+#[test]
+fn barcode_native_mode_rejects_invalid_data() {
+    for (ty, data, reason) in [
+        ("EAN13", "59012341234", "EAN13 requires 12 or 13 digits"),
+        ("ITF", "12345", "ITF requires an even number of digits"),
+        ("CODE39", "abc", "CODE39 does not support the character 'a'"),
+        ("CODE39", "*AB*C*", "CODE39 does not support the character '*'"),
+        ("CODABAR", "12345", "CODABAR data must start and end with A, B, C or D"),
+        ("CODABAR", "A12B34B", "CODABAR does not support the character 'B' here"),
+    ] {
+        let err = native_barcode(ty, data, false).unwrap_err();
+        assert!(err.contains(reason), "{ty} {data}: {err}");
+    }
+
+    for (ty, data) in [("ITF", "123456"), ("CODE39", "*CODE-39*"), ("CODABAR", "a40156d")] {
+        assert!(native_barcode(ty, data, false).is_ok(), "{ty} {data}");
+    }
+}
+
+#[test]
+fn code128_native_data_uses_optimal_code_sets() {
+    let to_escpos = |data: &str| code128::to_escpos(data).unwrap().into_bytes();
+    assert_eq!(to_escpos("12345678"), b"{C\x0c\x22\x38\x4e");
+    assert_eq!(to_escpos("Order 12345678"), b"{BOrder {C\x0c\x22\x38\x4e");
+    // Un carácter de control suelto dentro de B va con SHIFT; `{{` es un '{' literal
+    assert_eq!(to_escpos("a\nb"), b"{Ba{S\nb");
+    assert_eq!(to_escpos("a{{b"), b"{Ba{{b");
+    // `{1` = FNC1 (GS1-128 sobre CODE128)
+    assert_eq!(
+        to_escpos("{10109501101530003"),
+        b"{C{1\x01\x09\x32\x0b\x01\x35\x00\x03"
+    );
+    // Los datos que ya usan la sintaxis de ESC/POS no se tocan
+    assert_eq!(to_escpos("{BNo.{C\x0c\x22"), b"{BNo.{C\x0c\x22");
+    assert!(code128::to_escpos("caf\u{e9}").is_err());
+
+    // El símbolo raster es el mismo con texto libre o con la sintaxis generada
+    for data in ["Order 12345678", "a\nb", "{10109501101530003", "ABC123456def"] {
+        let converted = code128::to_escpos(data).unwrap();
+        assert_eq!(
+            encode_barcode("CODE128", data).unwrap().modules,
+            encode_barcode("CODE128", &converted).unwrap().modules,
+            "{data}"
+        );
+    }
+
+    let out = native_barcode("CODE128", "Order 12345678", false).unwrap();
+    let mut expected = vec![0x1D, 0x6B, 73, 14];
+    expected.extend_from_slice(b"{BOrder {C\x0c\x22\x38\x4e");
+    assert!(contains(&out, &expected));
+
+    // El constructor también convierte los datos, y falla si no puede
+    let command = BarcodeBuilder::new(BarcodeType::Code128, "123456789012".into())
+        .get_command()
+        .unwrap();
+    assert!(command.ends_with(&[73, 8, b'{', b'C', 12, 34, 56, 78, 90, 12]));
+    assert!(BarcodeBuilder::new(BarcodeType::Code128, "caf\u{e9}".into()).get_command().is_err());
+}
+
+#[test]
+fn gs1_ai_data_is_validated_and_separated_per_symbology() {
+    let data = "(01)09501101530003(17)250101(10)ABC";
+    // GS k: datos normalizados con paréntesis (el firmware pone los FNC1 y el HRI)
+    let out = native_barcode("GS1-128", data, false).unwrap();
+    assert!(contains(&out, &[&[0x1D, 0x6B, 74, 35][..], data.as_bytes()].concat()));
+
+    for (data, reason) in [
+        ("(01)09501101530004", "GS1 AI (01) GTIN check digit is 4 but should be 3"),
+        ("(17)251301", "GS1 AI (17) USE BY is not a valid YYMMDD date"),
+        ("(11)250230", "GS1 AI (11) PROD DATE is not a valid YYMMDD date"),
+        ("(10)ABCDEFGHIJKLMNOPQRSTU", "GS1 AI (10) BATCH/LOT must have 1-20 characters, got 21"),
+        ("(10)AB#1", "GS1 AI (10) BATCH/LOT contains '#'"),
+        ("(3103)1750", "GS1 AI (3103) MEASURE must have 6 characters, got 4"),
+        ("(05)123", "Unknown GS1 AI (05)"),
+        ("(310)001750", "Unknown GS1 AI (310)"),
+    ] {
+        let err = native_barcode("GS1-128", data, false).unwrap_err();
+        assert!(err.contains(reason), "{data}: {err}");
+        assert!(encode_barcode("GS1-DATABAR-EXPANDED", data).is_err(), "{data}");
+    }
+    assert!(native_barcode("GS1-128", "(17)250100(3922)1299", false).is_ok(), "DD=00, 392n");
+
+    // GS ( k: cadena de elementos sin paréntesis, GS (0x1D) como FNC1 separador
+    let out = gen(vec![PrintSections::Composite(Composite {
+        data: "(10)AB(01)09501101530003(21)X1".into(),
+        symbol_type: 48,
+        width: 2,
+        align: None,
+    })]);
+    assert!(contains(&out, b"10AB\x1d010950110153000321X1"), "FNC1 only after (10)");
+
+    // Stacked codifica el GTIN sin dígito de control; Expanded Stacked la cadena GS1
+    for (databar_type, expected) in [
+        ("STACKED", &b"0950110153000"[..]),
+        ("EXPANDED-STACKED", b"0109501101530003"),
+    ] {
+        let out = gen(vec![PrintSections::Gs1Databar2d(Gs1Databar2d {
+            data: "(01)09501101530003".into(),
+            databar_type: databar_type.into(),
+            width: 2,
+            align: None,
+        })]);
+        assert!(contains(&out, expected), "{databar_type}");
+        assert!(!contains(&out, b"(01)"), "{databar_type}");
+    }
+}
+
+#[test]
+fn barcode_raster_shrinks_modules_to_fit_the_paper() {
+    let mut barcode = barcode_with_mode("CODE128", "ABCDEFGHIJKLMNOPQRSTUVWX", "raster");
+    if let PrintSections::Barcode(ref mut barcode) = barcode {
+        barcode.width = 6;
+    }
+    let out = gen(vec![barcode]);
+    // Inicio + 24 caracteres + control: 26 × 11 + 13 + 20 de zona de silencio =
+    // 319 módulos; a 6 o 2 puntos no caben en 576, a 1 sí → 40 bytes por fila
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 40, 0x00]));
+}
+
+#[test]
+fn barcode_auto_mode_follows_printer_profile() {
+    let native = gen(vec![barcode_with_mode("GS1-128", "(01)09501101530003", "auto")]);
+    assert!(contains(&native, &[0x1D, 0x6B, 74]));
+
+    let mut request = job(vec![
+        barcode_with_mode("GS1-128", "(01)09501101530003", "auto"),
+        barcode_with_mode("EAN13", "590123412345", "auto"),
+    ]);
+    request.profile.native_gs1_barcodes = false;
+    let out = ProcessPrint::new().generate_document(&request).unwrap();
+    assert!(!contains(&out, &[0x1D, 0x6B, 74]), "GS1-128 rasterized");
+    assert!(contains(&out, &[0x1D, 0x6B, 67]), "EAN13 still native");
+}
+
+#[test]
+fn databar_limited_raster_matches_known_module_widths() {
+    // Anchos de elementos (espacio primero) de referencia
+    let widths = |data: &str| {
+        let modules = encode_barcode("GS1-DATABAR-LIMITED", data).unwrap().modules;
+        let mut widths = String::new();
+        let mut run = 1;
+        for pair in modules.windows(2) {
+            if pair[0] == pair[1] {
+                run += 1;
+            } else {
+                widths.push_str(&run.to_string());
+                run = 1;
+            }
+        }
+        widths.push_str(&run.to_string());
+        widths
+    };
+    assert_eq!(widths("1501234567890"), "1132223212111123112111121122112121123213222211");
+    assert_eq!(widths("00000000000017"), "1111111111116163112112111221111111111111626211");
+    assert_eq!(widths("1999999999999"), "1124221111131313111311111131111141133211142111");
+    let symbol = encode_barcode("GS1-DATABAR-LIMITED", "1501234567890").unwrap();
+    assert_eq!(symbol.modules.len(), 74);
+    assert_eq!(symbol.hri, "(01)15012345678907");
+    assert!(encode_barcode("GS1-DATABAR-LIMITED", "2501234567890").is_err(), "first digit 2");
+
+    // Nativo sigue usando GS k; auto sin firmware se rasteriza
+    let native = gen(vec![barcode_with_mode("GS1-DATABAR-LIMITED", "1501234567890", "auto")]);
+    assert!(contains(&native, &[0x1D, 0x6B, 77]));
+    let mut request = job(vec![barcode_with_mode("GS1-DATABAR-LIMITED", "1501234567890", "auto")]);
+    request.profile.native_gs1_barcodes = false;
+    let out = ProcessPrint::new().generate_document(&request).unwrap();
+    assert!(!contains(&out, &[0x1D, 0x6B, 77]));
+    assert!(contains(&out, &[0x1D, 0x76, 0x30]));
+}
+
 #[test]
 fn data_matrix_uses_gs_paren_k_with_cn_54() {
     let out = gen(vec![PrintSections::DataMatrix(DataMatrixModel {
         data: "DM data".into(),
         size: 5,
+        render_mode: None,
     })]);
     // cn = 54 (0x36) es el valor estándar de DataMatrix (cn=50 es MaxiCode)
     assert!(contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x36]), "DataMatrix (cn=54)");
@@ -366,10 +923,85 @@ fn pdf417_uses_gs_paren_k_with_cn_48() {
         width: 3,
         height: 5,
         error_correction: 2,
+        render_mode: None,
     })]);
     assert!(contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x30]), "PDF417 (cn=48)");
 }
 
+fn pdf417_with(data: &str, columns: u8, rows: u8, render_mode: &str) -> PrintSections {
+    PrintSections::Pdf417(Pdf417 {
+        data: data.into(),
+        columns,
+        rows,
+        width: 3,
+        height: 5,
+        error_correction: 0,
+        render_mode: Some(render_mode.into()),
+    })
+}
+
+#[test]
+fn pdf417_raster_honors_columns_and_rows() {
+    let options = Pdf417Options {
+        columns: 2,
+        rows: 5,
+        error_correction: 0,
+        row_height: 5,
+    };
+    let symbol = pdf417_encoder::encode(b"PDF data", options).unwrap();
+    // 2 columnas de datos: 17 × 2 + 69 = 103 módulos por fila
+    assert_eq!((symbol.columns, symbol.rows()), (103, 5));
+    let row = |y: u32| -> String {
+        (0..symbol.columns)
+            .map(|x| if symbol.get(x, y) { '1' } else { '0' })
+            .collect()
+    };
+    // Filas verificadas con un lector independiente (indicadores, datos y corrección)
+    assert_eq!(
+        row(0),
+        "11111111010101000111101010111100001101010000110000010000110001100100111101010111100001\
+         11111101000101001"
+    );
+    assert_eq!(
+        row(4),
+        "11111111010101000111010111001100001110100110010000011011101111101000111010111001100001\
+         11111101000101001"
+    );
+    for y in 0..5 {
+        assert!(row(y).starts_with("11111111010101000"), "start pattern");
+        assert!(row(y).ends_with("111111101000101001"), "stop pattern");
+    }
+
+    // Columnas fijas: las filas crecen con los datos
+    let options = Pdf417Options { rows: 0, ..options };
+    let symbol = pdf417_encoder::encode("PDF data ".repeat(10).as_bytes(), options).unwrap();
+    assert_eq!((symbol.columns, symbol.rows()), (103, 32));
+
+    // 309 × 75 puntos (módulos de 3 puntos, filas de 5 × 3) → 39 bytes × 75 filas
+    let out = gen(vec![pdf417_with("PDF data", 2, 5, "raster")]);
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 39, 0x00, 75, 0x00]));
+    assert!(!contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x30]), "no GS ( k");
+
+    // Columnas y filas fijas: los datos deben caber o se informa del error
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![pdf417_with(&"A".repeat(40), 2, 5, "raster")]))
+        .unwrap_err();
+    assert!(err.to_string().contains("2 columns × 5 rows"), "{err}");
+}
+
+#[test]
+fn pdf417_auto_mode_follows_profile() {
+    let section = pdf417_with("PDF data", 0, 0, "auto");
+    let out = gen(vec![section.clone()]);
+    assert!(contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x30]), "native by default");
+
+    let mut request = job(vec![section]);
+    request.profile.native_pdf417 = false;
+    let out = ProcessPrint::new().generate_document(&request).unwrap();
+    assert!(contains(&out, &[0x1D, 0x76, 0x30]), "rasterized");
+    assert!(!contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x30]));
+}
+
 #[test]
 fn aztec_uses_gs_paren_k_with_cn_53() {
     let out = gen(vec![PrintSections::Aztec(Aztec {
@@ -379,11 +1011,129 @@ fn aztec_uses_gs_paren_k_with_cn_53() {
         size: 3,
         error_correction: 23,
         align: None,
+        render_mode: None,
     })]);
     // cn = 53 (0x35)
     assert!(contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x35]), "Aztec (cn=53)");
 }
 
+fn aztec_with(data: &str, mode: u8, layers: u8, render_mode: &str) -> PrintSections {
+    PrintSections::Aztec(Aztec {
+        data: data.into(),
+        mode,
+        layers,
+        size: 3,
+        error_correction: 23,
+        align: None,
+        render_mode: Some(render_mode.into()),
+    })
+}
+
+#[test]
+fn data_matrix_raster_encodes_ecc200() {
+    // Ejemplo de la norma ISO/IEC 16022: "123456" → 10×10, datos 142 164 186
+    assert_eq!(
+        ecc200::symbol_codewords(b"123456").unwrap(),
+        vec![142, 164, 186, 114, 25, 5, 88, 102]
+    );
+
+    let symbol = ecc200::encode(b"123456").unwrap();
+    assert_eq!((symbol.columns, symbol.rows()), (10, 10));
+    for i in 0..10 {
+        assert!(symbol.get(0, i), "left finder solid");
+        assert!(symbol.get(i, 9), "bottom finder solid");
+        assert_eq!(symbol.get(i, 0), i % 2 == 0, "top timing");
+        assert_eq!(symbol.get(9, i), i % 2 == 1, "right timing");
+    }
+
+    // 300 bytes → 72×72 (4×4 regiones, 4 bloques RS intercalados)
+    let symbol = ecc200::encode("x".repeat(300).as_bytes()).unwrap();
+    assert_eq!(symbol.columns, 72);
+    assert!(ecc200::encode("x".repeat(1600).as_bytes()).is_err());
+
+    let out = gen(vec![PrintSections::DataMatrix(DataMatrixModel {
+        data: "123456".into(),
+        size: 4,
+        render_mode: Some("raster".into()),
+    })]);
+    // 10 módulos × 4 puntos = 40 → 5 bytes × 40 filas
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 5, 0x00, 40, 0x00]));
+    assert!(!contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x36]), "no GS ( k");
+}
+
+#[test]
+fn data_matrix_auto_mode_follows_profile() {
+    let section = PrintSections::DataMatrix(DataMatrixModel {
+        data: "DM data".into(),
+        size: 3,
+        render_mode: Some("auto".into()),
+    });
+    let out = gen(vec![section.clone()]);
+    assert!(contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x36]), "native by default");
+
+    let mut request = job(vec![section]);
+    request.profile.native_data_matrix = false;
+    let out = ProcessPrint::new().generate_document(&request).unwrap();
+    assert!(contains(&out, &[0x1D, 0x76, 0x30]), "rasterized");
+    assert!(!contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x36]));
+}
+
+#[test]
+fn aztec_raster_honors_mode_and_layers() {
+    // Compacto de 1 capa: 15×15 módulos × 3 puntos = 45 → 6 bytes × 45 filas
+    let out = gen(vec![aztec_with("HELLO", 1, 0, "raster")]);
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 6, 0x00, 45, 0x00]));
+    assert!(!contains(&out, &[0x1D, 0x28, 0x6B, 0x04, 0x00, 0x35]), "no GS ( k");
+
+    // Completo de 1 capa: 19×19 → 57 puntos → 8 bytes × 57 filas
+    let out = gen(vec![aztec_with("HELLO", 0, 0, "raster")]);
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 8, 0x00, 57, 0x00]));
+
+    let options = AztecOptions {
+        compact: false,
+        layers: 0,
+        error_correction: 23,
+    };
+    let symbol = aztec_encoder::encode(b"Order 12345 / caf\xc3\xa9", options).unwrap();
+    let center = symbol.columns / 2;
+    // Diana: centro negro, anillo 1 blanco, anillo 2 negro
+    assert!(symbol.get(center, center));
+    assert!(!symbol.get(center + 1, center));
+    assert!(symbol.get(center + 2, center));
+
+    // Capas fijas: los datos deben caber o se informa del error
+    let long = "A".repeat(200);
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![aztec_with(&long, 1, 2, "raster")]))
+        .unwrap_err();
+    assert!(err.to_string().contains("2 layers"), "{err}");
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![aztec_with("HELLO", 1, 5, "raster")]))
+        .unwrap_err();
+    assert!(err.to_string().contains("at most 4 layers"), "{err}");
+}
+
+#[test]
+fn reed_solomon_check_words_are_codeword_roots() {
+    for (primitive, size, n) in [(0x13, 16, 5), (0x43, 64, 7), (0x12D, 256, 10), (0x409, 1024, 12)] {
+        let field = GaloisField::new(primitive, size);
+        let data: Vec<u32> = (1..20).map(|i| (i * 37) % size).collect();
+        let codeword: Vec<u32> = data
+            .iter()
+            .copied()
+            .chain(field.check_words(&data, n))
+            .collect();
+        // c(α^i) = 0 para i = 1..n
+        for i in 1..=n as u32 {
+            let root = field.exp(i);
+            let value = codeword
+                .iter()
+                .fold(0, |acc, &c| field.multiply(acc, root) ^ c);
+            assert_eq!(value, 0, "GF({size}) root α^{i}");
+        }
+    }
+}
+
 #[test]
 fn gs1_databar_2d_uses_gs_paren_k_with_cn_51() {
     let out = gen(vec![PrintSections::Gs1Databar2d(Gs1Databar2d {
@@ -403,12 +1153,70 @@ fn maxicode_uses_gs_paren_k_with_cn_50() {
         data: "MaxiCode data".into(),
         mode: 4,
         align: None,
+        render_mode: None,
     })]);
     // cn = 50 (0x32); modo 4 -> n=0x34
     assert!(contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x32]), "MaxiCode (cn=50)");
     assert!(contains(&out, &[0x32, 0x43, 0x34]), "modo 4 (n=0x34)");
 }
 
+fn maxicode_with(data: &str, mode: u8, render_mode: &str) -> PrintSections {
+    PrintSections::MaxiCode(MaxiCode {
+        data: data.into(),
+        mode,
+        align: None,
+        render_mode: Some(render_mode.into()),
+    })
+}
+
+#[test]
+fn maxicode_raster_encodes_the_hexagonal_grid() {
+    let symbol = maxicode_encoder::encode("MaxiCode data", 4).unwrap();
+    assert_eq!((symbol.columns, symbol.rows()), (30, 33));
+    let row = |y: u32| -> String {
+        (0..symbol.columns)
+            .map(|x| if symbol.get(x, y) { '1' } else { '0' })
+            .collect()
+    };
+    // Filas verificadas con un lector independiente: datos, relleno superior
+    // derecho y mensaje primario (modo 4) junto a la marca superior izquierda
+    assert_eq!(row(0), "000100001000010101010101010111");
+    assert_eq!(row(9), "010101011111111100000001010100");
+    for (x, y) in [(10, 9), (11, 9), (11, 10), (7, 15), (8, 16), (20, 16), (20, 17)] {
+        assert!(symbol.get(x, y), "orientation module ({x}, {y})");
+    }
+
+    // Modos 2 y 3: el primario sale de "código postal␝país␝clase␝"
+    assert!(maxicode_encoder::encode("123456789\x1d840\x1d001\x1dMessage", 2).is_ok());
+    assert!(maxicode_encoder::encode("B1050\x1d056\x1d999\x1dMessage", 3).is_ok());
+    let err = maxicode_encoder::encode("B1050\x1d056\x1d999\x1dMessage", 2).unwrap_err();
+    assert!(err.to_string().contains("1-9 digits"), "{err}");
+    let err = maxicode_encoder::encode("No primary message", 3).unwrap_err();
+    assert!(err.to_string().contains("postcode<GS>country"), "{err}");
+    assert!(maxicode_encoder::encode(&"A".repeat(93), 4).is_ok());
+    assert!(maxicode_encoder::encode(&"A".repeat(94), 4).is_err());
+    assert!(maxicode_encoder::encode(&"A".repeat(78), 5).is_err());
+    assert!(maxicode_encoder::encode("日本", 4).is_err());
+
+    // Módulos de 0,88 mm = 7 puntos: 30 × 7 = 210 → 27 bytes × 203 filas
+    let out = gen(vec![maxicode_with("MaxiCode data", 4, "raster")]);
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 27, 0x00, 203, 0x00]));
+    assert!(!contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x32]), "no GS ( k");
+}
+
+#[test]
+fn maxicode_auto_mode_follows_profile() {
+    let section = maxicode_with("MaxiCode data", 4, "auto");
+    let out = gen(vec![section.clone()]);
+    assert!(contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x32]), "native by default");
+
+    let mut request = job(vec![section]);
+    request.profile.native_maxicode = false;
+    let out = ProcessPrint::new().generate_document(&request).unwrap();
+    assert!(contains(&out, &[0x1D, 0x76, 0x30]), "rasterized");
+    assert!(!contains(&out, &[0x1D, 0x28, 0x6B, 0x03, 0x00, 0x32]));
+}
+
 #[test]
 fn composite_uses_gs_paren_k_with_cn_52() {
     let out = gen(vec![PrintSections::Composite(Composite {
@@ -435,6 +1243,7 @@ fn image_emits_raster_command() {
         align: "center".into(),
         dithering: false,
         size: "normal".into(),
+        ..Default::default()
     })]);
     assert!(contains(&out, &[0x1D, 0x76, 0x30]), "GS v 0 (raster)");
 }
@@ -447,16 +1256,589 @@ fn empty_image_is_rejected() {
         align: "left".into(),
         dithering: false,
         size: "normal".into(),
+        ..Default::default()
     })]));
     assert!(result.is_err());
 }
 
+/// PNG en escala de grises codificado en base64; `shade(x, y)` da el valor de cada píxel.
+fn png_base64(width: u32, height: u32, shade: impl Fn(u32, u32) -> u8) -> String {
+    use base64::Engine as _;
+    let gray = image::GrayImage::from_fn(width, height, |x, y| image::Luma([shade(x, y)]));
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::ImageLuma8(gray)
+        .write_to(&mut png, image::ImageOutputFormat::Png)
+        .unwrap();
+    base64::engine::general_purpose::STANDARD.encode(png.into_inner())
+}
+
+fn image_section(data: String) -> Image {
+    Image {
+        data,
+        max_width: 0,
+        align: "left".into(),
+        dithering: true,
+        size: "normal".into(),
+        ..Default::default()
+    }
+}
+
+fn black_dots(binary: &image::GrayImage) -> usize {
+    binary.pixels().filter(|p| p[0] == 0).count()
+}
+
+#[test]
+fn dithering_algorithms_are_deterministic_and_keep_the_tone() {
+    let flat = image::GrayImage::from_pixel(16, 16, image::Luma([128]));
+    let gradient = image::GrayImage::from_fn(32, 8, |x, _| image::Luma([(x * 8) as u8]));
+    let mut patterns = HashSet::new();
+    for name in [
+        "threshold", "floyd_steinberg", "atkinson", "jarvis_judice_ninke", "stucki", "sierra",
+        "bayer4", "bayer8",
+    ] {
+        let algorithm = DitherAlgorithm::from_name(Some(name), true).unwrap();
+        let once = algorithm.apply(&gradient, 127);
+        assert_eq!(once, algorithm.apply(&gradient, 127), "{name} is deterministic");
+        patterns.insert(once.into_raw());
+        if name != "threshold" {
+            // Gris medio: alrededor de la mitad de los puntos en negro
+            let black = black_dots(&algorithm.apply(&flat, 127));
+            assert!((96..=160).contains(&black), "{name}: {black} of 256");
+        }
+    }
+    assert_eq!(patterns.len(), 8, "every algorithm gives its own pattern");
+
+    // Bayer 4×4 sobre gris 128: exactamente las 8 celdas de rango >= 8
+    let bayer = DitherAlgorithm::Bayer4.apply(&flat, 127);
+    assert_eq!(black_dots(&bayer), 128);
+    assert_eq!(bayer.get_pixel(1, 0)[0], 0, "rank 8 is black");
+    assert_eq!(bayer.get_pixel(0, 0)[0], 255, "rank 0 is white");
+    // Floyd-Steinberg sigue siendo el algoritmo por defecto
+    assert_eq!(
+        ImageOptions::default().binarize(&gradient),
+        DitherAlgorithm::FloydSteinberg.apply(&gradient, 127)
+    );
+    assert_eq!(DitherAlgorithm::from_name(Some("atkinson"), false).unwrap(), DitherAlgorithm::Threshold);
+    assert!(DitherAlgorithm::from_name(Some("random"), true).is_err());
+}
+
+#[test]
+fn image_tuning_controls_adjust_before_dithering() {
+    let adjust = |edit: fn(&mut Image), value: u8| {
+        let mut section = image_section(String::new());
+        edit(&mut section);
+        let options = ImageOptions::from_section(&section).unwrap();
+        options.adjust(&image::GrayImage::from_pixel(1, 1, image::Luma([value])))[(0, 0)][0]
+    };
+    assert_eq!(adjust(|_| {}, 64), 64);
+    assert_eq!(adjust(|i| i.brightness = Some(100.0), 64), 255);
+    assert_eq!(adjust(|i| i.brightness = Some(-50.0), 200), 73);
+    assert_eq!(adjust(|i| i.contrast = Some(-100.0), 10), 128);
+    assert_eq!(adjust(|i| i.contrast = Some(100.0), 100), 72);
+    assert_eq!(adjust(|i| i.gamma = Some(2.0), 64), 128);
+    assert_eq!(adjust(|i| i.invert = Some(true), 64), 191);
+
+    // El enfoque acentúa un borde
+    let edge = image::GrayImage::from_fn(4, 1, |x, _| image::Luma([if x < 2 { 100 } else { 160 }]));
+    let mut section = image_section(String::new());
+    section.sharpen = Some(1.0);
+    let sharp = ImageOptions::from_section(&section).unwrap().adjust(&edge);
+    assert!(sharp[(1, 0)][0] < 100 && sharp[(2, 0)][0] > 160, "{:?}", sharp.as_raw());
+
+    let rejected = |edit: fn(&mut Image), reason: &str| {
+        let mut section = image_section(TINY_PNG_BASE64.into());
+        edit(&mut section);
+        let err = ProcessPrint::new()
+            .generate_document(&job(vec![PrintSections::Image(section)]))
+            .unwrap_err();
+        assert!(err.to_string().contains(reason), "{err}");
+    };
+    rejected(|i| i.gamma = Some(0.0), "gamma must be between 0.1 and 10");
+    rejected(|i| i.brightness = Some(150.0), "brightness must be between -100 and 100");
+    rejected(|i| i.dithering_algorithm = Some("dots".into()), "Unknown dithering algorithm 'dots'");
+
+    // De extremo a extremo: el algoritmo y la inversión cambian los datos raster
+    let data = png_base64(16, 16, |x, y| ((x + y) * 8) as u8);
+    let print = |edit: fn(&mut Image)| {
+        let mut section = image_section(data.clone());
+        edit(&mut section);
+        gen(vec![PrintSections::Image(section)])
+    };
+    let default = print(|_| {});
+    assert_eq!(default, print(|_| {}), "same job, same bytes");
+    assert_ne!(default, print(|i| i.dithering_algorithm = Some("bayer8".into())));
+    assert_ne!(default, print(|i| i.invert = Some(true)));
+}
+
+#[test]
+fn image_command_modes_pack_columns_and_graphics() {
+    // 10×30 puntos negros: 2 franjas de 24 (la segunda con 6 filas)
+    let print = |command_mode: &str| {
+        let mut section = image_section(png_base64(10, 30, |_, _| 0));
+        section.command_mode = Some(command_mode.into());
+        gen(vec![PrintSections::Image(section)])
+    };
+
+    let out = print("bit_image_24");
+    let mut first = vec![0x1B, 0x33, 24, 0x1B, 0x2A, 33, 10, 0];
+    first.extend([0xFF; 30]);
+    first.push(0x0A);
+    let mut second = vec![0x1B, 0x2A, 33, 10, 0];
+    second.extend([0xFC, 0x00, 0x00].repeat(10));
+    second.extend([0x0A, 0x1B, 0x32]);
+    assert!(contains(&out, &first), "ESC 3 24 + first 24-dot band");
+    assert!(contains(&out, &second), "second band, then ESC 2");
+    assert!(!contains(&out, &[0x1D, 0x76, 0x30]), "no GS v 0");
+
+    // Modo 8: 30 filas se agrupan en 10 (puntos de triple altura)
+    let out = print("bit_image_8");
+    let mut bands = vec![0x1B, 0x2A, 1, 10, 0];
+    bands.extend([0xFF; 10]);
+    bands.extend([0x0A, 0x1B, 0x2A, 1, 10, 0]);
+    bands.extend([0xC0; 10]);
+    bands.extend([0x0A, 0x1B, 0x32]);
+    assert!(contains(&out, &bands), "two 8-dot bands");
+
+    let out = print("graphics");
+    let mut store = vec![0x1D, 0x28, 0x4C, 70, 0, 0x30, 0x70, 0x30, 1, 1, 0x31, 10, 0, 30, 0];
+    store.extend([0xFF, 0xC0].repeat(30));
+    store.extend([0x1D, 0x28, 0x4C, 2, 0, 0x30, 0x32]);
+    assert!(contains(&out, &store), "GS ( L fn 112 + fn 50");
+
+    let mut section = image_section(TINY_PNG_BASE64.into());
+    section.command_mode = Some("escp".into());
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![PrintSections::Image(section)]))
+        .unwrap_err();
+    assert!(err.to_string().contains("Unknown image command mode 'escp'"), "{err}");
+}
+
+#[test]
+fn tall_images_are_split_into_identical_raster_bands() {
+    // 12×50: una franja de 50 filas frente a 16 + 16 + 16 + 2
+    let data = png_base64(12, 50, |x, y| if (x + y) % 3 == 0 { 0 } else { 255 });
+    let section = |band_height: Option<u32>, band_delay_ms: Option<u32>| Image {
+        band_height,
+        band_delay_ms,
+        ..image_section(data.clone())
+    };
+    let raster_rows = |out: &[u8]| {
+        let mut heights = Vec::new();
+        let mut rows = Vec::new();
+        let mut i = 0;
+        while let Some(pos) = out[i..].windows(3).position(|w| w == [0x1D, 0x76, 0x30]) {
+            let start = i + pos;
+            let width_bytes = out[start + 4] as usize + out[start + 5] as usize * 256;
+            let height = out[start + 6] as usize + out[start + 7] as usize * 256;
+            heights.push(height);
+            rows.extend_from_slice(&out[start + 8..start + 8 + width_bytes * height]);
+            i = start + 8 + width_bytes * height;
+        }
+        (heights, rows)
+    };
+
+    let single = gen(vec![PrintSections::Image(section(None, None))]);
+    let banded = gen(vec![PrintSections::Image(section(Some(16), None))]);
+    let (single_heights, single_rows) = raster_rows(&single);
+    let (band_heights, band_rows) = raster_rows(&banded);
+    assert_eq!(single_heights, vec![50]);
+    assert_eq!(band_heights, vec![16, 16, 16, 2]);
+    assert_eq!(single_rows, band_rows, "same bitmap, split in bands");
+
+    // Con retardo: una pausa al final de cada franja salvo la última
+    let mut process = ProcessPrint::new();
+    let paced = process
+        .generate_document(&job(vec![PrintSections::Image(section(Some(16), Some(40)))]))
+        .unwrap();
+    assert_eq!(paced, banded, "pacing does not change the bytes");
+    let pauses = process.pauses().to_vec();
+    assert_eq!(pauses.len(), 3);
+    for pause in &pauses {
+        assert_eq!(pause.delay_ms, 40);
+        assert_eq!(&paced[pause.offset..pause.offset + 3], &[0x1D, 0x76, 0x30]);
+    }
+    let segments = paced_segments(&paced, &pauses);
+    assert_eq!(segments.len(), 4);
+    assert_eq!(segments.iter().map(|(s, _)| s.len()).sum::<usize>(), paced.len());
+    assert_eq!(segments.last().unwrap().1, 0);
+
+    // Sin retardo no hay pausas; las franjas de ESC * se redondean a 24 filas
+    process
+        .generate_document(&job(vec![PrintSections::Image(section(Some(16), None))]))
+        .unwrap();
+    assert!(process.pauses().is_empty());
+    let mut escpos = section(Some(30), None);
+    escpos.command_mode = Some("bit_image_24".into());
+    let mut whole = section(None, None);
+    whole.command_mode = Some("bit_image_24".into());
+    let count = |out: &[u8]| out.windows(2).filter(|w| w == &[0x1B, 0x2A]).count();
+    assert_eq!(count(&gen(vec![PrintSections::Image(escpos)])), 3);
+    assert_eq!(count(&gen(vec![PrintSections::Image(whole)])), 3);
+
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![PrintSections::Image(section(Some(0), None))]))
+        .unwrap_err();
+    assert!(err.to_string().contains("band_height must be greater than 0"), "{err}");
+}
+
+#[test]
+fn repeated_images_are_sent_once_as_download_graphics() {
+    let icon = image_section(png_base64(10, 2, |_, _| 0));
+    let other = image_section(png_base64(8, 1, |_, _| 0));
+    let sections = vec![
+        PrintSections::Image(icon.clone()),
+        PrintSections::Image(other.clone()),
+        PrintSections::Image(icon.clone()),
+        PrintSections::Image(icon.clone()),
+    ];
+
+    let mut request = job(sections.clone());
+    request.profile.download_graphics = true;
+    let out = ProcessPrint::new().generate_document(&request).unwrap();
+    assert!(contains(&out, &[0x1D, 0x28, 0x4C, 5, 0, 0x30, 0x51, b'C', b'L', b'R']), "fn 81 clears the buffer");
+    let mut define = vec![0x1D, 0x28, 0x4C, 15, 0, 0x30, 0x53, 0x30, b'0', b'0', 1, 10, 0, 2, 0, 0x31];
+    define.extend([0xFF, 0xC0, 0xFF, 0xC0]);
+    assert_eq!(count(&out, &define), 1, "fn 83 defines the repeated image once");
+    let print = [0x1B, 0x61, 0x00, 0x1D, 0x28, 0x4C, 6, 0, 0x30, 0x55, b'0', b'0', 1, 1];
+    assert_eq!(count(&out, &print), 3, "fn 85 prints every copy");
+    assert_eq!(count(&out, &[0x1D, 0x76, 0x30]), 1, "the single image stays GS v 0");
+
+    // Sin activarlo en el perfil (por defecto) cada copia va en raster
+    let out = gen(sections);
+    assert_eq!(count(&out, &[0x1D, 0x76, 0x30]), 4, "without download graphics every copy is raster");
+    assert!(!contains(&out, &[0x30, 0x53]), "no fn 83");
+    assert!(!contains(&out, &[0x30, 0x51, b'C', b'L', b'R']), "no fn 81");
+
+    // Una imagen multi_tone en un perfil sin multi_tone se imprime como raster
+    // con tramado; el plan usa esa misma imagen y la define una sola vez
+    let mut tone = icon.clone();
+    tone.command_mode = Some("multi_tone".to_string());
+    let mut request = job(vec![PrintSections::Image(tone.clone()), PrintSections::Image(tone)]);
+    request.profile.download_graphics = true;
+    let out = ProcessPrint::new().generate_document(&request).unwrap();
+    assert_eq!(count(&out, &define), 1, "fallback image defined once");
+    assert_eq!(count(&out, &print), 2, "both copies printed by key");
+    assert!(!contains(&out, &[0x1D, 0x76, 0x30]), "no inline copy");
+}
+
+#[test]
+fn images_load_from_files_and_registered_assets() {
+    use base64::Engine as _;
+    let data = png_base64(10, 2, |_, _| 0);
+    let png = base64::engine::general_purpose::STANDARD.decode(&data).unwrap();
+    let path = std::env::temp_dir().join(format!("thermal-printer-{}.png", std::process::id()));
+    std::fs::write(&path, png).unwrap();
+
+    let assets = ImageAssets::default();
+    assets.register("icon", &data).unwrap();
+    let print = |data: String| {
+        ProcessPrint::new()
+            .set_assets(assets.clone())
+            .generate_document(&job(vec![PrintSections::Image(image_section(data))]))
+    };
+    let expected = gen(vec![PrintSections::Image(image_section(data.clone()))]);
+    let file = format!("file://{}", path.display());
+    let err = print(file.clone()).unwrap_err();
+    assert!(err.to_string().contains("disabled"), "file:// is opt-in: {err}");
+    assets.set_allow_files(true);
+    assert_eq!(print(file.clone()).unwrap(), expected, "file://");
+    assert_eq!(print("asset:icon".into()).unwrap(), expected, "asset:");
+
+    // Misma imagen por otra vía: el mapa de bits sale de la caché
+    let options = ImageOptions::default();
+    let cached = assets.bitmap(&file, 384, &options).unwrap();
+    assert!(std::sync::Arc::ptr_eq(&cached, &assets.bitmap("asset:icon", 384, &options).unwrap()));
+    assert!(!std::sync::Arc::ptr_eq(&cached, &assets.bitmap("asset:icon", 200, &options).unwrap()));
+    std::fs::remove_file(&path).unwrap();
+
+    for missing in ["asset:nope", "resource://logo.png", file.as_str()] {
+        let err = print(missing.into()).unwrap_err();
+        assert_eq!(err.code(), "IMAGE_DECODE", "{missing}");
+    }
+    assert_eq!(assets.register("bad", "asset:nope").unwrap_err().code(), "INVALID_JOB");
+
+    // resource:// no sale de la carpeta de recursos
+    let dir = std::env::temp_dir().join(format!("thermal-printer-res-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("images")).unwrap();
+    std::fs::write(dir.join("images/icon.png"), base64::engine::general_purpose::STANDARD.decode(&data).unwrap()).unwrap();
+    assets.set_resource_dir(Some(dir.join("images")));
+    assert_eq!(print("resource://icon.png".into()).unwrap(), expected, "resource://");
+    for escape in ["resource://../images/icon.png", "resource:///etc/passwd"] {
+        let err = print(escape.into()).unwrap_err();
+        assert!(err.to_string().contains("must be relative"), "{escape}: {err}");
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn svg_images_are_rasterized_at_the_target_width() {
+    use base64::Engine as _;
+    // Mitad izquierda negra: el borde debe caer justo en la columna 50 de 100
+    let svg = r#"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="10" height="5" viewBox="0 0 10 5">
+  <rect x="0" y="0" width="5" height="5" fill="black"/>
+</svg>"#;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(svg);
+
+    let assets = ImageAssets::default();
+    let options = ImageOptions::default();
+    let bitmap = assets.bitmap(&encoded, 100, &options).unwrap();
+    assert_eq!(bitmap.dimensions(), (100, 50), "scaled up from 10x5, not clamped");
+    assert_eq!(bitmap.get_pixel(49, 25)[0], 0);
+    assert_eq!(bitmap.get_pixel(50, 25)[0], 255, "crisp edge, no resampling blur");
+    assert_eq!(black_dots(&bitmap), 50 * 50);
+    assert_eq!(assets.bitmap(svg, 100, &options).unwrap(), bitmap, "inline markup");
+    // Girado, el alto del SVG pasa a ser el ancho: se dibuja a 20× en lugar de 10×
+    let rotated = ImageOptions {
+        transform: ImageTransform { rotate: 90, ..Default::default() },
+        ..options
+    };
+    assert_eq!(assets.bitmap(svg, 100, &rotated).unwrap().dimensions(), (100, 200));
+
+    let mut section = image_section(encoded);
+    section.max_width = 100;
+    let out = gen(vec![PrintSections::Image(section)]);
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0, 13, 0, 50, 0]), "GS v 0 13 bytes x 50 rows");
+
+    let err = assets.bitmap("<svg", 100, &options).unwrap_err();
+    assert!(err.to_string().contains("SVG"), "{err}");
+}
+
+#[test]
+fn image_crop_rotate_trim_and_padding_run_before_and_after_dithering() {
+    // 40×12 en blanco con un bloque negro de 4×2 en (10, 4)
+    let data = png_base64(40, 12, |x, y| {
+        if (10..14).contains(&x) && (4..6).contains(&y) { 0 } else { 255 }
+    });
+    let assets = ImageAssets::default();
+    let bitmap = |edit: &dyn Fn(&mut Image)| {
+        let mut section = image_section(data.clone());
+        edit(&mut section);
+        let options = ImageOptions::from_section(&section)?;
+        assets.bitmap(&section.data, 384, &options).map(|bitmap| (*bitmap).clone())
+    };
+
+    assert_eq!(bitmap(&|s| s.rotate = Some(90)).unwrap().dimensions(), (12, 40));
+    let trimmed = bitmap(&|s| s.trim = Some(true)).unwrap();
+    assert_eq!(trimmed.dimensions(), (4, 2));
+    assert_eq!(black_dots(&trimmed), 8);
+    let crop = ImageCrop { x: 10, y: 4, width: 4, height: 2 };
+    assert_eq!(bitmap(&|s| s.crop = Some(crop)).unwrap(), trimmed);
+    let rotated = bitmap(&|s| {
+        s.rotate = Some(270);
+        s.trim = Some(true);
+    })
+    .unwrap();
+    assert_eq!(rotated.dimensions(), (2, 4));
+
+    let padded = bitmap(&|s| {
+        s.trim = Some(true);
+        s.padding = Some(ImagePadding { top: 1, left: 3, right: 1, ..Default::default() });
+    })
+    .unwrap();
+    assert_eq!(padded.dimensions(), (8, 3));
+    assert_eq!(black_dots(&padded), 8);
+    assert_eq!((padded.get_pixel(3, 1)[0], padded.get_pixel(2, 1)[0]), (0, 255));
+    // El relleno sigue en blanco aunque se invierta la imagen
+    let inverted = bitmap(&|s| {
+        s.crop = Some(crop);
+        s.invert = Some(true);
+        s.padding = Some(ImagePadding { left: 2, ..Default::default() });
+    })
+    .unwrap();
+    assert_eq!(black_dots(&inverted), 0);
+
+    let err = bitmap(&|s| s.crop = Some(ImageCrop { x: 38, ..crop })).unwrap_err();
+    assert!(err.to_string().contains("outside the 40x12 image"), "{err}");
+    assert!(bitmap(&|s| s.rotate = Some(45)).is_err());
+    let err = bitmap(&|s| s.padding = Some(ImagePadding { left: 200, right: 200, ..Default::default() }))
+        .unwrap_err();
+    assert!(err.to_string().contains("leaves no room"), "{err}");
+}
+
+#[test]
+fn multi_tone_images_send_one_bit_plane_per_color() {
+    // Blanco, gris claro, gris oscuro y negro: tonos 0, 1, 2 y 3
+    let mut section = image_section(png_base64(8, 1, |x, _| [255, 170, 85, 0][x as usize % 4]));
+    section.dithering = false;
+    section.command_mode = Some("multi_tone".into());
+
+    let mut request = job(vec![PrintSections::Image(section.clone())]);
+    request.profile.multi_tone = true;
+    let out = ProcessPrint::new().generate_document(&request).unwrap();
+    let plane = |color: u8, bits: u8| {
+        vec![0x1D, 0x28, 0x4C, 11, 0, 0x30, 0x70, 0x34, 1, 1, color, 8, 0, 1, 0, bits]
+    };
+    assert!(contains(&out, &plane(0x31, 0b0011_0011)), "c=49: high bit of the tone");
+    assert!(contains(&out, &plane(0x32, 0b0101_0101)), "c=50: low bit of the tone");
+    assert!(contains(&out, &[0x1D, 0x28, 0x4C, 2, 0, 0x30, 0x32]), "fn 50 prints");
+    assert!(!contains(&out, &[0x1D, 0x76, 0x30]));
+
+    // Sin tonos múltiples en el perfil (por defecto) se trama en blanco y negro
+    let out = gen(vec![PrintSections::Image(section)]);
+    assert!(contains(&out, &[0x1D, 0x76, 0x30]), "falls back to GS v 0");
+    assert!(!contains(&out, &[0x30, 0x70, 0x34]), "no multiple-tone data");
+
+    // La difusión de error reparte un gris plano entre los dos tonos vecinos
+    let options = ImageOptions { levels: 4, ..ImageOptions::default() };
+    let tones = options.binarize(&image::GrayImage::from_pixel(16, 16, image::Luma([128])));
+    let used: HashSet<u8> = tones.pixels().map(|p| p[0]).collect();
+    assert_eq!(used, HashSet::from([85, 170]));
+}
+
+#[test]
+fn two_color_images_send_black_and_red_planes() {
+    use base64::Engine as _;
+    // Negro, rojo, blanco, negro, rojo y blanco
+    let colors = [[0, 0, 0], [220, 20, 30], [255, 255, 255]];
+    let rgb = image::RgbImage::from_fn(8, 1, |x, _| image::Rgb(colors[x as usize % 3]));
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgb8(rgb)
+        .write_to(&mut png, image::ImageOutputFormat::Png)
+        .unwrap();
+    let mut section = image_section(base64::engine::general_purpose::STANDARD.encode(png.into_inner()));
+    section.dithering = false;
+    section.command_mode = Some("two_color".into());
+
+    let mut request = job(vec![PrintSections::Image(section.clone())]);
+    request.profile.two_color = true;
+    let out = ProcessPrint::new().generate_document(&request).unwrap();
+    let plane = |color: u8, bits: u8| {
+        vec![0x1D, 0x28, 0x4C, 11, 0, 0x30, 0x70, 0x30, 1, 1, color, 8, 0, 1, 0, bits]
+    };
+    assert!(contains(&out, &plane(0x31, 0b1001_0010)), "c=49: black plane");
+    assert!(contains(&out, &plane(0x32, 0b0100_1001)), "c=50: red plane");
+    assert!(contains(&out, &[0x1D, 0x28, 0x4C, 2, 0, 0x30, 0x32]), "fn 50 prints");
+    assert!(!contains(&out, &[0x1D, 0x76, 0x30]));
+
+    // Impresora de un color (por defecto): se trama en blanco y negro
+    let out = gen(vec![PrintSections::Image(section)]);
+    assert!(contains(&out, &[0x1D, 0x76, 0x30]), "falls back to GS v 0");
+    assert!(!contains(&out, &[0x30, 0x70, 0x30]), "no color planes");
+}
+
+#[test]
+fn logo_graphics_mode_uses_nv_graphics_keys() {
+    let logo = |key_code: u8, set_logo: Option<Image>| {
+        ProcessPrint::new().generate_document(&job(vec![PrintSections::Logo(Logo {
+            key_code: Some(key_code),
+            mode: Some("double_width".into()),
+            set_logo,
+            command_mode: Some("graphics".into()),
+            key: None,
+        })]))
+    };
+
+    let out = logo(7, None).unwrap();
+    assert!(
+        contains(&out, &[0x1D, 0x28, 0x4C, 6, 0, 0x30, 0x45, b'0', b'7', 2, 1]),
+        "GS ( L fn 69 kc1 kc2 x y"
+    );
+
+    let out = logo(7, Some(image_section(png_base64(10, 2, |_, _| 0)))).unwrap();
+    let mut define = vec![0x1D, 0x28, 0x4C, 15, 0, 0x30, 0x43, 0x30, b'0', b'7', 1, 10, 0, 2, 0, 0x31];
+    define.extend([0xFF, 0xC0, 0xFF, 0xC0]);
+    assert!(contains(&out, &define), "GS ( L fn 67 (store NV graphics)");
+    assert!(!contains(&out, &[0x1C, 0x71]), "no FS q");
+
+    let err = logo(150, None).unwrap_err();
+    assert!(err.to_string().contains("key_code must be 1-99"), "{err}");
+}
+
+#[test]
+fn logo_graphics_mode_accepts_two_character_key() {
+    let out = gen(vec![PrintSections::Logo(Logo {
+        key_code: Some(7),
+        mode: None,
+        set_logo: None,
+        command_mode: Some("graphics".into()),
+        key: Some("LG".into()),
+    })]);
+    assert!(contains(&out, &[0x30, 0x45, b'L', b'G', 1, 1]), "fn 69 with key LG");
+}
+
+#[test]
+fn nv_graphics_requests_store_print_and_delete_by_key() {
+    let request = |key: Option<&str>, image: Option<Image>| NvGraphicsRequest {
+        printer: "test".into(),
+        key: key.map(Into::into),
+        image,
+        mode: Some("quadruple".into()),
+        paper_size: None,
+    };
+
+    let out = ProcessNvGraphics::store_document(
+        &request(Some("A1"), Some(image_section(png_base64(10, 2, |_, _| 0)))),
+        &ImageAssets::default(),
+    )
+    .unwrap();
+    let mut define = vec![0x1D, 0x28, 0x4C, 15, 0, 0x30, 0x43, 0x30, b'A', b'1', 1, 10, 0, 2, 0, 0x31];
+    define.extend([0xFF, 0xC0, 0xFF, 0xC0]);
+    assert_eq!(out[2..], define[..], "ESC @ + fn 67");
+
+    let out = ProcessNvGraphics::print_document(&request(Some("A1"), None)).unwrap();
+    assert!(contains(&out, &[0x1D, 0x28, 0x4C, 6, 0, 0x30, 0x45, b'A', b'1', 2, 2]), "fn 69");
+
+    let out = ProcessNvGraphics::delete_document(&request(Some("A1"), None)).unwrap();
+    assert_eq!(out, [0x1D, 0x28, 0x4C, 4, 0, 0x30, 0x42, b'A', b'1'], "fn 66");
+    let out = ProcessNvGraphics::delete_document(&request(None, None)).unwrap();
+    assert_eq!(out, [0x1D, 0x28, 0x4C, 5, 0, 0x30, 0x41, b'C', b'L', b'R'], "fn 65");
+
+    for key in [None, Some("A"), Some("ABC"), Some("\u{7}x")] {
+        let err = ProcessNvGraphics::print_document(&request(key, None)).unwrap_err();
+        assert_eq!(err.code(), "INVALID_JOB", "{key:?}");
+    }
+    assert!(ProcessNvGraphics::store_document(&request(Some("A1"), None), &ImageAssets::default()).is_err());
+}
+
+#[test]
+fn nv_graphics_answers_are_parsed() {
+    let (query, responses) = ProcessNvGraphics::key_list_query();
+    assert_eq!(query, [0x1D, 0x28, 0x4C, 4, 0, 0x30, 0x40, b'K', b'C']);
+    assert_eq!(responses, 1);
+
+    let list = ProcessNvGraphics::key_list(b"\x37\x72\x40A1LG\x00").unwrap();
+    assert_eq!((list.keys, list.complete), (vec!["A1".to_string(), "LG".to_string()], true));
+    let list = ProcessNvGraphics::key_list(b"\x37\x72\x41A1\x00").unwrap();
+    assert!(!list.complete, "41H: more keys pending");
+    assert!(ProcessNvGraphics::key_list(b"\x37\x72\x40A\x00").is_err());
+
+    // 41H: se vuelve a pedir fn 64 hasta el bloque final (40H)
+    let mut blocks = vec![&b"\x37\x72\x41A1LG\x00"[..], b"\x37\x72\x41B2\x00", b"\x37\x72\x40C3\x00"].into_iter();
+    let mut requests = 0;
+    let list = ProcessNvGraphics::list_keys(|data, responses| {
+        assert_eq!((data, responses), (&query[..], 1));
+        requests += 1;
+        Ok(blocks.next().unwrap().to_vec())
+    })
+    .unwrap();
+    assert_eq!(requests, 3);
+    assert_eq!(list.keys, ["A1", "LG", "B2", "C3"]);
+    assert!(list.complete);
+    // Una impresora que repite el mismo bloque no deja el bucle abierto
+    let list = ProcessNvGraphics::list_keys(|_, _| Ok(b"\x37\x72\x41A1\x00".to_vec())).unwrap();
+    assert_eq!((list.keys, list.complete), (vec!["A1".to_string()], false));
+    let err = ProcessNvGraphics::list_keys(|_, _| Err(crate::error::Error::UnsupportedPlatform)).unwrap_err();
+    assert_eq!(err.code(), "UNSUPPORTED_PLATFORM");
+
+    let (query, responses) = ProcessNvGraphics::capacity_query();
+    assert_eq!(query, [0x1D, 0x28, 0x4C, 2, 0, 0x30, 0x30, 0x1D, 0x28, 0x4C, 2, 0, 0x30, 0x33]);
+    assert_eq!(responses, 2);
+    // Bytes previos (estado automático) se ignoran
+    let capacity =
+        ProcessNvGraphics::capacity(b"\x14\x37\x30262144\x00\x37\x31131072\x00").unwrap();
+    assert_eq!((capacity.total, capacity.remaining), (262144, 131072));
+    assert!(ProcessNvGraphics::capacity(b"\x37\x30262144\x00").is_err());
+}
+
 #[test]
 fn logo_emits_fs_p() {
     let out = gen(vec![PrintSections::Logo(Logo {
         key_code: Some(1),
         mode: Some("normal".into()),
         set_logo: None,
+        command_mode: None,
+        key: None,
     })]);
     assert!(contains(&out, &[0x1C, 0x70, 0x01]), "FS p n (print NV logo)");
 }
@@ -468,6 +1850,8 @@ fn logo_defaults_key_code_when_omitted() {
         key_code: None,
         mode: None,
         set_logo: None,
+        command_mode: None,
+        key: None,
     })]);
     assert!(contains(&out, &[0x1C, 0x70, 0x01]), "FS p n con key_code por defecto");
 }
@@ -484,7 +1868,10 @@ fn set_logo_emits_fs_q_store() {
             align: "center".into(),
             dithering: false,
             size: "normal".into(),
+            ..Default::default()
         }),
+        command_mode: None,
+        key: None,
     })]);
     // FS q n=1 xL xH yL yH (para 1x1 => 1 byte ancho, 1 byte alto)
     assert!(
@@ -506,11 +1893,286 @@ fn set_logo_rejects_empty_image() {
             align: "left".into(),
             dithering: false,
             size: "normal".into(),
+            ..Default::default()
         }),
+        command_mode: None,
+        key: None,
     })]));
     assert!(result.is_err());
 }
 
+// ─── Font text ───────────────────────────────────────────────────────────────
+
+/// Fuente TrueType mínima (1000 unidades/em, ascendente 800, descendente -200):
+/// espacio de 500 y un cuadrado de 800x700 con avance 1000 para ASCII, hebreo y
+/// árabe (cmap formato 13).
+fn box_font() -> Vec<u8> {
+    fn be(values: &[i32], width: usize) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|v| v.to_be_bytes()[4 - width..].to_vec())
+            .collect()
+    }
+    let mut head = be(&[0x0001_0000, 0x0001_0000, 0, 0x5F0F_3CF5], 4);
+    head.extend(be(&[0, 1000], 2));
+    head.extend([0; 16]); // created, modified
+    head.extend(be(&[100, 0, 900, 700, 0, 8, 2, 0, 0], 2));
+    let mut hhea = be(&[0x0001_0000], 4);
+    hhea.extend(be(&[800, -200, 0, 1000, 0, 100, 900, 1, 0, 0, 0, 0, 0, 0, 0, 3], 2));
+    let mut maxp = be(&[0x0000_5000], 4);
+    maxp.extend(be(&[3], 2));
+    let hmtx = be(&[500, 0, 500, 0, 1000, 100], 2);
+    let mut glyf = be(&[1, 100, 0, 900, 700, 3, 0], 2);
+    glyf.extend([0x01; 4]);
+    glyf.extend(be(&[100, 0, 800, 0, 0, 700, 0, -700, 0], 2)); // x, y, relleno
+    let loca = be(&[0, 0, 0, 18], 2);
+    let groups = [(0x20, 0x20, 1), (0x21, 0x7E, 2), (0x5D0, 0x5EA, 2), (0x600, 0x6FF, 2)];
+    let mut cmap = be(&[0, 1, 3, 10], 2);
+    cmap.extend(be(&[12], 4));
+    cmap.extend(be(&[13, 0], 2));
+    cmap.extend(be(&[16 + 12 * groups.len() as i32, 0, groups.len() as i32], 4));
+    for (start, end, glyph) in groups {
+        cmap.extend(be(&[start, end, glyph], 4));
+    }
+
+    let tables = [
+        (b"cmap", cmap),
+        (b"glyf", glyf),
+        (b"head", head),
+        (b"hhea", hhea),
+        (b"hmtx", hmtx),
+        (b"loca", loca),
+        (b"maxp", maxp),
+    ];
+    let mut font = be(&[0x0001_0000], 4);
+    font.extend(be(&[tables.len() as i32, 0, 0, 0], 2));
+    let mut offset = 12 + 16 * tables.len();
+    let mut data = Vec::new();
+    for (tag, table) in &tables {
+        font.extend_from_slice(*tag);
+        font.extend(be(&[0, offset as i32, table.len() as i32], 4));
+        data.extend(table);
+        while data.len() % 4 != 0 {
+            data.push(0);
+        }
+        offset = 12 + 16 * tables.len() + data.len();
+    }
+    font.extend(data);
+    font
+}
+
+fn font_text(text: &str) -> FontText {
+    use base64::Engine as _;
+    FontText {
+        text: text.into(),
+        font: FontTextFont::Data(base64::engine::general_purpose::STANDARD.encode(box_font())),
+        size: None,
+        align: None,
+        direction: None,
+        line_spacing: None,
+    }
+}
+
+/// Columnas (mín, máx) con tinta en la imagen renderizada
+fn ink_columns(image: &image::GrayImage) -> (u32, u32) {
+    let columns: Vec<u32> = image
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel[0] < 128)
+        .map(|(x, _, _)| x)
+        .collect();
+    (
+        *columns.iter().min().expect("text should leave ink"),
+        *columns.iter().max().unwrap(),
+    )
+}
+
+#[test]
+fn font_text_prints_as_raster_image_at_paper_width() {
+    let out = gen(vec![PrintSections::FontText(font_text("AB"))]);
+    // 576 puntos = 72 bytes por fila; una línea de 24 puntos (em 24, asc-desc 1000)
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 72, 0, 24, 0]));
+}
+
+#[test]
+fn font_text_aligns_by_paragraph_direction_and_wraps() {
+    use crate::commands_esc_pos::text::font_text::{FontText as Renderer, TextDirection};
+
+    let renderer = Renderer::new(box_font()).unwrap();
+    // Dos cuadrados de 24 puntos de avance: tinta en 2..46
+    let ltr = renderer.render("AB", 200).unwrap();
+    assert_eq!((ltr.width(), ltr.height()), (200, 24));
+    assert_eq!(ink_columns(&ltr), (2, 45));
+
+    // Hebreo: párrafo RTL, alineado a la derecha por defecto
+    let rtl = renderer.render("אב", 200).unwrap();
+    assert_eq!(ink_columns(&rtl), (154, 197));
+    let forced = renderer
+        .clone()
+        .set_direction(TextDirection::Ltr)
+        .render("אב", 200)
+        .unwrap();
+    assert_eq!(ink_columns(&forced), (2, 45));
+
+    // Tres palabras de 48 puntos en 110: "AB AB" cabe (108), la tercera baja
+    let wrapped = renderer.render("AB AB AB", 110).unwrap();
+    assert_eq!(wrapped.height(), 48);
+    // Una palabra más larga que la línea se corta por caracteres
+    let long = renderer.render("ABCDEFGH", 50).unwrap();
+    assert_eq!(long.height(), 24 * 4);
+}
+
+#[test]
+fn font_text_rejects_empty_text_and_invalid_font() {
+    let mut invalid = font_text("AB");
+    invalid.font = FontTextFont::Data("AAAA".into());
+    for section in [font_text(""), invalid] {
+        let err = ProcessPrint::new()
+            .generate_document(&job(vec![PrintSections::FontText(section)]))
+            .unwrap_err();
+        assert_eq!(err.code(), "INVALID_SECTION");
+    }
+}
+
+#[test]
+fn headings_with_registered_font_print_as_bitmaps_in_the_same_job() {
+    let fonts = FontRegistry::default();
+    fonts.register("brand", box_font()).unwrap();
+    let brand = |weight, letter_spacing| TextFont {
+        name: "brand".into(),
+        size: None,
+        weight,
+        letter_spacing,
+    };
+    let out = ProcessPrint::new()
+        .set_fonts(fonts.clone())
+        .generate_document(&job(vec![
+            PrintSections::Title(Title {
+                text: "AB".into(),
+                styles: None,
+                font: Some(brand(None, None)),
+            }),
+            PrintSections::Subtitle(Subtitle {
+                text: "AB".into(),
+                styles: Some(GlobalStyles {
+                    align: Some("right".into()),
+                    ..Default::default()
+                }),
+                font: Some(brand(Some(400), Some(6.0))),
+            }),
+            PrintSections::Text(Text {
+                text: "body".into(),
+                styles: None,
+            }),
+        ]))
+        .unwrap();
+    // Title 18 pt = 51 filas, Subtitle 14 pt = 40 filas, a 72 bytes por fila
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 72, 0, 51, 0]));
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 72, 0, 40, 0]));
+    assert!(!contains(&out, &[0x1B, 0x21, 0x30]), "no native double size");
+    assert!(contains(&out, b"body\n"));
+
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![PrintSections::Title(Title {
+            text: "AB".into(),
+            styles: None,
+            font: Some(brand(None, None)),
+        })]))
+        .unwrap_err();
+    assert_eq!(err.code(), "INVALID_SECTION");
+    assert!(fonts.register("broken", vec![0; 16]).is_err());
+
+    // Title y Subtitle siguen la misma regla: 700 salvo `bold: false`
+    let heading = |section: PrintSections| {
+        ProcessPrint::new()
+            .set_fonts(fonts.clone())
+            .generate_document(&job(vec![section]))
+            .unwrap()
+    };
+    let not_bold = GlobalStyles {
+        bold: Some(false),
+        align: None,
+        ..Default::default()
+    };
+    let title = |styles, weight| {
+        heading(PrintSections::Title(Title {
+            text: "AB".into(),
+            styles,
+            font: Some(brand(weight, None)),
+        }))
+    };
+    assert_eq!(title(None, None), title(None, Some(700)));
+    assert_eq!(title(Some(not_bold.clone()), None), title(None, Some(400)));
+    let subtitle = |styles, weight| {
+        heading(PrintSections::Subtitle(Subtitle {
+            text: "AB".into(),
+            styles,
+            font: Some(brand(weight, None)),
+        }))
+    };
+    assert_eq!(subtitle(None, None), subtitle(None, Some(700)));
+    assert_eq!(subtitle(Some(not_bold), None), subtitle(None, Some(400)));
+    assert_ne!(subtitle(None, Some(400)), subtitle(None, Some(700)));
+}
+
+#[test]
+fn font_text_accepts_a_registered_font_by_name() {
+    let fonts = FontRegistry::default();
+    fonts.register("brand", box_font()).unwrap();
+    let section = |font: Value| -> FontText {
+        serde_json::from_value(json!({ "text": "AB", "font": font })).unwrap()
+    };
+    let generate = |section: FontText| {
+        ProcessPrint::new()
+            .set_fonts(fonts.clone())
+            .generate_document(&job(vec![PrintSections::FontText(section)]))
+    };
+
+    // 18 pt = 51 filas; sin tamaño, los 24 puntos de la fuente A
+    let out = generate(section(json!({ "name": "brand", "size": 18 }))).unwrap();
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 72, 0, 51, 0]));
+    let out = generate(section(json!({ "name": "brand" }))).unwrap();
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 72, 0, 24, 0]));
+    // `size` de la sección, también en puntos de imprenta, manda: 14 pt = 40 filas
+    let mut sized = section(json!({ "name": "brand", "size": 18 }));
+    sized.size = Some(14.0);
+    let out = generate(sized).unwrap();
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 72, 0, 40, 0]));
+    // Con la fuente en base64 igual
+    let mut inline = font_text("AB");
+    inline.size = Some(14.0);
+    let out = generate(inline).unwrap();
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 72, 0, 40, 0]));
+
+    let err = generate(section(json!({ "name": "missing" }))).unwrap_err();
+    assert_eq!(err.code(), "INVALID_SECTION");
+    assert!(err.to_string().contains("Font 'missing' is not registered"), "{err}");
+    // Un string sigue siendo la fuente en base64
+    assert!(matches!(section(json!("AAAA")).font, FontTextFont::Data(_)));
+    // La ruta no puede salir de los recursos
+    let request = crate::models::register_font_request::RegisterFontRequest {
+        name: "escape".into(),
+        path: Some("../../etc/fonts/x.ttf".into()),
+        data: None,
+    };
+    let err = fonts
+        .register_request(&request, |_| unreachable!("path must be rejected first"))
+        .unwrap_err();
+    assert_eq!(err.code(), "INVALID_JOB");
+}
+
+#[test]
+fn font_weight_and_letter_spacing_widen_the_text() {
+    use crate::commands_esc_pos::text::font_text::FontText as Renderer;
+
+    let renderer = Renderer::new(box_font()).unwrap();
+    // Cuadrados en 2..21 y 26..45; 4 puntos de espaciado desplazan el segundo
+    let spaced = renderer.clone().set_letter_spacing(4.0).render("AB", 200).unwrap();
+    assert_eq!(ink_columns(&spaced), (2, 49));
+    // 700 en una fuente no variable a 24 puntos: 1 punto de negrita sintética
+    let bold = renderer.set_weight(700).render("AB", 200).unwrap();
+    assert_eq!(ink_columns(&bold), (2, 47));
+}
+
 // ─── Table ───────────────────────────────────────────────────────────────────
 
 #[test]
@@ -615,6 +2277,561 @@ fn table_truncate_cuts_and_does_not_wrap_below() {
     assert!(!text.contains("una linea"), "col2 truncada: el final no debe aparecer");
 }
 
+// ─── Errors ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn empty_job_is_invalid_job() {
+    let err = ProcessPrint::new().generate_document(&job(vec![])).unwrap_err();
+    assert_eq!(err.code(), "INVALID_JOB");
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn section_error_carries_index_and_kind() {
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![
+            PrintSections::Text(Text {
+                text: "ok".into(),
+                styles: None,
+            }),
+            PrintSections::Barcode(Barcode {
+                data: "12AB".into(),
+                barcode_type: "EAN13".into(),
+                width: 3,
+                height: 80,
+                text_position: "below".into(),
+                align: None,
+                render_mode: None,
+                auto_check_digit: None,
+            }),
+        ]))
+        .unwrap_err();
+
+    let value = serde_json::to_value(&err).unwrap();
+    assert_eq!(value["code"], "INVALID_SECTION");
+    assert_eq!(value["index"], 1);
+    assert_eq!(value["kind"], "Barcode");
+    assert_eq!(value["retryable"], false);
+    assert!(value["message"].as_str().unwrap().contains("numeric"));
+}
+
+#[test]
+fn undecodable_image_is_image_decode() {
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![PrintSections::Image(Image {
+            data: "not-an-image".into(),
+            max_width: 0,
+            align: "left".into(),
+            dithering: false,
+            size: "normal".into(),
+            ..Default::default()
+        })]))
+        .unwrap_err();
+    assert_eq!(err.code(), "IMAGE_DECODE");
+}
+
+#[test]
+fn strict_code_page_rejects_unencodable_char() {
+    let text = || {
+        PrintSections::Text(Text {
+            text: "a漢".into(),
+            styles: None,
+        })
+    };
+    let mut request = job(vec![text()]);
+    request.options.strict = true;
+
+    let err = ProcessPrint::new().generate_document(&request).unwrap_err();
+    let value = serde_json::to_value(&err).unwrap();
+    assert_eq!(value["code"], "ENCODING_FAILED");
+    assert_eq!(value["index"], 0);
+    assert_eq!(value["char"], "漢");
+
+    // Sin `strict` se mantiene el comportamiento anterior (UTF-8 en bruto).
+    let out = gen(vec![text()]);
+    assert!(contains(&out, "漢".as_bytes()));
+}
+
+// ─── Validation ──────────────────────────────────────────────────────────────
+
+fn validate(request: &PrintJobRequest) -> ValidationReport {
+    ProcessValidate::new().validate_document(request)
+}
+
+#[test]
+fn validation_collects_errors_from_every_section() {
+    let report = validate(&job(vec![
+        PrintSections::Qr(Qr {
+            data: "x".repeat(5000),
+            size: 6,
+            error_correction: "M".into(),
+            model: 2,
+            align: None,
+            render_mode: None,
+            target_width: None,
+            target_width_mm: None,
+        }),
+        PrintSections::Text(Text {
+            text: "ok".into(),
+            styles: None,
+        }),
+        PrintSections::Barcode(Barcode {
+            data: "12AB".into(),
+            barcode_type: "EAN13".into(),
+            width: 3,
+            height: 80,
+            text_position: "below".into(),
+            align: None,
+            render_mode: None,
+            auto_check_digit: None,
+        }),
+        PrintSections::Table(Table {
+            columns: 2,
+            column_widths: Some(vec![20, 20]),
+            header: None,
+            body: vec![
+                vec![
+                    Text {
+                        text: "a".into(),
+                        styles: None,
+                    },
+                    Text {
+                        text: "b".into(),
+                        styles: None,
+                    },
+                ],
+                vec![Text {
+                    text: "c".into(),
+                    styles: None,
+                }],
+            ],
+            truncate: false,
+            word_wrap: None,
+        }),
+    ]));
+
+    assert!(!report.valid);
+    let paths: Vec<&str> = report.errors.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "sections[0].data",
+            "sections[2].data",
+            "sections[3].column_widths",
+            "sections[3].body[1]",
+        ]
+    );
+    assert!(report.errors.iter().all(|e| e.code == "INVALID_SECTION"));
+    assert_eq!(report.errors[3].kind.as_deref(), Some("Table"));
+}
+
+#[test]
+fn validation_warns_about_unencodable_chars_and_errors_when_strict() {
+    let sections = vec![PrintSections::Text(Text {
+        text: "漢字漢".into(),
+        styles: None,
+    })];
+
+    let report = validate(&job(sections.clone()));
+    assert!(report.valid);
+    assert_eq!(report.warnings.len(), 2, "one warning per distinct char");
+    assert_eq!(report.warnings[0].code, "UNENCODABLE_CHAR");
+    assert_eq!(report.warnings[0].path, "sections[0].text");
+
+    let mut request = job(sections);
+    request.options.strict = true;
+    let report = validate(&request);
+    assert!(!report.valid);
+    assert_eq!(report.errors.len(), 2);
+    assert!(report.errors.iter().all(|e| e.code == "ENCODING_FAILED"));
+}
+
+#[test]
+fn validation_reports_job_level_and_image_problems() {
+    let mut request = job(vec![PrintSections::Image(Image {
+        data: "not-an-image".into(),
+        max_width: 0,
+        align: "left".into(),
+        dithering: false,
+        size: "normal".into(),
+        ..Default::default()
+    })]);
+    request.printer = String::new();
+
+    let report = validate(&request);
+    assert_eq!(report.errors.len(), 2);
+    assert_eq!(report.errors[0].path, "printer");
+    assert_eq!(report.errors[0].index, None);
+    assert_eq!(report.errors[1].code, "IMAGE_DECODE");
+    assert_eq!(report.errors[1].path, "sections[0].data");
+}
+
+#[test]
+fn validation_warns_when_image_is_wider_than_paper() {
+    let mut request = job(vec![PrintSections::Image(Image {
+        data: TINY_PNG_BASE64.into(),
+        max_width: 1000,
+        align: "left".into(),
+        dithering: false,
+        size: "normal".into(),
+        ..Default::default()
+    })]);
+    request.paper_size = crate::PaperSize::Mm58;
+
+    let report = validate(&request);
+    assert!(report.valid);
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].code, "IMAGE_TOO_WIDE");
+    assert_eq!(report.warnings[0].path, "sections[0].max_width");
+}
+
+// ─── Receipt builder ─────────────────────────────────────────────────────────
+
+#[test]
+fn receipt_builds_request_with_modifiers_on_last_section() {
+    let request = Receipt::new(crate::PaperSize::Mm58)
+        .printer("TM-T20")
+        .title("Shop")
+        .text("Total")
+        .bold(true)
+        .align("right")
+        .table([["Coffee", "2.50"], ["Cake", "3.00"]])
+        .header(["Item", "Price"])
+        .column_widths([24, 8])
+        .qr("https://example.com")
+        .qr_size(4)
+        .align("center")
+        .cut()
+        .build()
+        .unwrap();
+
+    assert_eq!(request.printer, "TM-T20");
+    assert_eq!(request.sections.len(), 5);
+
+    let PrintSections::Text(text) = &request.sections[1] else {
+        panic!("expected Text");
+    };
+    let styles = text.styles.as_ref().unwrap();
+    assert_eq!(styles.bold, Some(true));
+    assert_eq!(styles.align.as_deref(), Some("right"));
+    assert_eq!(styles.size.as_deref(), Some("normal"));
+
+    let PrintSections::Table(table) = &request.sections[2] else {
+        panic!("expected Table");
+    };
+    assert_eq!(table.columns, 2);
+    assert_eq!(table.header.as_ref().unwrap()[1].text, "Price");
+    assert_eq!(table.column_widths, Some(vec![24, 8]));
+
+    let PrintSections::Qr(qr) = &request.sections[3] else {
+        panic!("expected Qr");
+    };
+    assert_eq!(qr.size, 4);
+    assert_eq!(qr.align.as_deref(), Some("center"));
+}
+
+#[test]
+fn receipt_to_bytes_matches_generate_document() {
+    let receipt = Receipt::new(crate::PaperSize::Mm80)
+        .printer("test")
+        .text("Hello")
+        .underline(true)
+        .line('=')
+        .cut();
+
+    let bytes = receipt.to_bytes().unwrap();
+    let expected = ProcessPrint::new()
+        .generate_document(&receipt.build().unwrap())
+        .unwrap();
+    assert_eq!(bytes, expected);
+    assert!(contains(&bytes, &[0x1B, 0x2D, 0x01]), "underline on");
+}
+
+#[test]
+fn receipt_to_bytes_does_not_need_a_printer() {
+    let bytes = Receipt::new(crate::PaperSize::Mm80)
+        .text("Hi")
+        .to_bytes()
+        .unwrap();
+    assert!(contains(&bytes, b"Hi"));
+    assert!(Receipt::new(crate::PaperSize::Mm80).to_bytes().is_err());
+}
+
+#[test]
+fn receipt_modifier_without_matching_section_fails_build() {
+    let receipt = Receipt::new(crate::PaperSize::Mm80)
+        .printer("test")
+        .line('-')
+        .bold(true)
+        .table([["a", "b"]])
+        .qr_size(4);
+    let err = receipt.clone().build().unwrap_err();
+    assert_eq!(err.code(), "INVALID_JOB");
+    // Se informa el primero
+    assert!(err.to_string().contains("bold() needs a Title, Subtitle or Text"), "{err}");
+    assert!(err.to_string().contains("found Line"), "{err}");
+    assert_eq!(receipt.to_bytes().unwrap_err().code(), "INVALID_JOB");
+
+    let err = Receipt::new(crate::PaperSize::Mm80).header(["x"]).build().unwrap_err();
+    assert!(err.to_string().contains("found no section"), "{err}");
+}
+
+// ─── Page mode ───────────────────────────────────────────────────────────────
+
+fn page_element(x: u16, y: u16, content: PrintSections) -> PageElement {
+    PageElement {
+        x,
+        y,
+        width: None,
+        content,
+    }
+}
+
+#[test]
+fn page_mode_positions_elements_and_prints_page() {
+    let qr = PrintSections::Qr(Qr {
+        data: "HELLO".to_string(),
+        size: 4,
+        error_correction: "M".to_string(),
+        model: 2,
+        align: Some("center".to_string()),
+        render_mode: None,
+        target_width: None,
+        target_width_mm: None,
+    });
+    let out = gen(vec![PrintSections::PageMode(PageMode {
+        x: 0,
+        y: 0,
+        width: None,
+        height: None,
+        direction: None,
+        elements: vec![
+            page_element(0, 0, qr),
+            page_element(120, 10, text_section("Order #42")),
+        ],
+    })]);
+
+    // QR versión 1 (21 módulos) × 4 puntos = 84; el área termina en el elemento más bajo.
+    let area = PrinterControl::set_page_mode_area(0, 0, 576, 84);
+    let start = out.windows(2).position(|w| w == [0x1B, 0x4C]).expect("ESC L");
+    assert!(contains(&out[start..], &area), "ESC W with computed height");
+    assert!(contains(&out, &[0x1B, 0x54, 0x00]), "ESC T left_to_right");
+    // QR: fondo del símbolo en y = 84, x = 0
+    assert!(contains(&out, &[0x1D, 0x24, 84, 0x00, 0x1B, 0x24, 0x00, 0x00]));
+    // Texto: base de la primera línea en y = 10 + 24, x = 120
+    assert!(contains(&out, &[0x1D, 0x24, 34, 0x00, 0x1B, 0x24, 120, 0x00]));
+    assert!(contains(&out, b"Order #42"));
+    let end = out
+        .windows(4)
+        .position(|w| w == [0x1B, 0x0C, 0x1B, 0x53])
+        .expect("ESC FF, ESC S");
+    assert!(end > start);
+}
+
+#[test]
+fn page_mode_positions_every_text_line_at_the_element_x() {
+    let out = gen(vec![PrintSections::PageMode(PageMode {
+        x: 0,
+        y: 0,
+        width: None,
+        height: None,
+        direction: None,
+        elements: vec![page_element(120, 10, text_section("Order #42\nTable 7"))],
+    })]);
+    // Bases en y = 10 + 24 y 10 + 24 + 30, ambas en x = 120
+    let first = [&[0x1D, 0x24, 34, 0x00, 0x1B, 0x24, 120, 0x00][..], b"Order #42\n"].concat();
+    let second = [&[0x1D, 0x24, 64, 0x00, 0x1B, 0x24, 120, 0x00][..], b"Table 7\n"].concat();
+    assert!(contains(&out, &first));
+    assert!(contains(&out, &second));
+
+    // Las líneas más anchas que lo que queda del área (576 - 400 = 176 puntos,
+    // 14 caracteres) se parten antes de medir: dos líneas, área de 24 + 30
+    let out = gen(vec![PrintSections::PageMode(PageMode {
+        x: 0,
+        y: 0,
+        width: None,
+        height: None,
+        direction: None,
+        elements: vec![page_element(400, 0, text_section("Total   12.50 EUR paid"))],
+    })]);
+    assert!(contains(&out, &PrinterControl::set_page_mode_area(0, 0, 576, 54)));
+    let first = [&[0x1D, 0x24, 24, 0x00, 0x1B, 0x24, 0x90, 0x01][..], b"Total 12.50
+"].concat();
+    let second = [&[0x1D, 0x24, 54, 0x00, 0x1B, 0x24, 0x90, 0x01][..], b"EUR paid
+"].concat();
+    assert!(contains(&out, &first));
+    assert!(contains(&out, &second));
+
+    let long = PrintSections::PageMode(PageMode {
+        x: 0,
+        y: 0,
+        width: None,
+        height: None,
+        direction: None,
+        elements: vec![page_element(0, 0, text_section(&"x\n".repeat(3000)))],
+    });
+    let err = ProcessPrint::new().generate_document(&job(vec![long])).unwrap_err();
+    assert_eq!(err.code(), "INVALID_SECTION");
+    assert!(err.to_string().contains("too many lines"), "{}", err);
+}
+
+#[test]
+fn page_mode_from_json_with_rotation() {
+    let section: PrintSections = serde_json::from_value(json!({
+        "PageMode": {
+            "height": 400,
+            "direction": "bottom_to_top",
+            "elements": [
+                { "x": 20, "y": 0, "content": { "Text": { "text": "SIDE", "styles": null } } }
+            ]
+        }
+    }))
+    .expect("valid PageMode");
+
+    let out = gen(vec![section]);
+    assert!(contains(&out, &PrinterControl::set_page_mode_area(0, 0, 576, 400)));
+    assert!(contains(&out, &[0x1B, 0x54, 0x01]), "ESC T bottom_to_top");
+}
+
+#[test]
+fn page_mode_rejects_invalid_layouts() {
+    let vertical_without_height = PrintSections::PageMode(PageMode {
+        x: 0,
+        y: 0,
+        width: None,
+        height: None,
+        direction: Some("top_to_bottom".to_string()),
+        elements: vec![page_element(0, 0, text_section("x"))],
+    });
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![vertical_without_height]))
+        .unwrap_err();
+    assert!(err.to_string().contains("height is required"), "{}", err);
+
+    let nested_feed = PrintSections::PageMode(PageMode {
+        x: 0,
+        y: 0,
+        width: None,
+        height: None,
+        direction: None,
+        elements: vec![page_element(
+            0,
+            0,
+            PrintSections::Feed(Feed {
+                feed_type: "lines".to_string(),
+                value: 1,
+            }),
+        )],
+    });
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![nested_feed]))
+        .unwrap_err();
+    assert_eq!(err.code(), "INVALID_SECTION");
+    assert!(
+        err.to_string().contains("not supported inside PageMode"),
+        "{}",
+        err
+    );
+}
+
+// ─── Columns ─────────────────────────────────────────────────────────────────
+
+fn column(width: Option<u16>, valign: Option<&str>, content: Vec<PrintSections>) -> Column {
+    Column {
+        width,
+        valign: valign.map(str::to_string),
+        content,
+    }
+}
+
+#[test]
+fn columns_place_qr_beside_text_in_one_band() {
+    let qr = PrintSections::Qr(Qr {
+        data: "HELLO".to_string(),
+        size: 4,
+        error_correction: "M".to_string(),
+        model: 2,
+        align: None,
+        render_mode: None,
+        target_width: None,
+        target_width_mm: None,
+    });
+    let out = gen(vec![PrintSections::Columns(Columns {
+        columns: vec![
+            column(Some(100), None, vec![qr]),
+            column(None, Some("middle"), vec![text_section("Order #42\nTable 7")]),
+        ],
+        gap: None,
+    })]);
+
+    // Banda = alto del QR (21 × 4); el texto (24 + 30 = 54) se centra: y = 15.
+    assert!(contains(&out, &PrinterControl::set_page_mode_area(0, 0, 576, 84)));
+    assert!(contains(&out, &[0x1D, 0x24, 84, 0x00, 0x1B, 0x24, 0x00, 0x00]));
+    // Segunda columna: x = 100 + 16 de separación; bases de las líneas en 15 + 24
+    // y 15 + 30 + 24, cada una posicionada de nuevo en la columna.
+    let first = [&[0x1D, 0x24, 39, 0x00, 0x1B, 0x24, 116, 0x00][..], b"Order #42\n"].concat();
+    let second = [&[0x1D, 0x24, 69, 0x00, 0x1B, 0x24, 116, 0x00][..], b"Table 7\n"].concat();
+    assert!(contains(&out, &first));
+    assert!(contains(&out, &second));
+    assert_eq!(count(&out, &[0x1B, 0x4C]), 1, "single page mode band");
+}
+
+#[test]
+fn columns_wrap_text_to_column_width() {
+    // 576 - 456 - 16 = 104 puntos → 8 caracteres de fuente A
+    let out = gen(vec![PrintSections::Columns(Columns {
+        columns: vec![
+            column(Some(456), None, vec![text_section("left")]),
+            column(None, None, vec![text_section("one two three")]),
+        ],
+        gap: None,
+    })]);
+    // Columna derecha en x = 472; "three" pasa a la línea siguiente (y + 30)
+    let line = |y: u8, text: &[u8]| [&[0x1D, 0x24, y, 0x00, 0x1B, 0x24, 0xD8, 0x01][..], text].concat();
+    assert!(contains(&out, &line(24, b"one two\n")));
+    assert!(contains(&out, &line(54, b"three\n")));
+    assert!(contains(&out, &[0x1D, 0x24, 24, 0x00, 0x1B, 0x24, 0x00, 0x00, b'l']));
+}
+
+#[test]
+fn columns_limit_codes_to_their_column_width() {
+    let qr = PrintSections::Qr(Qr {
+        data: "HELLO".to_string(),
+        size: 4,
+        error_correction: "M".to_string(),
+        model: 2,
+        align: None,
+        render_mode: Some("raster".to_string()),
+        target_width: Some(400),
+        target_width_mm: None,
+    });
+    let out = gen(vec![PrintSections::Columns(Columns {
+        columns: vec![
+            column(Some(200), None, vec![qr]),
+            column(None, None, vec![text_section("Order #42")]),
+        ],
+        gap: None,
+    })]);
+    // 21 módulos en 200 puntos → 9 puntos por módulo (189), no los 19 de 400
+    assert!(contains(&out, &PrinterControl::set_page_mode_area(0, 0, 576, 189)));
+    assert!(contains(&out, &[0x1D, 0x76, 0x30, 0x00, 24, 0x00, 189, 0x00]));
+}
+
+#[test]
+fn columns_reject_widths_wider_than_paper() {
+    let err = ProcessPrint::new()
+        .generate_document(&job(vec![PrintSections::Columns(Columns {
+            columns: vec![
+                column(Some(400), None, vec![text_section("a")]),
+                column(Some(200), None, vec![text_section("b")]),
+            ],
+            gap: Some(0),
+        })]))
+        .unwrap_err();
+    assert_eq!(err.code(), "INVALID_SECTION");
+    assert!(err.to_string().contains("exceed the paper width"), "{}", err);
+}
+
 // ─── Physical test document (TestPrinter) ────────────────────────────────────
 
 /// Every boolean section flag that the configurable dump can toggle.